@@ -49,25 +49,7 @@ impl BenchContext {
         let mut benchmark_mode = BenchMode::new(bench_args.from, bench_args.to)?;
 
         // construct the authenticated provider
-        let auth_jwt = bench_args
-            .auth_jwtsecret
-            .clone()
-            .ok_or_else(|| eyre::eyre!("--jwtsecret must be provided for authenticated RPC"))?;
-
-        // fetch jwt from file
-        //
-        // the jwt is hex encoded so we will decode it after
-        let jwt = std::fs::read_to_string(auth_jwt)?;
-        let jwt = JwtSecret::from_hex(jwt)?;
-
-        // get engine url
-        let auth_url = Url::parse(&bench_args.engine_rpc_url)?;
-
-        // construct the authed transport
-        info!("Connecting to Engine RPC at {} for replay", auth_url);
-        let auth_transport = AuthenticatedTransportConnect::new(auth_url, jwt);
-        let client = ClientBuilder::default().connect_with(auth_transport).await?;
-        let auth_provider = RootProvider::<AnyNetwork>::new(client);
+        let auth_provider = auth_provider(bench_args).await?;
 
         let first_block = match benchmark_mode {
             BenchMode::Continuous => {
@@ -97,3 +79,29 @@ impl BenchContext {
         Ok(Self { auth_provider, block_provider, benchmark_mode, next_block })
     }
 }
+
+/// Creates the authenticated provider for engine API queries from the `--jwtsecret` and
+/// `--engine-rpc-url` arguments.
+pub(crate) async fn auth_provider(
+    bench_args: &BenchmarkArgs,
+) -> eyre::Result<RootProvider<AnyNetwork>> {
+    let auth_jwt = bench_args
+        .auth_jwtsecret
+        .clone()
+        .ok_or_else(|| eyre::eyre!("--jwtsecret must be provided for authenticated RPC"))?;
+
+    // fetch jwt from file
+    //
+    // the jwt is hex encoded so we will decode it after
+    let jwt = std::fs::read_to_string(auth_jwt)?;
+    let jwt = JwtSecret::from_hex(jwt)?;
+
+    // get engine url
+    let auth_url = Url::parse(&bench_args.engine_rpc_url)?;
+
+    // construct the authed transport
+    info!("Connecting to Engine RPC at {} for replay", auth_url);
+    let auth_transport = AuthenticatedTransportConnect::new(auth_url, jwt);
+    let client = ClientBuilder::default().connect_with(auth_transport).await?;
+    Ok(RootProvider::<AnyNetwork>::new(client))
+}
@@ -9,6 +9,7 @@ mod context;
 mod new_payload_fcu;
 mod new_payload_only;
 mod output;
+mod replay_engine;
 mod send_payload;
 
 /// `reth bench` command
@@ -41,6 +42,15 @@ pub enum Subcommands {
     /// `cast block latest--full --json | reth-bench send-payload --rpc-url localhost:5000
     /// --jwt-secret $(cat ~/.local/share/reth/mainnet/jwt.hex)`
     SendPayload(send_payload::Command),
+
+    /// Replays engine API messages recorded with `--debug.engine-api-store` against a node.
+    ///
+    /// The `newPayload` and `forkchoiceUpdated` calls are sent in the order they were received,
+    /// optionally with the recorded timing, and every status that differs from the recorded status
+    /// is reported as divergent. With `--from` and `--to`, messages are skipped until the first
+    /// payload of block `--from`, and the replay stops before the first payload after block
+    /// `--to`.
+    ReplayEngine(replay_engine::Command),
 }
 
 impl BenchmarkCommand {
@@ -53,6 +63,7 @@ impl BenchmarkCommand {
             Subcommands::NewPayloadFcu(command) => command.execute(ctx).await,
             Subcommands::NewPayloadOnly(command) => command.execute(ctx).await,
             Subcommands::SendPayload(command) => command.execute(ctx).await,
+            Subcommands::ReplayEngine(command) => command.execute(ctx).await,
         }
     }
 
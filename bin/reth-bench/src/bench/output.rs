@@ -1,6 +1,7 @@
 //! Contains various benchmark output formats, either for logging or for
 //! serialization to / from files.
 
+use alloy_primitives::B256;
 use reth_primitives_traits::constants::GIGAGAS;
 use serde::{ser::SerializeStruct, Serialize};
 use std::time::Duration;
@@ -14,6 +15,9 @@ pub(crate) const COMBINED_OUTPUT_SUFFIX: &str = "combined_latency.csv";
 /// This is the suffix for new payload output csv files.
 pub(crate) const NEW_PAYLOAD_OUTPUT_SUFFIX: &str = "new_payload_latency.csv";
 
+/// This is the suffix for engine API replay output csv files.
+pub(crate) const REPLAY_OUTPUT_SUFFIX: &str = "replay.csv";
+
 /// This represents the results of a single `newPayload` call in the benchmark, containing the gas
 /// used and the `newPayload` latency.
 #[derive(Debug)]
@@ -119,6 +123,62 @@ impl Serialize for CombinedResult {
     }
 }
 
+/// This represents the result of a single replayed engine API message.
+#[derive(Debug)]
+pub(crate) struct ReplayResult {
+    /// The name of the file the message was read from.
+    pub(crate) file_name: String,
+    /// The replayed method, either `newPayload` or `forkchoiceUpdated`.
+    pub(crate) method: &'static str,
+    /// The number of the payload, only known for `newPayload` calls.
+    pub(crate) block_number: Option<u64>,
+    /// The hash of the payload, or the head block hash of a `forkchoiceUpdated` call.
+    pub(crate) block_hash: B256,
+    /// The status the node that recorded the message responded with, if it was recorded.
+    pub(crate) expected_status: Option<&'static str>,
+    /// The returned payload status, or the error if the call failed.
+    pub(crate) status: String,
+    /// Whether the status diverges from the expected status.
+    pub(crate) diverged: bool,
+    /// The latency of the call.
+    pub(crate) latency: Duration,
+}
+
+impl std::fmt::Display for ReplayResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Replayed {} of block ", self.method)?;
+        if let Some(block_number) = self.block_number {
+            write!(f, "{block_number} ")?;
+        }
+        write!(f, "{}, status: {}", self.block_hash, self.status)?;
+        if let Some(expected) = self.expected_status {
+            write!(f, " (recorded: {expected})")?;
+        }
+        write!(f, ". Latency: {:?}", self.latency)
+    }
+}
+
+/// This is a [`Serialize`] implementation for the [`ReplayResult`] struct, serializing the latency
+/// as microseconds because the csv writer would fail otherwise.
+impl Serialize for ReplayResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let latency = self.latency.as_micros();
+        let mut state = serializer.serialize_struct("ReplayResult", 8)?;
+        state.serialize_field("file_name", &self.file_name)?;
+        state.serialize_field("method", &self.method)?;
+        state.serialize_field("block_number", &self.block_number)?;
+        state.serialize_field("block_hash", &self.block_hash)?;
+        state.serialize_field("expected_status", &self.expected_status)?;
+        state.serialize_field("status", &self.status)?;
+        state.serialize_field("diverged", &self.diverged)?;
+        state.serialize_field("latency", &latency)?;
+        state.end()
+    }
+}
+
 /// This represents a row of total gas data in the benchmark.
 #[derive(Debug)]
 pub(crate) struct TotalGasRow {
@@ -0,0 +1,384 @@
+//! Runs the `reth bench replay-engine` command, which sends engine API messages recorded with
+//! `--debug.engine-api-store` back to a node, in the order they were received.
+
+use crate::bench::{
+    context::auth_provider,
+    output::{ReplayResult, REPLAY_OUTPUT_SUFFIX},
+};
+use alloy_eips::eip7685::RequestsOrHash;
+use alloy_primitives::B256;
+use alloy_provider::{ext::EngineApi, network::AnyNetwork, Provider, RootProvider};
+use alloy_rpc_types_engine::{
+    ExecutionData, ExecutionPayload, ExecutionPayloadInputV2, ForkchoiceState, PayloadAttributes,
+    PayloadStatus,
+};
+use clap::Parser;
+use csv::Writer;
+use eyre::OptionExt;
+use reth_cli_runner::CliContext;
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiStatus,
+};
+use reth_ethereum_engine_primitives::EthEngineTypes;
+use reth_node_api::EngineApiMessageVersion;
+use reth_node_core::args::BenchmarkArgs;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// `reth benchmark replay-engine` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The directory with the engine API messages, as written by `--debug.engine-api-store`.
+    #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+    engine_api_store: PathBuf,
+
+    /// The replay speed relative to the recorded timing, for example `2` replays the messages
+    /// twice as fast as they were received.
+    ///
+    /// If not set, every message is sent as soon as the node responded to the previous one.
+    #[arg(long, value_name = "MULTIPLIER", value_parser = parse_speed, verbatim_doc_comment)]
+    speed: Option<f64>,
+
+    /// Removes the payload attributes from the replayed forkchoice updates, so the node does not
+    /// start building payloads.
+    #[arg(long, verbatim_doc_comment)]
+    skip_payload_attributes: bool,
+
+    #[command(flatten)]
+    benchmark: BenchmarkArgs,
+}
+
+impl Command {
+    /// Execute `benchmark replay-engine` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        // Ensure that output directory is a directory
+        if let Some(output) = &self.benchmark.output {
+            if output.is_file() {
+                return Err(eyre::eyre!("Output path must be a directory"));
+            }
+        }
+
+        let auth_provider = auth_provider(&self.benchmark).await?;
+        let store = EngineMessageStore::new(self.engine_api_store.clone());
+
+        let mut results = Vec::new();
+        let mut replay_started = None;
+        let mut message_version = None;
+
+        for path in store.engine_messages_iter()? {
+            let file_name = file_name(&path);
+            let message: StoredEngineApiMessage<EthEngineTypes> =
+                serde_json::from_slice(&reth_fs_util::read(&path)?)?;
+            let expected = EngineMessageStore::status(&path)?;
+
+            if let StoredEngineApiMessage::NewPayload { payload } = &message {
+                let block_number = payload.payload.block_number();
+                if replay_started.is_none() &&
+                    self.benchmark.from.is_some_and(|from| block_number < from)
+                {
+                    debug!(target: "reth-bench", block_number, %file_name, "Skipping payload");
+                    continue
+                }
+                if self.benchmark.to.is_some_and(|to| block_number > to) {
+                    info!(target: "reth-bench", block_number, "Reached end of replay range");
+                    break
+                }
+            }
+
+            // messages before the first replayed payload are skipped if a start block is set
+            if replay_started.is_none() &&
+                self.benchmark.from.is_some() &&
+                matches!(message, StoredEngineApiMessage::ForkchoiceUpdated { .. })
+            {
+                debug!(target: "reth-bench", %file_name, "Skipping forkchoice update");
+                continue
+            }
+
+            // wait until the message is due, relative to the first replayed message
+            let received_at = timestamp(&file_name)?;
+            let (started_at, first_received_at) =
+                *replay_started.get_or_insert_with(|| (Instant::now(), received_at));
+            if let Some(speed) = self.speed {
+                let offset = Duration::from_millis(received_at.saturating_sub(first_received_at));
+                tokio::time::sleep_until((started_at + offset.div_f64(speed)).into()).await;
+            }
+
+            let start = Instant::now();
+            let result = match message {
+                StoredEngineApiMessage::NewPayload { payload } => {
+                    let block_number = Some(payload.payload.block_number());
+                    let block_hash = payload.payload.block_hash();
+                    let version = new_payload_version(&payload);
+                    message_version = Some(version);
+                    let status = new_payload(&auth_provider, payload, version).await;
+                    replay_result(
+                        file_name,
+                        "newPayload",
+                        block_number,
+                        block_hash,
+                        expected,
+                        status,
+                        start,
+                    )
+                }
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                    let payload_attrs =
+                        if self.skip_payload_attributes { None } else { payload_attrs };
+                    let version =
+                        forkchoice_updated_version(payload_attrs.as_ref(), message_version);
+                    let status =
+                        forkchoice_updated(&auth_provider, state, payload_attrs, version).await;
+                    replay_result(
+                        file_name,
+                        "forkchoiceUpdated",
+                        None,
+                        state.head_block_hash,
+                        expected,
+                        status,
+                        start,
+                    )
+                }
+            };
+
+            if result.diverged {
+                warn!(target: "reth-bench", %result, file_name = %result.file_name, "Divergent payload status");
+            } else {
+                info!(target: "reth-bench", %result);
+            }
+            results.push(result);
+        }
+
+        // write the csv output to a file
+        if let Some(path) = &self.benchmark.output {
+            let output_path = path.join(REPLAY_OUTPUT_SUFFIX);
+            info!("Writing engine api replay output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for result in &results {
+                writer.serialize(result)?;
+            }
+            writer.flush()?;
+        }
+
+        let diverged = results.iter().filter(|result| result.diverged).count();
+        info!(replayed = results.len(), diverged, "Finished replaying engine API messages");
+        if diverged > 0 {
+            return Err(eyre::eyre!(
+                "{diverged} of {} replayed engine API messages returned a divergent status",
+                results.len()
+            ))
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the file name of the stored message.
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Returns the timestamp in milliseconds at which the stored message was received, which is the
+/// prefix of its file name.
+fn timestamp(file_name: &str) -> eyre::Result<u64> {
+    file_name
+        .split('-')
+        .next()
+        .and_then(|timestamp| timestamp.parse().ok())
+        .ok_or_eyre(format!("Could not parse timestamp from filename {file_name}"))
+}
+
+fn parse_speed(value: &str) -> eyre::Result<f64> {
+    let speed: f64 = value.parse()?;
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err(eyre::eyre!("speed must be a positive number"))
+    }
+    Ok(speed)
+}
+
+/// Collects the result of a replayed call.
+///
+/// The call diverged if its status differs from the `expected` status the node that recorded the
+/// message responded with. Messages that were recorded without a status can't diverge.
+fn replay_result(
+    file_name: String,
+    method: &'static str,
+    block_number: Option<u64>,
+    block_hash: B256,
+    expected: Option<StoredEngineApiStatus>,
+    status: eyre::Result<PayloadStatus>,
+    start: Instant,
+) -> ReplayResult {
+    let latency = start.elapsed();
+    let (replayed, status) = match status {
+        Ok(status) => {
+            let replayed = StoredEngineApiStatus::from_payload_status(&status.status);
+            let status = match status.status.validation_error() {
+                Some(error) => format!("{} ({error})", status.status.as_str()),
+                None => status.status.as_str().to_string(),
+            };
+            (replayed, status)
+        }
+        Err(err) => (StoredEngineApiStatus::Error, format!("error: {err}")),
+    };
+    ReplayResult {
+        file_name,
+        method,
+        block_number,
+        block_hash,
+        expected_status: expected.map(|expected| expected.as_str()),
+        status,
+        diverged: expected.is_some_and(|expected| expected != replayed),
+        latency,
+    }
+}
+
+/// Returns the `engine_newPayload` version the payload was sent with.
+fn new_payload_version(payload: &ExecutionData) -> EngineApiMessageVersion {
+    match &payload.payload {
+        ExecutionPayload::V1(_) => EngineApiMessageVersion::V1,
+        ExecutionPayload::V2(_) => EngineApiMessageVersion::V2,
+        ExecutionPayload::V3(_) if payload.sidecar.requests_hash().is_some() => {
+            EngineApiMessageVersion::V4
+        }
+        ExecutionPayload::V3(_) => EngineApiMessageVersion::V3,
+    }
+}
+
+/// Returns the `engine_forkchoiceUpdated` version of a forkchoice update.
+///
+/// This is derived from the payload attributes if present, otherwise from the version of the last
+/// replayed payload.
+fn forkchoice_updated_version(
+    payload_attrs: Option<&PayloadAttributes>,
+    last_payload_version: Option<EngineApiMessageVersion>,
+) -> EngineApiMessageVersion {
+    match payload_attrs {
+        Some(attrs) if attrs.parent_beacon_block_root.is_some() => EngineApiMessageVersion::V3,
+        Some(attrs) if attrs.withdrawals.is_some() => EngineApiMessageVersion::V2,
+        Some(_) => EngineApiMessageVersion::V1,
+        None => last_payload_version.unwrap_or(EngineApiMessageVersion::V3),
+    }
+}
+
+/// Calls `engine_newPayload` with the given version.
+async fn new_payload(
+    provider: &RootProvider<AnyNetwork>,
+    payload: ExecutionData,
+    version: EngineApiMessageVersion,
+) -> eyre::Result<PayloadStatus> {
+    let ExecutionData { payload, sidecar } = payload;
+    let status = match payload {
+        ExecutionPayload::V1(payload) => provider.new_payload_v1(payload).await?,
+        ExecutionPayload::V2(payload) => {
+            provider
+                .new_payload_v2(ExecutionPayloadInputV2 {
+                    execution_payload: payload.payload_inner,
+                    withdrawals: Some(payload.withdrawals),
+                })
+                .await?
+        }
+        ExecutionPayload::V3(payload) => {
+            let versioned_hashes = sidecar.versioned_hashes().cloned().unwrap_or_default();
+            let parent_beacon_block_root = sidecar
+                .parent_beacon_block_root()
+                .ok_or_eyre("missing parent beacon block root of V3 payload")?;
+
+            if version == EngineApiMessageVersion::V3 {
+                provider.new_payload_v3(payload, versioned_hashes, parent_beacon_block_root).await?
+            } else {
+                // `new_payload_v4` does not support sending `RequestsOrHash::Hash`
+                let requests_hash = sidecar.requests_hash().ok_or_eyre("missing requests hash")?;
+                provider
+                    .client()
+                    .request(
+                        "engine_newPayloadV4",
+                        (
+                            payload,
+                            versioned_hashes,
+                            parent_beacon_block_root,
+                            RequestsOrHash::Hash(requests_hash),
+                        ),
+                    )
+                    .await?
+            }
+        }
+    };
+    Ok(status)
+}
+
+/// Calls `engine_forkchoiceUpdated` with the given version.
+async fn forkchoice_updated(
+    provider: &RootProvider<AnyNetwork>,
+    state: ForkchoiceState,
+    payload_attrs: Option<PayloadAttributes>,
+    version: EngineApiMessageVersion,
+) -> eyre::Result<PayloadStatus> {
+    let updated = match version {
+        EngineApiMessageVersion::V1 => {
+            provider.fork_choice_updated_v1(state, payload_attrs).await?
+        }
+        EngineApiMessageVersion::V2 => {
+            provider.fork_choice_updated_v2(state, payload_attrs).await?
+        }
+        EngineApiMessageVersion::V3 | EngineApiMessageVersion::V4 | EngineApiMessageVersion::V5 => {
+            provider.fork_choice_updated_v3(state, payload_attrs).await?
+        }
+    };
+    Ok(updated.payload_status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::PayloadStatusEnum;
+
+    #[test]
+    fn parse_message_timestamp() {
+        assert_eq!(timestamp("1718000000000-fcu-0x00.json").unwrap(), 1718000000000);
+        assert_eq!(timestamp("1718000000001-new_payload-0x00.json").unwrap(), 1718000000001);
+        assert!(timestamp("fcu-0x00.json").is_err());
+    }
+
+    #[test]
+    fn derive_forkchoice_updated_version() {
+        let attrs = PayloadAttributes {
+            timestamp: 0,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: Default::default(),
+            withdrawals: Some(vec![]),
+            parent_beacon_block_root: None,
+        };
+        assert_eq!(forkchoice_updated_version(Some(&attrs), None), EngineApiMessageVersion::V2);
+        assert_eq!(
+            forkchoice_updated_version(None, Some(EngineApiMessageVersion::V4)),
+            EngineApiMessageVersion::V4
+        );
+        assert_eq!(forkchoice_updated_version(None, None), EngineApiMessageVersion::V3);
+    }
+
+    #[test]
+    fn compare_with_recorded_status() {
+        let result = |expected, status| {
+            replay_result(
+                String::new(),
+                "newPayload",
+                None,
+                B256::ZERO,
+                expected,
+                status,
+                Instant::now(),
+            )
+        };
+        let syncing = || Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing));
+
+        assert!(!result(Some(StoredEngineApiStatus::Syncing), syncing()).diverged);
+        assert!(result(Some(StoredEngineApiStatus::Valid), syncing()).diverged);
+        assert!(result(Some(StoredEngineApiStatus::Valid), Err(eyre::eyre!("timeout"))).diverged);
+        assert!(!result(Some(StoredEngineApiStatus::Error), Err(eyre::eyre!("timeout"))).diverged);
+        // messages recorded without a status are not compared
+        assert!(!result(None, syncing()).diverged);
+    }
+}
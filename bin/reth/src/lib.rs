@@ -43,6 +43,7 @@ pub mod payload {
     pub use reth_ethereum_payload_builder::EthereumExecutionPayloadValidator;
     pub use reth_payload_builder::*;
     pub use reth_payload_primitives::*;
+    pub use reth_payload_util::{BundlePool, PayloadBundle};
 }
 
 /// Re-exported from `reth_node_api`.
@@ -184,6 +185,9 @@ pub mod rpc {
 /// Ress subprotocol installation.
 pub mod ress;
 
+/// Snap subprotocol installation.
+pub mod snap;
+
 // re-export for convenience
 #[doc(inline)]
 pub use reth_cli_runner::{tokio_runtime, CliContext, CliRunner};
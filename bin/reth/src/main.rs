@@ -3,13 +3,32 @@
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
 
-use clap::Parser;
-use reth::{args::RessArgs, cli::Cli, ress::install_ress_subprotocol};
+use clap::{Args, Parser};
+use reth::{
+    args::{BundleArgs, OtsArgs, RessArgs, SnapArgs},
+    cli::Cli,
+    payload::BundlePool,
+    ress::install_ress_subprotocol,
+    snap::install_snap_subprotocol,
+};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_builder::NodeHandle;
-use reth_node_ethereum::EthereumNode;
+use reth_node_ethereum::{call_trace_index_exex, EthereumNode};
 use tracing::info;
 
+/// Additional arguments of the `node` command.
+#[derive(Debug, Clone, Args)]
+struct NodeExtArgs {
+    #[command(flatten)]
+    ress: RessArgs,
+    #[command(flatten)]
+    snap: SnapArgs,
+    #[command(flatten)]
+    ots: OtsArgs,
+    #[command(flatten)]
+    bundles: BundleArgs,
+}
+
 fn main() {
     reth_cli_util::sigsegv_handler::install();
 
@@ -19,15 +38,34 @@ fn main() {
     }
 
     if let Err(err) =
-        Cli::<EthereumChainSpecParser, RessArgs>::parse().run(async move |builder, ress_args| {
+        Cli::<EthereumChainSpecParser, NodeExtArgs>::parse().run(async move |builder, args| {
             info!(target: "reth::cli", "Launching node");
-            let NodeHandle { node, node_exit_future } =
-                builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
+            let mut ethereum_node = EthereumNode::default();
+            if args.bundles.enabled {
+                ethereum_node = ethereum_node.with_bundle_pool(BundlePool::default());
+            }
+
+            let NodeHandle { node, node_exit_future } = builder
+                .node(ethereum_node)
+                .install_exex_if(args.ots.index, "call-trace-index", async move |ctx| {
+                    Ok(call_trace_index_exex(ctx))
+                })
+                .launch_with_debug_capabilities()
+                .await?;
+
+            // Install snap subprotocol.
+            if args.snap.enabled {
+                install_snap_subprotocol(
+                    node.provider.clone(),
+                    node.network.clone(),
+                    node.task_executor.clone(),
+                )?;
+            }
 
             // Install ress subprotocol.
-            if ress_args.enabled {
+            if args.ress.enabled {
                 install_ress_subprotocol(
-                    ress_args,
+                    args.ress,
                     node.provider,
                     node.evm_config,
                     node.network,
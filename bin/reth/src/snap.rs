@@ -0,0 +1,35 @@
+use reth_network::{
+    protocol::IntoRlpxSubProtocol,
+    snap::{SnapProtocolHandler, SnapRequestHandler},
+    NetworkProtocols,
+};
+use reth_network_api::FullNetwork;
+use reth_provider::providers::{BlockchainProvider, ProviderNodeTypes};
+use reth_tasks::TaskExecutor;
+use tokio::sync::mpsc;
+use tracing::*;
+
+/// The capacity of the channel for incoming `snap` requests.
+const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
+/// Install `snap` subprotocol if it's enabled.
+pub fn install_snap_subprotocol<P, N>(
+    provider: BlockchainProvider<P>,
+    network: N,
+    task_executor: TaskExecutor,
+) -> eyre::Result<()>
+where
+    P: ProviderNodeTypes,
+    N: FullNetwork + NetworkProtocols,
+{
+    info!(target: "reth::cli", "Installing snap subprotocol");
+
+    let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+    task_executor.spawn_critical("p2p snap request handler", SnapRequestHandler::new(provider, rx));
+
+    let handler = SnapProtocolHandler::new(network.peers_handle().clone(), tx);
+    network.add_rlpx_sub_protocol(handler.into_rlpx_sub_protocol());
+    info!(target: "reth::cli", "Snap subprotocol support enabled");
+
+    Ok(())
+}
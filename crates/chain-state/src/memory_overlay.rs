@@ -1,15 +1,18 @@
 use super::ExecutedBlockWithTrieUpdates;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    keccak256, map::B256Map, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
 use reth_errors::ProviderResult;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives};
 use reth_storage_api::{
-    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
-    StateRootProvider, StorageRootProvider,
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateDumpProvider,
+    StateDumpProviderBox, StateProofProvider, StateProvider, StateRootProvider,
+    StorageRootProvider,
 };
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, TrieInput,
+    MultiProofTargets, StorageMultiProof, TrieAccount, TrieInput,
 };
 use revm_database::BundleState;
 use std::sync::OnceLock;
@@ -233,3 +236,92 @@ impl<N: NodePrimitives> StateProvider for MemoryOverlayStateProviderRef<'_, N> {
         self.historical.bytecode_by_hash(code_hash)
     }
 }
+
+/// A [`StateDumpProvider`] that overlays the state of in-memory blocks on top of the
+/// [`StateDumpProvider`] of the latest ancestor block stored in the database.
+#[expect(missing_debug_implementations)]
+pub struct MemoryOverlayStateDump {
+    /// Dump provider for the state that is not found in in-memory blocks.
+    historical: StateDumpProviderBox,
+    /// The aggregated hashed state of the in-memory blocks.
+    state: HashedPostState,
+    /// The addresses of the accounts changed by the in-memory blocks by their hashed address.
+    addresses: B256Map<Address>,
+    /// The contracts deployed by the in-memory blocks.
+    contracts: B256Map<Bytecode>,
+}
+
+impl MemoryOverlayStateDump {
+    /// Create a new memory overlay state dump provider.
+    ///
+    /// ## Arguments
+    ///
+    /// - `historical` - a dump provider for the latest ancestor block stored in the database.
+    /// - `in_memory` - the collection of executed ancestor blocks in reverse.
+    pub fn new<N: NodePrimitives>(
+        historical: StateDumpProviderBox,
+        in_memory: &[ExecutedBlockWithTrieUpdates<N>],
+    ) -> Self {
+        let mut state = HashedPostState::default();
+        let mut addresses = B256Map::default();
+        let mut contracts = B256Map::default();
+        for block in in_memory.iter().rev() {
+            state.extend_ref(&block.hashed_state);
+            let bundle = &block.execution_output.bundle;
+            addresses.extend(bundle.state.keys().map(|address| (keccak256(address), *address)));
+            contracts.extend(
+                bundle.contracts.iter().map(|(hash, code)| (*hash, Bytecode(code.clone()))),
+            );
+        }
+        Self { historical, state, addresses, contracts }
+    }
+}
+
+impl StateDumpProvider for MemoryOverlayStateDump {
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        max: usize,
+        max_storage: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>> {
+        let mut state = self.state.clone();
+        state.extend(hashed_state);
+        self.historical.account_range(state, start, max, max_storage)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        storage: HashedStorage,
+        start: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut hashed_storage =
+            self.state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        self.historical.storage_range(address, hashed_storage, start, max)
+    }
+
+    fn account_addresses(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        let mut addresses = B256Map::default();
+        let mut unresolved = Vec::new();
+        for hashed_address in hashed_addresses {
+            match self.addresses.get(hashed_address) {
+                Some(address) => {
+                    addresses.insert(*hashed_address, *address);
+                }
+                None => unresolved.push(*hashed_address),
+            }
+        }
+        addresses.extend(self.historical.account_addresses(&unresolved)?);
+        Ok(addresses)
+    }
+
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        match self.contracts.get(code_hash) {
+            Some(code) => Ok(Some(code.clone())),
+            None => self.historical.bytecode(code_hash),
+        }
+    }
+}
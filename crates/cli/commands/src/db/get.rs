@@ -1,8 +1,9 @@
 use alloy_consensus::Header;
-use alloy_primitives::{hex, BlockHash};
+use alloy_primitives::{hex, Address, BlockHash};
 use clap::Parser;
 use reth_db::static_file::{
     ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+    TransactionSenderMask,
 };
 use reth_db_api::{
     table::{Decompress, DupSort, Table},
@@ -72,6 +73,10 @@ impl Command {
                     StaticFileSegment::Receipts => {
                         (table_key::<tables::Receipts>(&key)?, <ReceiptMask<ReceiptTy<N>>>::MASK)
                     }
+                    StaticFileSegment::TransactionSenders => (
+                        table_key::<tables::TransactionSenders>(&key)?,
+                        <TransactionSenderMask>::MASK,
+                    ),
                     StaticFileSegment::BlockMeta => todo!(),
                 };
 
@@ -114,6 +119,10 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::TransactionSenders => {
+                                    let sender = Address::decompress(content[0].as_slice())?;
+                                    println!("{}", serde_json::to_string_pretty(&sender)?);
+                                }
                                 StaticFileSegment::BlockMeta => {
                                     todo!()
                                 }
@@ -92,6 +92,10 @@ where
 
     sf_provider.latest_writer(StaticFileSegment::Transactions)?.increment_block(header.number())?;
 
+    sf_provider
+        .latest_writer(StaticFileSegment::TransactionSenders)?
+        .increment_block(header.number())?;
+
     Ok(())
 }
 
@@ -100,6 +104,7 @@ where
 /// * Headers: It will push an empty block.
 /// * Transactions: It will not push any tx, only increments the end block range.
 /// * Receipts: It will not push any receipt, only increments the end block range.
+/// * Transaction senders: It will not push any sender, only increments the end block range.
 fn append_dummy_chain<N, F>(
     sf_provider: &StaticFileProvider<N>,
     target_height: BlockNumber,
@@ -111,8 +116,12 @@ where
 {
     let (tx, rx) = std::sync::mpsc::channel();
 
-    // Spawn jobs for incrementing the block end range of transactions and receipts
-    for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+    // Spawn jobs for incrementing the block end range of transactions, receipts and senders
+    for segment in [
+        StaticFileSegment::Transactions,
+        StaticFileSegment::Receipts,
+        StaticFileSegment::TransactionSenders,
+    ] {
         let tx_clone = tx.clone();
         let provider = sf_provider.clone();
         std::thread::spawn(move || {
@@ -152,9 +161,12 @@ where
 
     // If, for any reason, rayon crashes this verifies if all segments are at the same
     // target_height.
-    for segment in
-        [StaticFileSegment::Headers, StaticFileSegment::Receipts, StaticFileSegment::Transactions]
-    {
+    for segment in [
+        StaticFileSegment::Headers,
+        StaticFileSegment::Receipts,
+        StaticFileSegment::Transactions,
+        StaticFileSegment::TransactionSenders,
+    ] {
         assert_eq!(
             sf_provider.latest_writer(segment)?.user_header().block_end(),
             Some(target_height),
@@ -0,0 +1,584 @@
+//! Command that crawls the discovery network and produces a census of the found nodes
+
+use super::genesis_head;
+use alloy_primitives::{FixedBytes, B256};
+use clap::{Parser, ValueEnum};
+use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use reth_chainspec::{EnrForkIdEntry, EthChainSpec, ForkFilter, ForkHash, ForkId, Hardforks};
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_discv5::{discv5, Discv5};
+use reth_ecies::stream::ECIESStream;
+use reth_eth_wire::{HelloMessage, UnauthedEthStream, UnauthedP2PStream, UnifiedStatus};
+use reth_fs_util as fs;
+use reth_network::{config::rng_secret_key, NetworkPrimitives};
+use reth_network_peers::{pk2id, NodeRecord, PeerId};
+use secp256k1::{SecretKey, SECP256K1};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    time::Duration,
+};
+use tokio::{net::TcpStream, select};
+use tracing::{debug, info};
+
+/// Interval at which the discv4 DHT is walked with random lookups while crawling.
+const DISCV4_LOOKUP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum number of concurrent lookups of the neighbours of found nodes.
+const MAX_CONCURRENT_LOOKUPS: usize = 16;
+
+/// Crawl the discovery network and write a census of the found nodes.
+///
+/// Nodes are found by walking the discv4 and, optionally, the discv5 DHT: the neighbours of every
+/// found node are looked up until the DHT is exhausted or the duration has passed. Optionally, an
+/// `RLPx` and `eth` handshake is completed with every node to record its client version,
+/// capabilities, fork ID and head.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// How long to walk the DHT, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+    duration: u64,
+
+    /// Also walk the discv5 DHT.
+    #[arg(long)]
+    v5: bool,
+
+    /// Complete the `RLPx` and `eth` handshakes with every found node.
+    #[arg(long)]
+    handshake: bool,
+
+    /// Maximum number of concurrent handshakes.
+    #[arg(long, default_value_t = 32)]
+    concurrency: usize,
+
+    /// Timeout of a single handshake, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    handshake_timeout: u64,
+
+    /// Only keep nodes with a fork ID compatible with the chain.
+    #[arg(long = "filter.chain")]
+    filter_chain: bool,
+
+    /// Only keep nodes that advertise the given fork hash, e.g. `0x9f3d2254`.
+    #[arg(long = "filter.fork-hash", value_name = "HASH", value_parser = parse_fork_hash)]
+    filter_fork_hash: Option<ForkHash>,
+
+    /// Output format of the census.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+
+    /// Write the census to this file instead of stdout.
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<std::path::PathBuf>,
+}
+
+/// Output format of the census.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// JSON array of nodes.
+    Json,
+    /// CSV with one node per row.
+    Csv,
+}
+
+impl Command {
+    /// Execute the `p2p crawl` command.
+    pub async fn execute<N: NetworkPrimitives, Spec: EthChainSpec + Hardforks>(
+        self,
+        chain: &Spec,
+        discv4_addr: SocketAddr,
+        boot_nodes: Vec<NodeRecord>,
+        discv5_config: reth_discv5::Config,
+    ) -> eyre::Result<()> {
+        let head = genesis_head(chain);
+        let fork_filter = chain.fork_filter(head);
+        let key = rng_secret_key();
+
+        // advertise the same fork as the discv5 ENR
+        let mut discv4_config = Discv4Config::builder();
+        discv4_config
+            .add_boot_nodes(boot_nodes)
+            .lookup_interval(DISCV4_LOOKUP_INTERVAL)
+            .add_eip868_pair("eth", EnrForkIdEntry::from(chain.latest_fork_id()));
+        let local_node_record = NodeRecord::from_secret_key(discv4_addr, &key);
+        let (discv4, mut discv4_service) =
+            Discv4::bind(discv4_addr, local_node_record, key, discv4_config.build()).await?;
+        let mut discv4_updates = discv4_service.update_stream();
+        discv4_service.spawn();
+
+        let mut discv5 = None;
+        if self.v5 {
+            discv5 = Some(Discv5::start(&key, discv5_config).await?);
+        }
+
+        info!(duration = self.duration, v5 = self.v5, "Crawling discovery network");
+
+        let mut crawler = Crawler::default();
+        let mut lookups = FuturesUnordered::new();
+        let deadline = tokio::time::sleep(Duration::from_secs(self.duration));
+        tokio::pin!(deadline);
+        loop {
+            // look up the neighbours of the found nodes
+            while lookups.len() < MAX_CONCURRENT_LOOKUPS {
+                let Some(lookup) = crawler.pending.pop_front() else { break };
+                lookups.push(lookup_neighbours(
+                    lookup,
+                    &discv4,
+                    discv5.as_ref().map(|(d, _, _)| d),
+                ));
+            }
+
+            select! {
+                _ = &mut deadline => break,
+                update = discv4_updates.next() => {
+                    let Some(update) = update else { break };
+                    crawler.on_discv4_update(update);
+                }
+                update = async {
+                    match &mut discv5 {
+                        Some((_, updates, _)) => updates.recv().await,
+                        None => futures::future::pending().await,
+                    }
+                } => {
+                    let (Some(update), Some((discv5, _, _))) = (update, &discv5) else { break };
+                    crawler.on_discv5_update(discv5, update);
+                }
+                Some(neighbours) = lookups.next(), if !lookups.is_empty() => {
+                    match neighbours {
+                        Neighbours::Discv4(records) => {
+                            for record in records {
+                                crawler.insert(record, Lookup::Discv4(record.id));
+                            }
+                        }
+                        Neighbours::Discv5(enrs) => {
+                            let Some((discv5, _, _)) = &discv5 else { continue };
+                            for enr in enrs {
+                                crawler.on_discv5_enr(discv5, enr, None);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        discv4.terminate();
+
+        let nodes = crawler.nodes;
+        info!(nodes = nodes.len(), "Finished crawling discovery network");
+
+        let filter = CensusFilter {
+            fork_filter: self.filter_chain.then(|| fork_filter.clone()),
+            fork_hash: self.filter_fork_hash,
+        };
+        let mut nodes = nodes
+            .into_values()
+            .filter(|node| filter.matches(node.fork_id, true))
+            .collect::<Vec<_>>();
+
+        if self.handshake {
+            let status = UnifiedStatus::spec_builder(chain, &head);
+            let timeout = Duration::from_secs(self.handshake_timeout);
+            info!(nodes = nodes.len(), "Completing handshakes");
+
+            nodes = futures::stream::iter(nodes)
+                .map(|mut node| {
+                    let fork_filter = fork_filter.clone();
+                    async move {
+                        let handshake = handshake::<N>(&mut node, key, status, fork_filter);
+                        match tokio::time::timeout(timeout, handshake).await {
+                            Ok(Ok(())) => {}
+                            Ok(Err(err)) => node.error = Some(err.to_string()),
+                            Err(_) => node.error = Some("handshake timed out".to_string()),
+                        }
+                        debug!(id=%node.id, error=?node.error, "Finished handshake");
+                        node
+                    }
+                })
+                .buffer_unordered(self.concurrency.max(1))
+                .collect()
+                .await;
+
+            nodes.retain(|node| filter.matches(node.fork_id, false));
+        }
+
+        nodes.sort_unstable_by_key(|node| node.id);
+
+        let output = match self.format {
+            OutputFormat::Json => serde_json::to_string_pretty(&nodes)?,
+            OutputFormat::Csv => to_csv(&nodes)?,
+        };
+
+        match &self.output {
+            Some(path) => {
+                fs::write(path, output)?;
+                info!(path=?path, nodes = nodes.len(), "Wrote census");
+            }
+            None => println!("{output}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// A node found while crawling.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CrawledNode {
+    /// The node's id.
+    id: PeerId,
+    /// The node's IP address.
+    ip: IpAddr,
+    /// The node's `RLPx` port.
+    tcp_port: u16,
+    /// The node's discovery port.
+    udp_port: u16,
+    /// The discovery protocol the node was found with.
+    discovery: &'static str,
+    /// The fork hash of the node, from its status if the handshake succeeded or its ENR.
+    fork_hash: Option<String>,
+    /// The next fork of the node, from its status if the handshake succeeded or its ENR.
+    fork_next: Option<u64>,
+    /// The client version advertised in the `Hello` message.
+    client_version: Option<String>,
+    /// The capabilities advertised in the `Hello` message.
+    capabilities: Option<String>,
+    /// The negotiated `eth` version.
+    eth_version: Option<u8>,
+    /// The chain id of the node's status.
+    chain_id: Option<u64>,
+    /// The genesis hash of the node's status.
+    genesis: Option<B256>,
+    /// The head block hash of the node's status.
+    head_hash: Option<B256>,
+    /// The head block number of the node's status, only advertised since eth/69.
+    head_number: Option<u64>,
+    /// The error that aborted the handshake.
+    error: Option<String>,
+    /// The fork ID of the node, used for filtering.
+    #[serde(skip)]
+    fork_id: Option<ForkId>,
+}
+
+impl CrawledNode {
+    const fn new(record: NodeRecord, discovery: &'static str) -> Self {
+        Self {
+            id: record.id,
+            ip: record.address,
+            tcp_port: record.tcp_port,
+            udp_port: record.udp_port,
+            discovery,
+            fork_hash: None,
+            fork_next: None,
+            client_version: None,
+            capabilities: None,
+            eth_version: None,
+            chain_id: None,
+            genesis: None,
+            head_hash: None,
+            head_number: None,
+            error: None,
+            fork_id: None,
+        }
+    }
+
+    const fn node_record(&self) -> NodeRecord {
+        NodeRecord {
+            address: self.ip,
+            tcp_port: self.tcp_port,
+            udp_port: self.udp_port,
+            id: self.id,
+        }
+    }
+
+    fn set_fork_id(&mut self, fork_id: ForkId) {
+        self.fork_hash = Some(FixedBytes(fork_id.hash.0).to_string());
+        self.fork_next = Some(fork_id.next);
+        self.fork_id = Some(fork_id);
+    }
+}
+
+/// Filters nodes by their fork ID.
+#[derive(Debug)]
+struct CensusFilter {
+    /// Keeps nodes with a fork ID compatible with the chain.
+    fork_filter: Option<ForkFilter>,
+    /// Keeps nodes with this fork hash.
+    fork_hash: Option<ForkHash>,
+}
+
+impl CensusFilter {
+    /// Returns true if a node with the given fork ID should be kept.
+    ///
+    /// Nodes without a known fork ID are kept if `keep_unknown` is set, since the fork ID may
+    /// still be learned during the handshake.
+    fn matches(&self, fork_id: Option<ForkId>, keep_unknown: bool) -> bool {
+        if self.fork_filter.is_none() && self.fork_hash.is_none() {
+            return true
+        }
+        let Some(fork_id) = fork_id else { return keep_unknown };
+        if self.fork_hash.is_some_and(|hash| hash != fork_id.hash) {
+            return false
+        }
+        self.fork_filter.as_ref().is_none_or(|filter| filter.validate(fork_id).is_ok())
+    }
+}
+
+/// Collects the found nodes and the lookups of their neighbours that are still pending.
+#[derive(Debug, Default)]
+struct Crawler {
+    /// The found nodes.
+    nodes: HashMap<PeerId, CrawledNode>,
+    /// Nodes whose neighbours haven't been looked up yet.
+    pending: VecDeque<Lookup>,
+}
+
+impl Crawler {
+    /// Returns the node with the record's id, inserting it if it wasn't found before.
+    ///
+    /// The neighbours of newly inserted nodes are queued for lookup.
+    fn insert(&mut self, record: NodeRecord, lookup: Lookup) -> &mut CrawledNode {
+        self.nodes.entry(record.id).or_insert_with(|| {
+            let discovery = lookup.discovery();
+            self.pending.push_back(lookup);
+            CrawledNode::new(record, discovery)
+        })
+    }
+
+    fn on_discv4_update(&mut self, update: DiscoveryUpdate) {
+        match update {
+            DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
+                self.insert(record, Lookup::Discv4(record.id));
+            }
+            DiscoveryUpdate::EnrForkId(record, fork_id) => {
+                self.insert(record, Lookup::Discv4(record.id)).set_fork_id(fork_id);
+            }
+            DiscoveryUpdate::Removed(_) => {}
+            DiscoveryUpdate::Batch(updates) => {
+                for update in updates {
+                    self.on_discv4_update(update);
+                }
+            }
+        }
+    }
+
+    fn on_discv5_update(&mut self, discv5: &Discv5, update: discv5::Event) {
+        match update {
+            discv5::Event::SessionEstablished(enr, socket) => {
+                self.on_discv5_enr(discv5, enr, Some(socket))
+            }
+            discv5::Event::Discovered(enr) => self.on_discv5_enr(discv5, enr, None),
+            _ => {}
+        }
+    }
+
+    /// Inserts the node of a discv5 ENR, reachable at the given socket or the ENR's UDP socket.
+    fn on_discv5_enr(&mut self, discv5: &Discv5, enr: discv5::Enr, socket: Option<SocketAddr>) {
+        let Some(socket) = socket.or_else(|| enr.udp4_socket().map(Into::into)) else { return };
+        let Ok(record) = discv5.try_into_reachable(&enr, socket) else { return };
+
+        let node = self.insert(record, Lookup::Discv5(enr.node_id()));
+        if node.fork_id.is_none() {
+            if let Ok(fork_id) = discv5.get_fork_id(&enr) {
+                node.set_fork_id(fork_id);
+            }
+        }
+    }
+}
+
+/// A found node whose neighbours are looked up.
+#[derive(Debug, Clone, Copy)]
+enum Lookup {
+    /// A node found via discv4.
+    Discv4(PeerId),
+    /// A node found via discv5.
+    Discv5(discv5::enr::NodeId),
+}
+
+impl Lookup {
+    /// Returns the discovery protocol the node was found with.
+    const fn discovery(&self) -> &'static str {
+        match self {
+            Self::Discv4(_) => "discv4",
+            Self::Discv5(_) => "discv5",
+        }
+    }
+}
+
+/// The neighbours returned by a [`Lookup`].
+#[derive(Debug)]
+enum Neighbours {
+    Discv4(Vec<NodeRecord>),
+    Discv5(Vec<discv5::Enr>),
+}
+
+/// Looks up the neighbours of the node, i.e. the nodes closest to it in the DHT.
+///
+/// The lookup queries the node itself once it's reachable, failed lookups yield no neighbours.
+fn lookup_neighbours(
+    lookup: Lookup,
+    discv4: &Discv4,
+    discv5: Option<&Discv5>,
+) -> BoxFuture<'static, Neighbours> {
+    match lookup {
+        Lookup::Discv4(id) => {
+            let discv4 = discv4.clone();
+            Box::pin(async move { Neighbours::Discv4(discv4.lookup(id).await.unwrap_or_default()) })
+        }
+        Lookup::Discv5(id) => {
+            let Some(discv5) = discv5 else {
+                return Box::pin(async { Neighbours::Discv5(Vec::new()) })
+            };
+            let find_node = discv5.with_discv5(|discv5| discv5.find_node(id));
+            Box::pin(async move { Neighbours::Discv5(find_node.await.unwrap_or_default()) })
+        }
+    }
+}
+
+/// Completes the `RLPx` and `eth` handshakes with the node and records the exchanged `Hello` and
+/// `Status` messages.
+async fn handshake<N: NetworkPrimitives>(
+    node: &mut CrawledNode,
+    key: SecretKey,
+    mut status: UnifiedStatus,
+    fork_filter: ForkFilter,
+) -> eyre::Result<()> {
+    let record = node.node_record();
+    let outgoing = TcpStream::connect((record.address, record.tcp_port)).await?;
+    let ecies_stream = ECIESStream::connect(outgoing, key, record.id).await?;
+
+    let hello = HelloMessage::builder(pk2id(&key.public_key(SECP256K1))).build();
+    let (p2p_stream, their_hello) = UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
+    node.client_version = Some(their_hello.client_version.clone());
+    node.capabilities = Some(
+        their_hello.capabilities.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "),
+    );
+
+    let eth_version = p2p_stream.shared_capabilities().eth_version()?;
+    status.set_eth_version(eth_version);
+    let (_, their_status) =
+        UnauthedEthStream::new(p2p_stream).handshake::<N>(status, fork_filter).await?;
+
+    node.eth_version = Some(their_status.version.into());
+    node.chain_id = Some(their_status.chain.id());
+    node.genesis = Some(their_status.genesis);
+    node.head_hash = Some(their_status.blockhash);
+    node.head_number = their_status.latest_block;
+    node.set_fork_id(their_status.forkid);
+
+    Ok(())
+}
+
+/// Serializes the nodes as CSV with a header row.
+fn to_csv(nodes: &[CrawledNode]) -> eyre::Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for node in nodes {
+        writer.serialize(node)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Parses a hex encoded fork hash.
+fn parse_fork_hash(value: &str) -> eyre::Result<ForkHash> {
+    Ok(ForkHash(FixedBytes::<4>::from_str(value)?.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_network::{EthNetworkPrimitives, NetworkConfigBuilder, NetworkManager};
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn parse_crawl_command() {
+        let command = Command::try_parse_from([
+            "crawl",
+            "--duration",
+            "30",
+            "--handshake",
+            "--filter.fork-hash",
+            "0x9f3d2254",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert_eq!(command.duration, 30);
+        assert!(command.handshake);
+        assert_eq!(command.filter_fork_hash, Some(ForkHash([0x9f, 0x3d, 0x22, 0x54])));
+        assert_eq!(command.format, OutputFormat::Csv);
+    }
+
+    #[test]
+    fn filter_by_fork_hash() {
+        let fork_id = ForkId { hash: ForkHash([0x9f, 0x3d, 0x22, 0x54]), next: 0 };
+        let other = ForkId { hash: ForkHash([1, 2, 3, 4]), next: 0 };
+        let filter = CensusFilter { fork_filter: None, fork_hash: Some(fork_id.hash) };
+
+        assert!(filter.matches(Some(fork_id), false));
+        assert!(!filter.matches(Some(other), true));
+        assert!(filter.matches(None, true));
+        assert!(!filter.matches(None, false));
+    }
+
+    #[test]
+    fn census_to_csv() {
+        let record = NodeRecord::new(([127, 0, 0, 1], 30303).into(), PeerId::random());
+        let mut node = CrawledNode::new(record, "discv4");
+        node.set_fork_id(ForkId { hash: ForkHash([0x9f, 0x3d, 0x22, 0x54]), next: 0 });
+        node.client_version = Some("reth/v1.0.0".to_string());
+
+        let csv = to_csv(&[node]).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "id,ip,tcpPort,udpPort,discovery,forkHash,forkNext,clientVersion,capabilities,ethVersion,chainId,genesis,headHash,headNumber,error"
+        );
+        let row = lines.next().unwrap();
+        assert!(row.contains("127.0.0.1,30303,30303,discv4,0x9f3d2254,0,reth/v1.0.0"));
+    }
+
+    #[test]
+    fn crawler_looks_up_new_nodes_once() {
+        let record = NodeRecord::new(([127, 0, 0, 1], 30303).into(), PeerId::random());
+        let fork_id = ForkId { hash: ForkHash([0x9f, 0x3d, 0x22, 0x54]), next: 0 };
+
+        let mut crawler = Crawler::default();
+        crawler.on_discv4_update(DiscoveryUpdate::Added(record));
+        crawler.on_discv4_update(DiscoveryUpdate::EnrForkId(record, fork_id));
+
+        assert_eq!(crawler.nodes.len(), 1);
+        assert_eq!(crawler.nodes[&record.id].fork_id, Some(fork_id));
+        assert!(matches!(crawler.pending.pop_front(), Some(Lookup::Discv4(id)) if id == record.id));
+        assert!(crawler.pending.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn handshake_records_hello_and_status() {
+        let chain = MAINNET.clone();
+        let config = NetworkConfigBuilder::<EthNetworkPrimitives>::new(rng_secret_key())
+            .listener_addr((Ipv4Addr::LOCALHOST, 0).into())
+            .disable_discovery()
+            .build_with_noop_provider(chain.clone());
+        let network = NetworkManager::new(config).await.unwrap();
+        let record = NodeRecord::new(network.local_addr(), *network.peer_id());
+        tokio::spawn(network);
+
+        let head = genesis_head(&*chain);
+        let mut node = CrawledNode::new(record, "discv4");
+        handshake::<EthNetworkPrimitives>(
+            &mut node,
+            rng_secret_key(),
+            UnifiedStatus::spec_builder(&*chain, &head),
+            chain.fork_filter(head),
+        )
+        .await
+        .unwrap();
+
+        assert!(node.client_version.as_deref().is_some_and(|version| version.contains("reth")));
+        assert!(node.capabilities.as_deref().is_some_and(|caps| caps.contains("eth/68")));
+        assert_eq!(node.chain_id, Some(1));
+        assert_eq!(node.genesis, Some(chain.genesis_hash()));
+        assert!(node.fork_id.is_some());
+    }
+}
@@ -0,0 +1,179 @@
+//! Command that builds and signs an EIP-1459 DNS tree
+
+use clap::{Parser, ValueEnum};
+use reth_chainspec::ForkFilter;
+use reth_discv5::{discv5, enr::EnrCombinedKeyWrapper, Discv5};
+use reth_dns_discovery::{publish::EnrTreeBuilder, tree::LinkEntry, Enr};
+use reth_fs_util as fs;
+use secp256k1::SecretKey;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::info;
+
+/// Build a signed EIP-1459 DNS tree of node records.
+///
+/// The records are either given explicitly or collected by crawling the discv5 network of the
+/// chain.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The domain the tree is published under, e.g. `nodes.example.org`.
+    #[arg(long)]
+    domain: String,
+
+    /// Path to the file with the hex encoded secret key used to sign the tree root.
+    ///
+    /// The key identifies the tree in its `enrtree://` link, so it is never created implicitly.
+    #[arg(long, value_name = "PATH")]
+    signing_key: PathBuf,
+
+    /// ENR to include in the tree. Can be specified multiple times.
+    #[arg(long = "enr", value_name = "ENR")]
+    enrs: Vec<Enr<SecretKey>>,
+
+    /// File with ENRs to include in the tree, one per line.
+    #[arg(long, value_name = "FILE")]
+    enr_file: Option<PathBuf>,
+
+    /// Link to another tree to include, in `enrtree://<key>@<domain>` format. Can be specified
+    /// multiple times.
+    #[arg(long = "link", value_name = "LINK")]
+    links: Vec<LinkEntry>,
+
+    /// Crawl the discv5 network for the given number of seconds and include all discovered
+    /// nodes of this chain that advertise an `RLPx` port.
+    #[arg(long, value_name = "SECONDS")]
+    crawl_duration: Option<u64>,
+
+    /// Sequence number of the tree root. Defaults to the current unix timestamp.
+    #[arg(long)]
+    seq: Option<u64>,
+
+    /// Output format of the TXT records.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Zone)]
+    format: OutputFormat,
+
+    /// TTL of the records, in seconds, used in zone file output.
+    #[arg(long, default_value_t = 3600)]
+    ttl: u32,
+
+    /// Write the records to this file instead of stdout.
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// Output format of the DNS tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Zone file with one TXT record per line.
+    Zone,
+    /// JSON object mapping record names to TXT content.
+    Json,
+}
+
+impl Command {
+    /// Execute the `p2p dns` command.
+    ///
+    /// The discv5 config and fork filter are only used if the network is crawled.
+    pub async fn execute(
+        self,
+        discv5_config: reth_discv5::Config,
+        fork_filter: ForkFilter,
+    ) -> eyre::Result<()> {
+        let key =
+            fs::read_to_string(&self.signing_key)?.trim().parse::<SecretKey>().map_err(|err| {
+                eyre::eyre!("invalid signing key {}: {err}", self.signing_key.display())
+            })?;
+
+        let mut enrs = self.enrs;
+        if let Some(path) = &self.enr_file {
+            for line in fs::read_to_string(path)?.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue
+                }
+                enrs.push(line.parse().map_err(|err| eyre::eyre!("invalid ENR {line}: {err}"))?);
+            }
+        }
+
+        if let Some(secs) = self.crawl_duration {
+            enrs.extend(crawl(discv5_config, &fork_filter, Duration::from_secs(secs)).await?);
+        }
+
+        if enrs.is_empty() && self.links.is_empty() {
+            eyre::bail!(
+                "No records to publish. Set `--enr`, `--enr-file`, `--link` or `--crawl-duration`"
+            )
+        }
+
+        let seq = match self.seq {
+            Some(seq) => seq,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+
+        let tree = EnrTreeBuilder::new(self.domain)
+            .with_sequence_number(seq)
+            .with_enrs(enrs)
+            .with_links(self.links)
+            .build(&key)?;
+
+        let output = match self.format {
+            OutputFormat::Zone => tree.to_zone_file(self.ttl),
+            OutputFormat::Json => serde_json::to_string_pretty(&tree.txt_records())?,
+        };
+
+        match &self.output {
+            Some(path) => {
+                fs::write(path, output)?;
+                info!(path=?path, "Wrote DNS tree");
+            }
+            None => println!("{output}"),
+        }
+        info!(link=%tree.link(), entries=tree.entries().len(), seq, "Built DNS tree");
+
+        Ok(())
+    }
+}
+
+/// Runs discv5 for the given duration and returns the records of all discovered nodes that
+/// advertise an `RLPx` port and a fork id compatible with the given fork filter.
+async fn crawl(
+    config: reth_discv5::Config,
+    fork_filter: &ForkFilter,
+    duration: Duration,
+) -> eyre::Result<Vec<Enr<SecretKey>>> {
+    let sk = reth_network::config::rng_secret_key();
+    let (discv5, mut updates, _local_node_record) = Discv5::start(&sk, config).await?;
+
+    info!(?duration, "Crawling discv5 network");
+
+    let mut discovered = HashMap::new();
+    let crawl = async {
+        while let Some(update) = updates.recv().await {
+            if let discv5::Event::Discovered(enr) | discv5::Event::SessionEstablished(enr, _) =
+                update
+            {
+                discovered.insert(enr.node_id(), enr);
+            }
+        }
+    };
+    let _ = tokio::time::timeout(duration, crawl).await;
+
+    for enr in discv5.with_discv5(|discv5| discv5.table_entries_enr()) {
+        discovered.insert(enr.node_id(), enr);
+    }
+
+    let enrs = discovered
+        .into_values()
+        .filter(|enr| {
+            discv5.get_fork_id(enr).is_ok_and(|fork_id| fork_filter.validate(fork_id).is_ok())
+        })
+        .filter(|enr| enr.tcp4().or_else(|| enr.tcp6()).is_some())
+        .map(|enr| Enr::<SecretKey>::from(EnrCombinedKeyWrapper(enr)))
+        .collect::<Vec<_>>();
+
+    info!(nodes = enrs.len(), "Finished crawling discv5 network");
+
+    Ok(enrs)
+}
@@ -1,22 +1,26 @@
 //! P2P Debugging tool
 
-use std::{path::PathBuf, sync::Arc};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
 
 use alloy_eips::BlockHashOrNumber;
 use backon::{ConstantBuilder, Retryable};
 use clap::{Parser, Subcommand};
-use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks, Head};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_util::{get_secret_key, hash_or_num_value_parser};
 use reth_config::Config;
+use reth_discv5::NetworkStackId;
 use reth_network::{BlockDownloaderProvider, NetworkConfigBuilder, NetworkPrimitives};
 use reth_network_p2p::bodies::client::BodiesClient;
+use reth_network_peers::NodeRecord;
 use reth_node_core::{
-    args::{DatabaseArgs, DatadirArgs, NetworkArgs},
+    args::{DatabaseArgs, DatadirArgs, DiscoveryArgs, NetworkArgs},
     utils::get_single_header,
 };
 
 pub mod bootnode;
+pub mod crawl;
+pub mod dns;
 mod rlpx;
 
 /// `reth p2p` command
@@ -72,11 +76,42 @@ pub enum Subcommands {
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
+    /// Build a signed EIP-1459 DNS tree of node records
+    Dns(dns::Command),
+    /// Crawl the discovery network and write a census of the found nodes
+    Crawl(crawl::Command),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
     /// Execute `p2p` command
     pub async fn execute<N: NetworkPrimitives>(self) -> eyre::Result<()> {
+        let rlpx_socket = (self.network.addr, self.network.port).into();
+        let boot_nodes = self.chain.bootnodes().unwrap_or_default();
+
+        // these commands run discovery standalone and don't need the network
+        match self.command {
+            Subcommands::Dns(command) => {
+                let discv5_config =
+                    discv5_config(&self.network.discovery, &*self.chain, rlpx_socket, boot_nodes);
+                let fork_filter = self.chain.fork_filter(genesis_head(&*self.chain));
+                return command.execute(discv5_config, fork_filter).await
+            }
+            Subcommands::Crawl(command) => {
+                let discv4_addr =
+                    SocketAddr::new(self.network.discovery.addr, self.network.discovery.port);
+                let discv5_config = discv5_config(
+                    &self.network.discovery,
+                    &*self.chain,
+                    rlpx_socket,
+                    boot_nodes.clone(),
+                );
+                return command
+                    .execute::<N, _>(&*self.chain, discv4_addr, boot_nodes, discv5_config)
+                    .await
+            }
+            _ => {}
+        }
+
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
 
@@ -97,9 +132,6 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
         let secret_key_path =
             self.network.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
         let p2p_secret_key = get_secret_key(&secret_key_path)?;
-        let rlpx_socket = (self.network.addr, self.network.port).into();
-        let boot_nodes = self.chain.bootnodes().unwrap_or_default();
-
         let net = NetworkConfigBuilder::<N>::new(p2p_secret_key)
             .peer_config(config.peers_config_with_basic_nodes_from_file(None))
             .external_ip_resolver(self.network.nat)
@@ -161,12 +193,44 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Rlpx(command) => {
                 command.execute().await?;
             }
+            Subcommands::Dns(_) | Subcommands::Crawl(_) => {
+                unreachable!("handled before the network is started")
+            }
         }
 
         Ok(())
     }
 }
 
+/// Returns the discv5 config for commands that run discovery standalone, advertising the chain's
+/// latest fork.
+fn discv5_config<Spec: EthChainSpec + Hardforks>(
+    discovery: &DiscoveryArgs,
+    chain: &Spec,
+    rlpx_socket: SocketAddr,
+    boot_nodes: Vec<NodeRecord>,
+) -> reth_discv5::Config {
+    let mut builder = discovery.discovery_v5_builder(rlpx_socket, boot_nodes);
+    if let Some(network_stack_id) = NetworkStackId::id(chain) {
+        builder = builder.fork(network_stack_id, chain.latest_fork_id());
+    }
+    builder.build()
+}
+
+/// Returns the [`Head`] at the chain's genesis block.
+///
+/// Fork filters created for this head accept peers on any fork of the chain known to us.
+fn genesis_head<Spec: EthChainSpec>(chain: &Spec) -> Head {
+    let genesis = chain.genesis();
+    Head {
+        hash: chain.genesis_hash(),
+        number: 0,
+        timestamp: genesis.timestamp,
+        difficulty: genesis.difficulty,
+        total_difficulty: genesis.difficulty,
+    }
+}
+
 impl<C: ChainSpecParser> Command<C> {
     /// Returns the underlying chain being used to run this command
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
@@ -17,9 +17,10 @@ use reth_node_api::{HeaderTy, ReceiptTy, TxTy};
 use reth_node_core::args::StageEnum;
 use reth_provider::{
     writer::UnifiedStorageWriter, DatabaseProviderFactory, StaticFileProviderFactory,
+    StaticFileWriter,
 };
 use reth_prune::PruneSegment;
-use reth_stages::StageId;
+use reth_stages::{stages::SnapSyncStage, StageId};
 use reth_static_file_types::StaticFileSegment;
 use std::sync::Arc;
 
@@ -46,6 +47,7 @@ impl<C: ChainSpecParser> Command<C> {
             StageEnum::Headers => Some(StaticFileSegment::Headers),
             StageEnum::Bodies => Some(StaticFileSegment::Transactions),
             StageEnum::Execution => Some(StaticFileSegment::Receipts),
+            StageEnum::Senders => Some(StaticFileSegment::TransactionSenders),
             _ => None,
         };
 
@@ -95,6 +97,12 @@ impl<C: ChainSpecParser> Command<C> {
                 // Reset pruned numbers to not count them in the next rerun's stage progress
                 reset_prune_checkpoint(tx, PruneSegment::SenderRecovery)?;
                 reset_stage_checkpoint(tx, StageId::SenderRecovery)?;
+
+                // Static file segments start empty, so we need to initialize the genesis block.
+                provider_rw
+                    .static_file_provider()
+                    .latest_writer(StaticFileSegment::TransactionSenders)?
+                    .increment_block(0)?;
             }
             StageEnum::Execution => {
                 tx.clear::<tables::PlainAccountState>()?;
@@ -156,6 +164,17 @@ impl<C: ChainSpecParser> Command<C> {
                 reset_stage_checkpoint(tx, StageId::TransactionLookup)?;
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
+            StageEnum::SnapSync => {
+                tx.clear::<tables::HashedAccounts>()?;
+                tx.clear::<tables::HashedStorages>()?;
+                tx.clear::<tables::Bytecodes>()?;
+
+                reset_stage_checkpoint(tx, SnapSyncStage::<()>::ID)?;
+                tx.delete::<tables::StageCheckpointProgresses>(
+                    SnapSyncStage::<()>::ID.to_string(),
+                    None,
+                )?;
+            }
         }
 
         tx.put::<tables::StageCheckpoints>(StageId::Finish.to_string(), Default::default())?;
@@ -4,7 +4,7 @@
 
 use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::Sealable;
+use alloy_primitives::{Sealable, B256};
 use clap::Parser;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -14,11 +14,15 @@ use reth_config::config::{HashingConfig, SenderRecoveryConfig, TransactionLookup
 use reth_db_api::database_metrics::DatabaseMetrics;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
-    headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    headers::reverse_headers::ReverseHeadersDownloaderBuilder, snap::SnapStateDownloader,
 };
 use reth_eth_wire::NetPrimitivesFor;
 use reth_exex::ExExManagerHandle;
-use reth_network::BlockDownloaderProvider;
+use reth_network::{
+    protocol::IntoRlpxSubProtocol, snap::SnapProtocolHandler, BlockDownloaderProvider,
+    NetworkProtocols,
+};
+use reth_network_api::test_utils::PeersHandleProvider;
 use reth_network_p2p::HeadersClient;
 use reth_node_core::{
     args::{NetworkArgs, StageEnum},
@@ -40,13 +44,13 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, SnapSyncStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
 use std::{any::Any, net::SocketAddr, sync::Arc, time::Instant};
-use tokio::sync::watch;
+use tokio::sync::{mpsc, watch};
 use tracing::*;
 
 /// `reth stage` command
@@ -318,6 +322,45 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     )),
                     None,
                 ),
+                StageEnum::SnapSync => {
+                    let network_secret_path = self
+                        .network
+                        .p2p_secret_key
+                        .clone()
+                        .unwrap_or_else(|| data_dir.p2p_secret());
+                    let p2p_secret_key = get_secret_key(&network_secret_path)?;
+
+                    let default_peers_path = data_dir.known_peers();
+
+                    let network = self
+                        .network
+                        .network_config::<P>(
+                            &config,
+                            provider_factory.chain_spec(),
+                            p2p_secret_key,
+                            default_peers_path,
+                        )
+                        .build(provider_factory.clone())
+                        .start_network()
+                        .await?;
+
+                    // Requests from peers are not served while the state is being downloaded.
+                    let (to_request_handler, _) = mpsc::channel(1);
+                    let snap_handler = SnapProtocolHandler::new(
+                        network.peers_handle().clone(),
+                        to_request_handler,
+                    );
+                    let snap_client = snap_handler.client();
+                    network.add_rlpx_sub_protocol(snap_handler.into_rlpx_sub_protocol());
+
+                    (
+                        Box::new(SnapSyncStage::new(SnapStateDownloader::new(
+                            snap_client,
+                            B256::ZERO,
+                        ))),
+                        None,
+                    )
+                }
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
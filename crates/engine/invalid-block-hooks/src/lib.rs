@@ -1,5 +1,7 @@
 //! Invalid block hook implementations.
 
+mod trace;
 mod witness;
 
+pub use trace::{InvalidBlockOpcodeHook, InvalidBlockPreStateHook};
 pub use witness::InvalidBlockWitnessHook;
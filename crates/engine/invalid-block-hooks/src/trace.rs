@@ -0,0 +1,492 @@
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_primitives::{TxHash, B256};
+use alloy_rpc_types_trace::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace,
+    PreStateConfig, TraceResult,
+};
+use pretty_assertions::Comparison;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm, Evm};
+use reth_primitives_traits::{
+    BlockBody, NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction,
+};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm::DatabaseCommit;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
+
+/// The tracer used by a [`TraceHook`].
+#[derive(Debug, Clone, Copy)]
+enum BlockTracer {
+    /// Geth's `prestateTracer`.
+    PreState,
+    /// Geth's default struct logger.
+    Opcode,
+}
+
+impl BlockTracer {
+    /// Returns the name used in the names of the written files.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::PreState => "prestate",
+            Self::Opcode => "opcode",
+        }
+    }
+
+    /// Returns the tracing options that produce the same traces on the healthy node.
+    fn tracing_options(&self) -> GethDebugTracingOptions {
+        match self {
+            Self::PreState => GethDebugTracingOptions {
+                tracer: Some(GethDebugBuiltInTracerType::PreStateTracer.into()),
+                ..Default::default()
+            },
+            Self::Opcode => GethDebugTracingOptions::default(),
+        }
+    }
+
+    /// Returns the configuration of the inspector that records the traces.
+    fn inspector_config(&self) -> TracingInspectorConfig {
+        match self {
+            Self::PreState => {
+                TracingInspectorConfig::from_geth_prestate_config(&PreStateConfig::default())
+            }
+            Self::Opcode => {
+                TracingInspectorConfig::from_geth_config(&GethDefaultTracingOptions::default())
+            }
+        }
+    }
+}
+
+/// Re-executes an invalid block, traces each of its transactions and saves the traces to files.
+#[derive(Debug)]
+struct TraceHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the traces to. Additionally, diff files will be written to this
+    /// directory in case the traces differ from the ones of the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    /// The tracer to trace the transactions with.
+    tracer: BlockTracer,
+}
+
+impl<P, E, N> TraceHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let traces = self.trace_block(parent_header, block)?;
+
+        let mut paths = Vec::with_capacity(traces.len());
+        for (index, (tx_hash, trace)) in traces.iter().enumerate() {
+            paths.push(self.save_file(
+                format!(
+                    "{}_{}.{index}_{tx_hash}.{}.re_executed.json",
+                    block.number(),
+                    block.hash(),
+                    self.tracer.name()
+                ),
+                trace,
+            )?);
+        }
+
+        let Some(healthy_node_client) = &self.healthy_node_client else { return Ok(()) };
+
+        // Compare the traces against the healthy node. The healthy node traces its canonical block
+        // at the same height, so only the transactions that are included in both blocks can be
+        // compared.
+        let healthy_traces = futures::executor::block_on(async move {
+            DebugApiClient::<()>::debug_trace_block_by_number(
+                healthy_node_client,
+                block.number().into(),
+                Some(self.tracer.tracing_options()),
+            )
+            .await
+        })?;
+        let healthy_traces = healthy_traces
+            .into_iter()
+            .filter_map(|result| match result {
+                TraceResult::Success { result, tx_hash: Some(tx_hash) } => Some((tx_hash, result)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        for ((index, (tx_hash, trace)), re_executed_path) in traces.iter().enumerate().zip(paths) {
+            let Some(healthy_trace) = healthy_traces.get(tx_hash) else { continue };
+            if trace == healthy_trace {
+                continue
+            }
+
+            let prefix = format!(
+                "{}_{}.{index}_{tx_hash}.{}",
+                block.number(),
+                block.hash(),
+                self.tracer.name()
+            );
+            let healthy_path = self.save_file(format!("{prefix}.healthy.json"), healthy_trace)?;
+            let diff_path = self.save_diff(format!("{prefix}.diff"), trace, healthy_trace)?;
+            warn!(
+                target: "engine::invalid_block_hooks::trace",
+                tracer = self.tracer.name(),
+                %tx_hash,
+                diff_path = %diff_path.display(),
+                re_executed_path = %re_executed_path.display(),
+                healthy_path = %healthy_path.display(),
+                "Transaction trace mismatch against healthy node"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Executes the transactions of the block on top of the parent state and returns the trace of
+    /// each transaction.
+    fn trace_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<Vec<(TxHash, GethTrace)>> {
+        let mut db = CacheDB::new(StateProviderDatabase::new(
+            self.provider.state_by_block_hash(parent_header.hash())?,
+        ));
+        let evm_env = self.evm_config.evm_env(block.header());
+
+        // Apply the system calls that are executed before the transactions, these are not traced
+        let mut evm = self.evm_config.evm_with_env(&mut db, evm_env.clone());
+        SystemCaller::new(self.provider.chain_spec())
+            .apply_pre_execution_changes(block.header(), &mut evm)?;
+        drop(evm);
+
+        let mut traces = Vec::with_capacity(block.body().transaction_count());
+        for tx in block.transactions_recovered() {
+            let tx_hash = *tx.tx_hash();
+            let gas_limit = tx.gas_limit();
+
+            let mut inspector = TracingInspector::new(self.tracer.inspector_config());
+            let res = self
+                .evm_config
+                .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+                .transact(self.evm_config.tx_env(tx))?;
+
+            let builder = inspector.with_transaction_gas_limit(gas_limit).into_geth_builder();
+            let trace: GethTrace = match self.tracer {
+                BlockTracer::PreState => {
+                    builder.geth_prestate_traces(&res, &PreStateConfig::default(), &db)?.into()
+                }
+                BlockTracer::Opcode => builder
+                    .geth_traces(
+                        res.result.gas_used(),
+                        res.result.output().cloned().unwrap_or_default(),
+                        GethDefaultTracingOptions::default(),
+                    )
+                    .into(),
+            };
+
+            db.commit(res.state);
+            traces.push((tx_hash, trace));
+        }
+
+        Ok(traces)
+    }
+
+    /// Saves the diff of two values into a file with the given name in the output directory.
+    fn save_diff<T: PartialEq + Debug>(
+        &self,
+        filename: String,
+        original: &T,
+        new: &T,
+    ) -> eyre::Result<PathBuf> {
+        let path = self.output_directory.join(filename);
+        let diff = Comparison::new(original, new);
+        File::create(&path)?.write_all(diff.to_string().as_bytes())?;
+
+        Ok(path)
+    }
+
+    fn save_file<T: Serialize>(&self, filename: String, value: &T) -> eyre::Result<PathBuf> {
+        let path = self.output_directory.join(filename);
+        File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+        Ok(path)
+    }
+
+    fn invoke(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(
+                target: "engine::invalid_block_hooks::trace",
+                tracer = self.tracer.name(),
+                %err,
+                "Failed to invoke hook"
+            );
+        }
+    }
+}
+
+/// Generates a `prestateTracer` trace for every transaction of the given block and saves them to
+/// files.
+#[derive(Debug)]
+pub struct InvalidBlockPreStateHook<P, E>(TraceHook<P, E>);
+
+impl<P, E> InvalidBlockPreStateHook<P, E> {
+    /// Creates a new prestate hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self(TraceHook {
+            provider,
+            evm_config,
+            output_directory,
+            healthy_node_client,
+            tracer: BlockTracer::PreState,
+        })
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        self.0.invoke(parent_header, block)
+    }
+}
+
+/// Generates an opcode level struct log for every transaction of the given block and saves them to
+/// files.
+#[derive(Debug)]
+pub struct InvalidBlockOpcodeHook<P, E>(TraceHook<P, E>);
+
+impl<P, E> InvalidBlockOpcodeHook<P, E> {
+    /// Creates a new opcode hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self(TraceHook {
+            provider,
+            evm_config,
+            output_directory,
+            healthy_node_client,
+            tracer: BlockTracer::Opcode,
+        })
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+        + Send
+        + Sync
+        + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        self.0.invoke(parent_header, block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, SignableTransaction, TxLegacy};
+    use alloy_primitives::{Address, Signature, TxKind, U256};
+    use alloy_rpc_types_trace::geth::DefaultFrame;
+    use jsonrpsee::{
+        http_client::HttpClientBuilder,
+        server::{ServerBuilder, ServerHandle},
+        RpcModule,
+    };
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::Block as _;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use std::{collections::BTreeMap, fs, path::Path};
+
+    const SENDER: Address = Address::repeat_byte(0x11);
+    const RECIPIENT: Address = Address::repeat_byte(0x22);
+
+    /// Returns a provider with a funded sender and a block with a transfer from it on top of the
+    /// returned parent.
+    fn transfer_block() -> (MockEthProvider, SealedHeader, RecoveredBlock<Block>) {
+        let provider = MockEthProvider::default();
+        provider.add_account(SENDER, ExtendedAccount::new(0, U256::from(1_000_000)));
+
+        let parent = SealedHeader::seal_slow(Header { gas_limit: 1_000_000, ..Default::default() });
+        let tx = TransactionSigned::Legacy(
+            TxLegacy {
+                gas_price: 1,
+                gas_limit: 21_000,
+                to: TxKind::Call(RECIPIENT),
+                value: U256::from(1),
+                ..Default::default()
+            }
+            .into_signed(Signature::test_signature()),
+        );
+        let block = Block {
+            header: Header {
+                number: 1,
+                parent_hash: parent.hash(),
+                gas_limit: 1_000_000,
+                timestamp: 1,
+                ..Default::default()
+            },
+            body: BlockBody { transactions: vec![tx], ..Default::default() },
+        };
+
+        (provider, parent, block.seal_slow().with_senders(vec![SENDER]))
+    }
+
+    /// Starts a node that serves the given traces for `debug_traceBlockByNumber`.
+    async fn healthy_node(
+        traces: Vec<TraceResult>,
+    ) -> (jsonrpsee::http_client::HttpClient, ServerHandle) {
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let mut module = RpcModule::new(());
+        module.register_method("debug_traceBlockByNumber", move |_, _, _| traces.clone()).unwrap();
+        let client = HttpClientBuilder::default().build(format!("http://{addr}")).unwrap();
+        (client, server.start(module))
+    }
+
+    /// Returns the names of the files in the directory, sorted.
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn prestate_hook_writes_traces() {
+        let (provider, parent, block) = transfer_block();
+        let dir = tempfile::tempdir().unwrap();
+        let hook = InvalidBlockPreStateHook::new(
+            provider.clone(),
+            EthEvmConfig::new(provider.chain_spec()),
+            dir.path().to_path_buf(),
+            None,
+        );
+
+        hook.0.on_invalid_block(&parent, &block).unwrap();
+
+        let tx_hash = *block.body().transactions[0].tx_hash();
+        let name = format!("1_{}.0_{tx_hash}.prestate.re_executed.json", block.hash());
+        assert_eq!(file_names(dir.path()), vec![name.clone()]);
+
+        // the prestate contains the accounts touched by the transfer
+        let prestate: BTreeMap<Address, serde_json::Value> =
+            serde_json::from_slice(&fs::read(dir.path().join(name)).unwrap()).unwrap();
+        assert!(prestate.contains_key(&SENDER));
+        assert!(prestate.contains_key(&RECIPIENT));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn opcode_hook_diffs_traces_against_healthy_node() {
+        let (provider, parent, block) = transfer_block();
+        let tx_hash = *block.body().transactions[0].tx_hash();
+        let prefix = format!("1_{}.0_{tx_hash}.opcode", block.hash());
+        let hook = |dir: &Path, client| {
+            InvalidBlockOpcodeHook::new(
+                provider.clone(),
+                EthEvmConfig::new(provider.chain_spec()),
+                dir.to_path_buf(),
+                client,
+            )
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let trace = hook(dir.path(), None).0.trace_block(&parent, &block).unwrap().remove(0).1;
+        let GethTrace::Default(frame) = &trace else { panic!("expected struct logs") };
+        assert!(!frame.failed);
+
+        // traces that match the healthy node are not diffed
+        let (client, _server) = healthy_node(vec![TraceResult::Success {
+            result: trace.clone(),
+            tx_hash: Some(tx_hash),
+        }])
+        .await;
+        let hook_ = hook(dir.path(), Some(client));
+        let (parent_, block_) = (parent.clone(), block.clone());
+        tokio::task::spawn_blocking(move || hook_.0.on_invalid_block(&parent_, &block_))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(file_names(dir.path()), vec![format!("{prefix}.re_executed.json")]);
+
+        // a mismatch writes the trace of the healthy node and the diff
+        let dir = tempfile::tempdir().unwrap();
+        let healthy_trace = GethTrace::Default(DefaultFrame { failed: true, ..frame.clone() });
+        let (client, _server) = healthy_node(vec![TraceResult::Success {
+            result: healthy_trace,
+            tx_hash: Some(tx_hash),
+        }])
+        .await;
+        let hook_ = hook(dir.path(), Some(client));
+        tokio::task::spawn_blocking(move || hook_.0.on_invalid_block(&parent, &block))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            file_names(dir.path()),
+            vec![
+                format!("{prefix}.diff"),
+                format!("{prefix}.healthy.json"),
+                format!("{prefix}.re_executed.json"),
+            ]
+        );
+        let diff = fs::read_to_string(dir.path().join(format!("{prefix}.diff"))).unwrap();
+        // only the `failed` flag differs, unchanged lines are indented
+        let changed =
+            diff.lines().skip(1).filter(|line| !line.starts_with(' ')).collect::<Vec<_>>();
+        assert_eq!(changed.len(), 2);
+        assert!(changed.iter().all(|line| line.contains("failed")));
+    }
+}
@@ -1,11 +1,12 @@
 //! Contains the implementation of the mining mode for the local engine.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{TxHash, B256};
-use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_primitives::{TxHash, B256, U256};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadId};
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
-use reth_engine_primitives::BeaconEngineMessage;
+use reth_engine_primitives::{BeaconEngineMessage, LocalMinerError, LocalMinerMessage};
+use reth_evm::state_override::StateOverrideLayer;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
@@ -13,13 +14,17 @@ use reth_payload_primitives::{
 use reth_provider::BlockReader;
 use reth_transaction_pool::TransactionPool;
 use std::{
+    collections::BTreeMap,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     time::Interval,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -72,6 +77,25 @@ impl Future for MiningMode {
     }
 }
 
+/// Waits for the next tick of the given interval, never resolves if there is none.
+async fn tick_interval(interval: &mut Option<(u64, Interval)>) {
+    match interval {
+        Some((_, interval)) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Chain head recorded by a snapshot of the [`LocalMiner`].
+#[derive(Debug)]
+struct MinerSnapshot {
+    /// Timestamp of the snapshotted head.
+    last_timestamp: u64,
+    /// Latest mined blocks at the time of the snapshot.
+    last_block_hashes: Vec<B256>,
+}
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
 pub struct LocalMiner<T: PayloadTypes, B> {
@@ -87,6 +111,24 @@ pub struct LocalMiner<T: PayloadTypes, B> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// Receiver for messages from [`LocalMinerHandle`](reth_engine_primitives::LocalMinerHandle)s.
+    from_handle: UnboundedReceiver<LocalMinerMessage>,
+    /// Whether blocks are mined on the configured [`MiningMode`].
+    mining_enabled: bool,
+    /// Interval mining configured at runtime, takes precedence over the [`MiningMode`].
+    interval: Option<(u64, Interval)>,
+    /// Offset in seconds applied to the current time when computing block timestamps.
+    time_offset: i64,
+    /// Timestamp for the next block, if explicitly set.
+    next_timestamp: Option<u64>,
+    /// Fixed interval between the timestamps of consecutive blocks.
+    timestamp_interval: Option<u64>,
+    /// Snapshots of the chain head by id.
+    snapshots: BTreeMap<U256, MinerSnapshot>,
+    /// Id of the next snapshot.
+    next_snapshot_id: U256,
+    /// State overrides that are staged for the next mined block.
+    state_overrides: Option<StateOverrideLayer>,
 }
 
 impl<T, B> LocalMiner<T, B>
@@ -101,6 +143,8 @@ where
         to_engine: UnboundedSender<BeaconEngineMessage<T>>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<T>,
+        from_handle: UnboundedReceiver<LocalMinerMessage>,
+        state_overrides: Option<StateOverrideLayer>,
     ) {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
@@ -112,6 +156,15 @@ where
             payload_builder,
             last_timestamp: latest_header.timestamp(),
             last_block_hashes: vec![latest_header.hash()],
+            from_handle,
+            mining_enabled: true,
+            interval: None,
+            time_offset: 0,
+            next_timestamp: None,
+            timestamp_interval: None,
+            snapshots: BTreeMap::new(),
+            next_snapshot_id: U256::ZERO,
+            state_overrides,
         };
 
         // Spawn the miner
@@ -124,11 +177,19 @@ where
         loop {
             tokio::select! {
                 // Wait for the interval or the pool to receive a transaction
-                _ = &mut self.mode => {
+                _ = &mut self.mode, if self.mining_enabled && self.interval.is_none() => {
+                    if let Err(e) = self.advance().await {
+                        error!(target: "engine::local", "Error advancing the chain: {:?}", e);
+                    }
+                }
+                // Wait for the interval configured at runtime
+                _ = tick_interval(&mut self.interval), if self.interval.is_some() => {
                     if let Err(e) = self.advance().await {
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
                     }
                 }
+                // Handle requests from the miner handles
+                Some(msg) = self.from_handle.recv() => self.on_message(msg).await,
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
                     if let Err(e) = self.update_forkchoice_state().await {
@@ -139,6 +200,124 @@ where
         }
     }
 
+    /// Handles a message received from a
+    /// [`LocalMinerHandle`](reth_engine_primitives::LocalMinerHandle).
+    async fn on_message(&mut self, msg: LocalMinerMessage) {
+        match msg {
+            LocalMinerMessage::Mine { blocks, interval, tx } => {
+                let mut hashes = Vec::with_capacity(blocks as usize);
+                let mut result = Ok(());
+                for i in 0..blocks {
+                    if let Some(interval) = interval.filter(|_| i > 0) {
+                        self.next_timestamp = Some(self.last_timestamp + interval);
+                    }
+                    match self.advance().await {
+                        Ok(hash) => hashes.push(hash),
+                        Err(err) => {
+                            result = Err(err);
+                            break
+                        }
+                    }
+                }
+                // the mined blocks must be canonical once the request is answered
+                if result.is_ok() && !hashes.is_empty() {
+                    result = self.update_forkchoice_state().await;
+                }
+                let _ = tx.send(result.map(|_| hashes).map_err(|err| {
+                    error!(target: "engine::local", "Error advancing the chain: {:?}", err);
+                    LocalMinerError::Internal(err.into())
+                }));
+            }
+            LocalMinerMessage::SetAutomine(enabled) => {
+                self.mining_enabled = enabled;
+                if enabled {
+                    self.interval = None;
+                }
+            }
+            LocalMinerMessage::GetAutomine(tx) => {
+                let _ = tx.send(
+                    self.mining_enabled &&
+                        self.interval.is_none() &&
+                        matches!(self.mode, MiningMode::Instant(_)),
+                );
+            }
+            LocalMinerMessage::SetIntervalMining(seconds) => {
+                self.interval = (seconds > 0).then(|| {
+                    let period = Duration::from_secs(seconds);
+                    (
+                        seconds,
+                        tokio::time::interval_at(tokio::time::Instant::now() + period, period),
+                    )
+                });
+            }
+            LocalMinerMessage::GetIntervalMining(tx) => {
+                let _ = tx.send(self.interval.as_ref().map(|(seconds, _)| *seconds));
+            }
+            LocalMinerMessage::SetNextBlockTimestamp { timestamp, tx } => {
+                let res = if timestamp <= self.last_timestamp {
+                    Err(LocalMinerError::TimestampTooLow { timestamp, latest: self.last_timestamp })
+                } else {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            LocalMinerMessage::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add(seconds as i64);
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerMessage::SetTime { timestamp, tx } => {
+                self.time_offset = timestamp as i64 - Self::now() as i64;
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerMessage::SetBlockTimestampInterval(seconds) => {
+                self.timestamp_interval = Some(seconds);
+            }
+            LocalMinerMessage::RemoveBlockTimestampInterval(tx) => {
+                let _ = tx.send(self.timestamp_interval.take().is_some());
+            }
+            LocalMinerMessage::Snapshot(tx) => {
+                let id = self.next_snapshot_id;
+                self.next_snapshot_id += U256::from(1);
+                self.snapshots.insert(
+                    id,
+                    MinerSnapshot {
+                        last_timestamp: self.last_timestamp,
+                        last_block_hashes: self.last_block_hashes.clone(),
+                    },
+                );
+                let _ = tx.send(id);
+            }
+            LocalMinerMessage::Revert { id, tx } => {
+                let res = self.revert(id).await.map_err(|err| {
+                    error!(target: "engine::local", "Error reverting the chain: {:?}", err);
+                    LocalMinerError::Internal(err.into())
+                });
+                let _ = tx.send(res);
+            }
+        }
+    }
+
+    /// Returns the current unix timestamp in seconds.
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("cannot be earlier than UNIX_EPOCH")
+            .as_secs()
+    }
+
+    /// Returns the timestamp for the next block.
+    fn next_timestamp(&mut self) -> u64 {
+        if let Some(timestamp) = self.next_timestamp.take() {
+            return timestamp
+        }
+        if let Some(interval) = self.timestamp_interval {
+            return self.last_timestamp + interval
+        }
+        let now = (Self::now() as i64).saturating_add(self.time_offset).max(0) as u64;
+        std::cmp::max(self.last_timestamp + 1, now)
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -174,18 +353,19 @@ where
 
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    ///
+    /// Returns the hash of the new block.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let timestamp = self.next_timestamp();
+        let state = self.forkchoice_state();
+
+        if let Some(state_overrides) = &self.state_overrides {
+            state_overrides.stage(state.head_block_hash);
+        }
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
-            state: self.forkchoice_state(),
+            state,
             payload_attrs: Some(self.payload_attributes_builder.build(timestamp)),
             tx,
             version: EngineApiMessageVersion::default(),
@@ -197,14 +377,23 @@ where
         }
 
         let payload_id = res.payload_id.ok_or_eyre("No payload id")?;
+        let hash = self.insert_payload(payload_id).await?;
+        self.on_new_block(hash, timestamp);
 
+        Ok(hash)
+    }
+
+    /// Resolves the payload with the given id and inserts it through newPayload.
+    ///
+    /// Returns the hash of the inserted block.
+    async fn insert_payload(&self, payload_id: PayloadId) -> eyre::Result<B256> {
         let Some(Ok(payload)) =
             self.payload_builder.resolve_kind(payload_id, PayloadKind::WaitForPending).await
         else {
             eyre::bail!("No payload")
         };
 
-        let block = payload.block();
+        let hash = payload.block().hash();
 
         let (tx, rx) = oneshot::channel();
         let payload = T::block_to_payload(payload.block().clone());
@@ -216,14 +405,38 @@ where
             eyre::bail!("Invalid payload")
         }
 
+        Ok(hash)
+    }
+
+    /// Records a new head block.
+    fn on_new_block(&mut self, hash: B256, timestamp: u64) {
         self.last_timestamp = timestamp;
-        self.last_block_hashes.push(block.hash());
+        self.last_block_hashes.push(hash);
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
             self.last_block_hashes =
                 self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
         }
+    }
 
-        Ok(())
+    /// Reverts the chain to the snapshot with the given id, dropping it and all later snapshots.
+    ///
+    /// The engine unwinds its canonical chain when the forkchoice head is an ancestor of the
+    /// canonical head, so this makes the snapshotted head canonical again.
+    ///
+    /// Returns `false` if the snapshot does not exist.
+    async fn revert(&mut self, id: U256) -> eyre::Result<bool> {
+        let mut snapshots = self.snapshots.split_off(&id);
+        let Some(snapshot) = snapshots.remove(&id) else { return Ok(false) };
+
+        if let Some(state_overrides) = &self.state_overrides {
+            state_overrides.clear();
+        }
+        self.next_timestamp = None;
+        self.last_timestamp = snapshot.last_timestamp;
+        self.last_block_hashes = snapshot.last_block_hashes;
+        self.update_forkchoice_state().await?;
+
+        Ok(true)
     }
 }
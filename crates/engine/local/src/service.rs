@@ -18,7 +18,9 @@ use crate::miner::{LocalMiner, MiningMode};
 use futures_util::{Stream, StreamExt};
 use reth_chainspec::EthChainSpec;
 use reth_consensus::{ConsensusError, FullConsensus};
-use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconEngineMessage, EngineValidator};
+use reth_engine_primitives::{
+    BeaconConsensusEngineEvent, BeaconEngineMessage, EngineValidator, LocalMinerMessage,
+};
 use reth_engine_service::service::EngineMessageStream;
 use reth_engine_tree::{
     chain::{ChainEvent, HandlerEvent},
@@ -39,7 +41,7 @@ use reth_provider::{
 };
 use reth_prune::PrunerWithFactory;
 use reth_stages_api::MetricEventsSender;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tracing::error;
 
 /// Provides a local dev service engine that can be used to drive the
@@ -80,6 +82,7 @@ where
         mode: MiningMode,
         payload_attributes_builder: B,
         evm_config: C,
+        from_miner_handle: UnboundedReceiver<LocalMinerMessage>,
     ) -> Self
     where
         B: PayloadAttributesBuilder<<N::Payload as PayloadTypes>::PayloadAttributes>,
@@ -90,6 +93,8 @@ where
         let engine_kind =
             if chain_spec.is_optimism() { EngineApiKind::OpStack } else { EngineApiKind::Ethereum };
 
+        let state_overrides = evm_config.state_override_layer().cloned();
+
         let persistence_handle =
             PersistenceHandle::<N::Primitives>::spawn_service(provider, pruner, sync_metrics_tx);
         let canonical_in_memory_state = blockchain_db.canonical_in_memory_state();
@@ -101,7 +106,8 @@ where
             persistence_handle,
             payload_builder.clone(),
             canonical_in_memory_state,
-            tree_config,
+            // the miner reverts the chain to a snapshot by making its head canonical again
+            tree_config.with_unwind_canonical_header(true),
             invalid_block_hook,
             engine_kind,
             evm_config,
@@ -115,6 +121,8 @@ where
             to_engine,
             mode,
             payload_builder,
+            from_miner_handle,
+            state_overrides,
         );
 
         Self { handler, incoming_requests: from_engine }
@@ -1,7 +1,7 @@
 //! Events emitted by the beacon consensus engine.
 
 use crate::ForkchoiceStatus;
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use alloy_rpc_types_engine::ForkchoiceState;
@@ -24,8 +24,8 @@ pub enum BeaconConsensusEngineEvent<N: NodePrimitives = EthPrimitives> {
     CanonicalBlockAdded(ExecutedBlockWithTrieUpdates<N>, Duration),
     /// A canonical chain was committed, and the elapsed time committing the data
     CanonicalChainCommitted(Box<SealedHeader<N::BlockHeader>>, Duration),
-    /// The consensus engine processed an invalid block.
-    InvalidBlock(Box<SealedBlock<N::Block>>),
+    /// The consensus engine processed an invalid block, and the reason it was rejected
+    InvalidBlock(Box<SealedBlock<N::Block>>, InvalidBlockReason),
     /// The consensus engine is involved in live sync, and has specific progress
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
 }
@@ -63,8 +63,8 @@ where
             Self::CanonicalChainCommitted(block, duration) => {
                 write!(f, "CanonicalChainCommitted({:?}, {duration:?})", block.num_hash())
             }
-            Self::InvalidBlock(block) => {
-                write!(f, "InvalidBlock({:?})", block.num_hash())
+            Self::InvalidBlock(block, reason) => {
+                write!(f, "InvalidBlock({:?}, {reason})", block.num_hash())
             }
             Self::LiveSyncProgress(progress) => {
                 write!(f, "LiveSyncProgress({progress:?})")
@@ -73,6 +73,31 @@ where
     }
 }
 
+/// The reason the consensus engine rejected a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidBlockReason {
+    /// The block failed validation, with the validation error.
+    Validation(String),
+    /// The block descends from the given, previously rejected block.
+    InvalidAncestor(B256),
+}
+
+impl InvalidBlockReason {
+    /// Returns true if the block was only rejected because of an invalid ancestor.
+    pub const fn is_invalid_ancestor(&self) -> bool {
+        matches!(self, Self::InvalidAncestor(_))
+    }
+}
+
+impl Display for InvalidBlockReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Validation(err) => f.write_str(err),
+            Self::InvalidAncestor(hash) => write!(f, "links to previously rejected block {hash}"),
+        }
+    }
+}
+
 /// Progress of the consensus engine during live sync.
 #[derive(Clone, Debug)]
 pub enum ConsensusEngineLiveSyncProgress {
@@ -39,6 +39,9 @@ pub use event::*;
 mod invalid_block_hook;
 pub use invalid_block_hook::InvalidBlockHook;
 
+mod local_miner;
+pub use local_miner::{LocalMinerError, LocalMinerHandle, LocalMinerMessage};
+
 pub mod config;
 pub use config::*;
 
@@ -0,0 +1,175 @@
+//! Handle for controlling the local miner of a development node.
+
+use alloc::{boxed::Box, vec::Vec};
+use alloy_primitives::{B256, U256};
+use tokio::sync::{mpsc, oneshot};
+
+/// Errors returned by the local miner.
+#[derive(Debug, thiserror::Error)]
+pub enum LocalMinerError {
+    /// Thrown when the local miner task is unavailable/stopped.
+    #[error("local miner task stopped")]
+    MinerUnavailable,
+    /// Thrown when the requested timestamp is not greater than the timestamp of the latest block.
+    #[error("timestamp {timestamp} must be greater than the latest block timestamp {latest}")]
+    TimestampTooLow {
+        /// The requested timestamp.
+        timestamp: u64,
+        /// The timestamp of the latest block.
+        latest: u64,
+    },
+    /// An internal error occurred while advancing the chain.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+/// Messages that can be sent to the local miner.
+#[derive(Debug)]
+pub enum LocalMinerMessage {
+    /// Mines the given number of blocks, spacing their timestamps by `interval` seconds if set.
+    Mine {
+        /// Number of blocks to mine.
+        blocks: u64,
+        /// Timestamp interval between the mined blocks.
+        interval: Option<u64>,
+        /// The sender for returning the hashes of the mined blocks.
+        tx: oneshot::Sender<Result<Vec<B256>, LocalMinerError>>,
+    },
+    /// Enables or disables mining on the configured trigger.
+    SetAutomine(bool),
+    /// Returns whether blocks are mined as soon as transactions arrive.
+    GetAutomine(oneshot::Sender<bool>),
+    /// Mines a block every given number of seconds, zero disables interval mining.
+    SetIntervalMining(u64),
+    /// Returns the interval mining period in seconds, if enabled.
+    GetIntervalMining(oneshot::Sender<Option<u64>>),
+    /// Sets the timestamp of the next block.
+    SetNextBlockTimestamp {
+        /// The timestamp of the next block.
+        timestamp: u64,
+        /// The sender for returning the result.
+        tx: oneshot::Sender<Result<(), LocalMinerError>>,
+    },
+    /// Shifts the clock used for block timestamps forward by the given number of seconds.
+    IncreaseTime {
+        /// The number of seconds to add.
+        seconds: u64,
+        /// The sender for returning the total time offset in seconds.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Sets the clock used for block timestamps to the given timestamp.
+    SetTime {
+        /// The new current time.
+        timestamp: u64,
+        /// The sender for returning the total time offset in seconds.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Sets a fixed interval between the timestamps of consecutive blocks.
+    SetBlockTimestampInterval(u64),
+    /// Removes the fixed block timestamp interval, returns whether one was set.
+    RemoveBlockTimestampInterval(oneshot::Sender<bool>),
+    /// Snapshots the current chain head and returns the snapshot id.
+    Snapshot(oneshot::Sender<U256>),
+    /// Reverts the chain to the snapshot with the given id.
+    Revert {
+        /// The snapshot id.
+        id: U256,
+        /// The sender for returning whether the snapshot existed.
+        tx: oneshot::Sender<Result<bool, LocalMinerError>>,
+    },
+}
+
+/// A handle for interacting with the local miner of a development node.
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: mpsc::UnboundedSender<LocalMinerMessage>,
+}
+
+impl LocalMinerHandle {
+    /// Creates a new handle and the receiving end that must be passed to the local miner.
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<LocalMinerMessage>) {
+        let (to_miner, from_handle) = mpsc::unbounded_channel();
+        (Self { to_miner }, from_handle)
+    }
+
+    /// Sends a message to the miner and waits for the response.
+    async fn request<R>(
+        &self,
+        msg: impl FnOnce(oneshot::Sender<R>) -> LocalMinerMessage,
+    ) -> Result<R, LocalMinerError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(msg(tx)).map_err(|_| LocalMinerError::MinerUnavailable)?;
+        rx.await.map_err(|_| LocalMinerError::MinerUnavailable)
+    }
+
+    /// Sends a message to the miner without waiting for a response.
+    fn send(&self, msg: LocalMinerMessage) -> Result<(), LocalMinerError> {
+        self.to_miner.send(msg).map_err(|_| LocalMinerError::MinerUnavailable)
+    }
+
+    /// Mines the given number of blocks and returns their hashes.
+    pub async fn mine(
+        &self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<Vec<B256>, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::Mine { blocks, interval, tx }).await?
+    }
+
+    /// Enables or disables automatic mining.
+    pub fn set_automine(&self, enabled: bool) -> Result<(), LocalMinerError> {
+        self.send(LocalMinerMessage::SetAutomine(enabled))
+    }
+
+    /// Returns whether automatic mining is enabled.
+    pub async fn automine(&self) -> Result<bool, LocalMinerError> {
+        self.request(LocalMinerMessage::GetAutomine).await
+    }
+
+    /// Sets the interval mining period in seconds, zero disables interval mining.
+    pub fn set_interval_mining(&self, seconds: u64) -> Result<(), LocalMinerError> {
+        self.send(LocalMinerMessage::SetIntervalMining(seconds))
+    }
+
+    /// Returns the interval mining period in seconds, if enabled.
+    pub async fn interval_mining(&self) -> Result<Option<u64>, LocalMinerError> {
+        self.request(LocalMinerMessage::GetIntervalMining).await
+    }
+
+    /// Sets the timestamp of the next block.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetNextBlockTimestamp { timestamp, tx }).await?
+    }
+
+    /// Increases the time by the given number of seconds and returns the total time offset.
+    pub async fn increase_time(&self, seconds: u64) -> Result<i64, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::IncreaseTime { seconds, tx }).await
+    }
+
+    /// Sets the current time and returns the total time offset.
+    pub async fn set_time(&self, timestamp: u64) -> Result<i64, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::SetTime { timestamp, tx }).await
+    }
+
+    /// Sets a fixed interval between the timestamps of consecutive blocks.
+    pub fn set_block_timestamp_interval(&self, seconds: u64) -> Result<(), LocalMinerError> {
+        self.send(LocalMinerMessage::SetBlockTimestampInterval(seconds))
+    }
+
+    /// Removes the fixed block timestamp interval, returns whether one was set.
+    pub async fn remove_block_timestamp_interval(&self) -> Result<bool, LocalMinerError> {
+        self.request(LocalMinerMessage::RemoveBlockTimestampInterval).await
+    }
+
+    /// Snapshots the current chain head and returns the snapshot id.
+    pub async fn snapshot(&self) -> Result<U256, LocalMinerError> {
+        self.request(LocalMinerMessage::Snapshot).await
+    }
+
+    /// Reverts the chain to the snapshot with the given id.
+    ///
+    /// Returns `false` if the snapshot does not exist.
+    pub async fn revert(&self, id: U256) -> Result<bool, LocalMinerError> {
+        self.request(|tx| LocalMinerMessage::Revert { id, tx }).await?
+    }
+}
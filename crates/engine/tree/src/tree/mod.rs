@@ -27,7 +27,7 @@ use reth_consensus::{Consensus, FullConsensus};
 pub use reth_engine_primitives::InvalidBlockHook;
 use reth_engine_primitives::{
     BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, EngineValidator,
-    ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
+    ExecutionPayload, ForkchoiceStateTracker, InvalidBlockReason, OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_evm::{ConfigureEvm, Evm, SpecFor};
@@ -880,6 +880,28 @@ where
         if let Ok(Some(canonical_header)) = self.find_canonical_header(state.head_block_hash) {
             debug!(target: "engine::tree", head = canonical_header.number(), "fcu head block is already canonical");
 
+            // Dev nodes revert their chain by making an ancestor of the canonical head the new
+            // head, so the chain is unwound to it instead of skipping the update.
+            if self.config.unwind_canonical_header() &&
+                canonical_header.number() < self.state.tree_state.canonical_block_number()
+            {
+                self.unwind_canonical_chain(canonical_header.clone())?;
+
+                // update the safe and finalized blocks and ensure their values are valid
+                if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+                    // safe or finalized hashes are invalid
+                    return Ok(TreeOutcome::new(outcome))
+                }
+
+                if let Some(attr) = attrs {
+                    let updated =
+                        self.process_payload_attributes(attr, &canonical_header, state, version);
+                    return Ok(TreeOutcome::new(updated))
+                }
+
+                return Ok(valid_outcome(state.head_block_hash))
+            }
+
             // For OpStack the proposers are allowed to reorg their own chain at will, so we need to
             // always trigger a new payload job if requested.
             if self.engine_kind.is_opstack() {
@@ -1649,7 +1671,10 @@ where
 
         // insert the head block into the invalid header cache
         self.state.invalid_headers.insert_with_invalid_ancestor(head.hash(), header);
-        self.emit_event(BeaconConsensusEngineEvent::InvalidBlock(Box::new(head.clone())));
+        self.emit_event(BeaconConsensusEngineEvent::InvalidBlock(
+            Box::new(head.clone()),
+            InvalidBlockReason::InvalidAncestor(header.block.hash),
+        ));
 
         Ok(Some(status))
     }
@@ -1935,6 +1960,42 @@ where
         ));
     }
 
+    /// Unwinds the canonical chain to the given ancestor of the canonical head.
+    ///
+    /// The unwound blocks are dropped from the canonical in-memory state without notifying
+    /// listeners, so the transaction pool doesn't reinject their transactions. Unwound blocks that
+    /// were already persisted are removed once [`Self::find_disk_reorg`] detects them.
+    fn unwind_canonical_chain(
+        &mut self,
+        new_head: SealedHeader<N::BlockHeader>,
+    ) -> ProviderResult<()> {
+        let current_head = self.state.tree_state.canonical_block_number();
+        debug!(target: "engine::tree", from = current_head, to = new_head.number(), "unwinding canonical chain");
+
+        let old = (new_head.number() + 1..=current_head)
+            .filter_map(|number| self.canonical_in_memory_state.state_by_number(number))
+            .map(|state| state.block().block)
+            .collect::<Vec<_>>();
+
+        // a persisted head has to be tracked in memory, otherwise the latest state would still be
+        // read from the unwound blocks on disk
+        let mut new = Vec::new();
+        if self.canonical_in_memory_state.state_by_hash(new_head.hash()).is_none() {
+            let block = self
+                .canonical_block_by_hash(new_head.hash())?
+                .ok_or_else(|| ProviderError::HeaderNotFound(new_head.hash().into()))?;
+            new.push(ExecutedBlockWithTrieUpdates { block, trie: ExecutedTrieUpdates::Missing });
+        }
+
+        self.state.tree_state.set_canonical_head(new_head.num_hash());
+        self.update_reorg_metrics(old.len());
+        self.canonical_in_memory_state.update_chain(NewCanonicalChain::Reorg { new, old });
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+        self.metrics.tree.canonical_chain_height.set(new_head.number() as f64);
+
+        Ok(())
+    }
+
     /// This updates metrics based on the given reorg length.
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
@@ -2584,6 +2645,7 @@ where
         self.state.invalid_headers.insert(block.block_with_parent());
         self.emit_event(EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
             Box::new(block),
+            InvalidBlockReason::Validation(validation_err.to_string()),
         )));
         Ok(PayloadStatus::new(
             PayloadStatusEnum::Invalid { validation_error: validation_err.to_string() },
@@ -3275,6 +3337,7 @@ mod tests {
             match event {
                 EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
                     block,
+                    _,
                 )) => {
                     assert_eq!(block.hash(), expected_hash);
                 }
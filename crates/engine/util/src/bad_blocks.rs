@@ -0,0 +1,335 @@
+//! Stores blocks that were rejected by the engine to disk for later inspection.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, Bytes, B256};
+use futures::{Stream, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use reth_engine_primitives::BeaconConsensusEngineEvent;
+use reth_fs_util as fs;
+use reth_primitives_traits::{Block, NodePrimitives, SealedBlock};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+use tracing::*;
+
+/// The default number of bad blocks kept by the [`BadBlockStore`].
+pub const DEFAULT_MAX_BAD_BLOCKS: usize = 10;
+
+/// A block that was rejected by the engine.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The RLP encoded block.
+    pub rlp: Bytes,
+    /// Why the block was rejected.
+    pub reason: String,
+    /// Unix timestamp in milliseconds at which the block was rejected.
+    pub rejected_at: u64,
+    /// The position of the block in the order the blocks were rejected in.
+    pub sequence: u64,
+}
+
+impl StoredBadBlock {
+    /// Returns the name of the file the block is stored in.
+    fn filename(&self) -> String {
+        format!("{}-{}.json", self.number, self.hash)
+    }
+}
+
+/// A bounded store of the most recent blocks that were rejected by the engine.
+///
+/// If the store is backed by a directory, every block is written to its own JSON file, so the
+/// blocks survive restarts. Once the store is full, the oldest block is evicted.
+///
+/// The store is cheap to clone, all clones share the same blocks.
+#[derive(Clone, Debug)]
+pub struct BadBlockStore {
+    /// The directory the blocks are stored in, if any.
+    path: Option<PathBuf>,
+    inner: Arc<RwLock<BadBlockStoreInner>>,
+    /// Serializes the file operations, so they are applied in the order the blocks were inserted
+    /// in without holding the lock on the blocks.
+    io: Arc<Mutex<()>>,
+}
+
+#[derive(Debug)]
+struct BadBlockStoreInner {
+    /// The maximum number of blocks to keep.
+    max_blocks: usize,
+    /// The stored blocks, ordered from oldest to newest.
+    blocks: VecDeque<StoredBadBlock>,
+    /// The sequence number of the next inserted block.
+    next_sequence: u64,
+}
+
+impl BadBlockStore {
+    /// Creates a new in-memory [`BadBlockStore`] that keeps up to `max_blocks` blocks.
+    pub fn new(max_blocks: usize) -> Self {
+        Self::with_blocks(None, max_blocks, VecDeque::new())
+    }
+
+    /// Opens the [`BadBlockStore`] at the given directory, loading all previously stored blocks.
+    ///
+    /// If there are more than `max_blocks` stored blocks, the oldest ones are removed.
+    pub fn open(path: PathBuf, max_blocks: usize) -> eyre::Result<Self> {
+        fs::create_dir_all(&path)?;
+
+        let mut blocks = Vec::new();
+        for entry in fs::read_dir(&path)? {
+            let entry = entry?;
+            let file = entry.path();
+            if file.extension().is_none_or(|ext| ext != "json") {
+                warn!(target: "engine::bad_blocks", file = %file.display(), "Skipping non json file");
+                continue
+            }
+            match fs::read_json_file::<StoredBadBlock>(&file) {
+                Ok(block) => blocks.push(block),
+                Err(err) => {
+                    warn!(target: "engine::bad_blocks", file = %file.display(), %err, "Failed to read bad block")
+                }
+            }
+        }
+        blocks.sort_by_key(|block| block.sequence);
+        debug!(target: "engine::bad_blocks", path = %path.display(), count = blocks.len(), "Loaded bad blocks");
+
+        let store = Self::with_blocks(Some(path.clone()), max_blocks, blocks.into());
+        for block in store.inner.write().truncate() {
+            remove_block_file(&path, &block)?;
+        }
+        Ok(store)
+    }
+
+    fn with_blocks(
+        path: Option<PathBuf>,
+        max_blocks: usize,
+        blocks: VecDeque<StoredBadBlock>,
+    ) -> Self {
+        let next_sequence = blocks.back().map_or(0, |block| block.sequence + 1);
+        Self {
+            path,
+            inner: Arc::new(RwLock::new(BadBlockStoreInner { max_blocks, blocks, next_sequence })),
+            io: Default::default(),
+        }
+    }
+
+    /// Returns the directory the blocks are stored in, if the store is persisted.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.path.clone()
+    }
+
+    /// Inserts a rejected block into the store, evicting the oldest block if the store is full.
+    ///
+    /// Blocks that are already in the store are ignored. If the block can't be written to disk,
+    /// it is still kept in memory.
+    pub fn insert<B: Block>(&self, block: &SealedBlock<B>, reason: String) -> eyre::Result<()> {
+        if self.get(&block.hash()).is_some() {
+            return Ok(())
+        }
+
+        let mut block = StoredBadBlock {
+            hash: block.hash(),
+            number: block.number(),
+            // the rlp encoding of a `SealedBlock` only covers the body
+            rlp: alloy_rlp::encode(block.clone_block()).into(),
+            reason,
+            rejected_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            sequence: 0,
+        };
+
+        let (evicted, _io) = {
+            let mut inner = self.inner.write();
+            if inner.blocks.iter().any(|stored| stored.hash == block.hash) {
+                return Ok(())
+            }
+            block.sequence = inner.next_sequence;
+            inner.next_sequence += 1;
+            inner.blocks.push_back(block.clone());
+            let evicted = inner.truncate();
+            // the io lock is acquired before the blocks are released, so concurrent inserts touch
+            // the files in the same order they were inserted in
+            (evicted, self.io.lock())
+        };
+
+        if let Some(path) = &self.path {
+            fs::write_json_file(&path.join(block.filename()), &block)?;
+            for block in evicted {
+                remove_block_file(path, &block)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns all stored blocks, ordered from newest to oldest.
+    pub fn blocks(&self) -> Vec<StoredBadBlock> {
+        self.inner.read().blocks.iter().rev().cloned().collect()
+    }
+
+    /// Returns the stored block with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<StoredBadBlock> {
+        self.inner.read().blocks.iter().find(|block| block.hash == *hash).cloned()
+    }
+
+    /// Returns the number of stored blocks.
+    pub fn len(&self) -> usize {
+        self.inner.read().blocks.len()
+    }
+
+    /// Returns `true` if no blocks are stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().blocks.is_empty()
+    }
+}
+
+impl Default for BadBlockStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BAD_BLOCKS)
+    }
+}
+
+impl BadBlockStoreInner {
+    /// Evicts the oldest blocks until at most `max_blocks` blocks are stored and returns them.
+    fn truncate(&mut self) -> Vec<StoredBadBlock> {
+        let excess = self.blocks.len().saturating_sub(self.max_blocks);
+        self.blocks.drain(..excess).collect()
+    }
+}
+
+fn remove_block_file(path: &Path, block: &StoredBadBlock) -> eyre::Result<()> {
+    let file = path.join(block.filename());
+    if file.exists() {
+        fs::remove_file(file)?;
+    }
+    Ok(())
+}
+
+/// Inserts the invalid blocks reported by the engine into the given [`BadBlockStore`].
+///
+/// Blocks that were only rejected because they descend from a rejected block are skipped, so they
+/// don't evict the block that caused the rejection. Blocks are written to disk on a blocking task.
+/// Runs until the event stream is closed.
+pub async fn maintain_bad_block_store<N, S>(mut events: S, store: BadBlockStore)
+where
+    N: NodePrimitives,
+    S: Stream<Item = BeaconConsensusEngineEvent<N>> + Unpin,
+{
+    while let Some(event) = events.next().await {
+        if let BeaconConsensusEngineEvent::InvalidBlock(block, reason) = event {
+            if reason.is_invalid_ancestor() {
+                continue
+            }
+            let reason = reason.to_string();
+            let (number, hash) = (block.number(), block.hash());
+            trace!(target: "engine::bad_blocks", number, %hash, %reason, "Storing bad block");
+            let store = store.clone();
+            match tokio::task::spawn_blocking(move || store.insert(&block, reason)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    warn!(target: "engine::bad_blocks", number, %hash, %err, "Failed to store bad block")
+                }
+                Err(err) => {
+                    warn!(target: "engine::bad_blocks", number, %hash, %err, "Bad block task failed")
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_engine_primitives::InvalidBlockReason;
+    use reth_ethereum_primitives::{Block, EthPrimitives};
+
+    fn bad_block(number: BlockNumber) -> SealedBlock<Block> {
+        SealedBlock::seal_slow(Block {
+            header: Header { number, ..Default::default() },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn evicts_oldest_block() {
+        let store = BadBlockStore::new(2);
+        let blocks = (1..=3).map(bad_block).collect::<Vec<_>>();
+        for block in &blocks {
+            store.insert(block, format!("bad block {}", block.number())).unwrap();
+        }
+        // inserting a stored block again is a noop
+        store.insert(&blocks[2], "duplicate".to_string()).unwrap();
+
+        let stored = store.blocks();
+        assert_eq!(stored.iter().map(|block| block.number).collect::<Vec<_>>(), [3, 2]);
+        assert_eq!(stored[0].reason, "bad block 3");
+        assert_eq!(stored[0].rlp, Bytes::from(alloy_rlp::encode(blocks[2].clone_block())));
+        assert!(store.get(&blocks[0].hash()).is_none());
+        assert_eq!(store.get(&blocks[1].hash()).unwrap().reason, "bad block 2");
+    }
+
+    #[test]
+    fn reopen_persisted_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BadBlockStore::open(dir.path().to_path_buf(), 3).unwrap();
+        let blocks = (1..=3).map(bad_block).collect::<Vec<_>>();
+        for block in &blocks {
+            store.insert(block, String::new()).unwrap();
+        }
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+
+        // the oldest block is removed from disk if the limit was lowered
+        let reopened = BadBlockStore::open(dir.path().to_path_buf(), 2).unwrap();
+        assert_eq!(reopened.blocks(), store.blocks()[..2]);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        // blocks inserted after reopening are ordered after the loaded ones
+        let block = bad_block(4);
+        reopened.insert(&block, String::new()).unwrap();
+        assert_eq!(
+            reopened.blocks().iter().map(|block| block.sequence).collect::<Vec<_>>(),
+            [3, 2]
+        );
+        assert_eq!(
+            BadBlockStore::open(dir.path().to_path_buf(), 2).unwrap().blocks(),
+            reopened.blocks()
+        );
+    }
+
+    #[tokio::test]
+    async fn stores_invalid_block_events() {
+        let store = BadBlockStore::default();
+        let block = bad_block(1);
+        let descendant = bad_block(2);
+        let events = futures::stream::iter([
+            BeaconConsensusEngineEvent::<EthPrimitives>::InvalidBlock(
+                Box::new(block.clone()),
+                InvalidBlockReason::Validation("invalid state root".to_string()),
+            ),
+            BeaconConsensusEngineEvent::InvalidBlock(
+                Box::new(block.clone()),
+                InvalidBlockReason::Validation("again".to_string()),
+            ),
+            BeaconConsensusEngineEvent::InvalidBlock(
+                Box::new(descendant),
+                InvalidBlockReason::InvalidAncestor(block.hash()),
+            ),
+        ]);
+
+        maintain_bad_block_store(events, store.clone()).await;
+
+        let stored = store.blocks();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].hash, block.hash());
+        assert_eq!(stored[0].reason, "invalid state root");
+    }
+}
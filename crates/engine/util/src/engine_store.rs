@@ -1,20 +1,28 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus, PayloadStatusEnum};
 use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use reth_engine_primitives::{
+    BeaconEngineMessage, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
+    OnForkChoiceUpdated,
+};
+use reth_errors::RethResult;
 use reth_fs_util as fs;
 use reth_payload_primitives::PayloadTypes;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The file extension of the status files, which are stored next to the message files.
+const STATUS_EXTENSION: &str = "status";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,6 +42,55 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+/// The status the node responded with to a stored engine API message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StoredEngineApiStatus {
+    /// The payload or forkchoice state is valid.
+    Valid,
+    /// The payload or forkchoice state is invalid.
+    Invalid,
+    /// The node was syncing and could not validate the payload or forkchoice state.
+    Syncing,
+    /// The payload was accepted but not validated, only returned by `engine_newPayload`.
+    Accepted,
+    /// The call failed with an error.
+    Error,
+}
+
+impl StoredEngineApiStatus {
+    /// Converts the [`PayloadStatusEnum`] into a [`StoredEngineApiStatus`].
+    pub const fn from_payload_status(status: &PayloadStatusEnum) -> Self {
+        match status {
+            PayloadStatusEnum::Valid => Self::Valid,
+            PayloadStatusEnum::Invalid { .. } => Self::Invalid,
+            PayloadStatusEnum::Syncing => Self::Syncing,
+            PayloadStatusEnum::Accepted => Self::Accepted,
+        }
+    }
+
+    /// Returns the string representation of the status, as used by the engine API.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Valid => "VALID",
+            Self::Invalid => "INVALID",
+            Self::Syncing => "SYNCING",
+            Self::Accepted => "ACCEPTED",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+impl From<ForkchoiceStatus> for StoredEngineApiStatus {
+    fn from(status: ForkchoiceStatus) -> Self {
+        match status {
+            ForkchoiceStatus::Valid => Self::Valid,
+            ForkchoiceStatus::Invalid => Self::Invalid,
+            ForkchoiceStatus::Syncing => Self::Syncing,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
 #[derive(Debug)]
 pub struct EngineMessageStore {
@@ -51,51 +108,80 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message.
     pub fn on_message<T>(
         &self,
         msg: &BeaconEngineMessage<T>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<PathBuf>
     where
         T: PayloadTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
+        let path = match msg {
             BeaconEngineMessage::ForkchoiceUpdated {
                 state,
                 payload_attrs,
                 tx: _tx,
                 version: _version,
             } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
+                let path =
+                    self.path.join(format!("{}-fcu-{}.json", timestamp, state.head_block_hash));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
                     })?,
                 )?;
+                path
             }
             BeaconEngineMessage::NewPayload { payload, tx: _tx } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
+                let path = self.path.join(format!(
+                    "{}-new_payload-{}.json",
+                    timestamp,
+                    payload.block_hash()
+                ));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
                         payload: payload.clone(),
                     })?,
                 )?;
+                path
             }
         };
+        Ok(path)
+    }
+
+    /// Stores the status the node responded with to the message stored at the given path.
+    pub fn on_status(message: &Path, status: StoredEngineApiStatus) -> eyre::Result<()> {
+        fs::write(message.with_extension(STATUS_EXTENSION), serde_json::to_vec(&status)?)?;
         Ok(())
     }
 
+    /// Returns the status the node responded with to the message stored at the given path, if it
+    /// was recorded.
+    pub fn status(message: &Path) -> eyre::Result<Option<StoredEngineApiStatus>> {
+        let path = message.with_extension(STATUS_EXTENSION);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if entry.path().extension().is_some_and(|ext| ext == STATUS_EXTENSION) {
+                // statuses are read together with their message
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -140,11 +226,94 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
         let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
+        let Some(msg) = next else { return Poll::Ready(None) };
+        match this.store.on_message(&msg, SystemTime::now()) {
+            Ok(path) => Poll::Ready(Some(store_status(msg, path))),
+            Err(error) => {
                 error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+                Poll::Ready(Some(msg))
             }
         }
-        Poll::Ready(next)
+    }
+}
+
+/// Intercepts the response to the message, so that the status the node responded with is stored
+/// next to the message at the given path.
+fn store_status<T: PayloadTypes>(
+    msg: BeaconEngineMessage<T>,
+    path: PathBuf,
+) -> BeaconEngineMessage<T> {
+    match msg {
+        BeaconEngineMessage::NewPayload { payload, tx } => {
+            let (status_tx, status_rx) =
+                oneshot::channel::<Result<PayloadStatus, BeaconOnNewPayloadError>>();
+            tokio::spawn(async move {
+                let Ok(response) = status_rx.await else { return };
+                let status = match &response {
+                    Ok(status) => StoredEngineApiStatus::from_payload_status(&status.status),
+                    Err(_) => StoredEngineApiStatus::Error,
+                };
+                write_status(&path, status);
+                let _ = tx.send(response);
+            });
+            BeaconEngineMessage::NewPayload { payload, tx: status_tx }
+        }
+        BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+            let (status_tx, status_rx) = oneshot::channel::<RethResult<OnForkChoiceUpdated>>();
+            tokio::spawn(async move {
+                let Ok(response) = status_rx.await else { return };
+                let status = match &response {
+                    Ok(updated) => updated.forkchoice_status().into(),
+                    Err(_) => StoredEngineApiStatus::Error,
+                };
+                write_status(&path, status);
+                let _ = tx.send(response);
+            });
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx: status_tx }
+        }
+    }
+}
+
+/// Stores the status next to the message at the given path, logging any error.
+fn write_status(path: &Path, status: StoredEngineApiStatus) {
+    if let Err(error) = EngineMessageStore::on_status(path, status) {
+        error!(target: "engine::stream::store", ?path, %error, "Error storing Engine API status");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_payload_primitives::EngineApiMessageVersion;
+
+    #[tokio::test]
+    async fn stores_response_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, rx) = oneshot::channel();
+        let msg = BeaconEngineMessage::<EthEngineTypes>::ForkchoiceUpdated {
+            state: ForkchoiceState::default(),
+            payload_attrs: None,
+            version: EngineApiMessageVersion::V3,
+            tx,
+        };
+        let mut stream =
+            EngineStoreStream::new(futures::stream::iter([msg]), dir.path().to_path_buf());
+
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { tx, .. }) = stream.next().await else {
+            panic!("expected a forkchoice update")
+        };
+        let status = PayloadStatus::from_status(PayloadStatusEnum::Syncing);
+        tx.send(Ok(OnForkChoiceUpdated::valid(status))).unwrap();
+        // the response is forwarded to the original sender
+        assert!(rx.await.unwrap().is_ok());
+
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+        let messages = store.engine_messages_iter().unwrap().collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            EngineMessageStore::status(&messages[0]).unwrap(),
+            Some(StoredEngineApiStatus::Syncing)
+        );
     }
 }
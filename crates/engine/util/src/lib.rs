@@ -14,6 +14,8 @@ use reth_payload_primitives::PayloadTypes;
 use std::path::PathBuf;
 use tokio_util::either::Either;
 
+pub mod bad_blocks;
+
 pub mod engine_store;
 use engine_store::EngineStoreStream;
 
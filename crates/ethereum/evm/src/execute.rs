@@ -19,7 +19,7 @@ mod tests {
     use alloy_primitives::{b256, fixed_bytes, keccak256, Bytes, TxKind, B256, U256};
     use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumHardfork, ForkCondition, MAINNET};
     use reth_ethereum_primitives::{Block, BlockBody, Transaction};
-    use reth_evm::{execute::Executor, ConfigureEvm};
+    use reth_evm::{execute::Executor, state_override::StateOverrideLayer, ConfigureEvm};
     use reth_execution_types::BlockExecutionResult;
     use reth_primitives_traits::{
         crypto::secp256k1::public_key_to_address, Block as _, RecoveredBlock,
@@ -860,4 +860,65 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn state_overrides_and_impersonated_transactions() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let impersonated = address!("0x1000000000000000000000000000000000000000");
+        let recipient = address!("0x2000000000000000000000000000000000000000");
+        let signer_key_pair = generators::generate_key(&mut generators::rng());
+        let signer = public_key_to_address(signer_key_pair.public_key());
+
+        let header = Header {
+            parent_hash: B256::with_last_byte(1),
+            number: 1,
+            gas_limit: 1_000_000,
+            base_fee_per_gas: Some(0),
+            ..Header::default()
+        };
+
+        // the transaction is signed by a throwaway key but executed for the impersonated account
+        let tx = sign_tx_with_key_pair(
+            signer_key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price: 1,
+                gas_limit: 21_000,
+                to: TxKind::Call(recipient),
+                value: U256::from(1),
+                input: Bytes::new(),
+            }),
+        );
+
+        let layer = StateOverrideLayer::default();
+        layer.set_balance(impersonated, U256::from(ETH_TO_WEI));
+        layer.stage(header.parent_hash);
+        layer.impersonate_transaction(*tx.tx_hash(), impersonated);
+
+        let evm_config = evm_config(chain_spec).with_state_override_layer(layer);
+        let mut executor = evm_config.batch_executor(CacheDB::new(EmptyDB::default()));
+
+        let BlockExecutionResult { receipts, .. } = executor
+            .execute_one(
+                &Block { header, body: BlockBody { transactions: vec![tx], ..Default::default() } }
+                    .try_into_recovered()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(receipts[0].success);
+
+        let (impersonated, recipient, signer) = executor.with_state_mut(|state| {
+            (
+                state.basic(impersonated).unwrap().unwrap(),
+                state.basic(recipient).unwrap().unwrap(),
+                state.basic(signer).unwrap(),
+            )
+        });
+        assert_eq!(impersonated.balance, U256::from(ETH_TO_WEI - 21_000 - 1));
+        assert_eq!(impersonated.nonce, 1);
+        assert_eq!(recipient.balance, U256::from(1));
+        assert!(signer.is_none());
+    }
 }
@@ -19,6 +19,8 @@ extern crate alloc;
 
 use alloc::{borrow::Cow, sync::Arc};
 use alloy_consensus::{BlockHeader, Header};
+#[cfg(feature = "std")]
+use alloy_evm::block::{BlockExecutorFactory, BlockExecutorFor};
 pub use alloy_evm::EthEvm;
 use alloy_evm::{
     eth::{EthBlockExecutionCtx, EthBlockExecutorFactory},
@@ -32,7 +34,14 @@ use reth_evm::{
     precompiles::PrecompilesMap, ConfigureEvm, EvmEnv, EvmFactory, NextBlockEnvAttributes,
     TransactionEnv,
 };
+#[cfg(feature = "std")]
+use reth_evm::{
+    state_override::{StateOverrideExecutor, StateOverrideLayer},
+    Database, EvmFor, InspectorFor,
+};
 use reth_primitives_traits::{SealedBlock, SealedHeader};
+#[cfg(feature = "std")]
+use revm::database::State;
 use revm::{
     context::{BlockEnv, CfgEnv},
     context_interface::block::BlobExcessGasAndPrice,
@@ -64,6 +73,9 @@ pub struct EthEvmConfig<EvmFactory = EthEvmFactory> {
     pub executor_factory: EthBlockExecutorFactory<RethReceiptBuilder, Arc<ChainSpec>, EvmFactory>,
     /// Ethereum block assembler.
     pub block_assembler: EthBlockAssembler<ChainSpec>,
+    /// State overrides applied to executed blocks, only configured for development nodes.
+    #[cfg(feature = "std")]
+    pub state_override_layer: Option<StateOverrideLayer>,
 }
 
 impl EthEvmConfig {
@@ -93,6 +105,8 @@ impl<EvmFactory> EthEvmConfig<EvmFactory> {
                 chain_spec,
                 evm_factory,
             ),
+            #[cfg(feature = "std")]
+            state_override_layer: None,
         }
     }
 
@@ -106,6 +120,27 @@ impl<EvmFactory> EthEvmConfig<EvmFactory> {
         self.block_assembler.extra_data = extra_data;
         self
     }
+
+    /// Sets the [`StateOverrideLayer`] that is applied to executed blocks.
+    #[cfg(feature = "std")]
+    pub fn with_state_override_layer(mut self, layer: StateOverrideLayer) -> Self {
+        self.state_override_layer = Some(layer);
+        self
+    }
+}
+
+/// An EVM configuration that assembles blocks with configurable extra data.
+///
+/// This allows payload builders to override the extra data of the blocks they build at runtime.
+pub trait ConfigureExtraData {
+    /// Sets the extra data of assembled blocks.
+    fn set_extra_data(&mut self, extra_data: Bytes);
+}
+
+impl<EvmFactory> ConfigureExtraData for EthEvmConfig<EvmFactory> {
+    fn set_extra_data(&mut self, extra_data: Bytes) {
+        self.block_assembler.extra_data = extra_data;
+    }
 }
 
 impl<EvmF> ConfigureEvm for EthEvmConfig<EvmF>
@@ -263,6 +298,29 @@ where
             withdrawals: attributes.withdrawals.map(Cow::Owned),
         }
     }
+
+    #[cfg(feature = "std")]
+    fn create_executor<'a, DB, I>(
+        &'a self,
+        evm: EvmFor<Self, &'a mut State<DB>, I>,
+        ctx: EthBlockExecutionCtx<'a>,
+    ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, DB, I>
+    where
+        DB: Database,
+        I: InspectorFor<Self, &'a mut State<DB>> + 'a,
+    {
+        let parent_hash = ctx.parent_hash;
+        StateOverrideExecutor::new(
+            self.executor_factory.create_executor(evm, ctx),
+            self.state_override_layer.clone(),
+            parent_hash,
+        )
+    }
+
+    #[cfg(feature = "std")]
+    fn state_override_layer(&self) -> Option<&StateOverrideLayer> {
+        self.state_override_layer.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,9 @@
-use crate::EthEvmConfig;
+use crate::{ConfigureExtraData, EthEvmConfig};
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_consensus::Header;
 use alloy_eips::eip7685::Requests;
 use alloy_evm::precompiles::PrecompilesMap;
+use alloy_primitives::Bytes;
 use parking_lot::Mutex;
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_evm::{
@@ -43,6 +44,12 @@ impl MockEvmConfig {
     }
 }
 
+impl ConfigureExtraData for MockEvmConfig {
+    fn set_extra_data(&mut self, extra_data: Bytes) {
+        self.inner.set_extra_data(extra_data);
+    }
+}
+
 impl BlockExecutorFactory for MockEvmConfig {
     type EvmFactory = EthEvmFactory;
     type ExecutionCtx<'a> = EthBlockExecutionCtx<'a>;
@@ -0,0 +1,174 @@
+//! Execution extension that maintains the call trace index.
+//!
+//! The index records the addresses that were the caller or the callee of a call frame in every
+//! block and is required by the `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`
+//! endpoints.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockNumber, U256};
+use futures::TryStreamExt;
+use reth_evm::{execute::BlockExecutor, ConfigureEvm, Evm};
+use reth_exex::{ExExContext, ExExHead};
+use reth_node_api::FullNodeComponents;
+use reth_primitives_traits::{BlockTy, RecoveredBlock};
+use reth_provider::{
+    BlockReader, CallHistoryReader, CallHistoryWriter, DBProvider, DatabaseProviderFactory,
+    ProviderError, StateProviderBox, StateProviderFactory, TransactionVariant,
+};
+use reth_revm::{database::StateProviderDatabase, db::State};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome},
+    Inspector,
+};
+use std::collections::BTreeSet;
+use tracing::info;
+
+/// Maximum number of blocks that are written to the index at once while indexing the blocks that
+/// were synced before the extension was installed.
+const BACKFILL_BATCH_SIZE: u64 = 1_000;
+
+/// Runs the execution extension that maintains the call trace index.
+///
+/// Blocks that were synced before the extension was installed are indexed when it starts, which
+/// requires the state history of all blocks. Afterwards every committed block is indexed and
+/// reverted blocks are removed from the index.
+pub async fn call_trace_index_exex<Node>(mut ctx: ExExContext<Node>) -> eyre::Result<()>
+where
+    Node: FullNodeComponents<Provider: DatabaseProviderFactory<ProviderRW: CallHistoryWriter>>,
+{
+    let provider = ctx.provider().clone();
+    let evm_config = ctx.evm_config().clone();
+    let head = ctx.head;
+
+    // blocks that were indexed but not persisted before a restart are no longer canonical
+    let mut checkpoint = provider.call_history_checkpoint()?.unwrap_or_default();
+    if checkpoint > head.number {
+        let provider_rw = provider.database_provider_rw()?;
+        provider_rw.unwind_call_history(head.number + 1)?;
+        provider_rw.commit()?;
+        checkpoint = head.number;
+    }
+
+    while checkpoint < head.number {
+        let to = (checkpoint + BACKFILL_BATCH_SIZE).min(head.number);
+
+        // the blocks are traced before the write transaction is opened, so that persistence of
+        // new blocks is not blocked while tracing
+        let mut traces = Vec::new();
+        for block_number in checkpoint + 1..=to {
+            let block = provider
+                .recovered_block(block_number.into(), TransactionVariant::WithHash)?
+                .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+            let state = provider.history_by_block_hash(block.parent_hash())?;
+            traces.push((block_number, call_trace_addresses(&evm_config, state, &block)?));
+        }
+        write_call_traces(&provider, None, traces)?;
+
+        info!(target: "exex::call_trace_index", indexed = to, tip = head.number, "Indexed call traces");
+        checkpoint = to;
+    }
+
+    ctx.set_notifications_with_head(ExExHead { block: head });
+    while let Some(notification) = ctx.notifications.try_next().await? {
+        let reverted = notification.reverted_chain().map(|chain| chain.first().number());
+
+        if let Some(committed) = notification.committed_chain() {
+            let mut traces = Vec::new();
+            for block in committed.blocks_iter() {
+                let state = provider.history_by_block_hash(block.parent_hash())?;
+                traces.push((block.number(), call_trace_addresses(&evm_config, state, block)?));
+            }
+
+            // the committed blocks replace any previously indexed blocks at the same height
+            let unwind_to = reverted.into_iter().chain([committed.first().number()]).min();
+            write_call_traces(&provider, unwind_to, traces)?;
+            ctx.send_finished_height(committed.tip().num_hash())?;
+        } else if reverted.is_some() {
+            write_call_traces(&provider, reverted, Vec::new())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes all blocks starting at `unwind_to` from the index and inserts the given traces.
+fn write_call_traces<P>(
+    provider: &P,
+    unwind_to: Option<BlockNumber>,
+    traces: Vec<(BlockNumber, BTreeSet<Address>)>,
+) -> eyre::Result<()>
+where
+    P: DatabaseProviderFactory<ProviderRW: CallHistoryWriter>,
+{
+    let provider_rw = provider.database_provider_rw()?;
+    if let Some(unwind_to) = unwind_to {
+        provider_rw.unwind_call_history(unwind_to)?;
+    }
+    for (block_number, addresses) in traces {
+        provider_rw.insert_call_history(block_number, addresses)?;
+    }
+    provider_rw.commit()?;
+    Ok(())
+}
+
+/// Executes the transactions of the block on top of the given state and returns the addresses that
+/// were the caller or the callee of a call frame.
+fn call_trace_addresses<E: ConfigureEvm>(
+    evm_config: &E,
+    state: StateProviderBox,
+    block: &RecoveredBlock<BlockTy<E::Primitives>>,
+) -> eyre::Result<BTreeSet<Address>> {
+    let mut db = State::builder().with_database(StateProviderDatabase::new(state)).build();
+    let evm = evm_config.evm_with_env_and_inspector(
+        &mut db,
+        evm_config.evm_env(block.header()),
+        CallTraceInspector::default(),
+    );
+    let mut executor = evm_config.create_executor(evm, evm_config.context_for_block(block));
+
+    executor.apply_pre_execution_changes()?;
+    // system calls are not part of any transaction
+    executor.evm_mut().inspector_mut().addresses.clear();
+    for tx in block.transactions_recovered() {
+        executor.execute_transaction(tx)?;
+    }
+
+    Ok(std::mem::take(&mut executor.evm_mut().inspector_mut().addresses))
+}
+
+/// An [`Inspector`] that collects the callers and callees of all call frames.
+///
+/// The addresses match the `from` and `to` addresses of the call traces used by the `ots`
+/// namespace: the callee of a delegate call is the account whose code is executed.
+#[derive(Debug, Default)]
+struct CallTraceInspector {
+    addresses: BTreeSet<Address>,
+}
+
+impl<CTX> Inspector<CTX> for CallTraceInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let (from, to) = match inputs.scheme {
+            CallScheme::DelegateCall | CallScheme::CallCode | CallScheme::ExtDelegateCall => {
+                (inputs.target_address, inputs.bytecode_address)
+            }
+            _ => (inputs.caller, inputs.target_address),
+        };
+        self.addresses.insert(from);
+        self.addresses.insert(to);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.addresses.insert(inputs.caller);
+        self.addresses.extend(outcome.address);
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.addresses.extend([contract, target]);
+    }
+}
@@ -9,6 +9,7 @@ use reth_chainspec::ChainSpec;
 use reth_engine_primitives::{EngineValidator, PayloadValidator};
 use reth_ethereum_payload_builder::EthereumExecutionPayloadValidator;
 use reth_ethereum_primitives::Block;
+use reth_evm::state_override::StateOverrideLayer;
 use reth_node_api::PayloadTypes;
 use reth_payload_primitives::{
     validate_execution_requests, validate_version_specific_fields, EngineApiMessageVersion,
@@ -21,12 +22,23 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct EthereumEngineValidator {
     inner: EthereumExecutionPayloadValidator<ChainSpec>,
+    /// Layer recording the senders of impersonated transactions on dev nodes.
+    state_overrides: Option<StateOverrideLayer>,
 }
 
 impl EthereumEngineValidator {
     /// Instantiates a new validator.
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { inner: EthereumExecutionPayloadValidator::new(chain_spec) }
+        Self { inner: EthereumExecutionPayloadValidator::new(chain_spec), state_overrides: None }
+    }
+
+    /// Configures the [`StateOverrideLayer`] of a dev node.
+    ///
+    /// Impersonated transactions are signed with a throwaway key, their senders are resolved
+    /// through the layer instead of being recovered from the signature.
+    pub fn with_state_override_layer(mut self, layer: StateOverrideLayer) -> Self {
+        self.state_overrides = Some(layer);
+        self
     }
 
     /// Returns the chain spec used by the validator.
@@ -45,7 +57,16 @@ impl PayloadValidator for EthereumEngineValidator {
         payload: ExecutionData,
     ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError> {
         let sealed_block = self.inner.ensure_well_formed_payload(payload)?;
-        sealed_block.try_recover().map_err(|e| NewPayloadError::Other(e.into()))
+        let block = sealed_block.try_recover().map_err(|e| NewPayloadError::Other(e.into()))?;
+        let Some(layer) = &self.state_overrides else { return Ok(block) };
+
+        let (block, mut senders) = block.split_sealed();
+        for (tx, sender) in block.body().transactions.iter().zip(&mut senders) {
+            if let Some(impersonated) = layer.impersonated_sender(tx.tx_hash()) {
+                *sender = impersonated;
+            }
+        }
+        Ok(RecoveredBlock::new_sealed(block, senders))
     }
 }
 
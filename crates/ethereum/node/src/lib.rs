@@ -27,3 +27,6 @@ pub mod payload;
 
 pub mod engine;
 pub use engine::EthereumEngineValidator;
+
+pub mod call_trace_index;
+pub use call_trace_index::call_trace_index_exex;
@@ -9,8 +9,12 @@ use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
+use reth_ethereum_payload_builder::EthereumBuilderConfigHandle;
 use reth_ethereum_primitives::{EthPrimitives, PooledTransactionVariant, TransactionSigned};
-use reth_evm::{ConfigureEvm, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes};
+use reth_evm::{
+    state_override::StateOverrideLayer, ConfigureEvm, EvmFactory, EvmFactoryFor,
+    NextBlockEnvAttributes,
+};
 use reth_network::{EthNetworkPrimitives, NetworkHandle, PeersInfo};
 use reth_node_api::{AddOnsContext, FullNodeComponents, NodeAddOns, NodePrimitives, TxTy};
 use reth_node_builder::{
@@ -26,9 +30,18 @@ use reth_node_builder::{
     BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder, PayloadBuilderConfig,
     PayloadTypes,
 };
+use reth_payload_util::BundlePool;
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
-use reth_rpc::{eth::core::EthApiFor, ValidationApi};
-use reth_rpc_api::{eth::FullEthApiServer, servers::BlockSubmissionValidationApiServer};
+use reth_rpc::{
+    eth::core::EthApiFor, AnvilApi, EthFullBundle, HardhatApi, MinerApi, ValidationApi,
+};
+use reth_rpc_api::{
+    eth::FullEthApiServer,
+    servers::{
+        AnvilApiServer, BlockSubmissionValidationApiServer, EthBundleApiServer, HardhatApiServer,
+        MevFullApiServer, MinerApiServer,
+    },
+};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
@@ -42,11 +55,33 @@ use revm::context::TxEnv;
 use std::{default::Default, sync::Arc, time::SystemTime};
 
 /// Type configuration for a regular Ethereum node.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumNode;
+pub struct EthereumNode {
+    /// Payload builder configuration shared between the payload builder and the `miner_`
+    /// namespace.
+    pub builder_config: EthereumBuilderConfigHandle,
+    /// Pool of bundles shared between the payload builder and the bundle submission
+    /// endpoints.
+    ///
+    /// Bundle submission is disabled unless a pool is configured.
+    pub bundle_pool: Option<BundlePool<TransactionSigned>>,
+}
 
 impl EthereumNode {
+    /// Configures the payload builder configuration shared with the `miner_` namespace.
+    pub fn with_builder_config(mut self, builder_config: EthereumBuilderConfigHandle) -> Self {
+        self.builder_config = builder_config;
+        self
+    }
+
+    /// Configures the pool of bundles shared with `eth_sendBundle` and `mev_sendBundle`, which
+    /// enables bundle submission.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+
     /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node.
     pub fn components<Node>() -> ComponentsBuilder<
         Node,
@@ -155,6 +190,10 @@ where
     EthApiFor<N>: FullEthApiServer<Provider = N::Provider, Pool = N::Pool>,
 {
     inner: RpcAddOns<N, EthereumEthApiBuilder, EthereumEngineValidatorBuilder>,
+    /// Payload builder configuration that is updated by the `miner_` namespace.
+    builder_config: Option<EthereumBuilderConfigHandle>,
+    /// Pool of bundles that is filled by `eth_sendBundle` and `mev_sendBundle`.
+    bundle_pool: Option<BundlePool<TransactionSigned>>,
 }
 
 impl<N: FullNodeComponents> Default for EthereumAddOns<N>
@@ -162,7 +201,28 @@ where
     EthApiFor<N>: FullEthApiServer<Provider = N::Provider, Pool = N::Pool>,
 {
     fn default() -> Self {
-        Self { inner: Default::default() }
+        Self { inner: Default::default(), builder_config: None, bundle_pool: None }
+    }
+}
+
+impl<N: FullNodeComponents> EthereumAddOns<N>
+where
+    EthApiFor<N>: FullEthApiServer<Provider = N::Provider, Pool = N::Pool>,
+{
+    /// Configures the payload builder configuration that is updated by the `miner_` namespace.
+    ///
+    /// This should be the same handle the payload builder was configured with.
+    pub fn with_builder_config(mut self, builder_config: EthereumBuilderConfigHandle) -> Self {
+        self.builder_config = Some(builder_config);
+        self
+    }
+
+    /// Configures the pool of bundles that is filled by `eth_sendBundle` and `mev_sendBundle`.
+    ///
+    /// This should be the same pool the payload builder was configured with.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
     }
 }
 
@@ -193,14 +253,55 @@ where
             Box::new(ctx.node.task_executor().clone()),
             Arc::new(EthereumEngineValidator::new(ctx.config.chain.clone())),
         );
+        let local_miner_handle = ctx.local_miner_handle.clone();
+        let state_overrides = ctx.node.evm_config().state_override_layer().cloned();
+        let Self { inner, builder_config, bundle_pool } = self;
 
-        self.inner
-            .launch_add_ons_with(ctx, move |modules, _, _| {
+        inner
+            .launch_add_ons_with(ctx, move |modules, _, registry| {
                 modules.merge_if_module_configured(
                     RethRpcModule::Flashbots,
                     validation_api.into_rpc(),
                 )?;
 
+                if let Some(builder_config) = builder_config {
+                    modules.replace_if_module_configured(
+                        RethRpcModule::Miner,
+                        MinerApi::new(builder_config).into_rpc(),
+                    )?;
+                }
+
+                if let Some(bundle_pool) = bundle_pool {
+                    let bundle_api = EthFullBundle::new(
+                        registry.bundle_api(),
+                        registry.sim_bundle_api(),
+                        bundle_pool,
+                    );
+                    modules.replace_if_module_configured(
+                        RethRpcModule::Eth,
+                        EthBundleApiServer::into_rpc(bundle_api.clone()),
+                    )?;
+                    modules.replace_if_module_configured(
+                        RethRpcModule::Mev,
+                        MevFullApiServer::into_rpc(bundle_api),
+                    )?;
+                }
+
+                // cheat code namespaces are only available on dev nodes
+                if let Some(local_miner_handle) = local_miner_handle {
+                    let anvil_api = AnvilApi::new(
+                        registry.eth_api().clone(),
+                        local_miner_handle,
+                        state_overrides,
+                    );
+                    modules.merge_if_module_configured(
+                        RethRpcModule::Hardhat,
+                        HardhatApi::new(anvil_api.clone()).into_rpc(),
+                    )?;
+                    modules
+                        .merge_if_module_configured(RethRpcModule::Anvil, anvil_api.into_rpc())?;
+                }
+
                 Ok(())
             })
             .await
@@ -263,11 +364,21 @@ where
     >;
 
     fn components_builder(&self) -> Self::ComponentsBuilder {
-        Self::components()
+        let mut payload_builder =
+            EthereumPayloadBuilder::default().with_builder_config(self.builder_config.clone());
+        if let Some(bundle_pool) = &self.bundle_pool {
+            payload_builder = payload_builder.with_bundle_pool(bundle_pool.clone());
+        }
+        Self::components().payload(BasicPayloadServiceBuilder::new(payload_builder))
     }
 
     fn add_ons(&self) -> Self::AddOns {
-        EthereumAddOns::default()
+        let mut add_ons =
+            EthereumAddOns::default().with_builder_config(self.builder_config.clone());
+        if let Some(bundle_pool) = &self.bundle_pool {
+            add_ons = add_ons.with_bundle_pool(bundle_pool.clone());
+        }
+        add_ons
     }
 }
 
@@ -292,8 +403,12 @@ where
     type EVM = EthEvmConfig;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        let evm_config = EthEvmConfig::new(ctx.chain_spec())
+        let mut evm_config = EthEvmConfig::new(ctx.chain_spec())
             .with_extra_data(ctx.payload_builder_config().extra_data_bytes());
+        if ctx.is_dev() {
+            // dev nodes support injecting state changes via the `anvil_` namespace
+            evm_config = evm_config.with_state_override_layer(StateOverrideLayer::default());
+        }
         Ok(evm_config)
     }
 }
@@ -421,6 +536,10 @@ where
     type Validator = EthereumEngineValidator;
 
     async fn build(self, ctx: &AddOnsContext<'_, Node>) -> eyre::Result<Self::Validator> {
-        Ok(EthereumEngineValidator::new(ctx.config.chain.clone()))
+        let validator = EthereumEngineValidator::new(ctx.config.chain.clone());
+        Ok(match ctx.node.evm_config().state_override_layer() {
+            Some(layer) => validator.with_state_override_layer(layer.clone()),
+            None => validator,
+        })
     }
 }
@@ -4,19 +4,40 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
-use reth_ethereum_primitives::EthPrimitives;
+use reth_ethereum_payload_builder::EthereumBuilderConfigHandle;
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::ConfigureEvm;
+use reth_evm_ethereum::ConfigureExtraData;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
 };
+use reth_payload_util::BundlePool;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Shared builder configuration that can be updated at runtime.
+    pub builder_config: EthereumBuilderConfigHandle,
+    /// Pool of bundles that are included in built payloads, if bundles are enabled.
+    pub bundle_pool: Option<BundlePool<TransactionSigned>>,
+}
+
+impl EthereumPayloadBuilder {
+    /// Configures the shared builder configuration, e.g. to update it via the `miner_` namespace.
+    pub fn with_builder_config(mut self, builder_config: EthereumBuilderConfigHandle) -> Self {
+        self.builder_config = builder_config;
+        self
+    }
+
+    /// Configures the pool of bundles, e.g. to submit bundles via `eth_sendBundle`.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+}
 
 impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
 where
@@ -28,7 +49,8 @@ where
     Evm: ConfigureEvm<
             Primitives = PrimitivesTy<Types>,
             NextBlockEnvCtx = reth_evm::NextBlockEnvAttributes,
-        > + 'static,
+        > + ConfigureExtraData
+        + 'static,
     Types::Payload: PayloadTypes<
         BuiltPayload = EthBuiltPayload,
         PayloadAttributes = EthPayloadAttributes,
@@ -47,12 +69,17 @@ where
         let conf = ctx.payload_builder_config();
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
+        self.builder_config.set_gas_limit(gas_limit);
 
-        Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+        let mut payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
-        ))
+            self.builder_config,
+        );
+        if let Some(bundle_pool) = self.bundle_pool {
+            payload_builder = payload_builder.with_bundle_pool(bundle_pool);
+        }
+        Ok(payload_builder)
     }
 }
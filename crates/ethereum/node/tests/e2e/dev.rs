@@ -1,18 +1,25 @@
-use alloy_eips::eip2718::Encodable2718;
+use alloy_eips::{eip2718::Encodable2718, BlockNumberOrTag};
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_primitives::{b256, hex, Address, Bytes, B256, U256};
+use alloy_provider::{network::TransactionBuilder, DynProvider, Provider, ProviderBuilder};
+use alloy_rpc_types_eth::{Block, TransactionRequest};
 use futures::StreamExt;
 use reth_chainspec::ChainSpec;
 use reth_node_api::{BlockBody, FullNodeComponents, FullNodePrimitives, NodeTypes};
 use reth_node_builder::{
     rpc::RethRpcAddOns, EngineNodeLauncher, FullNode, NodeBuilder, NodeConfig, NodeHandle,
 };
-use reth_node_core::args::DevArgs;
+use reth_node_core::args::{DevArgs, RpcServerArgs};
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
 use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
 use reth_rpc_eth_api::helpers::EthTransactions;
+use reth_rpc_server_types::RpcModuleSelection;
 use reth_tasks::TaskManager;
-use std::sync::Arc;
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 #[tokio::test]
 async fn can_run_dev_node() -> eyre::Result<()> {
@@ -43,6 +50,236 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_send_impersonated_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    with_dev_rpc(|provider| async move {
+        // two accounts without keys send identical transactions
+        let senders = [Address::repeat_byte(0x11), Address::repeat_byte(0x22)];
+        for sender in senders {
+            fund_and_impersonate(&provider, sender).await?;
+        }
+        mine(&provider, 1).await?;
+
+        let mut pending = Vec::new();
+        for sender in senders {
+            let request = TransactionRequest::default()
+                .with_from(sender)
+                .with_to(Address::repeat_byte(0x33))
+                .with_value(U256::from(1));
+            pending.push(provider.send_transaction(request).await?);
+        }
+        mine(&provider, 1).await?;
+
+        for (sender, pending) in senders.into_iter().zip(pending) {
+            let receipt = pending.get_receipt().await?;
+            assert!(receipt.status());
+            assert_eq!(receipt.from, sender);
+
+            let tx = provider.get_transaction_by_hash(receipt.transaction_hash).await?.unwrap();
+            assert_eq!(tx.inner.signer(), sender);
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn can_revert_to_snapshot() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    with_dev_rpc(|provider| async move {
+        let sender = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x33);
+        fund_and_impersonate(&provider, sender).await?;
+        mine(&provider, 1).await?;
+
+        let snapshot_head = latest_block(&provider).await?;
+        let id: U256 = provider.raw_request("anvil_snapshot".into(), ()).await?;
+
+        let request = TransactionRequest::default()
+            .with_from(sender)
+            .with_to(recipient)
+            .with_value(U256::from(1));
+        let receipt = provider.send_transaction(request).await?.get_receipt().await?;
+        assert!(receipt.status());
+        // mine past the persistence threshold, so that reverted blocks are also on disk
+        mine(&provider, 5).await?;
+        assert_eq!(provider.get_balance(recipient).await?, U256::from(1));
+
+        assert!(provider.raw_request::<_, bool>("anvil_revert".into(), (id,)).await?);
+        let head = latest_block(&provider).await?;
+        assert_eq!(head.header.hash, snapshot_head.header.hash);
+        assert_eq!(provider.get_balance(recipient).await?, U256::ZERO);
+
+        // the reverted transaction is not mined again
+        mine(&provider, 1).await?;
+        let head = latest_block(&provider).await?;
+        assert_eq!(head.header.number, snapshot_head.header.number + 1);
+        assert_eq!(head.header.parent_hash, snapshot_head.header.hash);
+        assert!(head.transactions.is_empty());
+        assert_eq!(provider.get_balance(recipient).await?, U256::ZERO);
+
+        // snapshots can only be reverted once
+        assert!(!provider.raw_request::<_, bool>("anvil_revert".into(), (id,)).await?);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn can_control_block_timestamps() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    with_dev_rpc(|provider| async move {
+        mine(&provider, 1).await?;
+        let latest = latest_block(&provider).await?.header.timestamp;
+
+        provider
+            .raw_request::<_, ()>("anvil_setNextBlockTimestamp".into(), (latest + 100,))
+            .await?;
+        mine(&provider, 1).await?;
+        assert_eq!(latest_block(&provider).await?.header.timestamp, latest + 100);
+
+        // the next timestamp can't be before the latest block
+        assert!(provider
+            .raw_request::<_, ()>("anvil_setNextBlockTimestamp".into(), (latest,))
+            .await
+            .is_err());
+
+        // blocks mined at once are spaced by the requested interval
+        provider
+            .raw_request::<_, ()>("anvil_mine".into(), (U256::from(2), Some(U256::from(5))))
+            .await?;
+        let second = latest_block(&provider).await?;
+        let first = provider.get_block_by_hash(second.header.parent_hash).await?.unwrap();
+        assert_eq!(second.header.timestamp, first.header.timestamp + 5);
+
+        provider.raw_request::<_, ()>("anvil_setBlockTimestampInterval".into(), (10,)).await?;
+        let latest = latest_block(&provider).await?.header.timestamp;
+        mine(&provider, 1).await?;
+        assert_eq!(latest_block(&provider).await?.header.timestamp, latest + 10);
+        assert!(
+            provider
+                .raw_request::<_, bool>("anvil_removeBlockTimestampInterval".into(), ())
+                .await?
+        );
+
+        let offset: i64 =
+            provider.raw_request("anvil_increaseTime".into(), (U256::from(3600),)).await?;
+        assert_eq!(offset, 3600);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        mine(&provider, 1).await?;
+        assert!(latest_block(&provider).await?.header.timestamp >= now + 3600);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+async fn can_dump_and_load_state() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    with_dev_rpc(|provider| async move {
+        let account = Address::repeat_byte(0x11);
+        let contract = Address::repeat_byte(0x22);
+        let code = Bytes::from_static(&hex!("600160005260206000f3"));
+        let value = B256::with_last_byte(1);
+
+        mine(&provider, 1).await?;
+        let id: U256 = provider.raw_request("anvil_snapshot".into(), ()).await?;
+
+        provider.raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::from(7))).await?;
+        provider.raw_request::<_, ()>("anvil_setNonce".into(), (account, U256::from(3))).await?;
+        provider.raw_request::<_, ()>("anvil_setCode".into(), (contract, code.clone())).await?;
+        provider
+            .raw_request::<_, bool>("anvil_setStorageAt".into(), (contract, U256::from(1), value))
+            .await?;
+        mine(&provider, 1).await?;
+
+        let state: Bytes = provider.raw_request("anvil_dumpState".into(), ()).await?;
+
+        assert!(provider.raw_request::<_, bool>("anvil_revert".into(), (id,)).await?);
+        assert_eq!(provider.get_balance(account).await?, U256::ZERO);
+        assert!(provider.get_code_at(contract).await?.is_empty());
+
+        assert!(provider.raw_request::<_, bool>("anvil_loadState".into(), (state,)).await?);
+        mine(&provider, 1).await?;
+        assert_eq!(provider.get_balance(account).await?, U256::from(7));
+        assert_eq!(provider.get_transaction_count(account).await?, 3);
+        assert_eq!(provider.get_code_at(contract).await?, code);
+        assert_eq!(provider.get_storage_at(contract, U256::from(1)).await?, U256::from(1));
+
+        Ok(())
+    })
+    .await
+}
+
+/// Launches a dev node that serves all RPC modules over HTTP and runs `f` with a provider
+/// connected to it.
+async fn with_dev_rpc<F, Fut>(f: F) -> eyre::Result<()>
+where
+    F: FnOnce(DynProvider) -> Fut,
+    Fut: Future<Output = eyre::Result<()>>,
+{
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(
+            RpcServerArgs::default()
+                .with_unused_ports()
+                .with_http()
+                .with_http_api(RpcModuleSelection::All),
+        );
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let url = format!("http://{}", node.rpc_server_handle().http_local_addr().unwrap());
+    f(ProviderBuilder::new().connect_http(url.parse()?).erased()).await
+}
+
+/// Funds the account and lets it send transactions without a key.
+async fn fund_and_impersonate(provider: &DynProvider, account: Address) -> eyre::Result<()> {
+    provider
+        .raw_request::<_, ()>(
+            "anvil_setBalance".into(),
+            (account, U256::from(10).pow(U256::from(18))),
+        )
+        .await?;
+    provider.raw_request::<_, ()>("anvil_impersonateAccount".into(), (account,)).await?;
+    Ok(())
+}
+
+/// Mines the given number of blocks.
+async fn mine(provider: &DynProvider, blocks: u64) -> eyre::Result<()> {
+    provider.raw_request::<_, ()>("anvil_mine".into(), (U256::from(blocks), None::<U256>)).await?;
+    Ok(())
+}
+
+/// Returns the latest block.
+async fn latest_block(provider: &DynProvider) -> eyre::Result<Block> {
+    Ok(provider.get_block_by_number(BlockNumberOrTag::Latest).await?.unwrap())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
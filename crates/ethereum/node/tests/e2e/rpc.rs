@@ -1,7 +1,10 @@
 use crate::utils::eth_payload_attributes;
 use alloy_eips::{calc_next_block_base_fee, eip2718::Encodable2718};
-use alloy_primitives::{Address, B256, U256};
-use alloy_provider::{network::EthereumWallet, Provider, ProviderBuilder, SendableTx};
+use alloy_primitives::{hex, Address, B256, U256};
+use alloy_provider::{
+    network::{EthereumWallet, TransactionBuilder},
+    Provider, ProviderBuilder, SendableTx,
+};
 use alloy_rpc_types_beacon::relay::{
     BidTrace, BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
     SignedBidSubmissionV3, SignedBidSubmissionV4,
@@ -10,10 +13,15 @@ use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayloadV3};
 use alloy_rpc_types_eth::TransactionRequest;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
-use reth_e2e_test_utils::setup_engine;
-use reth_node_ethereum::EthereumNode;
+use reth_e2e_test_utils::{node::NodeTestContext, setup_engine, wallet::Wallet};
+use reth_node_builder::{EngineNodeLauncher, NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::args::{DiscoveryArgs, NetworkArgs, RpcServerArgs};
+use reth_node_ethereum::{call_trace_index_exex, node::EthereumAddOns, EthereumNode};
 use reth_payload_primitives::BuiltPayload;
-use std::sync::Arc;
+use reth_provider::{providers::BlockchainProvider, CallHistoryReader, ProviderError};
+use reth_rpc_server_types::RpcModuleSelection;
+use reth_tasks::TaskManager;
+use std::{sync::Arc, time::Duration};
 
 alloy_sol_types::sol! {
     #[sol(rpc, bytecode = "6080604052348015600f57600080fd5b5060405160db38038060db833981016040819052602a91607a565b60005b818110156074576040805143602082015290810182905260009060600160408051601f19818403018152919052805160209091012080555080606d816092565b915050602d565b505060b8565b600060208284031215608b57600080fd5b5051919050565b60006001820160b157634e487b7160e01b600052601160045260246000fd5b5060010190565b60168060c56000396000f3fe6080604052600080fdfea164736f6c6343000810000a")]
@@ -285,3 +293,102 @@ async fn test_flashbots_validate_v4() -> eyre::Result<()> {
         .is_err());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_ots_search_transactions_finds_internal_callees() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+    let node_config = NodeConfig::new(chain_spec.clone())
+        .with_network(NetworkArgs {
+            discovery: DiscoveryArgs { disable_discovery: true, ..Default::default() },
+            ..Default::default()
+        })
+        .with_unused_ports()
+        .with_rpc(
+            RpcServerArgs::default()
+                .with_unused_ports()
+                .with_http()
+                .with_http_api(RpcModuleSelection::All),
+        );
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config)
+        .testing_node(tasks.executor())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .install_exex("call-trace-index", async move |ctx| Ok(call_trace_index_exex(ctx)))
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+    let mut node = NodeTestContext::new(node, eth_payload_attributes).await?;
+    let genesis = node.block_hash(0);
+    node.update_forkchoice(genesis, genesis).await?;
+
+    let wallet = Wallet::default().with_chain_id(chain_spec.chain().into());
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.wallet_gen().swap_remove(0)))
+        .connect_http(node.rpc_url());
+
+    // the init code calls `callee`, which is not part of the transaction and whose state does not
+    // change
+    let callee = Address::repeat_byte(0x42);
+    let mut init_code = hex!("60006000600060006000").to_vec();
+    init_code.push(0x73);
+    init_code.extend_from_slice(callee.as_slice());
+    init_code.extend_from_slice(&hex!("5af100"));
+    let deploy = provider
+        .send_transaction(TransactionRequest::default().with_deploy_code(init_code))
+        .await?;
+    node.advance_block().await?;
+    let deploy = deploy.get_receipt().await?;
+    assert!(deploy.status());
+
+    let transfer = provider
+        .send_transaction(
+            TransactionRequest::default()
+                .with_to(Address::repeat_byte(0x43))
+                .with_value(U256::from(1)),
+        )
+        .await?;
+    node.advance_block().await?;
+    transfer.get_receipt().await?;
+
+    // wait for both blocks to be indexed
+    let index = node.inner.provider.clone();
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while index.call_history_checkpoint()?.unwrap_or_default() < 2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Ok::<_, ProviderError>(())
+    })
+    .await??;
+
+    let before: serde_json::Value =
+        provider.raw_request("ots_searchTransactionsBefore".into(), (callee, 0, 10)).await?;
+    let txs = before["txs"].as_array().unwrap();
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0]["hash"], serde_json::json!(deploy.transaction_hash));
+    assert_eq!(before["firstPage"], true);
+    assert_eq!(before["lastPage"], true);
+
+    let after: serde_json::Value =
+        provider.raw_request("ots_searchTransactionsAfter".into(), (callee, 0, 10)).await?;
+    let txs = after["txs"].as_array().unwrap();
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0]["hash"], serde_json::json!(deploy.transaction_hash));
+
+    Ok(())
+}
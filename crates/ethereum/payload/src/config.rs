@@ -1,5 +1,8 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use alloy_primitives::Bytes;
+use parking_lot::RwLock;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use std::sync::Arc;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -9,6 +12,11 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Extra data to include in built blocks, overrides the extra data of the EVM's block
+    /// assembler.
+    pub extra_data: Option<Bytes>,
+    /// Minimum effective tip per gas a transaction must pay to be included.
+    pub min_priority_fee: u128,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +28,12 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            extra_data: None,
+            min_priority_fee: 0,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +48,18 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Set the extra data of built blocks.
+    pub fn with_extra_data(mut self, extra_data: Bytes) -> Self {
+        self.extra_data = Some(extra_data);
+        self
+    }
+
+    /// Set the minimum effective tip per gas of included transactions.
+    pub const fn with_min_priority_fee(mut self, min_priority_fee: u128) -> Self {
+        self.min_priority_fee = min_priority_fee;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -45,6 +70,62 @@ impl EthereumBuilderConfig {
     }
 }
 
+/// Shareable handle to an [`EthereumBuilderConfig`] that can be updated at runtime.
+///
+/// The payload builder takes a snapshot of the configuration for every payload it builds, so
+/// updates, e.g. via the `miner_` namespace, are picked up by the next build attempt.
+#[derive(Debug, Clone, Default)]
+pub struct EthereumBuilderConfigHandle {
+    inner: Arc<RwLock<EthereumBuilderConfig>>,
+}
+
+impl EthereumBuilderConfigHandle {
+    /// Creates a new handle with the given initial configuration.
+    pub fn new(config: EthereumBuilderConfig) -> Self {
+        Self { inner: Arc::new(RwLock::new(config)) }
+    }
+
+    /// Returns a snapshot of the current configuration.
+    pub fn config(&self) -> EthereumBuilderConfig {
+        self.inner.read().clone()
+    }
+
+    /// Modifies the current configuration.
+    pub fn update(&self, f: impl FnOnce(&mut EthereumBuilderConfig)) {
+        f(&mut self.inner.write())
+    }
+
+    /// Sets the desired gas limit.
+    pub fn set_gas_limit(&self, desired_gas_limit: u64) {
+        self.update(|config| config.desired_gas_limit = desired_gas_limit)
+    }
+
+    /// Sets the extra data of built blocks.
+    pub fn set_extra_data(&self, extra_data: Bytes) {
+        self.update(|config| config.extra_data = Some(extra_data))
+    }
+
+    /// Sets the minimum effective tip per gas of included transactions.
+    pub fn set_min_priority_fee(&self, min_priority_fee: u128) {
+        self.update(|config| config.min_priority_fee = min_priority_fee)
+    }
+}
+
+impl PartialEq for EthereumBuilderConfigHandle {
+    /// Handles are equal if they share the same configuration.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for EthereumBuilderConfigHandle {}
+
+impl From<EthereumBuilderConfig> for EthereumBuilderConfigHandle {
+    fn from(config: EthereumBuilderConfig) -> Self {
+        Self::new(config)
+    }
+}
+
 /// Calculate the gas limit for the next block based on parent and desired gas limits.
 /// Ref: <https://github.com/ethereum/go-ethereum/blob/88cbfab332c96edfbe99d161d9df6a40721bd786/core/block_validator.go#L166>
 pub fn calculate_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
@@ -53,3 +134,23 @@ pub fn calculate_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64)
     let max_gas_limit = parent_gas_limit + delta;
     desired_gas_limit.clamp(min_gas_limit, max_gas_limit)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handle_updates_are_shared() {
+        let handle = EthereumBuilderConfigHandle::new(EthereumBuilderConfig::new());
+        let other = handle.clone();
+
+        other.set_gas_limit(60_000_000);
+        other.set_extra_data(Bytes::from_static(b"reth"));
+        other.set_min_priority_fee(1_000_000_000);
+
+        let config = handle.config();
+        assert_eq!(config.desired_gas_limit, 60_000_000);
+        assert_eq!(config.extra_data, Some(Bytes::from_static(b"reth")));
+        assert_eq!(config.min_priority_fee, 1_000_000_000);
+    }
+}
@@ -10,7 +10,7 @@
 #![allow(clippy::useless_let_if_seq)]
 
 use alloy_consensus::Transaction;
-use alloy_primitives::U256;
+use alloy_primitives::{Address, B256, U256};
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
@@ -20,12 +20,13 @@ use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
     execute::{BlockBuilder, BlockBuilderOutcome},
-    ConfigureEvm, Evm, NextBlockEnvAttributes,
+    ConfigureEvm, Database, Evm, EvmEnvFor, NextBlockEnvAttributes,
 };
-use reth_evm_ethereum::EthEvmConfig;
+use reth_evm_ethereum::{ConfigureExtraData, EthEvmConfig};
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_payload_util::{BundleBestTransactions, BundleOrTransaction, BundlePool, PayloadBundle};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_storage_api::StateProviderFactory;
@@ -34,7 +35,10 @@ use reth_transaction_pool::{
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
-use revm::context_interface::Block as _;
+use revm::{
+    context_interface::{result::ResultAndState, Block as _},
+    Database as _, DatabaseCommit,
+};
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
@@ -57,26 +61,70 @@ pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     pool: Pool,
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
-    /// Payload builder configuration.
-    builder_config: EthereumBuilderConfig,
+    /// Payload builder configuration, read on every build attempt.
+    builder_config: EthereumBuilderConfigHandle,
+    /// Bundles that are included in built payloads, if bundles are enabled.
+    bundle_pool: Option<BundlePool<TransactionSigned>>,
 }
 
 impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
-    pub const fn new(
+    ///
+    /// Accepts either a fixed [`EthereumBuilderConfig`] or a shared
+    /// [`EthereumBuilderConfigHandle`] that allows updating the configuration at runtime.
+    pub fn new(
         client: Client,
         pool: Pool,
         evm_config: EvmConfig,
-        builder_config: EthereumBuilderConfig,
+        builder_config: impl Into<EthereumBuilderConfigHandle>,
     ) -> Self {
-        Self { client, pool, evm_config, builder_config }
+        Self { client, pool, evm_config, builder_config: builder_config.into(), bundle_pool: None }
+    }
+
+    /// Configures the pool of bundles that are included in built payloads.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+
+    /// Returns the handle to the builder configuration.
+    pub const fn builder_config(&self) -> &EthereumBuilderConfigHandle {
+        &self.builder_config
+    }
+
+    /// Returns the pool of bundles that are included in built payloads, if bundles are enabled.
+    pub const fn bundle_pool(&self) -> Option<&BundlePool<TransactionSigned>> {
+        self.bundle_pool.as_ref()
+    }
+
+    /// Returns the bundles that can be included in the payload with the given config.
+    fn bundles(
+        &self,
+        config: &PayloadConfig<EthPayloadBuilderAttributes>,
+    ) -> Vec<Arc<PayloadBundle<TransactionSigned>>> {
+        let Some(bundle_pool) = &self.bundle_pool else { return Vec::new() };
+        let block_number = config.parent_header.number + 1;
+        bundle_pool.remove_expired(block_number);
+        bundle_pool.bundles_for_block(block_number, config.attributes.timestamp())
+    }
+
+    /// Removes the bundle that aborted a build attempt from the bundle pool, so that it isn't
+    /// included in the next attempt.
+    fn remove_failed_bundle(&self, err: &PayloadBuilderError) {
+        let (Some(bundle_pool), PayloadBuilderError::Other(err)) = (&self.bundle_pool, err) else {
+            return
+        };
+        if let Some(err) = err.downcast_ref::<BundleExecutionError>() {
+            bundle_pool.remove_bundle(&err.bundle);
+        }
     }
 }
 
 // Default implementation of [PayloadBuilder] for unit type
 impl<Pool, Client, EvmConfig> PayloadBuilder for EthereumPayloadBuilder<Pool, Client, EvmConfig>
 where
-    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + ConfigureExtraData,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
 {
@@ -87,21 +135,24 @@ where
         &self,
         args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        let bundles = self.bundles(&args.config);
         default_ethereum_payload(
             self.evm_config.clone(),
             self.client.clone(),
             self.pool.clone(),
-            self.builder_config.clone(),
+            self.builder_config.config(),
             args,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
+            bundles,
         )
+        .inspect_err(|err| self.remove_failed_bundle(err))
     }
 
     fn on_missing_payload(
         &self,
         _args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
     ) -> MissingPayloadBehaviour<Self::BuiltPayload> {
-        if self.builder_config.await_payload_on_missing {
+        if self.builder_config.config().await_payload_on_missing {
             MissingPayloadBehaviour::AwaitInProgress
         } else {
             MissingPayloadBehaviour::RaceEmptyPayload
@@ -112,16 +163,19 @@ where
         &self,
         config: PayloadConfig<Self::Attributes>,
     ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        let bundles = self.bundles(&config);
         let args = BuildArguments::new(Default::default(), config, Default::default(), None);
 
         default_ethereum_payload(
             self.evm_config.clone(),
             self.client.clone(),
             self.pool.clone(),
-            self.builder_config.clone(),
+            self.builder_config.config(),
             args,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
-        )?
+            bundles,
+        )
+        .inspect_err(|err| self.remove_failed_bundle(err))?
         .into_payload()
         .ok_or_else(|| PayloadBuilderError::MissingPayload)
     }
@@ -132,17 +186,25 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The given bundles are simulated on top of the state at the start of the block and interleaved
+/// with the pool transactions by the profit per gas they pay to the coinbase. A bundle is only
+/// included if none of its transactions fail or revert, unless they are allowed to. If a
+/// transaction of a simulated bundle fails to execute, the build attempt is aborted with a
+/// [`BundleExecutionError`].
 #[inline]
 pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
-    evm_config: EvmConfig,
+    mut evm_config: EvmConfig,
     client: Client,
     pool: Pool,
     builder_config: EthereumBuilderConfig,
     args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
     best_txs: F,
+    bundles: Vec<Arc<PayloadBundle<TransactionSigned>>>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
-    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + ConfigureExtraData,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
@@ -150,25 +212,30 @@ where
     let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
     let PayloadConfig { parent_header, attributes } = config;
 
+    if let Some(extra_data) = builder_config.extra_data.clone() {
+        evm_config.set_extra_data(extra_data);
+    }
+
     let state_provider = client.state_by_block_hash(parent_header.hash())?;
     let state = StateProviderDatabase::new(&state_provider);
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient(),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+    // the environment is kept around to simulate bundles before including them
+    let evm_env = evm_config
+        .next_evm_env(&parent_header, &next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
+    let evm = evm_config.evm_with_env(&mut db, evm_env.clone());
+    let ctx = evm_config.context_for_next_block(&parent_header, next_block_attributes);
+    let mut builder = evm_config.create_block_builder(evm, &parent_header, ctx);
 
     let chain_spec = client.chain_spec();
 
@@ -176,11 +243,6 @@ where
     let mut cumulative_gas_used = 0;
     let block_gas_limit: u64 = builder.evm_mut().block().gas_limit;
     let base_fee = builder.evm_mut().block().basefee;
-
-    let mut best_txs = best_txs(BestTransactionsAttributes::new(
-        base_fee,
-        builder.evm_mut().block().blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
     let mut total_fees = U256::ZERO;
 
     builder.apply_pre_execution_changes().map_err(|err| {
@@ -188,6 +250,32 @@ where
         PayloadBuilderError::Internal(err.into())
     })?;
 
+    // bundles are ranked by the profit they pay to the coinbase on top of the state at the start
+    // of the block, bundles that fail on it are discarded
+    let mut simulated_bundles = Vec::with_capacity(bundles.len());
+    for bundle in bundles {
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+        if let Some(profit) = simulate_bundle(
+            &evm_config,
+            &mut **builder.evm_mut().db_mut(),
+            evm_env.clone(),
+            &bundle,
+        ) {
+            simulated_bundles.push((profit, bundle));
+        }
+    }
+
+    let mut best_txs = BundleBestTransactions::new(
+        best_txs(BestTransactionsAttributes::new(
+            base_fee,
+            builder.evm_mut().block().blob_gasprice().map(|gasprice| gasprice as u64),
+        )),
+        simulated_bundles,
+        base_fee,
+    );
+
     // initialize empty blob sidecars at first. If cancun is active then this will be populated by
     // blob sidecars if any.
     let mut blob_sidecars = BlobSidecars::Empty;
@@ -198,7 +286,50 @@ where
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
-    while let Some(pool_tx) = best_txs.next() {
+    while let Some(item) = best_txs.next() {
+        let pool_tx = match item {
+            BundleOrTransaction::Transaction(pool_tx) => pool_tx,
+            BundleOrTransaction::Bundle(bundle) => {
+                // bundles must fit into the block as a whole, blob transactions are not supported
+                // in bundles
+                if cumulative_gas_used + bundle.gas_limit() > block_gas_limit ||
+                    bundle.transactions.iter().any(|tx| tx.as_eip4844().is_some())
+                {
+                    trace!(target: "payload_builder", bundle=?bundle.hash, "skipping bundle that can't be included");
+                    continue
+                }
+
+                // check if the job was cancelled, if so we can exit early
+                if cancel.is_cancelled() {
+                    return Ok(BuildOutcome::Cancelled)
+                }
+
+                if simulate_bundle(
+                    &evm_config,
+                    &mut **builder.evm_mut().db_mut(),
+                    evm_env.clone(),
+                    &bundle,
+                )
+                .is_none()
+                {
+                    continue
+                }
+
+                // the simulation succeeded on the current state, so the bundle's transactions
+                // execute the same way. Executed transactions can't be rolled back, so a failure
+                // nonetheless aborts the build attempt instead of including a partial bundle
+                let (gas_used, fees) =
+                    execute_bundle(&mut builder, &bundle, base_fee).map_err(|err| {
+                        warn!(target: "payload_builder", %err, "aborting payload with partially executed bundle");
+                        PayloadBuilderError::other(err)
+                    })?;
+                total_fees += fees;
+                cumulative_gas_used += gas_used;
+                trace!(target: "payload_builder", bundle=?bundle.hash, "included bundle");
+                continue
+            }
+        };
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -211,6 +342,17 @@ where
             continue
         }
 
+        // skip transactions that don't pay the configured minimum tip, this also removes all
+        // dependent transactions from the iterator
+        if builder_config.min_priority_fee > 0 &&
+            pool_tx
+                .effective_tip_per_gas(base_fee)
+                .is_none_or(|tip| tip < builder_config.min_priority_fee)
+        {
+            best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::Underpriced);
+            continue
+        }
+
         // check if the job was cancelled, if so we can exit early
         if cancel.is_cancelled() {
             return Ok(BuildOutcome::Cancelled)
@@ -342,3 +484,211 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// A transaction of a bundle failed to execute after the bundle was simulated successfully.
+#[derive(Debug, thiserror::Error)]
+#[error("bundle {bundle} failed to execute transaction {tx}: {source}")]
+pub struct BundleExecutionError {
+    /// Hash of the bundle.
+    pub bundle: B256,
+    /// Hash of the transaction that failed.
+    pub tx: B256,
+    /// The execution error.
+    #[source]
+    pub source: BlockExecutionError,
+}
+
+/// Executes all transactions of the bundle with the given block builder.
+///
+/// Returns the gas used by the bundle and the fees it paid.
+fn execute_bundle(
+    builder: &mut impl BlockBuilder<Primitives = EthPrimitives>,
+    bundle: &PayloadBundle<TransactionSigned>,
+    base_fee: u64,
+) -> Result<(u64, U256), BundleExecutionError> {
+    let mut cumulative_gas_used = 0;
+    let mut fees = U256::ZERO;
+    for tx in &bundle.transactions {
+        let gas_used = builder.execute_transaction(tx.clone()).map_err(|source| {
+            BundleExecutionError { bundle: bundle.hash, tx: *tx.tx_hash(), source }
+        })?;
+        let miner_fee =
+            tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
+        fees += U256::from(miner_fee) * U256::from(gas_used);
+        cumulative_gas_used += gas_used;
+    }
+    Ok((cumulative_gas_used, fees))
+}
+
+/// Simulates the bundle on top of the given state without modifying it.
+///
+/// Returns the profit per gas the bundle pays to the coinbase, including direct payments, or
+/// `None` if any of the bundle's transactions is invalid or reverts without being allowed to
+/// revert.
+fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    db: &mut State<DB>,
+    evm_env: EvmEnvFor<EvmConfig>,
+    bundle: &PayloadBundle<TransactionSigned>,
+) -> Option<u128>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: Database,
+{
+    let coinbase = evm_env.block_env.beneficiary;
+    // all changes are committed to the overlay which is discarded afterwards
+    let mut overlay = State::builder().with_database(db).build();
+    let balance_before = coinbase_balance(&mut overlay, coinbase)?;
+    let mut evm = evm_config.evm_with_env(&mut overlay, evm_env);
+
+    let mut gas_used = 0u64;
+
+    for tx in &bundle.transactions {
+        let ResultAndState { result, state } = match evm.transact(evm_config.tx_env(tx)) {
+            Ok(res) => res,
+            Err(err) => {
+                trace!(target: "payload_builder", %err, bundle=?bundle.hash, tx=?tx.hash(), "skipping bundle with invalid transaction");
+                return None
+            }
+        };
+
+        if !result.is_success() && !bundle.can_revert(tx.hash()) {
+            trace!(target: "payload_builder", bundle=?bundle.hash, tx=?tx.hash(), "skipping bundle with reverting transaction");
+            return None
+        }
+
+        gas_used += result.gas_used();
+        evm.db_mut().commit(state);
+    }
+
+    let profit = coinbase_balance(evm.db_mut(), coinbase)?.saturating_sub(balance_before);
+    let profit_per_gas = profit.checked_div(U256::from(gas_used)).unwrap_or_default();
+    Some(profit_per_gas.saturating_to())
+}
+
+/// Returns the balance of the coinbase in the given state, or `None` if it can't be loaded.
+fn coinbase_balance<DB: Database>(db: &mut State<DB>, coinbase: Address) -> Option<U256> {
+    match db.basic(coinbase) {
+        Ok(account) => Some(account.map(|account| account.balance).unwrap_or_default()),
+        Err(err) => {
+            trace!(target: "payload_builder", %err, %coinbase, "failed to load coinbase account");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, Signature, TxKind};
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_primitives_traits::{Recovered, SealedHeader};
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    fn transfer(sender: Address, nonce: u64) -> Recovered<TransactionSigned> {
+        payment(sender, nonce, Address::with_last_byte(0xff), U256::from(1))
+    }
+
+    fn payment(
+        sender: Address,
+        nonce: u64,
+        to: Address,
+        value: U256,
+    ) -> Recovered<TransactionSigned> {
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: 100_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(to),
+            value,
+            ..Default::default()
+        };
+        Recovered::new_unchecked(
+            TransactionSigned::new_unhashed(tx.into(), Signature::test_signature()),
+            sender,
+        )
+    }
+
+    #[test]
+    fn aborts_bundle_that_becomes_invalid_after_simulation() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).cancun_activated().build());
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+        let (alice, bob) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let mut db = CacheDB::new(EmptyDB::default());
+        for sender in [alice, bob] {
+            db.insert_account_info(
+                sender,
+                AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+            );
+        }
+        let mut state = State::builder().with_database(db).with_bundle_update().build();
+
+        let parent = SealedHeader::seal_slow(chain_spec.genesis_header().clone());
+        let attributes = NextBlockEnvAttributes {
+            timestamp: parent.timestamp + 12,
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            gas_limit: 30_000_000,
+            parent_beacon_block_root: Some(B256::ZERO),
+            withdrawals: None,
+        };
+        let evm_env = evm_config.next_evm_env(&parent, &attributes).unwrap();
+        let evm = evm_config.evm_with_env(&mut state, evm_env.clone());
+        let ctx = evm_config.context_for_next_block(&parent, attributes);
+        let mut builder = evm_config.create_block_builder(evm, &parent, ctx);
+        let base_fee = builder.evm_mut().block().basefee;
+
+        let bundle = PayloadBundle::new(vec![transfer(alice, 0), transfer(bob, 0)], 1);
+        assert!(simulate_bundle(&evm_config, &mut **builder.evm_mut().db_mut(), evm_env, &bundle)
+            .is_some());
+
+        // a transaction executed after the simulation invalidates the bundle's second transaction
+        builder.execute_transaction(transfer(bob, 0)).unwrap();
+
+        let err = execute_bundle(&mut builder, &bundle, base_fee).unwrap_err();
+        assert_eq!(err.bundle, bundle.hash);
+        assert_eq!(err.tx, *bundle.transactions[1].tx_hash());
+    }
+
+    #[test]
+    fn direct_coinbase_payment_raises_bundle_profit() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).cancun_activated().build());
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+        let (alice, bob) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let coinbase = Address::with_last_byte(0xcb);
+        let mut db = CacheDB::new(EmptyDB::default());
+        for sender in [alice, bob] {
+            db.insert_account_info(
+                sender,
+                AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+            );
+        }
+        let mut state = State::builder().with_database(db).build();
+
+        let parent = SealedHeader::seal_slow(chain_spec.genesis_header().clone());
+        let attributes = NextBlockEnvAttributes {
+            timestamp: parent.timestamp + 12,
+            suggested_fee_recipient: coinbase,
+            prev_randao: B256::ZERO,
+            gas_limit: 30_000_000,
+            parent_beacon_block_root: Some(B256::ZERO),
+            withdrawals: None,
+        };
+        let evm_env = evm_config.next_evm_env(&parent, &attributes).unwrap();
+
+        let tip = PayloadBundle::new(vec![transfer(alice, 0)], 1);
+        let tip_profit = simulate_bundle(&evm_config, &mut state, evm_env.clone(), &tip).unwrap();
+        assert_eq!(tip_profit, 100_000_000_000 - evm_env.block_env.basefee as u128);
+
+        let direct = PayloadBundle::new(vec![payment(bob, 0, coinbase, U256::from(21_000_000))], 1);
+        let direct_profit = simulate_bundle(&evm_config, &mut state, evm_env, &direct).unwrap();
+        assert_eq!(direct_profit, tip_profit + 1_000);
+    }
+}
@@ -46,6 +46,8 @@ pub use aliases::*;
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
+#[cfg(feature = "std")]
+pub mod state_override;
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
 pub mod test_utils;
@@ -279,6 +281,15 @@ pub trait ConfigureEvm: Clone + Debug + Send + Sync + Unpin {
         Ok(self.create_block_builder(evm, parent, ctx))
     }
 
+    /// Returns the [`StateOverrideLayer`](state_override::StateOverrideLayer) that is applied to
+    /// executed blocks, if any.
+    ///
+    /// This is only configured for development nodes.
+    #[cfg(feature = "std")]
+    fn state_override_layer(&self) -> Option<&state_override::StateOverrideLayer> {
+        None
+    }
+
     /// Returns a new [`BasicBlockExecutor`].
     #[auto_impl(keep_default_for(&, Arc))]
     fn executor<DB: Database>(&self, db: DB) -> BasicBlockExecutor<&Self, DB> {
@@ -0,0 +1,379 @@
+//! Support for injecting arbitrary state changes into locally built blocks.
+//!
+//! This is used by development nodes to implement "cheat codes" such as `anvil_setBalance`: the
+//! requested changes are collected in a [`StateOverrideLayer`], staged for a specific parent block
+//! and then applied by the [`StateOverrideExecutor`] before any other pre-execution changes of the
+//! block built on top of that parent.
+//!
+//! Because the changes are applied during block execution, they are part of the block's state
+//! transition and are therefore picked up by both block building and block validation.
+//!
+//! The layer also records the senders of impersonated transactions. These transactions are signed
+//! with a throwaway key, so the [`StateOverrideExecutor`] executes them on behalf of the recorded
+//! sender instead of the recovered signer.
+
+use crate::{
+    block::{CommitChanges, ExecutableTx, StateChangePreBlockSource, StateChangeSource},
+    Database, Evm, OnStateHook,
+};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use alloy_consensus::transaction::Recovered;
+use alloy_evm::block::{BlockExecutionError, BlockExecutionResult, BlockExecutor};
+use alloy_primitives::{keccak256, map::HashMap, Address, Bytes, B256, U256};
+use parking_lot::Mutex;
+use reth_primitives_traits::SignedTransaction;
+use revm::{
+    bytecode::Bytecode,
+    context::result::ExecutionResult,
+    database::State,
+    state::{Account, AccountInfo, AccountStatus, EvmState, EvmStorageSlot},
+    Database as _, DatabaseCommit,
+};
+
+/// Maximum number of parent blocks for which staged overrides are retained.
+const MAX_STAGED_OVERRIDES: usize = 256;
+
+/// Maximum number of impersonated transactions whose senders are retained.
+const MAX_IMPERSONATED_TRANSACTIONS: usize = 4096;
+
+/// The [`StateChangeSource`] reported for injected state changes.
+///
+/// [`StateChangePreBlockSource`] has no variant for injected changes, they are reported as part of
+/// the pre-block system calls. The source is only used for diagnostics by state hooks.
+pub const STATE_OVERRIDE_SOURCE: StateChangeSource =
+    StateChangeSource::PreBlock(StateChangePreBlockSource::BlockHashesContract);
+
+/// Overrides for a single account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    /// New balance of the account.
+    pub balance: Option<U256>,
+    /// New nonce of the account.
+    pub nonce: Option<u64>,
+    /// New code of the account.
+    pub code: Option<Bytes>,
+    /// Storage slots to overwrite.
+    pub storage: HashMap<B256, B256>,
+}
+
+impl AccountOverride {
+    /// Merges `other` into `self`, values in `other` take precedence.
+    pub fn merge(&mut self, other: Self) {
+        if other.balance.is_some() {
+            self.balance = other.balance;
+        }
+        if other.nonce.is_some() {
+            self.nonce = other.nonce;
+        }
+        if other.code.is_some() {
+            self.code = other.code;
+        }
+        self.storage.extend(other.storage);
+    }
+}
+
+/// A set of account overrides.
+pub type StateOverrides = HashMap<Address, AccountOverride>;
+
+#[derive(Debug, Default)]
+struct StateOverrideLayerInner {
+    /// Overrides that have not yet been assigned to a block.
+    pending: StateOverrides,
+    /// Overrides assigned to the block built on top of the given parent hash.
+    staged: VecDeque<(B256, Arc<StateOverrides>)>,
+    /// Senders of impersonated transactions by transaction hash.
+    impersonated: HashMap<B256, Address>,
+    /// Hashes of impersonated transactions in insertion order, used for eviction.
+    impersonated_order: VecDeque<B256>,
+}
+
+/// Shared collection of state overrides that should be applied to the next locally built block.
+///
+/// Overrides are first recorded as pending and are then [staged](Self::stage) for a parent
+/// block. Every block executed on top of that parent, whether it is being built or validated,
+/// applies the staged overrides before its regular pre-execution changes.
+#[derive(Debug, Clone, Default)]
+pub struct StateOverrideLayer {
+    inner: Arc<Mutex<StateOverrideLayerInner>>,
+}
+
+impl StateOverrideLayer {
+    /// Records an override for the given account.
+    pub fn set_account(&self, address: Address, account: AccountOverride) {
+        self.inner.lock().pending.entry(address).or_default().merge(account);
+    }
+
+    /// Records multiple account overrides.
+    pub fn extend(&self, overrides: impl IntoIterator<Item = (Address, AccountOverride)>) {
+        for (address, account) in overrides {
+            self.set_account(address, account);
+        }
+    }
+
+    /// Sets the balance of the given account.
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.set_account(address, AccountOverride { balance: Some(balance), ..Default::default() })
+    }
+
+    /// Sets the nonce of the given account.
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.set_account(address, AccountOverride { nonce: Some(nonce), ..Default::default() })
+    }
+
+    /// Sets the code of the given account.
+    pub fn set_code(&self, address: Address, code: Bytes) {
+        self.set_account(address, AccountOverride { code: Some(code), ..Default::default() })
+    }
+
+    /// Sets a storage slot of the given account.
+    pub fn set_storage(&self, address: Address, slot: B256, value: B256) {
+        self.set_account(
+            address,
+            AccountOverride { storage: HashMap::from_iter([(slot, value)]), ..Default::default() },
+        )
+    }
+
+    /// Returns `true` if there are overrides that have not been staged yet.
+    pub fn has_pending(&self) -> bool {
+        !self.inner.lock().pending.is_empty()
+    }
+
+    /// Assigns all pending overrides to the block that is built on top of `parent_hash`.
+    pub fn stage(&self, parent_hash: B256) {
+        let mut inner = self.inner.lock();
+        if inner.pending.is_empty() {
+            return
+        }
+        let pending = core::mem::take(&mut inner.pending);
+
+        if let Some((_, staged)) = inner.staged.iter_mut().find(|(hash, _)| *hash == parent_hash) {
+            let staged = Arc::make_mut(staged);
+            for (address, account) in pending {
+                staged.entry(address).or_default().merge(account);
+            }
+            return
+        }
+
+        if inner.staged.len() >= MAX_STAGED_OVERRIDES {
+            inner.staged.pop_front();
+        }
+        inner.staged.push_back((parent_hash, Arc::new(pending)));
+    }
+
+    /// Returns the overrides staged for the block built on top of `parent_hash`.
+    pub fn staged(&self, parent_hash: B256) -> Option<Arc<StateOverrides>> {
+        self.inner
+            .lock()
+            .staged
+            .iter()
+            .find(|(hash, _)| *hash == parent_hash)
+            .map(|(_, overrides)| overrides.clone())
+    }
+
+    /// Discards all pending and staged overrides.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.pending.clear();
+        inner.staged.clear();
+    }
+
+    /// Records that the transaction with the given hash is executed on behalf of `sender`.
+    pub fn impersonate_transaction(&self, tx_hash: B256, sender: Address) {
+        let mut inner = self.inner.lock();
+        if inner.impersonated.insert(tx_hash, sender).is_some() {
+            return
+        }
+        if inner.impersonated_order.len() >= MAX_IMPERSONATED_TRANSACTIONS {
+            if let Some(evicted) = inner.impersonated_order.pop_front() {
+                inner.impersonated.remove(&evicted);
+            }
+        }
+        inner.impersonated_order.push_back(tx_hash);
+    }
+
+    /// Returns the sender the transaction with the given hash is executed on behalf of, if it is
+    /// impersonated.
+    pub fn impersonated_sender(&self, tx_hash: &B256) -> Option<Address> {
+        self.inner.lock().impersonated.get(tx_hash).copied()
+    }
+}
+
+/// A [`BlockExecutor`] that applies [`StateOverrides`] before executing the block and executes
+/// impersonated transactions on behalf of their recorded sender.
+pub struct StateOverrideExecutor<E> {
+    inner: E,
+    layer: Option<StateOverrideLayer>,
+    overrides: Option<Arc<StateOverrides>>,
+    hook: Option<Box<dyn OnStateHook>>,
+}
+
+impl<E: core::fmt::Debug> core::fmt::Debug for StateOverrideExecutor<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StateOverrideExecutor")
+            .field("inner", &self.inner)
+            .field("layer", &self.layer)
+            .field("overrides", &self.overrides)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+impl<E> StateOverrideExecutor<E> {
+    /// Creates a new executor on top of `inner` for the block built on top of `parent_hash`.
+    ///
+    /// Without a [`StateOverrideLayer`] all calls are forwarded to `inner`.
+    pub fn new(inner: E, layer: Option<StateOverrideLayer>, parent_hash: B256) -> Self {
+        let overrides = layer.as_ref().and_then(|layer| layer.staged(parent_hash));
+        Self { inner, layer, overrides, hook: None }
+    }
+}
+
+impl<'db, DB, E> BlockExecutor for StateOverrideExecutor<E>
+where
+    DB: Database + 'db,
+    E: BlockExecutor<Transaction: SignedTransaction, Evm: Evm<DB = &'db mut State<DB>>>,
+{
+    type Transaction = E::Transaction;
+    type Receipt = E::Receipt;
+    type Evm = E::Evm;
+
+    fn apply_pre_execution_changes(&mut self) -> Result<(), BlockExecutionError> {
+        if let Some(overrides) = self.overrides.take() {
+            let db: &mut State<DB> = self.inner.evm_mut().db_mut();
+            let mut state = EvmState::default();
+
+            for (address, account) in overrides.iter() {
+                let mut info =
+                    db.basic(*address).map_err(BlockExecutionError::other)?.unwrap_or_default();
+                if let Some(balance) = account.balance {
+                    info.balance = balance;
+                }
+                if let Some(nonce) = account.nonce {
+                    info.nonce = nonce;
+                }
+                if let Some(code) = &account.code {
+                    info = AccountInfo {
+                        code_hash: keccak256(code),
+                        code: Some(Bytecode::new_raw(code.clone())),
+                        ..info
+                    };
+                }
+
+                let mut storage = HashMap::default();
+                for (slot, value) in &account.storage {
+                    let slot = U256::from_be_bytes(slot.0);
+                    let original =
+                        db.storage(*address, slot).map_err(BlockExecutionError::other)?;
+                    storage.insert(
+                        slot,
+                        EvmStorageSlot::new_changed(original, U256::from_be_bytes(value.0)),
+                    );
+                }
+
+                state.insert(*address, Account { info, storage, status: AccountStatus::Touched });
+            }
+
+            if let Some(hook) = &mut self.hook {
+                hook.on_state(STATE_OVERRIDE_SOURCE, &state);
+            }
+            db.commit(state);
+        }
+
+        if let Some(hook) = self.hook.take() {
+            self.inner.set_state_hook(Some(hook));
+        }
+
+        self.inner.apply_pre_execution_changes()
+    }
+
+    fn execute_transaction_with_commit_condition(
+        &mut self,
+        tx: impl ExecutableTx<Self>,
+        f: impl FnOnce(&ExecutionResult<<Self::Evm as Evm>::HaltReason>) -> CommitChanges,
+    ) -> Result<Option<u64>, BlockExecutionError> {
+        let Some(sender) =
+            self.layer.as_ref().and_then(|layer| layer.impersonated_sender(tx.tx().tx_hash()))
+        else {
+            return self.inner.execute_transaction_with_commit_condition(tx, f)
+        };
+        let tx = Recovered::new_unchecked(tx.tx().clone(), sender);
+        self.inner.execute_transaction_with_commit_condition(&tx, f)
+    }
+
+    fn finish(
+        self,
+    ) -> Result<(Self::Evm, BlockExecutionResult<Self::Receipt>), BlockExecutionError> {
+        self.inner.finish()
+    }
+
+    fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
+        if self.overrides.is_some() {
+            // keep the hook until the overrides have been applied so it observes them
+            self.hook = hook;
+        } else {
+            self.inner.set_state_hook(hook);
+        }
+    }
+
+    fn evm_mut(&mut self) -> &mut Self::Evm {
+        self.inner.evm_mut()
+    }
+
+    fn evm(&self) -> &Self::Evm {
+        self.inner.evm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_merges_overrides_for_same_parent() {
+        let layer = StateOverrideLayer::default();
+        let address = Address::with_last_byte(1);
+        let parent = B256::with_last_byte(1);
+
+        layer.set_balance(address, U256::from(1));
+        layer.set_nonce(address, 2);
+        assert!(layer.has_pending());
+        layer.stage(parent);
+        assert!(!layer.has_pending());
+
+        layer.set_balance(address, U256::from(3));
+        layer.set_storage(address, B256::ZERO, B256::with_last_byte(4));
+        layer.stage(parent);
+
+        let staged = layer.staged(parent).unwrap();
+        assert_eq!(
+            staged[&address],
+            AccountOverride {
+                balance: Some(U256::from(3)),
+                nonce: Some(2),
+                code: None,
+                storage: HashMap::from_iter([(B256::ZERO, B256::with_last_byte(4))]),
+            }
+        );
+        assert!(layer.staged(B256::with_last_byte(2)).is_none());
+
+        layer.clear();
+        assert!(layer.staged(parent).is_none());
+    }
+
+    #[test]
+    fn impersonated_transactions_are_evicted() {
+        let layer = StateOverrideLayer::default();
+        let sender = Address::with_last_byte(1);
+
+        for i in 0..=MAX_IMPERSONATED_TRANSACTIONS as u64 {
+            layer.impersonate_transaction(B256::from(U256::from(i)), sender);
+        }
+
+        assert_eq!(layer.impersonated_sender(&B256::ZERO), None);
+        assert_eq!(layer.impersonated_sender(&B256::from(U256::from(1))), Some(sender));
+        assert_eq!(
+            layer.impersonated_sender(&B256::from(U256::from(MAX_IMPERSONATED_TRANSACTIONS))),
+            Some(sender)
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Allow and deny lists of IP networks, similar to geth's `--netrestrict`.
+
+use parking_lot::RwLock;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// A range of IP addresses in CIDR notation, e.g. `10.0.0.0/8` or `fd00::/8`.
+///
+/// The host bits of the address are always cleared, so `10.1.2.3/8` and `10.0.0.0/8` are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IpNet {
+    /// The network address.
+    addr: IpAddr,
+    /// Number of leading bits that make up the network.
+    prefix_len: u8,
+}
+
+impl IpNet {
+    /// Creates a new network from the given address and prefix length.
+    ///
+    /// Returns an error if the prefix length exceeds the bit length of the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, IpNetParseError> {
+        let addr = addr.to_canonical();
+        let max_len = max_prefix_len(&addr);
+        if prefix_len > max_len {
+            return Err(IpNetParseError::InvalidPrefixLen(prefix_len))
+        }
+        let addr = match addr {
+            IpAddr::V4(ip) => IpAddr::V4(Ipv4Addr::from(u32::from(ip) & v4_mask(prefix_len))),
+            IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & v6_mask(prefix_len))),
+        };
+        Ok(Self { addr, prefix_len })
+    }
+
+    /// Returns the network address.
+    pub const fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length.
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns true if the given address is part of this network.
+    ///
+    /// IPv4-mapped IPv6 addresses are treated as IPv4 addresses.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                u32::from(ip) & v4_mask(self.prefix_len) == u32::from(net)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                u128::from(ip) & v6_mask(self.prefix_len) == u128::from(net)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<IpAddr> for IpNet {
+    /// Creates a network that only contains the given address.
+    fn from(addr: IpAddr) -> Self {
+        let addr = addr.to_canonical();
+        Self { addr, prefix_len: max_prefix_len(&addr) }
+    }
+}
+
+impl FromStr for IpNet {
+    type Err = IpNetParseError;
+
+    /// Parses a network in CIDR notation, e.g. `10.0.0.0/8`.
+    ///
+    /// A plain address without a prefix length is parsed as a network that only contains that
+    /// address.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((addr, prefix_len)) = s.split_once('/') else {
+            let addr =
+                s.parse::<IpAddr>().map_err(|_| IpNetParseError::InvalidAddr(s.to_string()))?;
+            return Ok(addr.into())
+        };
+        let addr =
+            addr.parse::<IpAddr>().map_err(|_| IpNetParseError::InvalidAddr(addr.to_string()))?;
+        let prefix_len = prefix_len
+            .parse::<u8>()
+            .map_err(|_| IpNetParseError::InvalidPrefix(prefix_len.to_string()))?;
+        Self::new(addr, prefix_len)
+    }
+}
+
+impl fmt::Display for IpNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// Errors that can occur when parsing an [`IpNet`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IpNetParseError {
+    /// The address part is not a valid IP address.
+    #[error("invalid IP address: {0}")]
+    InvalidAddr(String),
+    /// The prefix length is not a number.
+    #[error("invalid prefix length: {0}")]
+    InvalidPrefix(String),
+    /// The prefix length exceeds the bit length of the address.
+    #[error("prefix length {0} exceeds the address length")]
+    InvalidPrefixLen(u8),
+}
+
+/// Allow and deny lists of IP networks.
+///
+/// An address is allowed if the allow list is empty or one of its networks contains the address,
+/// and none of the networks in the deny list contains it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetRestrict {
+    /// Networks the node is restricted to. Empty means all networks are allowed.
+    pub allowed: Vec<IpNet>,
+    /// Networks the node refuses to communicate with.
+    pub denied: Vec<IpNet>,
+}
+
+impl NetRestrict {
+    /// Creates new allow and deny lists.
+    pub fn new(
+        allowed: impl IntoIterator<Item = IpNet>,
+        denied: impl IntoIterator<Item = IpNet>,
+    ) -> Self {
+        Self { allowed: allowed.into_iter().collect(), denied: denied.into_iter().collect() }
+    }
+
+    /// Returns true if neither list contains any networks, in which case all addresses are
+    /// allowed.
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty()
+    }
+
+    /// Returns true if communication with the given address is allowed.
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        (self.allowed.is_empty() || self.allowed.iter().any(|net| net.contains(ip))) &&
+            !self.denied.iter().any(|net| net.contains(ip))
+    }
+}
+
+/// A shared, runtime updatable [`NetRestrict`].
+///
+/// All clones share the same lists, so an update through one handle is applied by every component
+/// that holds a clone, e.g. the peers manager and the discovery services.
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    inner: Arc<RwLock<NetRestrict>>,
+}
+
+impl IpFilter {
+    /// Creates a new filter with the given lists.
+    pub fn new(restrict: NetRestrict) -> Self {
+        Self { inner: Arc::new(RwLock::new(restrict)) }
+    }
+
+    /// Returns true if communication with the given address is allowed.
+    #[inline]
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        self.inner.read().is_allowed(ip)
+    }
+
+    /// Returns a copy of the current lists.
+    pub fn get(&self) -> NetRestrict {
+        self.inner.read().clone()
+    }
+
+    /// Replaces the current lists.
+    pub fn set(&self, restrict: NetRestrict) {
+        *self.inner.write() = restrict;
+    }
+}
+
+impl PartialEq for IpFilter {
+    /// Compares the current lists of both filters.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner) || *self.inner.read() == *other.inner.read()
+    }
+}
+
+impl Eq for IpFilter {}
+
+impl From<NetRestrict> for IpFilter {
+    fn from(restrict: NetRestrict) -> Self {
+        Self::new(restrict)
+    }
+}
+
+const fn max_prefix_len(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+const fn v4_mask(prefix_len: u8) -> u32 {
+    if prefix_len == 0 {
+        0
+    } else {
+        u32::MAX << (32 - prefix_len)
+    }
+}
+
+const fn v6_mask(prefix_len: u8) -> u128 {
+    if prefix_len == 0 {
+        0
+    } else {
+        u128::MAX << (128 - prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_display_ip_net() {
+        let net: IpNet = "10.1.2.3/8".parse().unwrap();
+        assert_eq!(net.addr(), IpAddr::from([10, 0, 0, 0]));
+        assert_eq!(net.prefix_len(), 8);
+        assert_eq!(net.to_string(), "10.0.0.0/8");
+
+        let net: IpNet = "192.168.1.1".parse().unwrap();
+        assert_eq!(net.to_string(), "192.168.1.1/32");
+
+        let net: IpNet = "fd00::1/8".parse().unwrap();
+        assert_eq!(net.to_string(), "fd00::/8");
+
+        assert_eq!(
+            "10.0.0.0/33".parse::<IpNet>().unwrap_err(),
+            IpNetParseError::InvalidPrefixLen(33)
+        );
+        assert!(matches!("10.0.0/8".parse::<IpNet>(), Err(IpNetParseError::InvalidAddr(_))));
+        assert!(matches!("10.0.0.0/x".parse::<IpNet>(), Err(IpNetParseError::InvalidPrefix(_))));
+    }
+
+    #[test]
+    fn ip_net_contains() {
+        let net: IpNet = "10.0.0.0/8".parse().unwrap();
+        assert!(net.contains(&IpAddr::from([10, 255, 0, 1])));
+        assert!(!net.contains(&IpAddr::from([11, 0, 0, 1])));
+        // ipv4-mapped ipv6 addresses are treated as ipv4
+        assert!(net.contains(&IpAddr::V6(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped())));
+        assert!(!net.contains(&"fd00::1".parse().unwrap()));
+
+        let any: IpNet = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(&IpAddr::from([1, 2, 3, 4])));
+
+        let net: IpNet = "fd00::/8".parse().unwrap();
+        assert!(net.contains(&"fd12::1".parse().unwrap()));
+        assert!(!net.contains(&"fe80::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn net_restrict_allow_deny() {
+        let restrict =
+            NetRestrict::new(["10.0.0.0/8".parse().unwrap()], ["10.1.0.0/16".parse().unwrap()]);
+        assert!(restrict.is_allowed(&IpAddr::from([10, 0, 0, 1])));
+        assert!(!restrict.is_allowed(&IpAddr::from([10, 1, 0, 1])));
+        assert!(!restrict.is_allowed(&IpAddr::from([1, 1, 1, 1])));
+
+        // an empty allow list allows everything that's not denied
+        let restrict = NetRestrict::new([], ["1.1.1.0/24".parse().unwrap()]);
+        assert!(restrict.is_allowed(&IpAddr::from([8, 8, 8, 8])));
+        assert!(!restrict.is_allowed(&IpAddr::from([1, 1, 1, 1])));
+    }
+
+    #[test]
+    fn ip_filter_updates_are_shared() {
+        let filter = IpFilter::default();
+        let clone = filter.clone();
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        assert!(clone.is_allowed(&ip));
+
+        filter.set(NetRestrict::new([], [IpNet::from(ip)]));
+        assert!(!clone.is_allowed(&ip));
+        assert_eq!(clone.get(), filter.get());
+    }
+}
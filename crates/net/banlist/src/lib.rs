@@ -1,4 +1,4 @@
-//! Support for banning peers.
+//! Support for banning peers and restricting the IP networks the node communicates with.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -10,6 +10,9 @@
 
 type PeerId = alloy_primitives::B512;
 
+mod ip_filter;
+pub use ip_filter::{IpFilter, IpNet, IpNetParseError, NetRestrict};
+
 use std::{collections::HashMap, net::IpAddr, time::Instant};
 
 /// Determines whether or not the IP is globally routable.
@@ -112,14 +115,30 @@ impl BanList {
         self.banned_peers.contains_key(peer_id)
     }
 
-    /// Unbans the ip address
-    pub fn unban_ip(&mut self, ip: &IpAddr) {
-        self.banned_ips.remove(ip);
+    /// Returns all banned peers and the timestamp until which they are banned, `None` if they are
+    /// banned indefinitely.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (PeerId, Option<Instant>)> + '_ {
+        self.banned_peers.iter().map(|(peer_id, until)| (*peer_id, *until))
+    }
+
+    /// Returns all banned ip addresses and the timestamp until which they are banned, `None` if
+    /// they are banned indefinitely.
+    pub fn banned_ips(&self) -> impl Iterator<Item = (IpAddr, Option<Instant>)> + '_ {
+        self.banned_ips.iter().map(|(ip, until)| (*ip, *until))
     }
 
     /// Unbans the ip address
-    pub fn unban_peer(&mut self, peer_id: &PeerId) {
-        self.banned_peers.remove(peer_id);
+    ///
+    /// Returns `true` if the ip address was banned.
+    pub fn unban_ip(&mut self, ip: &IpAddr) -> bool {
+        self.banned_ips.remove(ip).is_some()
+    }
+
+    /// Unbans the peer
+    ///
+    /// Returns `true` if the peer was banned.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) -> bool {
+        self.banned_peers.remove(peer_id).is_some()
     }
 
     /// Bans the IP until the timestamp.
@@ -171,8 +190,10 @@ mod tests {
         let mut banlist = BanList::default();
         banlist.ban_peer(peer);
         assert!(banlist.is_banned_peer(&peer));
-        banlist.unban_peer(&peer);
+        assert_eq!(banlist.banned_peers().collect::<Vec<_>>(), vec![(peer, None)]);
+        assert!(banlist.unban_peer(&peer));
         assert!(!banlist.is_banned_peer(&peer));
+        assert!(!banlist.unban_peer(&peer));
     }
 
     #[test]
@@ -181,8 +202,10 @@ mod tests {
         let mut banlist = BanList::default();
         banlist.ban_ip(ip);
         assert!(banlist.is_banned_ip(&ip));
-        banlist.unban_ip(&ip);
+        assert_eq!(banlist.banned_ips().collect::<Vec<_>>(), vec![(ip, None)]);
+        assert!(banlist.unban_ip(&ip));
         assert!(!banlist.is_banned_ip(&ip));
+        assert!(!banlist.unban_ip(&ip));
     }
 
     #[test]
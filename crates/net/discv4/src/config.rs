@@ -5,7 +5,7 @@
 
 use alloy_primitives::bytes::Bytes;
 use alloy_rlp::Encodable;
-use reth_net_banlist::BanList;
+use reth_net_banlist::{BanList, IpFilter};
 use reth_net_nat::{NatResolver, ResolveNatInterval};
 use reth_network_peers::NodeRecord;
 use std::{
@@ -41,6 +41,9 @@ pub struct Discv4Config {
     /// Provides a way to ban peers and ips.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ban_list: BanList,
+    /// Restricts the IP networks the node communicates with.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ip_filter: IpFilter,
     /// Set the default duration for which nodes are banned for. This timeouts are checked every 5
     /// minutes, so the precision will be to the nearest 5 minutes. If set to `None`, bans from
     /// the filter will last indefinitely. Default is 1 hour.
@@ -126,6 +129,7 @@ impl Default for Discv4Config {
 
             lookup_interval: Duration::from_secs(20),
             ban_list: Default::default(),
+            ip_filter: Default::default(),
             ban_duration: Some(Duration::from_secs(60 * 60)), // 1 hour
             bootstrap_nodes: Default::default(),
             enable_dht_random_walk: true,
@@ -270,6 +274,12 @@ impl Discv4ConfigBuilder {
         self
     }
 
+    /// Restricts the IP networks the node communicates with. See [`IpFilter`].
+    pub fn ip_filter(&mut self, ip_filter: IpFilter) -> &mut Self {
+        self.config.ip_filter = ip_filter;
+        self
+    }
+
     /// Sets the lookup interval duration.
     pub const fn lookup_interval(&mut self, lookup_interval: Duration) -> &mut Self {
         self.config.lookup_interval = lookup_interval;
@@ -1198,6 +1198,11 @@ impl Discv4Service {
             return
         }
 
+        if !self.config.ip_filter.is_allowed(&node.address) {
+            trace!(target: "discv4", peer_id=?node.id, ip=?node.address, "not pinging restricted node");
+            return
+        }
+
         if self.pending_pings.contains_key(&node.id) ||
             self.pending_find_nodes.contains_key(&node.id)
         {
@@ -1432,6 +1437,10 @@ impl Discv4Service {
                 trace!(target: "discv4", peer_id=?node.id, ip=?node.address, "ignoring banned record");
                 continue
             }
+            if !self.config.ip_filter.is_allowed(&node.address) {
+                trace!(target: "discv4", peer_id=?node.id, ip=?node.address, "ignoring restricted record");
+                continue
+            }
 
             ctx.add_node(node);
         }
@@ -1770,6 +1779,11 @@ impl Discv4Service {
                     IngressEvent::BadPacket(from, err, data) => {
                         trace!(target: "discv4", ?from, %err, packet=?hex::encode(&data), "bad packet");
                     }
+                    IngressEvent::Packet(remote_addr, _)
+                        if !self.config.ip_filter.is_allowed(&remote_addr.ip()) =>
+                    {
+                        trace!(target: "discv4", from=?remote_addr, "dropping packet from restricted ip");
+                    }
                     IngressEvent::Packet(remote_addr, Packet { msg, node_id, hash }) => {
                         trace!(target: "discv4", r#type=?msg.msg_type(), from=?remote_addr,"received packet");
                         let event = match msg {
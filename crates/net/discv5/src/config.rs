@@ -13,6 +13,7 @@ use discv5::{
     ListenConfig,
 };
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_banlist::IpFilter;
 use reth_network_peers::NodeRecord;
 use tracing::warn;
 
@@ -85,6 +86,8 @@ pub struct ConfigBuilder {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     discovered_peer_filter: Option<MustNotIncludeKeys>,
+    /// Restricts the IP networks of discovered peers that are passed up to rlpx.
+    ip_filter: IpFilter,
 }
 
 impl ConfigBuilder {
@@ -100,6 +103,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            ip_filter,
         } = discv5_config;
 
         Self {
@@ -112,6 +116,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
             bootstrap_lookup_countdown: Some(bootstrap_lookup_countdown),
             discovered_peer_filter: Some(discovered_peer_filter),
+            ip_filter,
         }
     }
 
@@ -214,6 +219,12 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the [`IpFilter`] that restricts which discovered peers are passed up to rlpx.
+    pub fn ip_filter(mut self, ip_filter: IpFilter) -> Self {
+        self.ip_filter = ip_filter;
+        self
+    }
+
     /// Returns a new [`Config`].
     pub fn build(self) -> Config {
         let Self {
@@ -226,6 +237,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            ip_filter,
         } = self;
 
         let mut discv5_config = discv5_config.unwrap_or_else(|| {
@@ -256,6 +268,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            ip_filter,
         }
     }
 }
@@ -289,6 +302,8 @@ pub struct Config {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     pub(super) discovered_peer_filter: MustNotIncludeKeys,
+    /// Restricts the IP networks of discovered peers that are passed up to rlpx.
+    pub(super) ip_filter: IpFilter,
 }
 
 impl Config {
@@ -305,9 +320,15 @@ impl Config {
             bootstrap_lookup_interval: None,
             bootstrap_lookup_countdown: None,
             discovered_peer_filter: None,
+            ip_filter: IpFilter::default(),
         }
     }
 
+    /// Sets the [`IpFilter`] that restricts which discovered peers are passed up to rlpx.
+    pub fn set_ip_filter(&mut self, ip_filter: IpFilter) {
+        self.ip_filter = ip_filter;
+    }
+
     /// Inserts a new boot node to the list of boot nodes.
     pub fn insert_boot_node(&mut self, boot_node: BootNode) {
         self.bootstrap_nodes.insert(boot_node);
@@ -24,6 +24,7 @@ use futures::future::join_all;
 use itertools::Itertools;
 use rand::{Rng, RngCore};
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_banlist::IpFilter;
 use reth_network_peers::{NodeRecord, PeerId};
 use secp256k1::SecretKey;
 use tokio::{sync::mpsc, task};
@@ -73,6 +74,8 @@ pub struct Discv5 {
     fork_key: Option<&'static [u8]>,
     /// Filter applied to a discovered peers before passing it up to app.
     discovered_peer_filter: MustNotIncludeKeys,
+    /// Restricts the IP networks of discovered peers passed up to app.
+    ip_filter: IpFilter,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
 }
@@ -183,6 +186,7 @@ impl Discv5 {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            ip_filter,
             ..
         } = discv5_config;
 
@@ -218,7 +222,7 @@ impl Discv5 {
         );
 
         Ok((
-            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, metrics },
+            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, ip_filter, metrics },
             discv5_updates,
             bc_enr,
         ))
@@ -308,6 +312,16 @@ impl Discv5 {
                 return None
             }
         };
+        if !self.ip_filter.is_allowed(&node_record.address) {
+            trace!(target: "net::discv5",
+                ?enr,
+                "discovered peer is outside allowed ip networks"
+            );
+
+            self.metrics.discovered_peers.increment_established_sessions_filtered(1);
+
+            return None
+        }
         if let FilterOutcome::Ignore { reason } = self.filter_discovered_peer(enr) {
             trace!(target: "net::discv5",
                 ?enr,
@@ -689,6 +703,7 @@ mod test {
             rlpx_ip_mode: IpMode::Ip4,
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
+            ip_filter: IpFilter::default(),
             metrics: Discv5Metrics::default(),
         }
     }
@@ -1,4 +1,5 @@
 use crate::tree::LinkEntry;
+use reth_net_banlist::IpFilter;
 use std::{
     collections::HashSet,
     num::{NonZeroU32, NonZeroUsize},
@@ -28,6 +29,9 @@ pub struct DnsDiscoveryConfig {
     pub dns_record_cache_limit: NonZeroU32,
     /// Links to the DNS networks to bootstrap.
     pub bootstrap_dns_networks: Option<HashSet<LinkEntry>>,
+    /// Restricts the IP networks of resolved records that are passed on to subscribers.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ip_filter: IpFilter,
 }
 
 impl Default for DnsDiscoveryConfig {
@@ -38,6 +42,7 @@ impl Default for DnsDiscoveryConfig {
             recheck_interval: Duration::from_secs(60 * 30),
             dns_record_cache_limit: NonZeroU32::new(1_000).unwrap(),
             bootstrap_dns_networks: Some(Default::default()),
+            ip_filter: Default::default(),
         }
     }
 }
@@ -20,9 +20,10 @@ use crate::{
     tree::{DnsEntry, LinkEntry},
 };
 pub use config::DnsDiscoveryConfig;
-use enr::Enr;
+pub use enr::Enr;
 pub use error::ParseDnsEntryError;
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_banlist::IpFilter;
 use reth_network_peers::{pk2id, NodeRecord};
 use schnellru::{ByLength, LruMap};
 use secp256k1::SecretKey;
@@ -51,6 +52,7 @@ use tracing::{debug, trace};
 
 mod config;
 mod error;
+pub mod publish;
 mod query;
 pub mod resolver;
 mod sync;
@@ -110,6 +112,8 @@ pub struct DnsDiscoveryService<R: Resolver = DnsResolver> {
     recheck_interval: Duration,
     /// Links to the DNS networks to bootstrap.
     bootstrap_dns_networks: HashSet<LinkEntry>,
+    /// Restricts the IP networks of resolved records.
+    ip_filter: IpFilter,
 }
 
 // === impl DnsDiscoveryService ===
@@ -134,6 +138,7 @@ impl<R: Resolver> DnsDiscoveryService<R> {
             recheck_interval,
             dns_record_cache_limit,
             bootstrap_dns_networks,
+            ip_filter,
         } = config;
         let queries = QueryPool::new(resolver, max_requests_per_sec, lookup_timeout);
         let (command_tx, command_rx) = mpsc::unbounded_channel();
@@ -147,6 +152,7 @@ impl<R: Resolver> DnsDiscoveryService<R> {
             queued_events: Default::default(),
             recheck_interval,
             bootstrap_dns_networks: bootstrap_dns_networks.unwrap_or_default(),
+            ip_filter,
         }
     }
 
@@ -243,6 +249,10 @@ impl<R: Resolver> DnsDiscoveryService<R> {
 
     fn on_resolved_enr(&mut self, enr: Enr<SecretKey>) {
         if let Some(record) = convert_enr_node_record(&enr) {
+            if !self.ip_filter.is_allowed(&record.node_record.address) {
+                trace!(target: "disc::dns", ip=?record.node_record.address, "ignoring restricted record");
+                return
+            }
             self.notify(record);
         }
         self.queued_events.push_back(DnsDiscoveryEvent::Enr(enr))
@@ -0,0 +1,388 @@
+//! Building and signing [EIP-1459](https://eips.ethereum.org/EIPS/eip-1459) ENR trees for
+//! publication via DNS.
+//!
+//! A tree is built from a list of node records and links to other trees. Node records and links
+//! are each organized into a merkle tree of [`BranchEntry`] records, whose roots are referenced by
+//! the signed [`TreeRootEntry`]:
+//!
+//! ```text
+//! nodes.example.org                      enrtree-root:v1 e=<enr-root> l=<link-root> seq=1 sig=..
+//! <enr-root>.nodes.example.org           enrtree-branch:<hash>,<hash>,..
+//! <hash>.nodes.example.org               enr:-HW4Q..
+//! <link-root>.nodes.example.org          enrtree://AM5FCQ..@other.example.org
+//! ```
+//!
+//! Every non-root entry is published under the subdomain derived from its content, the base32
+//! encoded first 16 bytes of the keccak256 hash of the record text.
+
+use crate::tree::{BranchEntry, LinkEntry, NodeEntry, TreeRootEntry};
+use alloy_primitives::{keccak256, Bytes};
+use data_encoding::BASE32_NOPAD;
+use enr::{Enr, EnrKey, Error as EnrError, NodeId};
+use secp256k1::SecretKey;
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    fmt::Write,
+};
+
+/// Maximum number of child hashes in a single branch entry.
+///
+/// This keeps a branch record below the 370 byte limit of a TXT record that still fits into a
+/// single UDP DNS response.
+pub const MAX_BRANCH_CHILDREN: usize = 13;
+
+/// Maximum length of a single character-string in a TXT record, see RFC 1035.
+const MAX_TXT_STRING_LEN: usize = 255;
+
+/// Number of bytes of the keccak256 hash of an entry that make up its subdomain.
+const SUBDOMAIN_HASH_LEN: usize = 16;
+
+/// Builder for a signed ENR tree.
+///
+/// ```
+/// # fn t(enrs: Vec<enr::Enr<secp256k1::SecretKey>>, key: secp256k1::SecretKey) {
+/// use reth_dns_discovery::publish::EnrTreeBuilder;
+/// let tree = EnrTreeBuilder::new("nodes.example.org")
+///     .with_sequence_number(1)
+///     .with_enrs(enrs)
+///     .build(&key)
+///     .unwrap();
+/// println!("{}", tree.to_zone_file(3600));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnrTreeBuilder {
+    /// The domain the tree is published under.
+    domain: String,
+    /// The sequence number of the root entry.
+    sequence_number: u64,
+    /// The node records of the tree.
+    enrs: Vec<Enr<SecretKey>>,
+    /// Links to other trees.
+    links: Vec<LinkEntry>,
+}
+
+// === impl EnrTreeBuilder ===
+
+impl EnrTreeBuilder {
+    /// Creates a new builder for a tree published under the given domain.
+    pub fn new(domain: impl Into<String>) -> Self {
+        Self {
+            domain: domain.into().trim_end_matches('.').to_string(),
+            sequence_number: 0,
+            enrs: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Sets the sequence number of the root entry.
+    ///
+    /// Clients only replace a cached tree if the sequence number increased.
+    pub const fn with_sequence_number(mut self, sequence_number: u64) -> Self {
+        self.sequence_number = sequence_number;
+        self
+    }
+
+    /// Adds node records to the tree.
+    pub fn with_enrs(mut self, enrs: impl IntoIterator<Item = Enr<SecretKey>>) -> Self {
+        self.enrs.extend(enrs);
+        self
+    }
+
+    /// Adds links to other trees.
+    pub fn with_links(mut self, links: impl IntoIterator<Item = LinkEntry>) -> Self {
+        self.links.extend(links);
+        self
+    }
+
+    /// Builds the tree and signs the root entry with the given key.
+    ///
+    /// Node records are deduplicated by node id, keeping the record with the highest sequence
+    /// number. Entries are sorted so that the same input always produces the same entries, only the
+    /// root signature differs between builds.
+    pub fn build(self, key: &SecretKey) -> Result<SignedEnrTree, EnrError> {
+        let Self { domain, sequence_number, enrs, links } = self;
+
+        let mut latest: HashMap<NodeId, Enr<SecretKey>> = HashMap::with_capacity(enrs.len());
+        for enr in enrs {
+            match latest.entry(enr.node_id()) {
+                Entry::Occupied(mut entry) => {
+                    if entry.get().seq() < enr.seq() {
+                        entry.insert(enr);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(enr);
+                }
+            }
+        }
+        let mut enrs = latest.into_values().collect::<Vec<_>>();
+        enrs.sort_by_key(|enr| enr.node_id().raw());
+
+        let mut links = links.into_iter().map(|link| link.to_string()).collect::<Vec<_>>();
+        links.sort_unstable();
+        links.dedup();
+
+        let mut entries = BTreeMap::new();
+        let enr_entries =
+            enrs.into_iter().map(|enr| NodeEntry { enr }.to_string()).collect::<Vec<_>>();
+        let enr_root = build_subtree(enr_entries, &mut entries);
+        let enr_root = insert_entry(enr_root, &mut entries);
+        let link_root = build_subtree(links, &mut entries);
+        let link_root = insert_entry(link_root, &mut entries);
+
+        let mut root =
+            TreeRootEntry { enr_root, link_root, sequence_number, signature: Bytes::new() };
+        root.sign(key)?;
+
+        let link = LinkEntry { domain, pubkey: key.public() };
+
+        Ok(SignedEnrTree { link, root, entries })
+    }
+}
+
+/// A signed ENR tree, ready to be published as DNS TXT records.
+#[derive(Debug, Clone)]
+pub struct SignedEnrTree {
+    /// The link that resolves this tree.
+    link: LinkEntry,
+    /// The signed root entry.
+    root: TreeRootEntry,
+    /// All non-root entries keyed by their subdomain hash.
+    entries: BTreeMap<String, String>,
+}
+
+// === impl SignedEnrTree ===
+
+impl SignedEnrTree {
+    /// Returns the domain the tree is published under.
+    pub fn domain(&self) -> &str {
+        &self.link.domain
+    }
+
+    /// Returns the `enrtree://` link that resolves this tree, signed by the tree's key.
+    pub const fn link(&self) -> &LinkEntry {
+        &self.link
+    }
+
+    /// Returns the signed root entry.
+    pub const fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns all non-root entries keyed by their subdomain hash.
+    pub const fn entries(&self) -> &BTreeMap<String, String> {
+        &self.entries
+    }
+
+    /// Returns the TXT records of the tree keyed by their fully qualified name, without the
+    /// trailing dot.
+    ///
+    /// The root entry is published under the domain itself.
+    pub fn txt_records(&self) -> BTreeMap<String, String> {
+        let domain = self.domain();
+        let mut records = self
+            .entries
+            .iter()
+            .map(|(hash, entry)| (format!("{hash}.{domain}"), entry.clone()))
+            .collect::<BTreeMap<_, _>>();
+        records.insert(domain.to_string(), self.root.to_string());
+        records
+    }
+
+    /// Returns the tree in zone file format, with the root record first.
+    ///
+    /// Records longer than 255 bytes are split into multiple character-strings.
+    pub fn to_zone_file(&self, ttl: u32) -> String {
+        let domain = self.domain();
+        let mut zone = String::new();
+        let _ = writeln!(zone, "; enrtree {}", self.link);
+        let _ = writeln!(zone, "{domain}.\t{ttl}\tIN\tTXT\t{}", quote_txt(&self.root.to_string()));
+        for (hash, entry) in &self.entries {
+            let _ = writeln!(zone, "{hash}.{domain}.\t{ttl}\tIN\tTXT\t{}", quote_txt(entry));
+        }
+        zone
+    }
+}
+
+/// Builds the subtree for the given entries and returns the text of its root entry.
+///
+/// All entries referenced by the returned root are inserted into `entries`, the root itself is
+/// not.
+fn build_subtree(mut children: Vec<String>, entries: &mut BTreeMap<String, String>) -> String {
+    if children.len() == 1 {
+        return children.pop().expect("exists")
+    }
+    if children.len() <= MAX_BRANCH_CHILDREN {
+        let children =
+            children.into_iter().map(|child| insert_entry(child, entries)).collect::<Vec<_>>();
+        return BranchEntry { children }.to_string()
+    }
+    let subtrees = children
+        .chunks(MAX_BRANCH_CHILDREN)
+        .map(|chunk| build_subtree(chunk.to_vec(), entries))
+        .collect();
+    build_subtree(subtrees, entries)
+}
+
+/// Inserts the entry under its subdomain hash and returns the hash.
+fn insert_entry(entry: String, entries: &mut BTreeMap<String, String>) -> String {
+    let hash = subdomain_hash(&entry);
+    entries.insert(hash.clone(), entry);
+    hash
+}
+
+/// Returns the subdomain of an entry: the base32 encoded, truncated keccak256 hash of its text.
+pub fn subdomain_hash(entry: &str) -> String {
+    BASE32_NOPAD.encode(&keccak256(entry.as_bytes())[..SUBDOMAIN_HASH_LEN])
+}
+
+/// Quotes the TXT content for a zone file, splitting it into character-strings of at most 255
+/// bytes.
+fn quote_txt(content: &str) -> String {
+    // entries only contain base32, base64url and a few ascii separators, so splitting at byte
+    // boundaries is safe
+    content
+        .as_bytes()
+        .chunks(MAX_TXT_STRING_LEN)
+        .map(|chunk| format!("\"{}\"", String::from_utf8_lossy(chunk)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tree::DnsEntry, DnsDiscoveryConfig, DnsDiscoveryEvent, DnsDiscoveryService, MapResolver,
+    };
+    use secp256k1::rand::thread_rng;
+    use std::{collections::HashSet, net::Ipv4Addr, num::NonZeroUsize, sync::Arc, time::Duration};
+    use tokio_stream::StreamExt;
+
+    fn random_enr(port: u16) -> Enr<SecretKey> {
+        let secret_key = SecretKey::new(&mut thread_rng());
+        Enr::builder().ip4(Ipv4Addr::LOCALHOST).udp4(port).tcp4(port).build(&secret_key).unwrap()
+    }
+
+    #[test]
+    fn build_empty_tree() {
+        let key = SecretKey::new(&mut thread_rng());
+        let tree = EnrTreeBuilder::new("nodes.example.org").build(&key).unwrap();
+
+        let empty_branch = "enrtree-branch:";
+        assert_eq!(tree.root().enr_root, subdomain_hash(empty_branch));
+        assert_eq!(tree.root().link_root, subdomain_hash(empty_branch));
+        assert_eq!(tree.entries().len(), 1);
+        assert!(tree.root().verify::<SecretKey>(&key.public()));
+    }
+
+    #[test]
+    fn build_tree_limits_branch_size() {
+        let key = SecretKey::new(&mut thread_rng());
+        let enrs = (0..100).map(|i| random_enr(30303 + i)).collect::<Vec<_>>();
+        let tree = EnrTreeBuilder::new("nodes.example.org.")
+            .with_sequence_number(7)
+            .with_enrs(enrs.clone())
+            .build(&key)
+            .unwrap();
+
+        assert_eq!(tree.domain(), "nodes.example.org");
+        assert_eq!(tree.root().sequence_number, 7);
+
+        let mut nodes = 0;
+        for (hash, entry) in tree.entries() {
+            assert_eq!(hash, &subdomain_hash(entry));
+            match entry.parse::<DnsEntry<SecretKey>>().unwrap() {
+                DnsEntry::Branch(branch) => {
+                    assert!(branch.children.len() <= MAX_BRANCH_CHILDREN);
+                    assert!(entry.len() <= 370);
+                }
+                DnsEntry::Node(_) => nodes += 1,
+                entry => panic!("unexpected entry {entry}"),
+            }
+        }
+        assert_eq!(nodes, enrs.len());
+    }
+
+    #[test]
+    fn build_tree_is_deterministic() {
+        let key = SecretKey::new(&mut thread_rng());
+        let mut enrs = (0..20).map(|i| random_enr(30303 + i)).collect::<Vec<_>>();
+        let tree =
+            EnrTreeBuilder::new("nodes.example.org").with_enrs(enrs.clone()).build(&key).unwrap();
+
+        enrs.reverse();
+        enrs.push(enrs[0].clone());
+        let other = EnrTreeBuilder::new("nodes.example.org").with_enrs(enrs).build(&key).unwrap();
+
+        // signatures are randomized, everything else is derived from the records
+        assert_eq!(tree.root().enr_root, other.root().enr_root);
+        assert_eq!(tree.root().link_root, other.root().link_root);
+        assert_eq!(tree.entries(), other.entries());
+    }
+
+    #[test]
+    fn zone_file_splits_long_records() {
+        let content = "a".repeat(300);
+        assert_eq!(quote_txt(&content), format!("\"{}\" \"{}\"", "a".repeat(255), "a".repeat(45)));
+
+        let key = SecretKey::new(&mut thread_rng());
+        let tree = EnrTreeBuilder::new("nodes.example.org")
+            .with_enrs([random_enr(30303)])
+            .build(&key)
+            .unwrap();
+        let zone = tree.to_zone_file(3600);
+        let mut lines = zone.lines().skip(1);
+        assert_eq!(
+            lines.next().unwrap(),
+            format!("nodes.example.org.\t3600\tIN\tTXT\t\"{}\"", tree.root())
+        );
+        assert_eq!(lines.count(), tree.entries().len());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn round_trip_resolve_published_tree() {
+        reth_tracing::init_test_tracing();
+
+        let key = SecretKey::new(&mut thread_rng());
+        let enrs = (0..30).map(|i| random_enr(30303 + i)).collect::<Vec<_>>();
+        let link = LinkEntry {
+            domain: "other.example.org".to_string(),
+            pubkey: SecretKey::new(&mut thread_rng()).public(),
+        };
+        let tree = EnrTreeBuilder::new("nodes.example.org")
+            .with_sequence_number(1)
+            .with_enrs(enrs.clone())
+            .with_links([link.clone()])
+            .build(&key)
+            .unwrap();
+
+        let resolver = MapResolver::default();
+        for (name, txt) in tree.txt_records() {
+            resolver.insert(name, txt);
+        }
+        let linked = tree.entries().values().find(|entry| entry.starts_with("enrtree://"));
+        assert_eq!(linked.unwrap(), &link.to_string());
+
+        let config = DnsDiscoveryConfig {
+            max_requests_per_sec: NonZeroUsize::new(100).unwrap(),
+            ..Default::default()
+        };
+        let mut service = DnsDiscoveryService::new(Arc::new(resolver), config);
+        service.sync_tree_with_link(tree.link().clone());
+
+        let mut expected = enrs.iter().map(|enr| enr.to_base64()).collect::<HashSet<_>>();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !expected.is_empty() {
+                let DnsDiscoveryEvent::Enr(enr) = service.next().await.unwrap();
+                assert!(expected.remove(&enr.to_base64()), "unexpected enr {enr}");
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(service.trees.get(tree.link()).unwrap().root(), tree.root());
+        assert_eq!(tree.link().pubkey, key.public());
+    }
+}
@@ -24,8 +24,9 @@ impl<P: ConnectionProvider> Resolver for hickory_resolver::Resolver<P> {
             }
             Ok(lookup) => {
                 let txt = lookup.into_iter().next()?;
-                let entry = txt.iter().next()?;
-                String::from_utf8(entry.to_vec()).ok()
+                // records longer than 255 bytes are split into multiple character-strings
+                let entry = txt.iter().flat_map(|data| data.iter().copied()).collect::<Vec<_>>();
+                String::from_utf8(entry).ok()
             }
         }
     }
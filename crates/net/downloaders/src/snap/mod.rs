@@ -0,0 +1,6 @@
+mod proof;
+
+/// A downloader for the state at a given root over the `snap` protocol.
+pub mod state;
+
+pub use state::SnapStateDownloader;
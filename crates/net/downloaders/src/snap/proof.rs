@@ -0,0 +1,498 @@
+//! Verification of `snap` range proofs.
+
+use alloy_consensus::constants::EMPTY_ROOT_HASH;
+use alloy_primitives::{keccak256, map::B256Map, Bytes, B256};
+use alloy_rlp::{Decodable, EMPTY_STRING_CODE};
+use alloy_trie::{
+    nodes::{BranchNodeRef, ExtensionNodeRef, LeafNodeRef, RlpNode, TrieNode},
+    Nibbles, TrieMask,
+};
+use reth_network_p2p::error::{DownloadError, DownloadResult};
+use std::cmp::Ordering;
+
+/// Verifies that the given leaves are all leaves of the trie with the given root from `origin`
+/// up to and including the last key.
+///
+/// The proof must contain the nodes on the paths to `origin` and to the last key. An empty proof
+/// is only valid if the leaves make up the entire trie. If there are no leaves, the proof must
+/// prove that the trie has no leaves at or after `origin`.
+///
+/// The keys must be in ascending order and not before `origin`.
+///
+/// Returns whether the trie has more leaves after the last key.
+pub(crate) fn verify_range_proof(
+    root: B256,
+    origin: B256,
+    leaves: &[(B256, Vec<u8>)],
+    proof: &[Bytes],
+) -> DownloadResult<bool> {
+    if proof.is_empty() {
+        let mut trie = Node::Empty;
+        for (key, value) in leaves {
+            trie.insert(&Nibbles::unpack(key), value.clone())?;
+        }
+        return if trie.hash() == root { Ok(false) } else { Err(invalid_proof()) }
+    }
+
+    let nodes = proof.iter().map(|node| (keccak256(node), node)).collect::<B256Map<_>>();
+    let origin = Nibbles::unpack(origin);
+    let mut trie = Node::Hash(root);
+    trie.resolve(&origin, &nodes)?;
+
+    let Some((last, _)) = leaves.last() else {
+        // a proof of absence for everything at or after origin
+        return if trie.has_right(&origin, true)? { Err(invalid_proof()) } else { Ok(false) }
+    };
+    let last = Nibbles::unpack(last);
+    trie.resolve(&last, &nodes)?;
+    let has_more = trie.has_right(&last, false)?;
+
+    // drop everything between the two boundary paths and rebuild it from the given leaves, which
+    // only reproduces the root if no leaf in the range was left out or altered
+    trie.unset_range(&origin, &last)?;
+    for (key, value) in leaves {
+        trie.insert(&Nibbles::unpack(key), value.clone())?;
+    }
+    if trie.hash() != root {
+        return Err(invalid_proof())
+    }
+
+    Ok(has_more)
+}
+
+/// Returns the error for a range proof that failed verification.
+const fn invalid_proof() -> DownloadError {
+    DownloadError::InvalidStateRange { reason: "invalid range proof" }
+}
+
+/// A partially resolved trie node.
+#[derive(Debug, Default)]
+enum Node {
+    /// An empty subtrie.
+    #[default]
+    Empty,
+    /// A subtrie that is only known by its hash.
+    Hash(B256),
+    /// A leaf with the remaining key.
+    Leaf(Nibbles, Vec<u8>),
+    /// An extension with the shared key.
+    Extension(Nibbles, Box<Self>),
+    /// A branch without a value.
+    Branch(Box<[Self; 16]>),
+}
+
+impl Node {
+    /// Returns a branch without children.
+    fn empty_branch() -> Self {
+        Self::Branch(Box::new(std::array::from_fn(|_| Self::Empty)))
+    }
+
+    /// Decodes a proof node.
+    fn decode(mut rlp: &[u8]) -> DownloadResult<Self> {
+        let node = TrieNode::decode(&mut rlp)
+            .map_err(|_| DownloadError::InvalidStateRange { reason: "invalid proof node" })?;
+        Ok(match node {
+            TrieNode::EmptyRoot => Self::Empty,
+            TrieNode::Leaf(leaf) => Self::Leaf(leaf.key, leaf.value),
+            TrieNode::Extension(extension) => {
+                Self::Extension(extension.key, Box::new(Self::from_rlp_node(&extension.child)?))
+            }
+            TrieNode::Branch(branch) => {
+                let mut node = Self::empty_branch();
+                let Self::Branch(children) = &mut node else { unreachable!() };
+                for (nibble, child) in branch.as_ref().children() {
+                    if let Some(child) = child {
+                        children[nibble as usize] = Self::from_rlp_node(child)?;
+                    }
+                }
+                node
+            }
+        })
+    }
+
+    /// Returns the child referenced by the given node, which is either a hash or embedded.
+    fn from_rlp_node(rlp: &RlpNode) -> DownloadResult<Self> {
+        match rlp.as_hash() {
+            Some(hash) => Ok(Self::Hash(hash)),
+            None => Self::decode(rlp.as_slice()),
+        }
+    }
+
+    /// Resolves the nodes on the given path with the proof nodes.
+    fn resolve(&mut self, path: &[u8], proof: &B256Map<&Bytes>) -> DownloadResult<()> {
+        if let Self::Hash(hash) = self {
+            let Some(rlp) = proof.get(hash) else { return Ok(()) };
+            *self = Self::decode(rlp)?;
+        }
+        match self {
+            Self::Extension(key, child) if path.starts_with(key) => {
+                child.resolve(&path[key.len()..], proof)
+            }
+            Self::Branch(children) => match path.split_first() {
+                Some((nibble, rest)) => children[*nibble as usize].resolve(rest, proof),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns whether the subtrie has a leaf after the given path, or at it if `inclusive`.
+    fn has_right(&self, path: &[u8], inclusive: bool) -> DownloadResult<bool> {
+        match self {
+            Self::Empty => Ok(false),
+            Self::Hash(_) => Err(missing_node()),
+            Self::Leaf(key, _) => Ok(match key[..].cmp(path) {
+                Ordering::Less => false,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => true,
+            }),
+            Self::Extension(key, child) => match key[..].cmp(prefix(path, key.len())) {
+                Ordering::Less => Ok(false),
+                Ordering::Equal => child.has_right(&path[key.len()..], inclusive),
+                Ordering::Greater => Ok(true),
+            },
+            Self::Branch(children) => {
+                let (nibble, rest) = split_path(path)?;
+                if children[nibble + 1..].iter().any(|child| !matches!(child, Self::Empty)) {
+                    return Ok(true)
+                }
+                children[nibble].has_right(rest, inclusive)
+            }
+        }
+    }
+
+    /// Removes all leaves from `left` up to and including `right`.
+    fn unset_range(&mut self, left: &[u8], right: &[u8]) -> DownloadResult<()> {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Hash(_) => Err(missing_node()),
+            Self::Leaf(key, _) => {
+                if &key[..] >= left && &key[..] <= right {
+                    *self = Self::Empty;
+                }
+                Ok(())
+            }
+            Self::Extension(key, child) => {
+                let len = key.len();
+                match (key[..].cmp(prefix(left, len)), key[..].cmp(prefix(right, len))) {
+                    (Ordering::Equal, Ordering::Equal) => {
+                        child.unset_range(&left[len..], &right[len..])
+                    }
+                    (Ordering::Equal, Ordering::Less) => child.unset_right(&left[len..]),
+                    (Ordering::Greater, Ordering::Equal) => child.unset_left(&right[len..]),
+                    (Ordering::Greater, Ordering::Less) => {
+                        *self = Self::Empty;
+                        Ok(())
+                    }
+                    // the subtrie is outside of the range
+                    _ => Ok(()),
+                }
+            }
+            Self::Branch(children) => {
+                let (left_nibble, left) = split_path(left)?;
+                let (right_nibble, right) = split_path(right)?;
+                if left_nibble == right_nibble {
+                    return children[left_nibble].unset_range(left, right)
+                }
+                for child in &mut children[left_nibble + 1..right_nibble] {
+                    *child = Self::Empty;
+                }
+                children[left_nibble].unset_right(left)?;
+                children[right_nibble].unset_left(right)
+            }
+        }
+    }
+
+    /// Removes all leaves at or after the given path.
+    fn unset_right(&mut self, path: &[u8]) -> DownloadResult<()> {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Hash(_) => Err(missing_node()),
+            Self::Leaf(key, _) => {
+                if &key[..] >= path {
+                    *self = Self::Empty;
+                }
+                Ok(())
+            }
+            Self::Extension(key, child) => match key[..].cmp(prefix(path, key.len())) {
+                Ordering::Less => Ok(()),
+                Ordering::Equal => child.unset_right(&path[key.len()..]),
+                Ordering::Greater => {
+                    *self = Self::Empty;
+                    Ok(())
+                }
+            },
+            Self::Branch(children) => {
+                let (nibble, rest) = split_path(path)?;
+                for child in &mut children[nibble + 1..] {
+                    *child = Self::Empty;
+                }
+                children[nibble].unset_right(rest)
+            }
+        }
+    }
+
+    /// Removes all leaves at or before the given path.
+    fn unset_left(&mut self, path: &[u8]) -> DownloadResult<()> {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Hash(_) => Err(missing_node()),
+            Self::Leaf(key, _) => {
+                if &key[..] <= path {
+                    *self = Self::Empty;
+                }
+                Ok(())
+            }
+            Self::Extension(key, child) => match key[..].cmp(prefix(path, key.len())) {
+                Ordering::Less => {
+                    *self = Self::Empty;
+                    Ok(())
+                }
+                Ordering::Equal => child.unset_left(&path[key.len()..]),
+                Ordering::Greater => Ok(()),
+            },
+            Self::Branch(children) => {
+                let (nibble, rest) = split_path(path)?;
+                for child in &mut children[..nibble] {
+                    *child = Self::Empty;
+                }
+                children[nibble].unset_left(rest)
+            }
+        }
+    }
+
+    /// Inserts a leaf at the given path.
+    fn insert(&mut self, path: &[u8], value: Vec<u8>) -> DownloadResult<()> {
+        match std::mem::take(self) {
+            Self::Empty => *self = Self::Leaf(Nibbles::from_nibbles_unchecked(path), value),
+            Self::Hash(_) => {
+                return Err(DownloadError::InvalidStateRange { reason: "leaf outside of proof" })
+            }
+            Self::Leaf(key, existing) => {
+                if key[..] == *path {
+                    *self = Self::Leaf(key, value);
+                    return Ok(())
+                }
+                let common = key.common_prefix_length(path);
+                if common == key.len() || common == path.len() {
+                    return Err(DownloadError::InvalidStateRange { reason: "invalid leaf key" })
+                }
+                let mut branch = Self::empty_branch();
+                if let Self::Branch(children) = &mut branch {
+                    children[key[common] as usize] =
+                        Self::Leaf(Nibbles::from_nibbles_unchecked(&key[common + 1..]), existing);
+                    children[path[common] as usize] =
+                        Self::Leaf(Nibbles::from_nibbles_unchecked(&path[common + 1..]), value);
+                }
+                *self = Self::with_prefix(&path[..common], branch);
+            }
+            Self::Extension(key, mut child) => {
+                let common = key.common_prefix_length(path);
+                if common == key.len() {
+                    child.insert(&path[common..], value)?;
+                    *self = Self::Extension(key, child);
+                    return Ok(())
+                }
+                if common == path.len() {
+                    return Err(DownloadError::InvalidStateRange { reason: "invalid leaf key" })
+                }
+                let mut branch = Self::empty_branch();
+                if let Self::Branch(children) = &mut branch {
+                    children[key[common] as usize] = Self::with_prefix(&key[common + 1..], *child);
+                    children[path[common] as usize] =
+                        Self::Leaf(Nibbles::from_nibbles_unchecked(&path[common + 1..]), value);
+                }
+                *self = Self::with_prefix(&path[..common], branch);
+            }
+            Self::Branch(mut children) => {
+                let result = match split_path(path) {
+                    Ok((nibble, rest)) => children[nibble].insert(rest, value),
+                    Err(err) => Err(err),
+                };
+                *self = Self::Branch(children);
+                return result
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the node behind an extension with the given key, if the key is not empty.
+    fn with_prefix(key: &[u8], node: Self) -> Self {
+        if key.is_empty() {
+            node
+        } else {
+            Self::Extension(Nibbles::from_nibbles_unchecked(key), Box::new(node))
+        }
+    }
+
+    /// Returns the reference to this node in its parent.
+    fn rlp(&self) -> RlpNode {
+        let mut buf = Vec::new();
+        match self {
+            Self::Empty => RlpNode::from_rlp(&[EMPTY_STRING_CODE]),
+            Self::Hash(hash) => RlpNode::word_rlp(hash),
+            Self::Leaf(key, value) => LeafNodeRef::new(key, value).rlp(&mut buf),
+            Self::Extension(key, child) => {
+                ExtensionNodeRef::new(key, child.rlp().as_slice()).rlp(&mut buf)
+            }
+            Self::Branch(children) => {
+                let mut state_mask = TrieMask::default();
+                let mut stack = Vec::new();
+                for (nibble, child) in children.iter().enumerate() {
+                    if !matches!(child, Self::Empty) {
+                        state_mask.set_bit(nibble as u8);
+                        stack.push(child.rlp());
+                    }
+                }
+                BranchNodeRef::new(&stack, state_mask).rlp(&mut buf)
+            }
+        }
+    }
+
+    /// Returns the hash of this node as the root of a trie.
+    fn hash(&self) -> B256 {
+        match self {
+            Self::Empty => EMPTY_ROOT_HASH,
+            Self::Hash(hash) => *hash,
+            node => {
+                let rlp = node.rlp();
+                rlp.as_hash().unwrap_or_else(|| keccak256(rlp.as_slice()))
+            }
+        }
+    }
+}
+
+/// Returns the error for a boundary path that is not covered by the proof.
+const fn missing_node() -> DownloadError {
+    DownloadError::InvalidStateRange { reason: "missing proof node" }
+}
+
+/// Returns at most the first `len` nibbles of the path.
+fn prefix(path: &[u8], len: usize) -> &[u8] {
+    &path[..len.min(path.len())]
+}
+
+/// Splits the first nibble off the path.
+fn split_path(path: &[u8]) -> DownloadResult<(usize, &[u8])> {
+    path.split_first()
+        .map(|(nibble, rest)| (*nibble as usize, rest))
+        .ok_or(DownloadError::InvalidStateRange { reason: "invalid leaf key" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_trie::{proof::ProofRetainer, HashBuilder};
+
+    /// Returns sorted leaves with hashed keys.
+    fn leaves(count: u64) -> Vec<(B256, Vec<u8>)> {
+        let mut leaves = (0..count)
+            .map(|i| (keccak256(i.to_be_bytes()), alloy_rlp::encode(U256::from(i + 1))))
+            .collect::<Vec<_>>();
+        leaves.sort_unstable_by_key(|(key, _)| *key);
+        leaves
+    }
+
+    /// Returns the root of the trie and the proof for the given targets.
+    fn proof(leaves: &[(B256, Vec<u8>)], targets: &[B256]) -> (B256, Vec<Bytes>) {
+        let retainer = ProofRetainer::new(targets.iter().map(Nibbles::unpack).collect());
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        let proof = hash_builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        (root, proof)
+    }
+
+    /// Returns the key right after the given one.
+    fn next_key(key: B256) -> B256 {
+        B256::from(U256::from_be_bytes(key.0) + U256::from(1))
+    }
+
+    #[test]
+    fn verify_entire_trie() {
+        let leaves = leaves(100);
+        let (root, _) = proof(&leaves, &[]);
+
+        assert!(!verify_range_proof(root, B256::ZERO, &leaves, &[]).unwrap());
+
+        let mut missing = leaves.clone();
+        missing.remove(42);
+        assert!(verify_range_proof(root, B256::ZERO, &missing, &[]).is_err());
+
+        let mut altered = leaves;
+        altered[42].1 = alloy_rlp::encode(U256::ZERO);
+        assert!(verify_range_proof(root, B256::ZERO, &altered, &[]).is_err());
+    }
+
+    #[test]
+    fn verify_partial_ranges() {
+        let leaves = leaves(100);
+
+        // from the start of the trie
+        let (root, range_proof) = proof(&leaves, &[B256::ZERO, leaves[49].0]);
+        assert!(verify_range_proof(root, B256::ZERO, &leaves[..50], &range_proof).unwrap());
+
+        // starting at an existing key
+        let (_, range_proof) = proof(&leaves, &[leaves[10].0, leaves[49].0]);
+        assert!(verify_range_proof(root, leaves[10].0, &leaves[10..50], &range_proof).unwrap());
+
+        // starting between two keys
+        let origin = next_key(leaves[9].0);
+        let (_, range_proof) = proof(&leaves, &[origin, leaves[49].0]);
+        assert!(verify_range_proof(root, origin, &leaves[10..50], &range_proof).unwrap());
+
+        // up to the end of the trie
+        let (_, range_proof) = proof(&leaves, &[leaves[50].0, leaves[99].0]);
+        assert!(!verify_range_proof(root, leaves[50].0, &leaves[50..], &range_proof).unwrap());
+
+        // a single leaf
+        let (_, range_proof) = proof(&leaves, &[leaves[42].0]);
+        assert!(verify_range_proof(root, leaves[42].0, &leaves[42..43], &range_proof).unwrap());
+    }
+
+    #[test]
+    fn reject_incomplete_ranges() {
+        let leaves = leaves(100);
+        let (root, range_proof) = proof(&leaves, &[leaves[10].0, leaves[49].0]);
+
+        // a missing leaf in the middle of the range
+        let mut missing = leaves[10..50].to_vec();
+        missing.remove(20);
+        assert!(verify_range_proof(root, leaves[10].0, &missing, &range_proof).is_err());
+
+        // a missing leaf at the start of the range
+        let (_, range_proof) = proof(&leaves, &[leaves[9].0, leaves[49].0]);
+        assert!(verify_range_proof(root, leaves[9].0, &leaves[10..50], &range_proof).is_err());
+
+        // an altered value
+        let (_, range_proof) = proof(&leaves, &[leaves[10].0, leaves[49].0]);
+        let mut altered = leaves[10..50].to_vec();
+        altered[5].1 = alloy_rlp::encode(U256::ZERO);
+        assert!(verify_range_proof(root, leaves[10].0, &altered, &range_proof).is_err());
+
+        // a proof for another range
+        let (_, other_proof) = proof(&leaves, &[leaves[60].0, leaves[70].0]);
+        assert!(verify_range_proof(root, leaves[10].0, &leaves[10..50], &other_proof).is_err());
+    }
+
+    #[test]
+    fn verify_proof_of_absence() {
+        let leaves = leaves(100);
+
+        let origin = next_key(leaves[99].0);
+        let (root, absence_proof) = proof(&leaves, &[origin]);
+        assert!(!verify_range_proof(root, origin, &[], &absence_proof).unwrap());
+
+        // the last leaf is withheld
+        let (_, absence_proof) = proof(&leaves, &[leaves[99].0]);
+        assert!(verify_range_proof(root, leaves[99].0, &[], &absence_proof).is_err());
+    }
+}
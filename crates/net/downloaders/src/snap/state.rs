@@ -0,0 +1,520 @@
+use alloy_consensus::constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY};
+use alloy_primitives::{keccak256, map::B256Set, B256, U256};
+use alloy_rlp::Decodable;
+use alloy_trie::TrieAccount;
+use futures::Stream;
+use reth_eth_wire_types::snap::{
+    AccountData, GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage, SlimAccount,
+    StorageData,
+};
+use reth_network_p2p::{
+    error::{DownloadError, DownloadResult, PeerRequestResult},
+    snap::{
+        client::SnapClient,
+        downloader::{SnapStateBatch, StateDownloader},
+    },
+};
+use reth_network_peers::PeerId;
+use reth_primitives_traits::{Account, Bytecode};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tracing::trace;
+
+use super::proof::verify_range_proof;
+
+/// The default soft limit for the size of a single `snap` response.
+pub const DEFAULT_SNAP_RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// The default timeout for a single `snap` request.
+pub const DEFAULT_SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of accounts to request storage ranges for in a single request.
+const MAX_STORAGE_ACCOUNTS_PER_REQUEST: usize = 128;
+
+/// The maximum number of bytecodes to request in a single request.
+const MAX_CODES_PER_REQUEST: usize = 64;
+
+/// Downloads the entire state at a given state root from `snap` peers.
+///
+/// The downloader walks the account range in ascending order and yields one [`SnapStateBatch`]
+/// per account range response, including the storage and bytecodes of all accounts in the
+/// range. The stream ends once the account range is exhausted.
+///
+/// Account ranges are verified against the state root and storage ranges against the storage
+/// root of their account, using the range proofs of the responses. Bytecodes are checked against
+/// their hashes. Peers that serve invalid responses are reported.
+///
+/// If a batch fails to download, the error is yielded and the same batch is retried when the
+/// stream is polled again.
+#[must_use = "Stream does nothing unless polled"]
+pub struct SnapStateDownloader<C> {
+    /// The client to send requests with.
+    client: C,
+    /// The state root to download.
+    root: B256,
+    /// The hashed address of the next account to download, `None` if the download is complete.
+    next_account: Option<B256>,
+    /// The soft limit for the size of a single response.
+    response_bytes: u64,
+    /// The timeout for a single request.
+    request_timeout: Duration,
+    /// The in progress download of the next batch.
+    fut: Option<NextBatchFut>,
+}
+
+impl<C> SnapStateDownloader<C>
+where
+    C: SnapClient + Clone + Unpin + 'static,
+{
+    /// Creates a new downloader for the state with the given root.
+    pub const fn new(client: C, root: B256) -> Self {
+        Self {
+            client,
+            root,
+            next_account: Some(B256::ZERO),
+            response_bytes: DEFAULT_SNAP_RESPONSE_BYTES,
+            request_timeout: DEFAULT_SNAP_REQUEST_TIMEOUT,
+            fut: None,
+        }
+    }
+
+    /// Sets the soft limit for the size of a single response.
+    pub const fn with_response_bytes(mut self, response_bytes: u64) -> Self {
+        self.response_bytes = response_bytes;
+        self
+    }
+
+    /// Sets the timeout for a single request.
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+}
+
+impl<C> fmt::Debug for SnapStateDownloader<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapStateDownloader")
+            .field("root", &self.root)
+            .field("next_account", &self.next_account)
+            .field("response_bytes", &self.response_bytes)
+            .field("request_timeout", &self.request_timeout)
+            .field("in_progress", &self.fut.is_some())
+            .finish()
+    }
+}
+
+impl<C> StateDownloader for SnapStateDownloader<C>
+where
+    C: SnapClient + Clone + Unpin + 'static,
+{
+    fn set_target(&mut self, root: B256, start: B256) {
+        self.root = root;
+        self.next_account = Some(start);
+        self.fut = None;
+    }
+}
+
+impl<C> Stream for SnapStateDownloader<C>
+where
+    C: SnapClient + Clone + Unpin + 'static,
+{
+    type Item = DownloadResult<SnapStateBatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(fut) = &mut this.fut {
+                let (next_account, item) = ready!(fut.as_mut().poll(cx));
+                this.fut = None;
+                this.next_account = next_account;
+                return Poll::Ready(Some(item))
+            }
+
+            let Some(origin) = this.next_account else { return Poll::Ready(None) };
+            let mut state = DownloaderState {
+                client: this.client.clone(),
+                root: this.root,
+                next_account: Some(origin),
+                response_bytes: this.response_bytes,
+                request_timeout: this.request_timeout,
+            };
+            this.fut = Some(Box::pin(async move {
+                let item = state.download_batch(origin).await;
+                (state.next_account, item)
+            }));
+        }
+    }
+}
+
+/// The download of the next batch, resolving to the next account to download and the batch.
+type NextBatchFut =
+    Pin<Box<dyn Future<Output = (Option<B256>, DownloadResult<SnapStateBatch>)> + Send + Sync>>;
+
+/// The state of a single batch download.
+struct DownloaderState<C> {
+    /// The client to send requests with.
+    client: C,
+    /// The state root to download.
+    root: B256,
+    /// The hashed address of the next account to download, `None` if the download is complete.
+    next_account: Option<B256>,
+    /// The soft limit for the size of a single response.
+    response_bytes: u64,
+    /// The timeout for a single request.
+    request_timeout: Duration,
+}
+
+impl<C: SnapClient> DownloaderState<C> {
+    async fn download_batch(&mut self, origin: B256) -> DownloadResult<SnapStateBatch> {
+        let request = GetAccountRangeMessage {
+            request_id: 0,
+            root_hash: self.root,
+            starting_hash: origin,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: self.response_bytes,
+        };
+        let (peer_id, response) = self.request(self.client.get_account_range(request)).await?;
+
+        if response.accounts.is_empty() && response.proof.is_empty() {
+            // the peer does not serve the requested root
+            return Err(DownloadError::EmptyResponse)
+        }
+
+        let accounts = self.check_response(peer_id, decode_accounts(origin, response.accounts))?;
+        let leaves = accounts
+            .iter()
+            .map(|(hashed_address, account)| {
+                let account = TrieAccount {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    storage_root: account.storage_root,
+                    code_hash: account.code_hash,
+                };
+                (*hashed_address, alloy_rlp::encode(account))
+            })
+            .collect::<Vec<_>>();
+        let has_more = self.check_response(
+            peer_id,
+            verify_range_proof(self.root, origin, &leaves, &response.proof),
+        )?;
+
+        if accounts.is_empty() {
+            // a proof of absence for the remainder of the range
+            trace!(target: "downloaders::snap", %origin, "Account range complete");
+            self.next_account = None;
+            return Ok(SnapStateBatch::default())
+        }
+
+        let storage_accounts = accounts
+            .iter()
+            .filter(|(_, account)| account.storage_root != EMPTY_ROOT_HASH)
+            .map(|(hashed_address, account)| (*hashed_address, account.storage_root))
+            .collect();
+        let storages = self.download_storages(storage_accounts).await?;
+
+        let code_hashes = accounts
+            .iter()
+            .map(|(_, account)| account.code_hash)
+            .filter(|code_hash| *code_hash != KECCAK_EMPTY)
+            .collect::<B256Set>();
+        let bytecodes = self.download_bytecodes(code_hashes.into_iter().collect()).await?;
+
+        let batch = SnapStateBatch {
+            accounts: accounts
+                .into_iter()
+                .map(|(hashed_address, account)| {
+                    let account = Account {
+                        nonce: account.nonce,
+                        balance: account.balance,
+                        bytecode_hash: (account.code_hash != KECCAK_EMPTY)
+                            .then_some(account.code_hash),
+                    };
+                    (hashed_address, account)
+                })
+                .collect(),
+            storages,
+            bytecodes,
+        };
+
+        let last = batch.last_account().expect("account range is not empty");
+        trace!(target: "downloaders::snap", %origin, %last, accounts = batch.accounts.len(), "Downloaded account range");
+        self.next_account = if has_more { increment_key(last) } else { None };
+
+        Ok(batch)
+    }
+
+    /// Downloads the complete storage of the given accounts, given by their hashed address and
+    /// storage root.
+    async fn download_storages(
+        &self,
+        mut pending: Vec<(B256, B256)>,
+    ) -> DownloadResult<Vec<(B256, Vec<(B256, U256)>)>> {
+        let mut storages = Vec::with_capacity(pending.len());
+
+        while !pending.is_empty() {
+            let accounts =
+                pending.iter().take(MAX_STORAGE_ACCOUNTS_PER_REQUEST).copied().collect::<Vec<_>>();
+            let request = GetStorageRangesMessage {
+                request_id: 0,
+                root_hash: self.root,
+                account_hashes: accounts
+                    .iter()
+                    .map(|(hashed_address, _)| *hashed_address)
+                    .collect(),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: self.response_bytes,
+            };
+            let (peer_id, response) = self.request(self.client.get_storage_ranges(request)).await?;
+
+            if response.slots.is_empty() {
+                return Err(DownloadError::EmptyResponse)
+            }
+            if response.slots.len() > accounts.len() {
+                self.client.report_bad_message(peer_id);
+                return Err(DownloadError::InvalidStateRange {
+                    reason: "more storage ranges than requested",
+                })
+            }
+
+            // only the last storage range of the response can be incomplete
+            let served = response.slots.len();
+            for (idx, ((hashed_address, storage_root), slots)) in
+                accounts.into_iter().zip(response.slots).enumerate()
+            {
+                let mut slots = self.check_response(peer_id, decode_slots(B256::ZERO, slots))?;
+                // the proof only covers the last range, all others must be complete
+                let proof = if idx + 1 == served { response.proof.as_slice() } else { &[] };
+                let has_more = self.check_response(
+                    peer_id,
+                    verify_range_proof(storage_root, B256::ZERO, &slot_leaves(&slots), proof),
+                )?;
+                if has_more {
+                    slots = self
+                        .download_storage_remainder(hashed_address, storage_root, slots)
+                        .await?;
+                }
+                storages.push((hashed_address, slots));
+            }
+
+            pending.drain(..served);
+        }
+
+        Ok(storages)
+    }
+
+    /// Downloads the remaining storage of a single account that did not fit into one response.
+    async fn download_storage_remainder(
+        &self,
+        hashed_address: B256,
+        storage_root: B256,
+        mut slots: Vec<(B256, U256)>,
+    ) -> DownloadResult<Vec<(B256, U256)>> {
+        loop {
+            let Some(origin) = slots.last().and_then(|(slot, _)| increment_key(*slot)) else {
+                return Ok(slots)
+            };
+            let request = GetStorageRangesMessage {
+                request_id: 0,
+                root_hash: self.root,
+                account_hashes: vec![hashed_address],
+                starting_hash: origin,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: self.response_bytes,
+            };
+            let (peer_id, mut response) =
+                self.request(self.client.get_storage_ranges(request)).await?;
+
+            if response.slots.len() > 1 {
+                self.client.report_bad_message(peer_id);
+                return Err(DownloadError::InvalidStateRange {
+                    reason: "more storage ranges than requested",
+                })
+            }
+            let range = response.slots.pop().unwrap_or_default();
+            if range.is_empty() && response.proof.is_empty() {
+                return Err(DownloadError::EmptyResponse)
+            }
+
+            let range = self.check_response(peer_id, decode_slots(origin, range))?;
+            if response.proof.is_empty() {
+                // without a proof the remainder must be complete, so the entire storage is checked
+                slots.extend(range);
+                self.check_response(
+                    peer_id,
+                    verify_range_proof(storage_root, B256::ZERO, &slot_leaves(&slots), &[]),
+                )?;
+                return Ok(slots)
+            }
+
+            let has_more = self.check_response(
+                peer_id,
+                verify_range_proof(storage_root, origin, &slot_leaves(&range), &response.proof),
+            )?;
+            slots.extend(range);
+            if !has_more {
+                return Ok(slots)
+            }
+        }
+    }
+
+    /// Downloads the bytecodes with the given hashes.
+    async fn download_bytecodes(
+        &self,
+        mut pending: Vec<B256>,
+    ) -> DownloadResult<Vec<(B256, Bytecode)>> {
+        let mut bytecodes = Vec::with_capacity(pending.len());
+
+        while !pending.is_empty() {
+            let hashes = pending.iter().take(MAX_CODES_PER_REQUEST).copied().collect::<Vec<_>>();
+            let request = GetByteCodesMessage {
+                request_id: 0,
+                hashes: hashes.clone(),
+                response_bytes: self.response_bytes,
+            };
+            let (peer_id, response) = self.request(self.client.get_byte_codes(request)).await?;
+
+            if response.codes.is_empty() {
+                return Err(DownloadError::EmptyResponse)
+            }
+
+            let mut received = B256Set::default();
+            for code in response.codes {
+                let code_hash = keccak256(&code);
+                if !hashes.contains(&code_hash) {
+                    self.client.report_bad_message(peer_id);
+                    return Err(DownloadError::InvalidStateRange {
+                        reason: "bytecode was not requested",
+                    })
+                }
+                if received.insert(code_hash) {
+                    bytecodes.push((code_hash, Bytecode::new_raw(code)));
+                }
+            }
+
+            pending.retain(|code_hash| !received.contains(code_hash));
+        }
+
+        Ok(bytecodes)
+    }
+
+    /// Awaits the response of a request with the configured timeout.
+    async fn request<T>(
+        &self,
+        request: impl Future<Output = PeerRequestResult<T>>,
+    ) -> DownloadResult<(PeerId, T)> {
+        match tokio::time::timeout(self.request_timeout, request).await {
+            Ok(response) => Ok(response?.split()),
+            Err(_) => Err(DownloadError::Timeout),
+        }
+    }
+
+    /// Reports the peer if the response failed validation.
+    fn check_response<T>(&self, peer_id: PeerId, result: DownloadResult<T>) -> DownloadResult<T> {
+        if result.is_err() {
+            self.client.report_bad_message(peer_id);
+        }
+        result
+    }
+}
+
+/// Decodes the accounts of an account range response starting at `origin`.
+fn decode_accounts(
+    origin: B256,
+    accounts: Vec<AccountData>,
+) -> DownloadResult<Vec<(B256, SlimAccount)>> {
+    check_ordering(origin, accounts.iter().map(|account| account.hash))?;
+    accounts
+        .into_iter()
+        .map(|AccountData { hash, body }| {
+            SlimAccount::decode(&mut body.as_ref())
+                .map(|account| (hash, account))
+                .map_err(|_| DownloadError::InvalidStateRange { reason: "invalid account body" })
+        })
+        .collect()
+}
+
+/// Decodes the slots of a storage range response starting at `origin`.
+fn decode_slots(origin: B256, slots: Vec<StorageData>) -> DownloadResult<Vec<(B256, U256)>> {
+    check_ordering(origin, slots.iter().map(|slot| slot.hash))?;
+    slots
+        .into_iter()
+        .map(|StorageData { hash, data }| {
+            U256::decode(&mut data.as_ref())
+                .map(|value| (hash, value))
+                .map_err(|_| DownloadError::InvalidStateRange { reason: "invalid slot value" })
+        })
+        .collect()
+}
+
+/// Returns the trie leaves of the given storage slots.
+fn slot_leaves(slots: &[(B256, U256)]) -> Vec<(B256, Vec<u8>)> {
+    slots.iter().map(|(slot, value)| (*slot, alloy_rlp::encode(value))).collect()
+}
+
+/// Checks that the keys are strictly ascending and not before `origin`.
+fn check_ordering(origin: B256, keys: impl IntoIterator<Item = B256>) -> DownloadResult<()> {
+    let mut prev: Option<B256> = None;
+    for key in keys {
+        if key < origin || prev.is_some_and(|prev| key <= prev) {
+            return Err(DownloadError::InvalidStateRange { reason: "keys are not ordered" })
+        }
+        prev = Some(key);
+    }
+    Ok(())
+}
+
+/// Returns the key following the given key, or `None` if it's the last possible key.
+fn increment_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0)
+        .checked_add(U256::from(1))
+        .map(|key| B256::from(key.to_be_bytes::<32>()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use alloy_rlp::Encodable;
+
+    #[test]
+    fn increment_keys() {
+        assert_eq!(increment_key(B256::ZERO), Some(B256::with_last_byte(1)));
+        assert_eq!(
+            increment_key(B256::with_last_byte(0xff)),
+            Some(B256::from(U256::from(0x100).to_be_bytes::<32>()))
+        );
+        assert_eq!(increment_key(B256::repeat_byte(0xff)), None);
+    }
+
+    #[test]
+    fn decode_ordered_slots() {
+        let slot = |key: u8, value: u64| {
+            let mut data = Vec::new();
+            U256::from(value).encode(&mut data);
+            StorageData { hash: B256::with_last_byte(key), data: Bytes::from(data) }
+        };
+
+        let slots = decode_slots(B256::with_last_byte(1), vec![slot(1, 10), slot(2, 20)]).unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                (B256::with_last_byte(1), U256::from(10)),
+                (B256::with_last_byte(2), U256::from(20))
+            ]
+        );
+
+        // before origin
+        assert!(decode_slots(B256::with_last_byte(2), vec![slot(1, 10)]).is_err());
+        // not ascending
+        assert!(decode_slots(B256::ZERO, vec![slot(2, 20), slot(1, 10)]).is_err());
+        // duplicate
+        assert!(decode_slots(B256::ZERO, vec![slot(1, 10), slot(1, 10)]).is_err());
+    }
+}
@@ -6,8 +6,9 @@
 //! Current version: snap/1
 
 use alloc::vec::Vec;
-use alloy_primitives::{Bytes, B256};
-use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use alloy_consensus::constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY};
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
 use reth_codecs_derive::add_arbitrary_tests;
 
 /// Message IDs for the snap sync protocol
@@ -61,6 +62,94 @@ pub struct AccountData {
     pub body: Bytes,
 }
 
+/// An account body in the slim format used by [`AccountData`].
+///
+/// The slim format is the consensus encoding of an account, except that the empty storage root
+/// and the empty code hash are both encoded as empty strings.
+// https://github.com/ethereum/devp2p/blob/master/caps/snap.md#data-format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct SlimAccount {
+    /// Account nonce
+    pub nonce: u64,
+    /// Account balance
+    pub balance: U256,
+    /// Root of the account storage trie
+    pub storage_root: B256,
+    /// Hash of the account code
+    pub code_hash: B256,
+}
+
+impl SlimAccount {
+    /// Returns the slim encoding of the given hash, replacing the empty value with an empty
+    /// string.
+    fn slim_hash(hash: &B256, empty: B256) -> &[u8] {
+        if *hash == empty {
+            &[]
+        } else {
+            hash.as_slice()
+        }
+    }
+
+    /// Decodes a hash in the slim encoding, restoring the empty value from an empty string.
+    fn decode_slim_hash(buf: &mut &[u8], empty: B256) -> alloy_rlp::Result<B256> {
+        let bytes = Header::decode_bytes(buf, false)?;
+        match bytes.len() {
+            0 => Ok(empty),
+            32 => Ok(B256::from_slice(bytes)),
+            _ => Err(alloy_rlp::Error::UnexpectedLength),
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            Self::slim_hash(&self.storage_root, EMPTY_ROOT_HASH).length() +
+            Self::slim_hash(&self.code_hash, KECCAK_EMPTY).length()
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        Self::slim_hash(&self.storage_root, EMPTY_ROOT_HASH).encode(out);
+        Self::slim_hash(&self.code_hash, KECCAK_EMPTY).encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let this = Self {
+            nonce: Decodable::decode(buf)?,
+            balance: Decodable::decode(buf)?,
+            storage_root: Self::decode_slim_hash(buf, EMPTY_ROOT_HASH)?,
+            code_hash: Self::decode_slim_hash(buf, KECCAK_EMPTY)?,
+        };
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(this)
+    }
+}
+
 /// Response containing a number of consecutive accounts and the Merkle proofs for the entire range.
 // http://github.com/ethereum/devp2p/blob/master/caps/snap.md#accountrange-0x01
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
@@ -406,6 +495,30 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_slim_account_roundtrip() {
+        let empty = SlimAccount {
+            nonce: 0,
+            balance: U256::ZERO,
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+        let encoded = alloy_rlp::encode(empty);
+        // empty storage root and code hash are encoded as empty strings
+        assert_eq!(encoded, [0xc4, 0x80, 0x80, 0x80, 0x80]);
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), empty);
+
+        let contract = SlimAccount {
+            nonce: 7,
+            balance: U256::from(1_000_000u64),
+            storage_root: b256_from_u64(1),
+            code_hash: b256_from_u64(2),
+        };
+        let encoded = alloy_rlp::encode(contract);
+        assert_eq!(encoded.len(), contract.length());
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), contract);
+    }
+
     #[test]
     fn test_unknown_message_id() {
         // Create some random data
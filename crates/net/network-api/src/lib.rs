@@ -24,6 +24,7 @@ pub mod test_utils;
 use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
+pub use reth_net_banlist::{IpNet, NetRestrict};
 pub use reth_network_p2p::{BlockClient, HeadersClient};
 pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 
@@ -34,12 +35,18 @@ pub use events::{
     PeerRequestSender,
 };
 
+use alloy_primitives::Address;
 use reth_eth_wire_types::{
     capability::Capabilities, DisconnectReason, EthVersion, NetworkPrimitives, UnifiedStatus,
 };
 use reth_network_p2p::sync::NetworkSyncUpdater;
 use reth_network_peers::NodeRecord;
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// The `PeerId` type.
 pub type PeerId = alloy_primitives::B512;
@@ -52,6 +59,7 @@ pub trait FullNetwork:
     + NetworkInfo
     + NetworkEventListenerProvider
     + Peers
+    + SenderBans
     + PeersHandleProvider
     + Clone
     + Unpin
@@ -66,6 +74,7 @@ impl<T> FullNetwork for T where
         + NetworkInfo
         + NetworkEventListenerProvider
         + Peers
+        + SenderBans
         + PeersHandleProvider
         + Clone
         + Unpin
@@ -214,6 +223,151 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Bans the given peer for the given duration, or indefinitely if no duration is given.
+    ///
+    /// An active session with the peer is disconnected.
+    ///
+    /// Returns `true` if the peer was not banned before.
+    fn ban_peer(
+        &self,
+        peer_id: PeerId,
+        duration: Option<Duration>,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send {
+        let _ = (peer_id, duration);
+        futures::future::ready(Ok(false))
+    }
+
+    /// Lifts the ban of the given peer and resets its reputation.
+    ///
+    /// Returns `true` if the peer was banned.
+    fn unban_peer(
+        &self,
+        peer_id: PeerId,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send {
+        let _ = peer_id;
+        futures::future::ready(Ok(false))
+    }
+
+    /// Bans the given ip address for the given duration, or indefinitely if no duration is given.
+    ///
+    /// Active sessions with peers at this address are disconnected.
+    ///
+    /// Returns `false` if the address can't be banned because it is not globally routable.
+    fn ban_ip(
+        &self,
+        ip: IpAddr,
+        duration: Option<Duration>,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send {
+        let _ = (ip, duration);
+        futures::future::ready(Ok(false))
+    }
+
+    /// Lifts the ban of the given ip address.
+    ///
+    /// Returns `true` if the ip address was banned.
+    fn unban_ip(&self, ip: IpAddr) -> impl Future<Output = Result<bool, NetworkError>> + Send {
+        let _ = ip;
+        futures::future::ready(Ok(false))
+    }
+
+    /// Returns all banned peers and ip addresses.
+    fn bans(&self) -> impl Future<Output = Result<Vec<Ban>, NetworkError>> + Send {
+        futures::future::ready(Ok(Vec::new()))
+    }
+
+    /// Returns the reputation of all peers in the peer set.
+    fn peer_reputations(
+        &self,
+    ) -> impl Future<Output = Result<Vec<PeerReputation>, NetworkError>> + Send {
+        futures::future::ready(Ok(Vec::new()))
+    }
+
+    /// Returns the IP networks the node is restricted to and the networks it refuses to
+    /// communicate with.
+    fn net_restrict(&self) -> NetRestrict {
+        NetRestrict::default()
+    }
+
+    /// Replaces the allowed and denied IP networks.
+    ///
+    /// Connected peers whose address is no longer allowed are disconnected.
+    fn set_net_restrict(&self, restrict: NetRestrict) {
+        let _ = restrict;
+    }
+}
+
+/// Provides access to the transaction senders that are banned because their transactions were
+/// repeatedly rejected by the pool.
+pub trait SenderBans: Send + Sync {
+    /// Returns all transaction senders that are currently banned.
+    fn banned_senders(
+        &self,
+    ) -> impl Future<Output = Result<Vec<BannedSender>, NetworkError>> + Send;
+
+    /// Lifts the ban of the given transaction sender and resets its score.
+    ///
+    /// Returns `true` if the sender was banned.
+    fn unban_sender(
+        &self,
+        sender: Address,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Lifts the bans of all transaction senders and resets their scores.
+    fn clear_sender_bans(&self) -> impl Future<Output = Result<(), NetworkError>> + Send;
+}
+
+/// A transaction sender that is temporarily banned.
+///
+/// Transactions of banned senders that are received from peers are not imported into the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct BannedSender {
+    /// The address of the sender.
+    pub address: Address,
+    /// The score the sender accumulated before it was banned.
+    pub score: u32,
+    /// Number of seconds until the ban is lifted.
+    pub expires_in: u64,
+}
+
+/// An entry of the network's ban list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ban {
+    /// The banned peer or ip address.
+    pub target: BanTarget,
+    /// The time at which the ban is lifted, `None` if the ban is indefinite.
+    pub banned_until: Option<Instant>,
+}
+
+/// What a [`Ban`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanTarget {
+    /// A peer, identified by its id.
+    Peer(PeerId),
+    /// All peers at the ip address.
+    Ip(IpAddr),
+}
+
+/// Reputation of a peer in the peer set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerReputation {
+    /// The identifier of the peer.
+    pub peer_id: PeerId,
+    /// The tcp address of the peer.
+    pub addr: SocketAddr,
+    /// The kind of the peer.
+    pub kind: PeerKind,
+    /// The current reputation of the peer.
+    pub reputation: Reputation,
+    /// Whether the reputation is below the ban threshold.
+    pub is_banned: bool,
+    /// The most recent reputation changes that were applied to the peer, oldest first.
+    pub recent_changes: Vec<ReputationChangeKind>,
 }
 
 /// Info about an active peer session.
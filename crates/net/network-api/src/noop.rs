@@ -6,6 +6,7 @@
 use core::{fmt, marker::PhantomData};
 use std::net::{IpAddr, SocketAddr};
 
+use alloy_primitives::Address;
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{
@@ -21,9 +22,9 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use crate::{
     events::{NetworkPeersEvents, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
-    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
+    BannedSender, BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, SenderBans,
 };
 
 /// A type that implements all network trait that does nothing.
@@ -161,6 +162,23 @@ where
     }
 }
 
+impl<Net> SenderBans for NoopNetwork<Net>
+where
+    Net: Send + Sync,
+{
+    async fn banned_senders(&self) -> Result<Vec<BannedSender>, NetworkError> {
+        Ok(vec![])
+    }
+
+    async fn unban_sender(&self, _sender: Address) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn clear_sender_bans(&self) -> Result<(), NetworkError> {
+        Ok(())
+    }
+}
+
 impl<Net> BlockDownloaderProvider for NoopNetwork<Net>
 where
     Net: NetworkPrimitives + Default,
@@ -1,13 +1,18 @@
 //! Interaction with `reth_network::PeersManager`, for integration testing. Otherwise
 //! `reth_network::NetworkManager` manages `reth_network::PeersManager`.
 
-use std::net::SocketAddr;
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use derive_more::Constructor;
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{Peer, ReputationChangeKind};
 use tokio::sync::{mpsc, oneshot};
 
+use crate::{Ban, PeerReputation};
+
 /// Provides an API for managing the peers of the network.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait PeersHandleProvider {
@@ -61,6 +66,58 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Bans a peer for the given duration, or indefinitely if no duration is given.
+    ///
+    /// Returns `true` if the peer was not banned before.
+    pub async fn ban_peer(&self, peer_id: PeerId, duration: Option<Duration>) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::BanPeer(peer_id, duration, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Lifts the ban of a peer. Returns `true` if the peer was banned.
+    pub async fn unban_peer(&self, peer_id: PeerId) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::UnbanPeer(peer_id, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Bans an ip address for the given duration, or indefinitely if no duration is given.
+    ///
+    /// Returns `false` if the address is not globally routable and can't be banned.
+    pub async fn ban_ip(&self, ip: IpAddr, duration: Option<Duration>) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::BanIp(ip, duration, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Lifts the ban of an ip address. Returns `true` if the address was banned.
+    pub async fn unban_ip(&self, ip: IpAddr) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::UnbanIp(ip, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Returns all banned peers and ip addresses.
+    pub async fn bans(&self) -> Vec<Ban> {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetBans(tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Returns the reputation of all peers in the peerset.
+    pub async fn reputations(&self) -> Vec<PeerReputation> {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetReputations(tx));
+
+        rx.await.unwrap_or_default()
+    }
 }
 
 /// Commands the `PeersManager` listens for.
@@ -78,4 +135,18 @@ pub enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Ban a peer for the given duration, or indefinitely, responds whether the peer was not
+    /// banned before.
+    BanPeer(PeerId, Option<Duration>, oneshot::Sender<bool>),
+    /// Lift the ban of a peer, responds whether the peer was banned.
+    UnbanPeer(PeerId, oneshot::Sender<bool>),
+    /// Ban an ip address for the given duration, or indefinitely, responds whether the address
+    /// was banned.
+    BanIp(IpAddr, Option<Duration>, oneshot::Sender<bool>),
+    /// Lift the ban of an ip address, responds whether the address was banned.
+    UnbanIp(IpAddr, oneshot::Sender<bool>),
+    /// Get all banned peers and ip addresses.
+    GetBans(oneshot::Sender<Vec<Ban>>),
+    /// Get the reputation of all peers.
+    GetReputations(oneshot::Sender<Vec<PeerReputation>>),
 }
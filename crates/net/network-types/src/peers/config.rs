@@ -7,7 +7,7 @@ use std::{
     time::Duration,
 };
 
-use reth_net_banlist::BanList;
+use reth_net_banlist::{BanList, IpFilter};
 use reth_network_peers::{NodeRecord, TrustedPeer};
 use tracing::info;
 
@@ -150,6 +150,9 @@ pub struct PeersConfig {
     /// Restrictions on `PeerIds` and Ips.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ban_list: BanList,
+    /// Allowed and denied IP networks for peer connections and discovery.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ip_filter: IpFilter,
     /// Restrictions on connections.
     pub connection_info: ConnectionsConfig,
     /// How to weigh reputation changes.
@@ -172,6 +175,7 @@ impl Default for PeersConfig {
             connection_info: Default::default(),
             reputation_weights: Default::default(),
             ban_list: Default::default(),
+            ip_filter: Default::default(),
             // Ban peers for 12h
             ban_duration: Duration::from_secs(60 * 60 * 12),
             backoff_durations: Default::default(),
@@ -191,6 +195,12 @@ impl PeersConfig {
         self
     }
 
+    /// Restricts peer connections and discovery to the allowed IP networks of the filter.
+    pub fn with_ip_filter(mut self, ip_filter: IpFilter) -> Self {
+        self.ip_filter = ip_filter;
+        self
+    }
+
     /// Configure how long to ban bad peers
     pub const fn with_ban_duration(mut self, ban_duration: Duration) -> Self {
         self.ban_duration = ban_duration;
@@ -8,11 +8,12 @@ pub use config::{ConnectionsConfig, PeersConfig};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
 use alloy_eip2124::ForkId;
+use std::collections::VecDeque;
 use tracing::trace;
 
 use crate::{
-    is_banned_reputation, PeerAddr, PeerConnectionState, PeerKind, ReputationChangeOutcome,
-    DEFAULT_REPUTATION,
+    is_banned_reputation, peers::reputation::MAX_TRACKED_REPUTATION_CHANGES, PeerAddr,
+    PeerConnectionState, PeerKind, ReputationChangeOutcome, DEFAULT_REPUTATION,
 };
 
 /// Tracks info about a single peer.
@@ -35,6 +36,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// The most recent reputation changes that were applied to the peer, oldest first.
+    pub recent_reputation_changes: VecDeque<ReputationChangeKind>,
 }
 
 // === impl Peer ===
@@ -66,6 +69,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            recent_reputation_changes: VecDeque::new(),
         }
     }
 
@@ -106,6 +110,16 @@ impl Peer {
         ReputationChangeOutcome::None
     }
 
+    /// Records a reputation change that was applied to the peer.
+    ///
+    /// Only the [`MAX_TRACKED_REPUTATION_CHANGES`] most recent changes are kept.
+    pub fn record_reputation_change(&mut self, kind: ReputationChangeKind) {
+        if self.recent_reputation_changes.len() >= MAX_TRACKED_REPUTATION_CHANGES {
+            self.recent_reputation_changes.pop_front();
+        }
+        self.recent_reputation_changes.push_back(kind);
+    }
+
     /// Returns true if the peer's reputation is below the banned threshold.
     #[inline]
     pub const fn is_banned(&self) -> bool {
@@ -42,6 +42,9 @@ const BAD_ANNOUNCEMENT_REPUTATION_CHANGE: i32 = REPUTATION_UNIT;
 /// untrusted peers.
 pub const MAX_TRUSTED_PEER_REPUTATION_CHANGE: Reputation = 2 * REPUTATION_UNIT;
 
+/// The number of most recent reputation changes that are tracked per peer.
+pub const MAX_TRACKED_REPUTATION_CHANGES: usize = 10;
+
 /// Returns `true` if the given reputation is below the [`BANNED_REPUTATION`] threshold
 #[inline]
 pub const fn is_banned_reputation(reputation: i32) -> bool {
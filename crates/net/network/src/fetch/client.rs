@@ -15,9 +15,12 @@ use reth_network_p2p::{
 };
 use reth_network_peers::PeerId;
 use reth_network_types::ReputationChangeKind;
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use std::{
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
 
@@ -84,11 +87,22 @@ impl<N: NetworkPrimitives> BodiesClient for FetchClient<N> {
         &self,
         request: Vec<B256>,
         priority: Priority,
+    ) -> Self::Output {
+        self.get_block_bodies_with_range_hint(request, priority, None)
+    }
+
+    /// Sends a `GetBlockBodies` request to an available peer that advertises the given block
+    /// range.
+    fn get_block_bodies_with_range_hint(
+        &self,
+        request: Vec<B256>,
+        priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
     ) -> Self::Output {
         let (response, rx) = oneshot::channel();
         if self
             .request_tx
-            .send(DownloadRequest::GetBlockBodies { request, response, priority })
+            .send(DownloadRequest::GetBlockBodies { request, response, priority, range_hint })
             .is_ok()
         {
             Box::pin(FlattenedResponse::from(rx))
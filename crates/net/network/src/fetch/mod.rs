@@ -4,10 +4,13 @@ mod client;
 
 pub use client::FetchClient;
 
-use crate::message::BlockRequest;
+use crate::{message::BlockRequest, metrics::StateFetcherMetrics};
+use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
 use futures::StreamExt;
-use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
+use reth_eth_wire::{
+    BlockRangeUpdate, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
@@ -18,6 +21,7 @@ use reth_network_peers::PeerId;
 use reth_network_types::ReputationChangeKind;
 use std::{
     collections::{HashMap, VecDeque},
+    ops::RangeInclusive,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -54,6 +58,8 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest<N>>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest<N>>,
+    /// Metrics for the fetcher.
+    metrics: StateFetcherMetrics,
 }
 
 // === impl StateSyncer ===
@@ -70,15 +76,20 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            metrics: Default::default(),
         }
     }
 
     /// Invoked when connected to a new peer.
+    ///
+    /// The `block_range` is the `[earliest, latest]` range the peer advertised in its eth/69
+    /// status, if any.
     pub(crate) fn new_active_peer(
         &mut self,
         peer_id: PeerId,
         best_hash: B256,
         best_number: u64,
+        block_range: Option<RangeInclusive<u64>>,
         timeout: Arc<AtomicU64>,
     ) {
         self.peers.insert(
@@ -87,6 +98,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 state: PeerState::Idle,
                 best_hash,
                 best_number,
+                block_range,
                 timeout,
                 last_response_likely_bad: false,
             },
@@ -117,12 +129,28 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             if number > peer.best_number {
                 peer.best_hash = hash;
                 peer.best_number = number;
+                if let Some(range) = &mut peer.block_range {
+                    if number > *range.end() {
+                        *range = *range.start()..=number;
+                    }
+                }
                 return true
             }
         }
         false
     }
 
+    /// Updates the block range the peer advertised via an eth/69 `BlockRangeUpdate`.
+    pub(crate) fn update_peer_block_range(&mut self, peer_id: &PeerId, update: &BlockRangeUpdate) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.block_range = Some(update.earliest..=update.latest);
+            if update.latest > peer.best_number {
+                peer.best_hash = update.latest_hash;
+                peer.best_number = update.latest;
+            }
+        }
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -130,11 +158,15 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
     }
 
-    /// Returns the _next_ idle peer that's ready to accept a request,
+    /// Returns the _next_ idle peer that's ready to accept a request for the given block range,
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+    ///
+    /// If a range is given, only peers that can serve it are considered, see [`Peer::can_serve`].
+    fn next_best_peer(&self, range: Option<&RangeInclusive<u64>>) -> Option<PeerId> {
+        let mut idle = self.peers.iter().filter(|(_, peer)| {
+            peer.state.is_idle() && range.is_none_or(|range| peer.can_serve(range))
+        });
 
         let mut best_peer = idle.next()?;
 
@@ -163,12 +195,51 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        if !self.peers.values().any(|peer| peer.state.is_idle()) {
+            return PollAction::NoPeersAvailable
+        }
 
-        let request = self.queued_requests.pop_front().expect("not empty");
-        let request = self.prepare_block_request(peer_id, request);
+        // send the first queued request an idle peer can serve, requests that wait for a busy peer
+        // don't block the requests queued behind them
+        for idx in 0..self.queued_requests.len() {
+            let range = self.queued_requests[idx].range_hint();
+
+            let peer_id = match self.next_best_peer(range.as_ref()) {
+                Some(peer_id) => peer_id,
+                None => {
+                    // no idle peer advertises the requested range
+                    let Some(range) = range else { continue };
+
+                    // if a busy peer can serve the range, wait for it to become idle
+                    if self
+                        .peers
+                        .values()
+                        .any(|peer| !peer.state.is_closing() && peer.can_serve(&range))
+                    {
+                        continue
+                    }
 
-        PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
+                    // no connected peer advertises the range, fall back to any idle peer
+                    let Some(peer_id) = self.next_best_peer(None) else { continue };
+                    match &self.queued_requests[idx] {
+                        DownloadRequest::GetBlockHeaders { .. } => {
+                            self.metrics.misrouted_headers_requests.increment(1)
+                        }
+                        DownloadRequest::GetBlockBodies { .. } => {
+                            self.metrics.misrouted_bodies_requests.increment(1)
+                        }
+                    }
+                    peer_id
+                }
+            };
+
+            let request = self.queued_requests.remove(idx).expect("exists");
+            let request = self.prepare_block_request(peer_id, request);
+
+            return PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
+        }
+
+        PollAction::NoPeersAvailable
     }
 
     /// Advance the state the syncer
@@ -225,13 +296,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
                 let inflight = Request { request: request.clone(), response };
                 self.inflight_headers_requests.insert(peer_id, inflight);
-                let HeadersRequest { start, limit, direction } = request;
-                BlockRequest::GetBlockHeaders(GetBlockHeaders {
-                    start_block: start,
-                    limit,
-                    skip: 0,
-                    direction,
-                })
+                BlockRequest::GetBlockHeaders(get_block_headers(request))
             }
             DownloadRequest::GetBlockBodies { request, response, .. } => {
                 let inflight = Request { request: request.clone(), response };
@@ -243,9 +308,17 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
     /// Returns a new followup request for the peer.
     ///
+    /// The followup is the first queued request whose block range the peer advertises, requests
+    /// the peer can't serve are left for [`Self::poll_action`] to route.
+    ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
-        let req = self.queued_requests.pop_front()?;
+        let peer = self.peers.get(&peer_id)?;
+        let idx = self
+            .queued_requests
+            .iter()
+            .position(|req| req.range_hint().is_none_or(|range| peer.can_serve(&range)))?;
+        let req = self.queued_requests.remove(idx)?;
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
@@ -338,6 +411,10 @@ struct Peer {
     best_hash: B256,
     /// Tracks the best number of the peer.
     best_number: u64,
+    /// The `[earliest, latest]` block range the peer advertised via eth/69.
+    ///
+    /// This is `None` for peers on older eth versions, which are assumed to serve all blocks.
+    block_range: Option<RangeInclusive<u64>>,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
     /// Tracks whether the peer has recently responded with a likely bad response.
@@ -353,6 +430,14 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer advertised that it can serve the given block range.
+    ///
+    /// Only the start of the range is checked against the peer's latest block, because range
+    /// updates are only sent periodically and the peer likely has newer blocks than advertised.
+    fn can_serve(&self, range: &RangeInclusive<u64>) -> bool {
+        self.block_range.as_ref().is_none_or(|available| available.contains(range.start()))
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -376,6 +461,11 @@ impl PeerState {
         matches!(self, Self::Idle)
     }
 
+    /// Returns true if the peer session is about to close.
+    const fn is_closing(&self) -> bool {
+        matches!(self, Self::Closing)
+    }
+
     /// Resets the state on a received response.
     ///
     /// If the state was already marked as `Closing` do nothing.
@@ -414,6 +504,8 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<N::BlockBody>>>,
         priority: Priority,
+        /// The block numbers the requested bodies belong to, if known.
+        range_hint: Option<RangeInclusive<u64>>,
     },
 }
 
@@ -441,6 +533,40 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
     }
+
+    /// Returns the range of block numbers this request targets, if known.
+    ///
+    /// This is unknown for headers requests that start at a hash and bodies requests without a
+    /// range hint.
+    fn range_hint(&self) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::GetBlockHeaders { request, .. } => {
+                headers_range(&get_block_headers(request.clone()))
+            }
+            Self::GetBlockBodies { range_hint, .. } => range_hint.clone(),
+        }
+    }
+}
+
+/// Converts a [`HeadersRequest`] into the [`GetBlockHeaders`] message sent to the peer.
+const fn get_block_headers(request: HeadersRequest) -> GetBlockHeaders {
+    let HeadersRequest { start, limit, direction } = request;
+    GetBlockHeaders { start_block: start, limit, skip: 0, direction }
+}
+
+/// Returns the range of block numbers spanned by a [`GetBlockHeaders`] request, if it starts at a
+/// block number.
+///
+/// With a non-zero `skip`, the requested headers are `skip + 1` blocks apart, so the range covers
+/// `(limit - 1) * (skip + 1)` blocks after (or before) the start block.
+fn headers_range(request: &GetBlockHeaders) -> Option<RangeInclusive<u64>> {
+    let BlockHashOrNumber::Number(start) = request.start_block else { return None };
+    let span = request.limit.saturating_sub(1).saturating_mul(u64::from(request.skip) + 1);
+    if request.direction.is_rising() {
+        Some(start..=start.saturating_add(span))
+    } else {
+        Some(start.saturating_sub(span)..=start)
+    }
 }
 
 /// An action the syncer can emit.
@@ -471,6 +597,7 @@ mod tests {
     use crate::{peers::PeersManager, PeersConfig};
     use alloy_consensus::Header;
     use alloy_primitives::B512;
+    use reth_eth_wire::HeadersDirection;
     use std::future::poll_fn;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -486,6 +613,7 @@ mod tests {
                 request: vec![],
                 response: tx,
                 priority: Priority::default(),
+                range_hint: None,
             });
             assert!(fetcher.poll(cx).is_pending());
 
@@ -502,20 +630,20 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(peer1, B256::random(), 1, None, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, None, Arc::new(AtomicU64::new(1)));
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -530,18 +658,228 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout));
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(peer1, B256::random(), 1, None, Arc::new(AtomicU64::new(30)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, None, Arc::clone(&peer2_timeout));
+        fetcher.new_active_peer(peer3, B256::random(), 3, None, Arc::new(AtomicU64::new(50)));
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_peer_block_range_selection() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let expired = B512::random();
+        let archive = B512::random();
+
+        // the peer with expired history has the lowest timeout
+        fetcher.new_active_peer(
+            expired,
+            B256::random(),
+            100,
+            Some(50..=100),
+            Arc::new(AtomicU64::new(10)),
+        );
+        fetcher.new_active_peer(
+            archive,
+            B256::random(),
+            100,
+            Some(0..=100),
+            Arc::new(AtomicU64::new(50)),
+        );
+
+        assert_eq!(fetcher.next_best_peer(None), Some(expired));
+        assert_eq!(fetcher.next_best_peer(Some(&(60..=80))), Some(expired));
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(archive));
+        assert_eq!(fetcher.next_best_peer(Some(&(200..=300))), None);
+
+        // the peer stopped serving old blocks
+        fetcher.update_peer_block_range(
+            &archive,
+            &BlockRangeUpdate { earliest: 30, latest: 200, latest_hash: B256::random() },
+        );
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), None);
+        assert_eq!(fetcher.next_best_peer(Some(&(150..=160))), Some(archive));
+    }
+
+    #[tokio::test]
+    async fn test_poll_action_routes_by_block_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let expired = B512::random();
+        let archive = B512::random();
+        fetcher.new_active_peer(
+            expired,
+            B256::random(),
+            100,
+            Some(50..=100),
+            Arc::new(AtomicU64::new(10)),
+        );
+        fetcher.new_active_peer(
+            archive,
+            B256::random(),
+            100,
+            Some(0..=100),
+            Arc::new(AtomicU64::new(50)),
+        );
+
+        let headers_request = |start: u64| {
+            let (tx, _rx) = oneshot::channel();
+            DownloadRequest::GetBlockHeaders {
+                request: HeadersRequest {
+                    start: start.into(),
+                    limit: 10,
+                    direction: HeadersDirection::Rising,
+                },
+                response: tx,
+                priority: Priority::default(),
+            }
+        };
+
+        // old blocks go to the archive peer
+        fetcher.queued_requests.push_back(headers_request(10));
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            panic!("expected a request")
+        };
+        assert_eq!(peer_id, archive);
+
+        // the archive peer is busy, so the request waits for it
+        fetcher.queued_requests.push_back(headers_request(20));
+        assert!(matches!(fetcher.poll_action(), PollAction::NoPeersAvailable));
+
+        // once the archive peer is gone, the request falls back to the remaining peer
+        fetcher.on_session_closed(&archive);
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            panic!("expected a request")
+        };
+        assert_eq!(peer_id, expired);
+    }
+
+    #[tokio::test]
+    async fn test_poll_action_skips_requests_waiting_for_busy_peers() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let expired = B512::random();
+        let archive = B512::random();
+        fetcher.new_active_peer(
+            expired,
+            B256::random(),
+            100,
+            Some(50..=100),
+            Arc::new(AtomicU64::new(10)),
+        );
+        fetcher.new_active_peer(
+            archive,
+            B256::random(),
+            100,
+            Some(0..=100),
+            Arc::new(AtomicU64::new(50)),
+        );
+
+        let headers_request = |start: u64| {
+            let (tx, _rx) = oneshot::channel();
+            DownloadRequest::GetBlockHeaders {
+                request: HeadersRequest {
+                    start: start.into(),
+                    limit: 10,
+                    direction: HeadersDirection::Rising,
+                },
+                response: tx,
+                priority: Priority::default(),
+            }
+        };
+
+        // occupy the archive peer with an old request
+        fetcher.queued_requests.push_back(headers_request(10));
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            panic!("expected a request")
+        };
+        assert_eq!(peer_id, archive);
+
+        // the old request waits for the archive peer, the recent one behind it is sent right away
+        fetcher.queued_requests.push_back(headers_request(20));
+        fetcher.queued_requests.push_back(headers_request(80));
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            panic!("expected a request")
+        };
+        assert_eq!(peer_id, expired);
+        assert_eq!(fetcher.queued_requests.len(), 1);
+        assert_eq!(fetcher.queued_requests[0].range_hint(), Some(20..=29));
+
+        // once the archive peer responds, it follows up with the waiting request
+        let headers = (10..20).map(|number| Header { number, ..Default::default() }).collect();
+        let outcome = fetcher.on_block_headers_response(archive, Ok(headers));
+        assert!(
+            matches!(outcome, Some(BlockResponseOutcome::Request(peer_id, _)) if peer_id == archive)
+        );
+        assert!(fetcher.queued_requests.is_empty());
+    }
+
+    #[test]
+    fn test_download_request_range_hint() {
+        let headers_request = |start: BlockHashOrNumber, direction| {
+            let (tx, _rx) = oneshot::channel();
+            DownloadRequest::<EthNetworkPrimitives>::GetBlockHeaders {
+                request: HeadersRequest { start, limit: 10, direction },
+                response: tx,
+                priority: Priority::default(),
+            }
+        };
+
+        assert_eq!(
+            headers_request(100u64.into(), HeadersDirection::Rising).range_hint(),
+            Some(100..=109)
+        );
+        assert_eq!(
+            headers_request(100u64.into(), HeadersDirection::Falling).range_hint(),
+            Some(91..=100)
+        );
+        assert_eq!(
+            headers_request(5u64.into(), HeadersDirection::Falling).range_hint(),
+            Some(0..=5)
+        );
+        assert_eq!(
+            headers_request(B256::random().into(), HeadersDirection::Rising).range_hint(),
+            None
+        );
+
+        let (tx, _rx) = oneshot::channel();
+        let bodies_request = DownloadRequest::<EthNetworkPrimitives>::GetBlockBodies {
+            request: vec![B256::random()],
+            response: tx,
+            priority: Priority::default(),
+            range_hint: Some(7..=7),
+        };
+        assert_eq!(bodies_request.range_hint(), Some(7..=7));
+    }
+
+    #[test]
+    fn test_headers_range_with_skip() {
+        let request = |start: u64, skip, direction| GetBlockHeaders {
+            start_block: start.into(),
+            limit: 10,
+            skip,
+            direction,
+        };
+
+        assert_eq!(headers_range(&request(100, 0, HeadersDirection::Rising)), Some(100..=109));
+        assert_eq!(headers_range(&request(100, 2, HeadersDirection::Rising)), Some(100..=127));
+        assert_eq!(headers_range(&request(100, 2, HeadersDirection::Falling)), Some(73..=100));
+        assert_eq!(headers_range(&request(20, 4, HeadersDirection::Falling)), Some(0..=20));
     }
 
     #[tokio::test]
@@ -600,6 +938,7 @@ mod tests {
             peer_id,
             Default::default(),
             Default::default(),
+            None,
             Default::default(),
         );
 
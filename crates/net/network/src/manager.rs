@@ -244,7 +244,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             hello_message,
             status,
             fork_filter,
-            dns_discovery_config,
+            mut dns_discovery_config,
             extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
@@ -252,6 +252,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             handshake,
         } = config;
 
+        // the allowed and denied ip networks are shared by the peers manager and all discovery
+        // services, so that runtime updates apply to all of them
+        let ip_filter = peers_config.ip_filter.clone();
+
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -272,11 +276,17 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             // add the forkid entry for EIP-868, but wrap it in an `EnrForkIdEntry` for proper
             // encoding
             disc_config.add_eip868_pair("eth", EnrForkIdEntry::from(status.forkid));
+            disc_config.ip_filter = ip_filter.clone();
         }
 
         if let Some(discv5) = discovery_v5_config.as_mut() {
             // merge configured boot nodes
-            discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
+            discv5.extend_unsigned_boot_nodes(resolved_boot_nodes);
+            discv5.set_ip_filter(ip_filter.clone());
+        }
+
+        if let Some(dns_config) = dns_discovery_config.as_mut() {
+            dns_config.ip_filter = ip_filter.clone();
         }
 
         let discovery = Discovery::new(
@@ -333,6 +343,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5,
             event_sender.clone(),
             nat,
+            ip_filter,
         );
 
         Ok(Self {
@@ -616,7 +627,18 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             PeerMessage::SendTransactions(_) => {
                 unreachable!("Not emitted by session")
             }
-            PeerMessage::BlockRangeUpdated(_) => {}
+            PeerMessage::BlockRangeUpdated(update) => {
+                if update.earliest > update.latest {
+                    // an inverted range is invalid
+                    self.swarm
+                        .state_mut()
+                        .peers_mut()
+                        .apply_reputation_change(&peer_id, ReputationChangeKind::BadMessage);
+                    self.metrics.invalid_messages_received.increment(1);
+                } else {
+                    self.swarm.state_mut().update_peer_block_range(&peer_id, update);
+                }
+            }
             PeerMessage::Other(other) => {
                 debug!(target: "net", message_id=%other.id, "Ignoring unsupported message");
             }
@@ -719,6 +741,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::InternalBlockRangeUpdate(block_range_update) => {
                 self.swarm.sessions_mut().update_advertised_block_range(block_range_update);
             }
+            NetworkHandleMessage::IpFilterUpdated => {
+                self.swarm.state_mut().peers_mut().on_ip_filter_updated();
+            }
             NetworkHandleMessage::EthMessage { peer_id, message } => {
                 self.swarm.sessions_mut().send_message(&peer_id, message)
             }
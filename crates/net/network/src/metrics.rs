@@ -132,6 +132,14 @@ pub struct TransactionsManagerMetrics {
     /// measure.
     pub(crate) capacity_pending_pool_imports: Counter,
 
+    /* ================ SENDER REPUTATION ================ */
+    /// Total number of times a transaction sender was banned.
+    pub(crate) sender_bans: Counter,
+    /// Number of transaction senders that are currently banned.
+    pub(crate) banned_senders: Gauge,
+    /// Total number of transactions received from banned senders, that were not imported.
+    pub(crate) transactions_from_banned_senders: Counter,
+
     /* ================ POLL DURATION ================ */
 
     /* -- Total poll duration of `TransactionsManager` future -- */
@@ -226,6 +234,18 @@ pub struct TransactionFetcherMetrics {
     pub(crate) duration_fill_request_from_hashes_pending_fetch: Gauge,
 }
 
+/// Metrics for the [`StateFetcher`](crate::fetch::StateFetcher).
+#[derive(Metrics)]
+#[metrics(scope = "network.fetcher")]
+pub struct StateFetcherMetrics {
+    /// Number of `GetBlockHeaders` requests sent to a peer that does not advertise the requested
+    /// block range, because no connected peer advertised it.
+    pub(crate) misrouted_headers_requests: Counter,
+    /// Number of `GetBlockBodies` requests sent to a peer that does not advertise the requested
+    /// block range, because no connected peer advertised it.
+    pub(crate) misrouted_bodies_requests: Counter,
+}
+
 /// Measures the duration of executing the given code block. The duration is added to the given
 /// accumulator value passed as a mutable reference.
 #[macro_export]
@@ -331,6 +351,27 @@ pub struct EthRequestHandlerMetrics {
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
 }
 
+/// Metrics for the `SnapRequestHandler`
+#[derive(Metrics)]
+#[metrics(scope = "network")]
+pub struct SnapRequestHandlerMetrics {
+    /// Number of `GetAccountRange` requests received
+    pub(crate) snap_account_range_requests_received_total: Counter,
+
+    /// Number of `GetStorageRanges` requests received
+    pub(crate) snap_storage_ranges_requests_received_total: Counter,
+
+    /// Number of `GetByteCodes` requests received
+    pub(crate) snap_byte_codes_requests_received_total: Counter,
+
+    /// Number of `GetTrieNodes` requests received
+    pub(crate) snap_trie_nodes_requests_received_total: Counter,
+
+    /// Duration in seconds of call to poll
+    /// [`SnapRequestHandler`](crate::snap::SnapRequestHandler).
+    pub(crate) acc_duration_poll_snap_req_handler: Gauge,
+}
+
 /// Eth67 announcement metrics, track entries by `TxType`
 #[derive(Metrics)]
 #[metrics(scope = "network.transaction_fetcher")]
@@ -2,7 +2,7 @@ use crate::{
     config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol,
     swarm::NetworkConnectionState, transactions::TransactionsHandle, FetchClient,
 };
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256};
 use enr::Enr;
 use futures::StreamExt;
 use parking_lot::Mutex;
@@ -13,12 +13,13 @@ use reth_eth_wire::{
     NewPooledTransactionHashes, SharedTransactions,
 };
 use reth_ethereum_forks::Head;
+use reth_net_banlist::IpFilter;
 use reth_network_api::{
     events::{NetworkPeersEvents, PeerEvent, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
-    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
-    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    Ban, BannedSender, BlockDownloaderProvider, DiscoveryEvent, NetRestrict, NetworkError,
+    NetworkEvent, NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo,
+    PeerReputation, PeerRequest, Peers, PeersInfo, SenderBans,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -26,11 +27,12 @@ use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
@@ -66,6 +68,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         discv5: Option<Discv5>,
         event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
         nat: Option<NatResolver>,
+        ip_filter: IpFilter,
     ) -> Self {
         let inner = NetworkInner {
             num_active_peers,
@@ -83,6 +86,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             discv5,
             event_sender,
             nat,
+            ip_filter,
         };
         Self { inner: Arc::new(inner) }
     }
@@ -356,6 +360,62 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    async fn ban_peer(
+        &self,
+        peer_id: PeerId,
+        duration: Option<Duration>,
+    ) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.ban_peer(peer_id, duration).await)
+    }
+
+    async fn unban_peer(&self, peer_id: PeerId) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.unban_peer(peer_id).await)
+    }
+
+    async fn ban_ip(&self, ip: IpAddr, duration: Option<Duration>) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.ban_ip(ip, duration).await)
+    }
+
+    async fn unban_ip(&self, ip: IpAddr) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.unban_ip(ip).await)
+    }
+
+    async fn bans(&self) -> Result<Vec<Ban>, NetworkError> {
+        Ok(self.inner.peers.bans().await)
+    }
+
+    async fn peer_reputations(&self) -> Result<Vec<PeerReputation>, NetworkError> {
+        Ok(self.inner.peers.reputations().await)
+    }
+
+    fn net_restrict(&self) -> NetRestrict {
+        self.inner.ip_filter.get()
+    }
+
+    fn set_net_restrict(&self, restrict: NetRestrict) {
+        self.inner.ip_filter.set(restrict);
+        self.send_message(NetworkHandleMessage::IpFilterUpdated);
+    }
+}
+
+impl<N: NetworkPrimitives> SenderBans for NetworkHandle<N> {
+    async fn banned_senders(&self) -> Result<Vec<BannedSender>, NetworkError> {
+        let Some(transactions) = self.transactions_handle().await else { return Ok(Vec::new()) };
+        Ok(transactions.banned_senders().await?)
+    }
+
+    async fn unban_sender(&self, sender: Address) -> Result<bool, NetworkError> {
+        let Some(transactions) = self.transactions_handle().await else { return Ok(false) };
+        Ok(transactions.unban_sender(sender).await?)
+    }
+
+    async fn clear_sender_bans(&self) -> Result<(), NetworkError> {
+        if let Some(transactions) = self.transactions_handle().await {
+            transactions.clear_sender_bans();
+        }
+        Ok(())
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -464,6 +524,8 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
     /// The NAT resolver
     nat: Option<NatResolver>,
+    /// Allowed and denied IP networks, shared with the peers manager and discovery services.
+    ip_filter: IpFilter,
 }
 
 /// Provides access to modify the network's additional protocol handlers.
@@ -548,4 +610,6 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     ConnectPeer(PeerId, PeerKind, PeerAddr),
     /// Message to update the node's advertised block range information.
     InternalBlockRangeUpdate(BlockRangeUpdate),
+    /// Notifies the manager that the allowed and denied IP networks were updated.
+    IpFilterUpdated,
 }
@@ -10,8 +10,11 @@ use futures::StreamExt;
 
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
-use reth_net_banlist::BanList;
-use reth_network_api::test_utils::{PeerCommand, PeersHandle};
+use reth_net_banlist::{is_global, BanList, IpFilter};
+use reth_network_api::{
+    test_utils::{PeerCommand, PeersHandle},
+    Ban, BanTarget, PeerReputation,
+};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     peers::{
@@ -69,6 +72,8 @@ pub struct PeersManager {
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
     ban_list: BanList,
+    /// Restricts the IP networks of peers we connect to or accept connections from.
+    ip_filter: IpFilter,
     /// Tracks currently backed off peers.
     backed_off_peers: HashMap<PeerId, std::time::Instant>,
     /// Interval at which to check for peers to unban and release from the backoff map.
@@ -99,6 +104,7 @@ impl PeersManager {
             connection_info,
             reputation_weights,
             ban_list,
+            ip_filter,
             ban_duration,
             backoff_durations,
             trusted_nodes,
@@ -151,6 +157,7 @@ impl PeersManager {
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
             ban_list,
+            ip_filter,
             backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
@@ -245,6 +252,10 @@ impl PeersManager {
             return Err(InboundConnectionError::IpBanned)
         }
 
+        if !self.ip_filter.is_allowed(&addr) {
+            return Err(InboundConnectionError::IpRestricted)
+        }
+
         // check if we even have slots for a new incoming connection
         if !self.connection_info.has_in_capacity() {
             if self.trusted_peer_ids.is_empty() {
@@ -393,6 +404,117 @@ impl PeersManager {
         self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
     }
 
+    /// Bans the peer for the given duration, or indefinitely, on request, e.g. via the admin API.
+    ///
+    /// Disconnects an active session with the peer. Returns `true` if the peer was not banned
+    /// before, otherwise the ban is replaced.
+    fn ban_peer_for(&mut self, peer_id: PeerId, duration: Option<Duration>) -> bool {
+        let was_banned = self.ban_list.is_banned_peer(&peer_id) ||
+            self.peers.get(&peer_id).is_some_and(|peer| peer.is_banned());
+
+        // a duration that overflows bans the peer indefinitely
+        let until = duration.and_then(|duration| std::time::Instant::now().checked_add(duration));
+        self.ban_list.ban_peer_with(peer_id, until);
+        self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            if peer.state.is_connected() {
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+        !was_banned
+    }
+
+    /// Lifts the ban of the peer, and resets its reputation if it is below the ban threshold.
+    ///
+    /// Returns `true` if the peer was banned.
+    fn unban_peer_manually(&mut self, peer_id: PeerId) -> bool {
+        let mut was_banned = self.ban_list.unban_peer(&peer_id);
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            if peer.is_banned() {
+                peer.unban();
+                was_banned = true;
+            }
+        }
+        if was_banned {
+            self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
+        }
+        was_banned
+    }
+
+    /// Bans the ip address for the given duration, or indefinitely, on request, e.g. via the
+    /// admin API.
+    ///
+    /// Disconnects all active sessions with peers at this address. Returns `false` if the address
+    /// is not globally routable and therefore can't be banned.
+    fn ban_ip_for(&mut self, ip: IpAddr, duration: Option<Duration>) -> bool {
+        if !is_global(&ip) {
+            return false
+        }
+        // a duration that overflows bans the ip indefinitely
+        let until = duration.and_then(|duration| std::time::Instant::now().checked_add(duration));
+        self.ban_list.ban_ip_with(ip, until);
+
+        for (peer_id, peer) in &mut self.peers {
+            if peer.state.is_connected() && peer.addr.tcp().ip() == ip {
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+        true
+    }
+
+    /// Returns all entries of the ban list.
+    fn bans(&self) -> Vec<Ban> {
+        let peers = self
+            .ban_list
+            .banned_peers()
+            .map(|(peer_id, banned_until)| Ban { target: BanTarget::Peer(peer_id), banned_until });
+        let ips = self
+            .ban_list
+            .banned_ips()
+            .map(|(ip, banned_until)| Ban { target: BanTarget::Ip(ip), banned_until });
+        peers.chain(ips).collect()
+    }
+
+    /// Returns the reputation of all peers.
+    fn reputations(&self) -> Vec<PeerReputation> {
+        self.peers
+            .iter()
+            .map(|(peer_id, peer)| PeerReputation {
+                peer_id: *peer_id,
+                addr: peer.addr.tcp(),
+                kind: peer.kind,
+                reputation: peer.reputation,
+                is_banned: peer.is_banned(),
+                recent_changes: peer.recent_reputation_changes.iter().copied().collect(),
+            })
+            .collect()
+    }
+
+    /// Invoked when the allowed and denied IP networks were updated.
+    ///
+    /// Disconnects all connected peers whose address is no longer allowed.
+    pub(crate) fn on_ip_filter_updated(&mut self) {
+        for (peer_id, peer) in &mut self.peers {
+            if peer.state.is_connected() && !self.ip_filter.is_allowed(&peer.addr.tcp().ip()) {
+                trace!(target: "net::peers", ?peer_id, addr=?peer.addr.tcp(), "disconnecting peer outside allowed ip networks");
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+    }
+
     /// Bans the IP temporarily with the configured ban timeout
     fn ban_ip(&mut self, ip: IpAddr) {
         self.ban_list.ban_ip_until(ip, std::time::Instant::now() + self.ban_duration);
@@ -457,6 +579,7 @@ impl PeersManager {
         let outcome = if let Some(peer) = self.peers.get_mut(peer_id) {
             // First check if we should reset the reputation
             if rep.is_reset() {
+                peer.record_reputation_change(rep);
                 peer.reset_reputation()
             } else {
                 let mut reputation_change = self.reputation_weights.change(rep).as_i32();
@@ -478,6 +601,7 @@ impl PeersManager {
                         reputation_change = MAX_TRUSTED_PEER_REPUTATION_CHANGE;
                     }
                 }
+                peer.record_reputation_change(rep);
                 peer.apply_reputation(reputation_change)
             }
         } else {
@@ -741,6 +865,11 @@ impl PeersManager {
             return
         }
 
+        if !self.ip_filter.is_allowed(&addr.tcp().ip()) {
+            trace!(target: "net::peers", ?peer_id, addr=?addr.tcp(), "ignoring node outside allowed ip networks");
+            return
+        }
+
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let peer = entry.get_mut();
@@ -822,6 +951,11 @@ impl PeersManager {
             return
         }
 
+        if !self.ip_filter.is_allowed(&addr.tcp().ip()) {
+            trace!(target: "net::peers", ?peer_id, addr=?addr.tcp(), "ignoring node outside allowed ip networks");
+            return
+        }
+
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let peer = entry.get_mut();
@@ -881,7 +1015,8 @@ impl PeersManager {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
                 peer.state.is_unconnected() &&
-                (!self.trusted_nodes_only || peer.is_trusted())
+                (!self.trusted_nodes_only || peer.is_trusted()) &&
+                self.ip_filter.is_allowed(&peer.addr.tcp().ip())
         });
 
         // keep track of the best peer, if there's one
@@ -994,6 +1129,24 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::BanPeer(peer_id, duration, tx) => {
+                        let _ = tx.send(self.ban_peer_for(peer_id, duration));
+                    }
+                    PeerCommand::UnbanPeer(peer_id, tx) => {
+                        let _ = tx.send(self.unban_peer_manually(peer_id));
+                    }
+                    PeerCommand::BanIp(ip, duration, tx) => {
+                        let _ = tx.send(self.ban_ip_for(ip, duration));
+                    }
+                    PeerCommand::UnbanIp(ip, tx) => {
+                        let _ = tx.send(self.ban_list.unban_ip(&ip));
+                    }
+                    PeerCommand::GetBans(tx) => {
+                        let _ = tx.send(self.bans());
+                    }
+                    PeerCommand::GetReputations(tx) => {
+                        let _ = tx.send(self.reputations());
+                    }
                 }
             }
 
@@ -1184,6 +1337,8 @@ pub enum PeerAction {
 pub enum InboundConnectionError {
     /// The remote's ip address is banned
     IpBanned,
+    /// The remote's ip address is outside the allowed ip networks
+    IpRestricted,
     /// No capacity for new inbound connections
     ExceedsCapacity,
 }
@@ -1201,11 +1356,12 @@ mod tests {
         errors::{EthHandshakeError, EthStreamError, P2PHandshakeError, P2PStreamError},
         DisconnectReason,
     };
-    use reth_net_banlist::BanList;
-    use reth_network_api::Direction;
+    use reth_net_banlist::{BanList, IpFilter, NetRestrict};
+    use reth_network_api::{Ban, BanTarget, Direction};
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::{DEFAULT_REPUTATION, MAX_TRACKED_REPUTATION_CHANGES},
+        BackoffKind, Peer, ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1358,6 +1514,104 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_ban_peer_for_duration() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_ok());
+        peers.on_incoming_session_established(peer, socket_addr);
+        peers.queued_actions.clear();
+
+        assert!(peers.ban_peer_for(peer, Some(Duration::from_secs(60))));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::BanPeer { peer_id }) if peer_id == peer
+        ));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::Disconnect { peer_id, .. }) if peer_id == peer
+        ));
+
+        let bans = peers.bans();
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].target, BanTarget::Peer(peer));
+        assert!(bans[0].banned_until.is_some());
+
+        // banning again replaces the ban, a duration that overflows bans indefinitely
+        assert!(!peers.ban_peer_for(peer, Some(Duration::MAX)));
+        let bans = peers.bans();
+        assert_eq!(bans.len(), 1);
+        assert!(bans[0].banned_until.is_none());
+        peers.queued_actions.clear();
+
+        assert!(peers.unban_peer_manually(peer));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::UnBanPeer { peer_id }) if peer_id == peer
+        ));
+        assert!(peers.bans().is_empty());
+        assert!(!peers.unban_peer_manually(peer));
+    }
+
+    #[tokio::test]
+    async fn test_ban_ip_for_duration() {
+        let peer = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1));
+        let socket_addr = SocketAddr::new(ip, 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        assert!(peers.on_incoming_pending_session(ip).is_ok());
+        peers.on_incoming_session_established(peer, socket_addr);
+        peers.queued_actions.clear();
+
+        // non-global addresses can't be banned
+        assert!(!peers.ban_ip_for(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), None));
+        assert!(peers.queued_actions.is_empty());
+
+        assert!(peers.ban_ip_for(ip, None));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::Disconnect { peer_id, .. }) if peer_id == peer
+        ));
+        assert_eq!(peers.bans(), vec![Ban { target: BanTarget::Ip(ip), banned_until: None }]);
+        assert_eq!(peers.on_incoming_pending_session(ip), Err(InboundConnectionError::IpBanned));
+
+        assert!(peers.ban_list.unban_ip(&ip));
+        assert!(peers.bans().is_empty());
+
+        // a duration that overflows bans indefinitely
+        assert!(peers.ban_ip_for(ip, Some(Duration::MAX)));
+        assert_eq!(peers.bans(), vec![Ban { target: BanTarget::Ip(ip), banned_until: None }]);
+    }
+
+    #[tokio::test]
+    async fn test_recent_reputation_changes() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+
+        peers.apply_reputation_change(&peer, ReputationChangeKind::BadMessage);
+        peers.apply_reputation_change(&peer, ReputationChangeKind::Timeout);
+
+        let reputations = peers.reputations();
+        assert_eq!(reputations.len(), 1);
+        assert_eq!(reputations[0].peer_id, peer);
+        assert_eq!(reputations[0].reputation, peers.peers[&peer].reputation);
+        assert_eq!(
+            reputations[0].recent_changes,
+            vec![ReputationChangeKind::BadMessage, ReputationChangeKind::Timeout]
+        );
+
+        // only the most recent changes are tracked
+        for _ in 0..MAX_TRACKED_REPUTATION_CHANGES {
+            peers.apply_reputation_change(&peer, ReputationChangeKind::Other(1));
+        }
+        let recent_changes = &peers.reputations()[0].recent_changes;
+        assert_eq!(recent_changes.len(), MAX_TRACKED_REPUTATION_CHANGES);
+        assert!(recent_changes.iter().all(|kind| *kind == ReputationChangeKind::Other(1)));
+    }
+
     #[tokio::test]
     async fn test_backoff_on_busy() {
         let peer = PeerId::random();
@@ -2290,6 +2544,70 @@ mod tests {
         assert_eq!(peer_id, given_peer_id)
     }
 
+    #[tokio::test]
+    async fn test_on_pending_ip_restricted() {
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        let ip_filter = IpFilter::new(NetRestrict::new(["10.0.0.0/8".parse().unwrap()], []));
+        let config = PeersConfig::test().with_ip_filter(ip_filter);
+        let mut peer_manager = PeersManager::new(config);
+
+        assert_eq!(
+            peer_manager.on_incoming_pending_session(ip),
+            Err(InboundConnectionError::IpRestricted)
+        );
+        assert_eq!(peer_manager.connection_info.num_pending_in, 0);
+        assert!(peer_manager
+            .on_incoming_pending_session(Ipv4Addr::new(10, 0, 0, 1).into())
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_peer_ip_restricted() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let ip_filter = IpFilter::new(NetRestrict::new([], ["127.0.0.0/8".parse().unwrap()]));
+        let config = PeersConfig::test().with_ip_filter(ip_filter);
+        let mut peer_manager = PeersManager::new(config);
+
+        peer_manager.add_peer(PeerId::random(), PeerAddr::from_tcp(socket_addr), None);
+        peer_manager.add_and_connect(PeerId::random(), PeerAddr::from_tcp(socket_addr), None);
+
+        assert!(peer_manager.peers.is_empty());
+        assert!(peer_manager.queued_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ip_filter_updated_disconnects_peers() {
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        let socket_addr = SocketAddr::new(ip, 8008);
+        let given_peer_id = PeerId::random();
+        let ip_filter = IpFilter::default();
+        let config = PeersConfig::test().with_ip_filter(ip_filter.clone());
+        let mut peer_manager = PeersManager::new(config);
+
+        assert!(peer_manager.on_incoming_pending_session(ip).is_ok());
+        peer_manager.on_incoming_session_established(given_peer_id, socket_addr);
+        let Some(PeerAction::PeerAdded(_)) = peer_manager.queued_actions.pop_front() else {
+            panic!()
+        };
+
+        // updating the filter without affecting the peer is a no-op
+        ip_filter.set(NetRestrict::new([], ["10.0.0.0/8".parse().unwrap()]));
+        peer_manager.on_ip_filter_updated();
+        assert!(peer_manager.queued_actions.is_empty());
+
+        ip_filter.set(NetRestrict::new([], ["127.0.0.0/8".parse().unwrap()]));
+        peer_manager.on_ip_filter_updated();
+        let Some(PeerAction::Disconnect { peer_id, .. }) = peer_manager.queued_actions.pop_front()
+        else {
+            panic!()
+        };
+        assert_eq!(peer_id, given_peer_id);
+        assert_eq!(
+            peer_manager.peers.get(&given_peer_id).unwrap().state,
+            PeerConnectionState::DisconnectingIn
+        );
+    }
+
     #[test]
     fn test_connection_limits() {
         let mut info = ConnectionInfo::default();
@@ -651,13 +651,16 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         }
     }
 
-    pub(crate) const fn update_advertised_block_range(
-        &mut self,
-        block_range_update: BlockRangeUpdate,
-    ) {
+    /// Updates the block range advertised in our status and announces it to active sessions.
+    pub(crate) fn update_advertised_block_range(&mut self, block_range_update: BlockRangeUpdate) {
         self.status.earliest_block = Some(block_range_update.earliest);
         self.status.latest_block = Some(block_range_update.latest);
         self.status.blockhash = block_range_update.latest_hash;
+
+        // announce the new range to all active sessions, non eth/69 sessions drop it
+        for peer_id in self.active_sessions.keys() {
+            self.send_message(peer_id, PeerMessage::BlockRangeUpdated(block_range_update.clone()));
+        }
     }
 }
 
@@ -0,0 +1,156 @@
+//! A client implementation that can interact with connected `snap` peers.
+
+use crate::{snap::SnapPeerRequest, FlattenedResponse};
+use futures::future::{self, Either};
+use parking_lot::RwLock;
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_api::{test_utils::PeersHandle, ReputationChangeKind};
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+    snap::client::SnapClient,
+};
+use reth_network_peers::PeerId;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// The shared registry of peers with an active `snap` connection.
+#[derive(Debug, Clone, Default)]
+pub struct SnapPeers {
+    /// Senders for requests to the connection of each peer.
+    peers: Arc<RwLock<HashMap<PeerId, UnboundedSender<SnapPeerRequest>>>>,
+    /// Counter used to rotate requests over all peers.
+    next_peer: Arc<AtomicUsize>,
+}
+
+impl SnapPeers {
+    /// Registers the connection of a peer.
+    pub fn insert(&self, peer_id: PeerId, to_connection: UnboundedSender<SnapPeerRequest>) {
+        self.peers.write().insert(peer_id, to_connection);
+    }
+
+    /// Removes the connection of a peer if it has been closed.
+    ///
+    /// An open connection belongs to a newer session with the same peer and is kept.
+    pub fn remove_closed(&self, peer_id: &PeerId) {
+        let mut peers = self.peers.write();
+        if peers.get(peer_id).is_some_and(|to_connection| to_connection.is_closed()) {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Returns the number of connected `snap` peers.
+    pub fn len(&self) -> usize {
+        self.peers.read().len()
+    }
+
+    /// Returns `true` if there are no connected `snap` peers.
+    pub fn is_empty(&self) -> bool {
+        self.peers.read().is_empty()
+    }
+
+    /// Sends the request to the next peer in rotation.
+    ///
+    /// Returns the request back if there's no connected peer.
+    fn send(&self, request: SnapPeerRequest) -> Result<(), SnapPeerRequest> {
+        let peers = self.peers.read();
+        if peers.is_empty() {
+            return Err(request)
+        }
+        let idx = self.next_peer.fetch_add(1, Ordering::Relaxed) % peers.len();
+        let (_, to_connection) = peers.iter().nth(idx).expect("index is in bounds");
+        to_connection.send(request).map_err(|err| err.0)
+    }
+}
+
+/// Front-end API for fetching state from connected `snap` peers.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    /// Registry of connected `snap` peers.
+    peers: SnapPeers,
+    /// The handle to the peers.
+    peers_handle: PeersHandle,
+}
+
+impl SnapFetchClient {
+    /// Creates a new client that routes requests to the given peers.
+    pub const fn new(peers: SnapPeers, peers_handle: PeersHandle) -> Self {
+        Self { peers, peers_handle }
+    }
+
+    /// Sends the request created by `request` to a connected peer.
+    fn send_request<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<PeerRequestResult<T>>) -> SnapPeerRequest,
+    ) -> SnapClientFuture<PeerRequestResult<T>> {
+        let (response, rx) = oneshot::channel();
+        if self.peers.send(request(response)).is_ok() {
+            Either::Left(FlattenedResponse::from(rx))
+        } else {
+            Either::Right(future::err(RequestError::ConnectionDropped))
+        }
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+// The `Output` futures of the [SnapClient] impl of [SnapFetchClient] that either return a response
+// or an error.
+type SnapClientFuture<T> = Either<FlattenedResponse<T>, future::Ready<T>>;
+
+impl SnapClient for SnapFetchClient {
+    type Output = SnapClientFuture<PeerRequestResult<AccountRangeMessage>>;
+    type StorageRangesOutput = SnapClientFuture<PeerRequestResult<StorageRangesMessage>>;
+    type ByteCodesOutput = SnapClientFuture<PeerRequestResult<ByteCodesMessage>>;
+    type TrieNodesOutput = SnapClientFuture<PeerRequestResult<TrieNodesMessage>>;
+
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(|response| SnapPeerRequest::GetAccountRange { request, response })
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::StorageRangesOutput {
+        self.send_request(|response| SnapPeerRequest::GetStorageRanges { request, response })
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::ByteCodesOutput {
+        self.send_request(|response| SnapPeerRequest::GetByteCodes { request, response })
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::TrieNodesOutput {
+        self.send_request(|response| SnapPeerRequest::GetTrieNodes { request, response })
+    }
+}
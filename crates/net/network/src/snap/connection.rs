@@ -0,0 +1,302 @@
+//! The `snap` protocol connection with a single peer.
+
+use crate::snap::{client::SnapPeers, IncomingSnapRequest};
+use alloy_primitives::bytes::BytesMut;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::multiplex::ProtocolConnection;
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, SnapProtocolMessage, StorageRangesMessage,
+    TrieNodesMessage,
+};
+use reth_network_api::{test_utils::PeersHandle, ReputationChangeKind};
+use reth_network_p2p::error::{PeerRequestResult, RequestResult};
+use reth_network_peers::{PeerId, WithPeerId};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::trace;
+
+/// A request sent to a `snap` peer.
+///
+/// The request id of the message is assigned by the connection.
+#[derive(Debug)]
+pub enum SnapPeerRequest {
+    /// Request a range of accounts.
+    GetAccountRange {
+        /// The account range request.
+        request: GetAccountRangeMessage,
+        /// The sender for the response.
+        response: oneshot::Sender<PeerRequestResult<AccountRangeMessage>>,
+    },
+    /// Request storage ranges.
+    GetStorageRanges {
+        /// The storage ranges request.
+        request: GetStorageRangesMessage,
+        /// The sender for the response.
+        response: oneshot::Sender<PeerRequestResult<StorageRangesMessage>>,
+    },
+    /// Request contract bytecodes.
+    GetByteCodes {
+        /// The bytecodes request.
+        request: GetByteCodesMessage,
+        /// The sender for the response.
+        response: oneshot::Sender<PeerRequestResult<ByteCodesMessage>>,
+    },
+    /// Request trie nodes.
+    GetTrieNodes {
+        /// The trie nodes request.
+        request: GetTrieNodesMessage,
+        /// The sender for the response.
+        response: oneshot::Sender<PeerRequestResult<TrieNodesMessage>>,
+    },
+}
+
+impl SnapPeerRequest {
+    /// Returns the protocol message for this request with the given request id.
+    fn to_message(&self, request_id: u64) -> SnapProtocolMessage {
+        match self {
+            Self::GetAccountRange { request, .. } => {
+                SnapProtocolMessage::GetAccountRange(GetAccountRangeMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+            Self::GetStorageRanges { request, .. } => {
+                SnapProtocolMessage::GetStorageRanges(GetStorageRangesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+            Self::GetByteCodes { request, .. } => {
+                SnapProtocolMessage::GetByteCodes(GetByteCodesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+            Self::GetTrieNodes { request, .. } => {
+                SnapProtocolMessage::GetTrieNodes(GetTrieNodesMessage {
+                    request_id,
+                    ..request.clone()
+                })
+            }
+        }
+    }
+
+    /// Sends the response to the requester.
+    ///
+    /// Returns `false` if the response does not match the request.
+    fn send_response(self, peer_id: PeerId, message: SnapProtocolMessage) -> bool {
+        match (self, message) {
+            (Self::GetAccountRange { response, .. }, SnapProtocolMessage::AccountRange(msg)) => {
+                let _ = response.send(Ok(WithPeerId::new(peer_id, msg)));
+            }
+            (Self::GetStorageRanges { response, .. }, SnapProtocolMessage::StorageRanges(msg)) => {
+                let _ = response.send(Ok(WithPeerId::new(peer_id, msg)));
+            }
+            (Self::GetByteCodes { response, .. }, SnapProtocolMessage::ByteCodes(msg)) => {
+                let _ = response.send(Ok(WithPeerId::new(peer_id, msg)));
+            }
+            (Self::GetTrieNodes { response, .. }, SnapProtocolMessage::TrieNodes(msg)) => {
+                let _ = response.send(Ok(WithPeerId::new(peer_id, msg)));
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// The connection handler for the `snap` protocol with a single peer.
+///
+/// Incoming requests are delegated to the [`SnapRequestHandler`](crate::snap::SnapRequestHandler)
+/// and outgoing requests are received from the [`SnapFetchClient`](crate::snap::SnapFetchClient).
+#[derive(Debug)]
+#[must_use = "Connection does nothing unless polled."]
+pub struct SnapConnection {
+    /// Peer ID.
+    peer_id: PeerId,
+    /// Protocol connection.
+    conn: ProtocolConnection,
+    /// Stream of outgoing requests.
+    commands: UnboundedReceiverStream<SnapPeerRequest>,
+    /// Sender half for incoming requests to the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Registry of connected `snap` peers.
+    peers: SnapPeers,
+    /// Peers handle.
+    peers_handle: PeersHandle,
+    /// Incremental counter for request ids.
+    next_id: u64,
+    /// Collection of inflight requests.
+    inflight_requests: HashMap<u64, SnapPeerRequest>,
+    /// Responses to incoming requests that are being served.
+    pending_responses: FuturesUnordered<PendingResponseFut>,
+    /// Flag indicating whether this stream has previously been terminated.
+    terminated: bool,
+}
+
+impl SnapConnection {
+    /// Create new connection.
+    pub fn new(
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+        commands: UnboundedReceiverStream<SnapPeerRequest>,
+        to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+        peers: SnapPeers,
+        peers_handle: PeersHandle,
+    ) -> Self {
+        Self {
+            peer_id,
+            conn,
+            commands,
+            to_request_handler,
+            peers,
+            peers_handle,
+            next_id: 0,
+            inflight_requests: HashMap::default(),
+            pending_responses: FuturesUnordered::new(),
+            terminated: false,
+        }
+    }
+
+    /// Returns the next request id
+    const fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Report bad message from current peer.
+    fn report_bad_message(&self) {
+        self.peers_handle.reputation_change(self.peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn on_command(&mut self, command: SnapPeerRequest) -> SnapProtocolMessage {
+        let request_id = self.next_id();
+        let message = command.to_message(request_id);
+        self.inflight_requests.insert(request_id, command);
+        message
+    }
+
+    /// Delegates the request to the request handler and queues the response.
+    fn delegate_request<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<RequestResult<T>>) -> IncomingSnapRequest,
+        into_message: fn(T) -> SnapProtocolMessage,
+    ) where
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        if self.to_request_handler.try_send(request(tx)).is_err() {
+            trace!(target: "net::snap", peer_id = %self.peer_id, "request handler is busy, dropping request");
+            return
+        }
+        self.pending_responses
+            .push(Box::pin(async move { rx.await.ok().and_then(Result::ok).map(into_message) }));
+    }
+
+    fn on_snap_message(&mut self, message: SnapProtocolMessage) {
+        let peer_id = self.peer_id;
+        match message {
+            SnapProtocolMessage::GetAccountRange(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                SnapProtocolMessage::AccountRange,
+            ),
+            SnapProtocolMessage::GetStorageRanges(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                SnapProtocolMessage::StorageRanges,
+            ),
+            SnapProtocolMessage::GetByteCodes(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                SnapProtocolMessage::ByteCodes,
+            ),
+            SnapProtocolMessage::GetTrieNodes(request) => self.delegate_request(
+                |response| IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                SnapProtocolMessage::TrieNodes,
+            ),
+            response => {
+                let request_id = match &response {
+                    SnapProtocolMessage::AccountRange(msg) => msg.request_id,
+                    SnapProtocolMessage::StorageRanges(msg) => msg.request_id,
+                    SnapProtocolMessage::ByteCodes(msg) => msg.request_id,
+                    SnapProtocolMessage::TrieNodes(msg) => msg.request_id,
+                    _ => unreachable!("requests are handled above"),
+                };
+                let delivered = self
+                    .inflight_requests
+                    .remove(&request_id)
+                    .is_some_and(|request| request.send_response(peer_id, response));
+                if !delivered {
+                    self.report_bad_message();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        self.commands.close();
+        self.peers.remove_closed(&self.peer_id);
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.terminated {
+            return Poll::Ready(None)
+        }
+
+        'conn: loop {
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                let message = this.on_command(cmd);
+                trace!(target: "net::snap", peer_id = %this.peer_id, id = ?message.message_id(), "Sending snap request");
+                return Poll::Ready(Some(message.encode().as_ref().into()))
+            }
+
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                // responses that could not be served are not answered
+                if let Some(response) = response {
+                    return Poll::Ready(Some(response.encode().as_ref().into()))
+                }
+                continue
+            }
+
+            if let Poll::Ready(maybe_msg) = this.conn.poll_next_unpin(cx) {
+                let Some(next) = maybe_msg else { break 'conn };
+                let Some((&id, mut payload)) = next.split_first() else {
+                    this.report_bad_message();
+                    continue
+                };
+                match SnapProtocolMessage::decode(id, &mut payload) {
+                    Ok(message) => {
+                        trace!(target: "net::snap", peer_id = %this.peer_id, id = ?message.message_id(), "Processing snap message");
+                        this.on_snap_message(message);
+                    }
+                    Err(error) => {
+                        trace!(target: "net::snap", peer_id = %this.peer_id, %error, "Error decoding snap message");
+                        this.report_bad_message();
+                    }
+                }
+                continue
+            }
+
+            return Poll::Pending
+        }
+
+        // Terminating the connection.
+        this.terminated = true;
+        Poll::Ready(None)
+    }
+}
+
+type PendingResponseFut = Pin<Box<dyn Future<Output = Option<SnapProtocolMessage>> + Send>>;
@@ -0,0 +1,94 @@
+//! The `snap` protocol handler.
+
+use crate::{
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+    snap::{snap_protocol, IncomingSnapRequest, SnapConnection, SnapFetchClient, SnapPeers},
+};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network_api::{test_utils::PeersHandle, Direction, PeerId};
+use std::net::SocketAddr;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// The protocol handler for `snap/1`.
+///
+/// Every peer that supports `snap` is registered in the shared [`SnapPeers`] while connected, so
+/// that requests can be routed to it through the [`SnapFetchClient`].
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Peers handle.
+    peers_handle: PeersHandle,
+    /// Sender half for incoming requests to the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Registry of connected `snap` peers.
+    peers: SnapPeers,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new protocol handler that delegates incoming requests to the given channel.
+    pub fn new(
+        peers_handle: PeersHandle,
+        to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    ) -> Self {
+        Self { peers_handle, to_request_handler, peers: SnapPeers::default() }
+    }
+
+    /// Returns a client that sends requests to the connected `snap` peers.
+    pub fn client(&self) -> SnapFetchClient {
+        SnapFetchClient::new(self.peers.clone(), self.peers_handle.clone())
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = Self;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.clone())
+    }
+}
+
+impl ConnectionHandler for SnapProtocolHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        snap_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.peers.insert(peer_id, tx);
+
+        SnapConnection::new(
+            peer_id,
+            conn,
+            UnboundedReceiverStream::from(rx),
+            self.to_request_handler,
+            self.peers,
+            self.peers_handle,
+        )
+    }
+}
@@ -0,0 +1,36 @@
+//! Support for the `snap` protocol.
+//!
+//! The `snap` protocol runs side-by-side with `eth` as an additional `RLPx` subprotocol and
+//! allows peers to exchange ranges of the state together with the proofs required to verify
+//! them.
+//!
+//! - [`SnapProtocolHandler`] negotiates the protocol and drives the per peer connection.
+//! - [`SnapRequestHandler`] serves incoming requests from our database.
+//! - [`SnapFetchClient`] sends requests to connected `snap` peers.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+mod client;
+mod connection;
+mod handler;
+mod requests;
+
+pub use client::{SnapFetchClient, SnapPeers};
+pub use connection::{SnapConnection, SnapPeerRequest};
+pub use handler::SnapProtocolHandler;
+pub use requests::{
+    IncomingSnapRequest, SnapRequestHandler, MAX_ACCOUNTS_SERVE, MAX_CODES_SERVE,
+    MAX_STORAGE_SLOTS_SERVE, MAX_TRIE_NODES_SERVE,
+};
+
+use reth_eth_wire::{protocol::Protocol, Capability};
+
+/// Returns the capability for `snap/1`.
+pub const fn snap_capability() -> Capability {
+    Capability::new_static("snap", 1)
+}
+
+/// Returns the protocol for `snap/1`, which uses 8 message ids.
+pub const fn snap_protocol() -> Protocol {
+    Protocol::new(snap_capability(), 8)
+}
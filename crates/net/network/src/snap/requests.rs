@@ -0,0 +1,460 @@
+//! State range management for the `snap` protocol.
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, eth_requests::SOFT_RESPONSE_LIMIT,
+    metered_poll_nested_stream_with_budget, metrics::SnapRequestHandlerMetrics,
+};
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::Encodable;
+use futures::StreamExt;
+use reth_eth_wire_types::snap::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, SlimAccount, StorageData,
+    StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_p2p::error::RequestResult;
+use reth_network_peers::PeerId;
+use reth_storage_api::{
+    errors::provider::ProviderResult, StateProviderFactory, StateRangeProviderFactory,
+};
+use reth_trie_common::Nibbles;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/master/eth/protocols/snap/handler.go>
+
+/// Maximum number of accounts to serve in a single account range response.
+///
+/// Used to limit lookups.
+pub const MAX_ACCOUNTS_SERVE: usize = 8 * 1024;
+
+/// Maximum number of storage slots to serve in a single storage ranges response.
+///
+/// Used to limit lookups.
+pub const MAX_STORAGE_SLOTS_SERVE: usize = 16 * 1024;
+
+/// Maximum number of contract codes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_CODES_SERVE: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODES_SERVE: usize = 1024;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// Ranges are served from the state of the most recent blocks on disk, every request is served
+/// from a single consistent view of the database. Requests for any other state root are answered
+/// with empty responses, as mandated by the protocol.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can interact with the state.
+    client: C,
+    /// Incoming requests from the [`SnapConnection`](crate::snap::SnapConnection)s.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: StateRangeProviderFactory + StateProviderFactory,
+{
+    /// Returns the account range response for the given request.
+    fn get_account_range_response(
+        &self,
+        request: &GetAccountRangeMessage,
+    ) -> ProviderResult<AccountRangeMessage> {
+        let GetAccountRangeMessage {
+            request_id,
+            root_hash,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = *request;
+        let mut response =
+            AccountRangeMessage { request_id, accounts: Vec::new(), proof: Vec::new() };
+
+        if starting_hash > limit_hash {
+            return Ok(response)
+        }
+        let Some(state) = self.client.state_range_by_root(root_hash)? else { return Ok(response) };
+
+        let soft_limit = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+        let mut total_bytes = 0;
+        for (hash, account) in
+            state.hashed_account_range(starting_hash, limit_hash, MAX_ACCOUNTS_SERVE)?
+        {
+            let body = SlimAccount {
+                nonce: account.nonce,
+                balance: account.balance,
+                storage_root: account.storage_root,
+                code_hash: account.code_hash,
+            };
+            let data = AccountData { hash, body: alloy_rlp::encode(body).into() };
+            total_bytes += data.length();
+            response.accounts.push(data);
+
+            if total_bytes > soft_limit {
+                break
+            }
+        }
+
+        // prove the origin and the last returned account, which also covers an empty range
+        let mut keys = vec![starting_hash];
+        keys.extend(response.accounts.last().map(|account| account.hash));
+        response.proof = state.range_proof(None, &keys)?;
+
+        Ok(response)
+    }
+
+    /// Returns the storage ranges response for the given request.
+    ///
+    /// The origin and limit only apply to the first requested account, all following accounts are
+    /// served from the start of their storage. A proof is only attached if the last served
+    /// storage range is incomplete.
+    fn get_storage_ranges_response(
+        &self,
+        request: &GetStorageRangesMessage,
+    ) -> ProviderResult<StorageRangesMessage> {
+        let GetStorageRangesMessage {
+            request_id,
+            root_hash,
+            ref account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = *request;
+        let mut response =
+            StorageRangesMessage { request_id, slots: Vec::new(), proof: Vec::new() };
+
+        let Some(state) = self.client.state_range_by_root(root_hash)? else { return Ok(response) };
+
+        let soft_limit = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+        let mut total_bytes = 0;
+        let mut total_slots = 0;
+
+        for (idx, hashed_address) in account_hashes.iter().copied().enumerate() {
+            if total_bytes > soft_limit || total_slots >= MAX_STORAGE_SLOTS_SERVE {
+                break
+            }
+
+            let (origin, limit) = if idx == 0 {
+                // an unset limit is treated as the end of the key space
+                (
+                    starting_hash,
+                    if limit_hash.is_zero() { B256::repeat_byte(0xff) } else { limit_hash },
+                )
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let max = MAX_STORAGE_SLOTS_SERVE - total_slots;
+            let storage = state.hashed_storage_range(hashed_address, origin, limit, max)?;
+            let mut slots = Vec::with_capacity(storage.len());
+            let mut aborted = storage.len() >= max;
+            for (hash, value) in storage {
+                let data = StorageData { hash, data: alloy_rlp::encode(value).into() };
+                total_bytes += data.length();
+                slots.push(data);
+
+                if total_bytes > soft_limit {
+                    aborted = true;
+                    break
+                }
+            }
+            total_slots += slots.len();
+
+            if !origin.is_zero() || aborted {
+                // the last range is partial and requires a proof of both edges
+                let mut keys = vec![origin];
+                keys.extend(slots.last().map(|slot| slot.hash));
+                response.proof = state.range_proof(Some(hashed_address), &keys)?;
+                response.slots.push(slots);
+                break
+            }
+            response.slots.push(slots);
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the bytecodes response for the given request.
+    fn get_byte_codes_response(
+        &self,
+        request: &GetByteCodesMessage,
+    ) -> ProviderResult<ByteCodesMessage> {
+        let mut response = ByteCodesMessage { request_id: request.request_id, codes: Vec::new() };
+
+        let soft_limit = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+        let mut total_bytes = 0;
+        let state = self.client.latest()?;
+        for hash in request.hashes.iter().take(MAX_CODES_SERVE) {
+            // unknown codes are skipped, the requester can match the codes by hash
+            let Some(code) = state.bytecode_by_hash(hash)? else { continue };
+            let code = code.original_bytes();
+            total_bytes += code.len();
+            response.codes.push(code);
+
+            if total_bytes > soft_limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the trie nodes response for the given request.
+    ///
+    /// Serving stops at the first node that is not found, since the response must be a prefix of
+    /// the requested nodes.
+    fn get_trie_nodes_response(
+        &self,
+        request: &GetTrieNodesMessage,
+    ) -> ProviderResult<TrieNodesMessage> {
+        let mut response = TrieNodesMessage { request_id: request.request_id, nodes: Vec::new() };
+
+        let Some(state) = self.client.state_range_by_root(request.root_hash)? else {
+            return Ok(response)
+        };
+
+        let soft_limit = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+        let mut total_bytes = 0;
+        'paths: for path in &request.paths {
+            let (hashed_address, paths) = if path.slot_paths.is_empty() {
+                (None, vec![decode_compact_path(&path.account_path)])
+            } else {
+                // storage nodes are requested by the full hashed address of the account
+                if path.account_path.len() != 32 {
+                    break
+                }
+                let paths = path.slot_paths.iter().map(decode_compact_path).collect();
+                (Some(B256::from_slice(&path.account_path)), paths)
+            };
+
+            for node in state.trie_nodes(hashed_address, &paths)? {
+                let Some(node) = node else { break 'paths };
+                total_bytes += node.len();
+                response.nodes.push(node);
+
+                if response.nodes.len() >= MAX_TRIE_NODES_SERVE || total_bytes > soft_limit {
+                    break 'paths
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn on_account_range_request(
+        &self,
+        peer_id: PeerId,
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let res = self.get_account_range_response(&request).unwrap_or_else(|error| {
+            trace!(target: "net::snap", %peer_id, ?request, %error, "failed to serve account range");
+            AccountRangeMessage { request_id: request.request_id, accounts: vec![], proof: vec![] }
+        });
+        let _ = response.send(Ok(res));
+    }
+
+    fn on_storage_ranges_request(
+        &self,
+        peer_id: PeerId,
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let res = self.get_storage_ranges_response(&request).unwrap_or_else(|error| {
+            trace!(target: "net::snap", %peer_id, ?request, %error, "failed to serve storage ranges");
+            StorageRangesMessage { request_id: request.request_id, slots: vec![], proof: vec![] }
+        });
+        let _ = response.send(Ok(res));
+    }
+
+    fn on_byte_codes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    ) {
+        self.metrics.snap_byte_codes_requests_received_total.increment(1);
+        let res = self.get_byte_codes_response(&request).unwrap_or_else(|error| {
+            trace!(target: "net::snap", %peer_id, ?request, %error, "failed to serve bytecodes");
+            ByteCodesMessage { request_id: request.request_id, codes: vec![] }
+        });
+        let _ = response.send(Ok(res));
+    }
+
+    fn on_trie_nodes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let res = self.get_trie_nodes_response(&request).unwrap_or_else(|error| {
+            trace!(target: "net::snap", %peer_id, ?request, %error, "failed to serve trie nodes");
+            TrieNodesMessage { request_id: request.request_id, nodes: vec![] }
+        });
+        let _ = response.send(Ok(res));
+    }
+}
+
+/// Decodes a trie path in the compact (hex-prefix) encoding into nibbles.
+fn decode_compact_path(compact: &Bytes) -> Nibbles {
+    let nibbles = Nibbles::unpack(compact);
+    match nibbles.first() {
+        // odd length paths are prefixed with a single flag nibble
+        Some(flag) if flag & 1 == 1 => nibbles.slice(1..),
+        // even length paths are prefixed with the flag nibble and a padding nibble
+        Some(_) => nibbles.slice(2..),
+        None => nibbles,
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: StateRangeProviderFactory + StateProviderFactory + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// All `snap` requests delegated by the [`SnapConnection`](crate::snap::SnapConnection)s.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that requested the account range.
+        peer_id: PeerId,
+        /// The specific account range requested.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the account range.
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    },
+    /// Request storage ranges from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that requested the storage ranges.
+        peer_id: PeerId,
+        /// The specific storage ranges requested.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage ranges.
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    },
+    /// Request contract bytecodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that requested the bytecodes.
+        peer_id: PeerId,
+        /// The specific bytecodes requested.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that requested the trie nodes.
+        peer_id: PeerId,
+        /// The specific trie nodes requested.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_compact_paths() {
+        // even length path: flag nibble 0 followed by a padding nibble
+        let path = decode_compact_path(&Bytes::from_static(&[0x00, 0x12, 0x34]));
+        assert_eq!(path, Nibbles::from_nibbles([1, 2, 3, 4]));
+
+        // odd length path: flag nibble 1 followed by the first nibble
+        let path = decode_compact_path(&Bytes::from_static(&[0x11, 0x23]));
+        assert_eq!(path, Nibbles::from_nibbles([1, 2, 3]));
+
+        // the root node
+        let path = decode_compact_path(&Bytes::from_static(&[0x00]));
+        assert_eq!(path, Nibbles::default());
+        assert_eq!(decode_compact_path(&Bytes::new()), Nibbles::default());
+    }
+}
@@ -12,8 +12,8 @@ use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use rand::seq::SliceRandom;
 use reth_eth_wire::{
-    BlockHashNumber, Capabilities, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives,
-    NewBlockHashes, NewBlockPayload, UnifiedStatus,
+    BlockHashNumber, BlockRangeUpdate, Capabilities, DisconnectReason, EthNetworkPrimitives,
+    NetworkPrimitives, NewBlockHashes, NewBlockPayload, UnifiedStatus,
 };
 use reth_ethereum_forks::ForkId;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
@@ -155,7 +155,18 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         // find the corresponding block number
         let block_number =
             self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
-        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        let block_range = status
+            .earliest_block
+            .zip(status.latest_block)
+            .filter(|(earliest, latest)| earliest <= latest)
+            .map(|(earliest, latest)| earliest..=latest);
+        self.state_fetcher.new_active_peer(
+            peer,
+            status.blockhash,
+            block_number,
+            block_range,
+            timeout,
+        );
 
         self.active_peers.insert(
             peer,
@@ -255,6 +266,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
+    /// Updates the block range the peer advertised via an eth/69 `BlockRangeUpdate`.
+    pub(crate) fn update_peer_block_range(&mut self, peer_id: &PeerId, update: BlockRangeUpdate) {
+        self.state_fetcher.update_peer_block_range(peer_id, &update);
+    }
+
     /// Invoked when a new [`ForkId`] is activated.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         self.discovery.update_fork_id(fork_id)
@@ -195,6 +195,9 @@ impl<N: NetworkPrimitives> Swarm<N> {
                         InboundConnectionError::IpBanned => {
                             trace!(target: "net", ?remote_addr, "The incoming ip address is in the ban list");
                         }
+                        InboundConnectionError::IpRestricted => {
+                            trace!(target: "net", ?remote_addr, "The incoming ip address is outside the allowed ip networks");
+                        }
                         InboundConnectionError::ExceedsCapacity => {
                             trace!(target: "net", ?remote_addr, "No capacity for incoming connection");
                             self.sessions.try_disconnect_incoming_connection(
@@ -36,6 +36,7 @@ pub const SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE: usize = 2 * 1024 *
 /// Constants used by [`TransactionsManager`](super::TransactionsManager).
 pub mod tx_manager {
     use super::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
+    use std::time::Duration;
 
     /// Default limit for number of transactions to keep track of for a single peer.
     ///
@@ -53,6 +54,21 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default limit for number of transaction senders to keep a score for.
+    pub const DEFAULT_MAX_COUNT_TRACKED_SENDERS: u32 = 4096;
+
+    /// Default score at which a transaction sender is banned.
+    ///
+    /// Default is 0, sender bans are opt-in. See
+    /// [`sender_penalty`](crate::transactions::sender_reputation::sender_penalty) for the
+    /// penalties of rejected transactions.
+    pub const DEFAULT_SENDER_BAN_THRESHOLD: u32 = 0;
+
+    /// Default duration a transaction sender is banned for.
+    ///
+    /// Default is 30 minutes.
+    pub const DEFAULT_SENDER_BAN_DURATION: Duration = Duration::from_secs(30 * 60);
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
@@ -6,6 +6,7 @@ pub mod config;
 pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
+pub mod sender_reputation;
 pub mod validation;
 
 pub use self::constants::{
@@ -17,6 +18,7 @@ pub use config::{
     TransactionFetcherConfig, TransactionPropagationMode, TransactionPropagationPolicy,
     TransactionsManagerConfig,
 };
+pub use sender_reputation::{SenderReputation, SenderReputationConfig};
 pub use validation::*;
 
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
@@ -33,7 +35,7 @@ use crate::{
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     NetworkHandle,
 };
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{Address, TxHash, B256};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use reth_eth_wire::{
@@ -46,7 +48,8 @@ use reth_ethereum_primitives::TransactionSigned;
 use reth_metrics::common::mpsc::UnboundedMeteredReceiver;
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
-    NetworkEvent, NetworkEventListenerProvider, PeerKind, PeerRequest, PeerRequestSender, Peers,
+    BannedSender, NetworkEvent, NetworkEventListenerProvider, PeerKind, PeerRequest,
+    PeerRequestSender, Peers,
 };
 use reth_network_p2p::{
     error::{RequestError, RequestResult},
@@ -190,6 +193,27 @@ impl<N: NetworkPrimitives> TransactionsHandle<N> {
         rx.await
     }
 
+    /// Request the transaction senders that are currently banned.
+    pub async fn banned_senders(&self) -> Result<Vec<BannedSender>, RecvError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(TransactionsCommand::GetBannedSenders(tx));
+        rx.await
+    }
+
+    /// Lifts the ban of the given transaction sender.
+    ///
+    /// Returns `true` if the sender was banned.
+    pub async fn unban_sender(&self, sender: Address) -> Result<bool, RecvError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(TransactionsCommand::UnbanSender { sender, tx });
+        rx.await
+    }
+
+    /// Lifts the bans of all transaction senders.
+    pub fn clear_sender_bans(&self) {
+        self.send(TransactionsCommand::ClearSenderBans)
+    }
+
     /// Request the transaction hashes known by a specific peer.
     pub async fn get_peer_transaction_hashes(
         &self,
@@ -274,6 +298,12 @@ pub struct TransactionsManager<
     pending_pool_imports_info: PendingPoolImportsInfo,
     /// Bad imports.
     bad_imports: LruCache<TxHash>,
+    /// Senders of the transactions that are currently imported into the `Pool`.
+    import_senders: HashMap<TxHash, Address>,
+    /// Scores of senders whose transactions were rejected by the `Pool`.
+    ///
+    /// Transactions of banned senders are not imported.
+    sender_reputation: SenderReputation,
     /// All the connected peers.
     peers: HashMap<PeerId, PeerMetadata<N>>,
     /// Send half for the command channel.
@@ -365,6 +395,8 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
                 DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             ),
             bad_imports: LruCache::new(DEFAULT_MAX_COUNT_BAD_IMPORTS),
+            import_senders: Default::default(),
+            sender_reputation: SenderReputation::new(transactions_manager_config.sender_reputation),
             peers: Default::default(),
             command_tx,
             command_rx: UnboundedReceiverStream::new(command_rx),
@@ -410,6 +442,34 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
     /// Clear the transaction
     fn on_good_import(&mut self, hash: TxHash) {
         self.transactions_by_peers.remove(&hash);
+        self.import_senders.remove(&hash);
+    }
+
+    /// Penalizes the sender of a transaction that was rejected by the pool, and bans it if its
+    /// score crosses the configured threshold.
+    ///
+    /// Queued transactions of a banned sender that were received from peers are removed from the
+    /// pool. Its executable transactions are kept, removing them would leave the transactions
+    /// that follow them with a nonce gap.
+    fn penalize_sender(&mut self, sender: Address, err: &PoolError) {
+        if self.sender_reputation.on_import_error(sender, err) {
+            debug!(target: "net::tx", %sender, kind=%err.kind, "Banned transaction sender");
+            self.metrics.sender_bans.increment(1);
+            self.update_banned_senders_metric();
+
+            let hashes = self
+                .pool
+                .get_queued_transactions_by_sender(sender)
+                .into_iter()
+                .filter(|tx| tx.origin.is_external())
+                .map(|tx| *tx.hash())
+                .collect::<Vec<_>>();
+            self.pool.remove_transactions(hashes);
+        }
+    }
+
+    fn update_banned_senders_metric(&self) {
+        self.metrics.banned_senders.set(self.sender_reputation.num_banned() as f64);
     }
 
     /// Penalize the peers that intentionally sent the bad transaction, and cache it to avoid
@@ -437,9 +497,16 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, P: TransactionPropagationPolic
     /// - wrong versioned kzg commitment hash
     fn on_bad_import(&mut self, err: PoolError) {
         let peers = self.transactions_by_peers.remove(&err.hash);
+        let sender = self.import_senders.remove(&err.hash);
 
         // if we're _currently_ syncing, we ignore a bad transaction
-        if !err.is_bad_transaction() || self.network.is_syncing() {
+        if self.network.is_syncing() {
+            return
+        }
+        if let Some(sender) = sender {
+            self.penalize_sender(sender, &err);
+        }
+        if !err.is_bad_transaction() {
             return
         }
         // otherwise we penalize the peer that sent the bad transaction, with the assumption that
@@ -785,7 +852,13 @@ where
         // filter all transactions unknown to the peer
         let mut full_transactions = FullTransactionsBuilder::new(peer.version);
 
-        let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::pool_tx);
+        // private transactions are never propagated, not even if forced
+        let to_propagate = self
+            .pool
+            .get_all(txs)
+            .into_iter()
+            .filter(|tx| tx.propagate)
+            .map(PropagateTransaction::pool_tx);
 
         if propagation_mode.is_forced() {
             // skip cache check if forced
@@ -861,6 +934,7 @@ where
                 .pool
                 .get_all(hashes)
                 .into_iter()
+                .filter(|tx| tx.propagate)
                 .map(PropagateTransaction::pool_tx)
                 .collect::<Vec<_>>();
 
@@ -1010,7 +1084,12 @@ where
     /// __without__ their sidecar, because 4844 transactions are only ever announced as hashes.
     fn propagate_all(&mut self, hashes: Vec<TxHash>) {
         let propagated = self.propagate_transactions(
-            self.pool.get_all(hashes).into_iter().map(PropagateTransaction::pool_tx).collect(),
+            self.pool
+                .get_all(hashes)
+                .into_iter()
+                .filter(|tx| tx.propagate)
+                .map(PropagateTransaction::pool_tx)
+                .collect(),
             PropagationMode::Basic,
         );
 
@@ -1087,6 +1166,18 @@ where
                 let sender = self.peers.get(&peer_id).map(|peer| peer.request_tx.clone());
                 peer_request_sender.send(sender).ok();
             }
+            TransactionsCommand::GetBannedSenders(tx) => {
+                tx.send(self.sender_reputation.banned()).ok();
+            }
+            TransactionsCommand::UnbanSender { sender, tx } => {
+                let banned = self.sender_reputation.unban(&sender);
+                self.update_banned_senders_metric();
+                tx.send(banned).ok();
+            }
+            TransactionsCommand::ClearSenderBans => {
+                self.sender_reputation.clear();
+                self.update_banned_senders_metric();
+            }
         }
     }
 
@@ -1280,6 +1371,18 @@ where
                     }
                 };
 
+                // skip transactions of banned senders
+                if self.sender_reputation.is_banned(tx.signer_ref()) {
+                    trace!(target: "net::tx",
+                        peer_id=format!("{peer_id:#}"),
+                        hash=%tx.tx_hash(),
+                        sender=%tx.signer(),
+                        "received a transaction from a banned sender"
+                    );
+                    self.metrics.transactions_from_banned_senders.increment(1);
+                    continue
+                }
+
                 match self.transactions_by_peers.entry(*tx.tx_hash()) {
                     Entry::Occupied(mut entry) => {
                         // transaction was already inserted
@@ -1297,6 +1400,7 @@ where
                         } else {
                             // this is a new transaction that should be imported into the pool
 
+                            self.import_senders.insert(*tx.tx_hash(), tx.signer());
                             let pool_transaction = Pool::Transaction::from_pooled(tx);
                             new_txs.push(pool_transaction);
 
@@ -1913,6 +2017,12 @@ enum TransactionsCommand<N: NetworkPrimitives = EthNetworkPrimitives> {
         peer_id: PeerId,
         peer_request_sender: oneshot::Sender<Option<PeerRequestSender<PeerRequest<N>>>>,
     },
+    /// Request the transaction senders that are currently banned.
+    GetBannedSenders(oneshot::Sender<Vec<BannedSender>>),
+    /// Lift the ban of a transaction sender.
+    UnbanSender { sender: Address, tx: oneshot::Sender<bool> },
+    /// Lift the bans of all transaction senders.
+    ClearSenderBans,
 }
 
 /// All events related to transactions emitted by the network.
@@ -0,0 +1,300 @@
+//! Reputation of transaction senders.
+//!
+//! Transactions received from peers that are rejected by the pool are attributed to their sender.
+//! Each rejection adds a [penalty](sender_penalty) to the score of the sender, senders whose score
+//! reaches the configured threshold are banned for a while and their transactions are no longer
+//! imported into the pool.
+
+use super::constants::tx_manager::{
+    DEFAULT_MAX_COUNT_TRACKED_SENDERS, DEFAULT_SENDER_BAN_DURATION, DEFAULT_SENDER_BAN_THRESHOLD,
+};
+use crate::cache::LruMap;
+use alloy_primitives::Address;
+use reth_network_api::BannedSender;
+use reth_transaction_pool::error::{PoolError, PoolErrorKind};
+use std::time::{Duration, Instant};
+
+/// Configuration for the [`SenderReputation`] table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct SenderReputationConfig {
+    /// Max number of senders to keep a score for.
+    pub max_tracked_senders: u32,
+    /// Score at which a sender is banned.
+    ///
+    /// A threshold of `0` disables sender bans, which is the default.
+    pub ban_threshold: u32,
+    /// How long a sender is banned for.
+    ///
+    /// This is also the time after which the score of a sender that is not penalized again is
+    /// reset.
+    pub ban_duration: Duration,
+}
+
+impl Default for SenderReputationConfig {
+    fn default() -> Self {
+        Self {
+            max_tracked_senders: DEFAULT_MAX_COUNT_TRACKED_SENDERS,
+            ban_threshold: DEFAULT_SENDER_BAN_THRESHOLD,
+            ban_duration: DEFAULT_SENDER_BAN_DURATION,
+        }
+    }
+}
+
+/// Returns the penalty for a transaction that was rejected by the pool with the given error.
+///
+/// Only rejections the signer alone is responsible for are penalized: malformed transactions,
+/// exceeding the slot capacity of the sender, conflicting transaction types and fee caps below the
+/// protocol minimum.
+///
+/// Rejections that depend on the other transactions in the pool or on the order in which
+/// transactions arrive, such as underpriced replacements, nonce gaps or transactions discarded to
+/// make room, are not penalized: any peer can provoke them by relaying outdated transactions of
+/// the sender. Neither are internal errors or transactions with an outdated nonce.
+pub fn sender_penalty(err: &PoolError) -> u32 {
+    if err.is_bad_transaction() {
+        return 16
+    }
+    match &err.kind {
+        PoolErrorKind::SpammerExceededCapacity(_) => 8,
+        PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) |
+        PoolErrorKind::ExistingConflictingTransactionType(_, _) => 4,
+        PoolErrorKind::AlreadyImported |
+        PoolErrorKind::Other(_) |
+        PoolErrorKind::DiscardedOnInsert |
+        PoolErrorKind::ReplacementUnderpriced |
+        PoolErrorKind::InvalidTransaction(_) => 0,
+    }
+}
+
+/// Score of a single sender.
+#[derive(Debug, Clone, Copy)]
+struct SenderScore {
+    /// Accumulated penalties.
+    score: u32,
+    /// When the sender was last penalized.
+    last_penalty: Instant,
+    /// Until when the sender is banned, if it is banned.
+    banned_until: Option<Instant>,
+}
+
+/// Bounded table of transaction sender scores.
+///
+/// Only the most recently penalized senders are tracked, see
+/// [`SenderReputationConfig::max_tracked_senders`].
+#[derive(Debug)]
+pub struct SenderReputation {
+    /// How scores are tracked and when senders are banned.
+    config: SenderReputationConfig,
+    /// Scores of the most recently penalized senders.
+    senders: LruMap<Address, SenderScore>,
+}
+
+impl SenderReputation {
+    /// Creates a new table with the given configuration.
+    pub fn new(config: SenderReputationConfig) -> Self {
+        Self { senders: LruMap::new(config.max_tracked_senders), config }
+    }
+
+    /// Returns `true` if the sender is currently banned.
+    ///
+    /// Expired bans are lifted.
+    pub fn is_banned(&mut self, sender: &Address) -> bool {
+        let Some(entry) = self.senders.peek_mut(sender) else { return false };
+        let banned_until = entry.banned_until;
+        match banned_until {
+            Some(until) if until > Instant::now() => true,
+            Some(_) => {
+                self.senders.remove(sender);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Penalizes the sender of a transaction that was rejected by the pool with the given error.
+    ///
+    /// Returns `true` if the sender was banned as a result.
+    pub fn on_import_error(&mut self, sender: Address, err: &PoolError) -> bool {
+        let penalty = sender_penalty(err);
+        if penalty == 0 || self.config.ban_threshold == 0 {
+            return false
+        }
+
+        let now = Instant::now();
+        let SenderReputationConfig { ban_threshold, ban_duration, .. } = self.config;
+        let Some(entry) = self.senders.get_or_insert(sender, || SenderScore {
+            score: 0,
+            last_penalty: now,
+            banned_until: None,
+        }) else {
+            return false
+        };
+
+        match entry.banned_until {
+            // already banned
+            Some(until) if until > now => return false,
+            // ban expired, start over
+            Some(_) => {
+                entry.score = 0;
+                entry.banned_until = None;
+            }
+            // forget penalties that are too old
+            None if now.duration_since(entry.last_penalty) > ban_duration => entry.score = 0,
+            None => {}
+        }
+        entry.score = entry.score.saturating_add(penalty);
+        entry.last_penalty = now;
+
+        if entry.score >= ban_threshold {
+            entry.banned_until = Some(now + ban_duration);
+            return true
+        }
+        false
+    }
+
+    /// Returns all senders that are currently banned.
+    pub fn banned(&self) -> Vec<BannedSender> {
+        let now = Instant::now();
+        self.senders
+            .iter()
+            .filter_map(|(address, entry)| {
+                let banned_until = entry.banned_until.filter(|until| *until > now)?;
+                Some(BannedSender {
+                    address: *address,
+                    score: entry.score,
+                    expires_in: banned_until.duration_since(now).as_secs(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the number of senders that are currently banned.
+    pub fn num_banned(&self) -> usize {
+        let now = Instant::now();
+        self.senders
+            .iter()
+            .filter(|(_, entry)| entry.banned_until.is_some_and(|until| until > now))
+            .count()
+    }
+
+    /// Lifts the ban of the sender and resets its score.
+    ///
+    /// Returns `true` if the sender was banned.
+    pub fn unban(&mut self, sender: &Address) -> bool {
+        let banned = self.is_banned(sender);
+        self.senders.remove(sender);
+        banned
+    }
+
+    /// Lifts all bans and resets all scores.
+    pub fn clear(&mut self) {
+        self.senders.clear();
+    }
+}
+
+impl Default for SenderReputation {
+    fn default() -> Self {
+        Self::new(SenderReputationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
+    use alloy_primitives::B256;
+    use reth_primitives_traits::transaction::error::InvalidTransactionError;
+    use reth_transaction_pool::error::{Eip4844PoolTransactionError, InvalidPoolTransactionError};
+
+    fn rejected(kind: PoolErrorKind) -> PoolError {
+        PoolError::new(B256::random(), kind)
+    }
+
+    #[test]
+    fn ban_after_threshold() {
+        let mut reputation = SenderReputation::new(SenderReputationConfig {
+            ban_threshold: 8,
+            ..Default::default()
+        });
+        let sender = Address::random();
+
+        // internal errors are not attributed to the sender
+        assert!(!reputation.on_import_error(sender, &PoolError::other(B256::ZERO, "err")));
+        assert!(!reputation.is_banned(&sender));
+
+        let conflicting = || {
+            rejected(PoolErrorKind::ExistingConflictingTransactionType(sender, EIP4844_TX_TYPE_ID))
+        };
+        assert!(!reputation.on_import_error(sender, &conflicting()));
+        assert!(!reputation.is_banned(&sender));
+        assert!(reputation.on_import_error(sender, &conflicting()));
+        assert!(reputation.is_banned(&sender));
+
+        let banned = reputation.banned();
+        assert_eq!(banned.len(), 1);
+        assert_eq!(banned[0].address, sender);
+        assert_eq!(banned[0].score, 8);
+        assert_eq!(reputation.num_banned(), 1);
+
+        // other senders are unaffected
+        assert!(!reputation.is_banned(&Address::random()));
+
+        assert!(reputation.unban(&sender));
+        assert!(!reputation.is_banned(&sender));
+        assert!(!reputation.unban(&sender));
+        assert_eq!(reputation.num_banned(), 0);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut reputation = SenderReputation::default();
+        let sender = Address::random();
+
+        for _ in 0..64 {
+            assert!(!reputation.on_import_error(
+                sender,
+                &rejected(PoolErrorKind::SpammerExceededCapacity(sender))
+            ));
+        }
+        assert!(!reputation.is_banned(&sender));
+    }
+
+    #[test]
+    fn outdated_nonce_is_not_penalized() {
+        let err =
+            rejected(PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Consensus(
+                InvalidTransactionError::NonceNotConsistent { tx: 0, state: 1 },
+            )));
+        assert_eq!(sender_penalty(&err), 0);
+    }
+
+    #[test]
+    fn pool_dependent_rejections_are_not_penalized() {
+        for kind in [
+            PoolErrorKind::ReplacementUnderpriced,
+            PoolErrorKind::DiscardedOnInsert,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Underpriced),
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip4844(
+                Eip4844PoolTransactionError::Eip4844NonceGap,
+            )),
+        ] {
+            assert_eq!(sender_penalty(&rejected(kind)), 0);
+        }
+    }
+
+    #[test]
+    fn ban_expires() {
+        let mut reputation = SenderReputation::new(SenderReputationConfig {
+            ban_threshold: 1,
+            ban_duration: Duration::ZERO,
+            ..Default::default()
+        });
+        let sender = Address::random();
+
+        assert!(reputation
+            .on_import_error(sender, &rejected(PoolErrorKind::SpammerExceededCapacity(sender))));
+        assert!(!reputation.is_banned(&sender));
+        assert!(reputation.banned().is_empty());
+    }
+}
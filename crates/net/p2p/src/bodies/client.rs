@@ -1,4 +1,5 @@
 use std::{
+    ops::RangeInclusive,
     pin::Pin,
     task::{ready, Context, Poll},
 };
@@ -29,6 +30,20 @@ pub trait BodiesClient: DownloadClient {
     fn get_block_bodies_with_priority(&self, hashes: Vec<B256>, priority: Priority)
         -> Self::Output;
 
+    /// Fetches the block bodies for the requested hashes with priority and a hint of the block
+    /// numbers the hashes belong to.
+    ///
+    /// Clients that know which peers can serve which blocks can use the hint to route the
+    /// request, by default it is ignored.
+    fn get_block_bodies_with_range_hint(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+        _range_hint: Option<RangeInclusive<u64>>,
+    ) -> Self::Output {
+        self.get_block_bodies_with_priority(hashes, priority)
+    }
+
     /// Fetches a single block body for the requested hash.
     fn get_block_body(&self, hash: B256) -> SingleBodyRequest<Self::Output> {
         self.get_block_body_with_priority(hash, Priority::Normal)
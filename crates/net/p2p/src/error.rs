@@ -184,6 +184,15 @@ pub enum DownloadError {
         /// Invalid block number range.
         range: RangeInclusive<BlockNumber>,
     },
+
+    /* ==================== SNAP ERRORS ==================== */
+    /// Received a state range response that does not match the request.
+    #[display("received invalid state range response: {reason}")]
+    InvalidStateRange {
+        /// Why the response was rejected.
+        reason: &'static str,
+    },
+
     /* ==================== COMMON ERRORS ==================== */
     /// Timed out while waiting for request id response.
     #[display("timed out while waiting for response")]
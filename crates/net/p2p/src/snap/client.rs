@@ -1,6 +1,9 @@
 use crate::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
 use futures::Future;
-use reth_eth_wire_types::snap::{AccountRangeMessage, GetAccountRangeMessage};
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
+};
 
 /// The snap sync downloader client
 #[auto_impl::auto_impl(&, Arc, Box)]
@@ -8,6 +11,18 @@ pub trait SnapClient: DownloadClient {
     /// The output future type for account range requests
     type Output: Future<Output = PeerRequestResult<AccountRangeMessage>> + Send + Sync + Unpin;
 
+    /// The output future type for storage ranges requests
+    type StorageRangesOutput: Future<Output = PeerRequestResult<StorageRangesMessage>>
+        + Send
+        + Sync
+        + Unpin;
+
+    /// The output future type for bytecodes requests
+    type ByteCodesOutput: Future<Output = PeerRequestResult<ByteCodesMessage>> + Send + Sync + Unpin;
+
+    /// The output future type for trie nodes requests
+    type TrieNodesOutput: Future<Output = PeerRequestResult<TrieNodesMessage>> + Send + Sync + Unpin;
+
     /// Sends the account range request to the p2p network and returns the account range
     /// response received from a peer.
     fn get_account_range(&self, request: GetAccountRangeMessage) -> Self::Output {
@@ -21,4 +36,46 @@ pub trait SnapClient: DownloadClient {
         request: GetAccountRangeMessage,
         priority: Priority,
     ) -> Self::Output;
+
+    /// Sends the storage ranges request to the p2p network and returns the storage ranges
+    /// response received from a peer.
+    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::StorageRangesOutput {
+        self.get_storage_ranges_with_priority(request, Priority::Normal)
+    }
+
+    /// Sends the storage ranges request to the p2p network with priority set and returns
+    /// the storage ranges response received from a peer.
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        priority: Priority,
+    ) -> Self::StorageRangesOutput;
+
+    /// Sends the bytecodes request to the p2p network and returns the bytecodes response
+    /// received from a peer.
+    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::ByteCodesOutput {
+        self.get_byte_codes_with_priority(request, Priority::Normal)
+    }
+
+    /// Sends the bytecodes request to the p2p network with priority set and returns the
+    /// bytecodes response received from a peer.
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        priority: Priority,
+    ) -> Self::ByteCodesOutput;
+
+    /// Sends the trie nodes request to the p2p network and returns the trie nodes response
+    /// received from a peer.
+    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::TrieNodesOutput {
+        self.get_trie_nodes_with_priority(request, Priority::Normal)
+    }
+
+    /// Sends the trie nodes request to the p2p network with priority set and returns the trie
+    /// nodes response received from a peer.
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        priority: Priority,
+    ) -> Self::TrieNodesOutput;
 }
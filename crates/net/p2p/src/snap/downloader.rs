@@ -0,0 +1,37 @@
+use crate::error::DownloadResult;
+use alloy_primitives::{B256, U256};
+use futures::Stream;
+use reth_primitives_traits::{Account, Bytecode};
+
+/// A consecutive range of the state downloaded from `snap` peers.
+///
+/// All entries are keyed by their hashed key, which is all the `snap` protocol exposes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SnapStateBatch {
+    /// Accounts in ascending order of their hashed address.
+    pub accounts: Vec<(B256, Account)>,
+    /// Storage slots of the accounts in this batch in ascending order of their hashed slot.
+    pub storages: Vec<(B256, Vec<(B256, U256)>)>,
+    /// Bytecodes referenced by the accounts in this batch.
+    pub bytecodes: Vec<(B256, Bytecode)>,
+}
+
+impl SnapStateBatch {
+    /// Returns the hashed address of the last account in the batch.
+    pub fn last_account(&self) -> Option<B256> {
+        self.accounts.last().map(|(hashed_address, _)| *hashed_address)
+    }
+}
+
+/// A downloader capable of fetching the state at a given state root.
+///
+/// The downloader yields batches of consecutive accounts in ascending order of their hashed
+/// address, and terminates once all accounts have been downloaded.
+pub trait StateDownloader:
+    Send + Sync + Stream<Item = DownloadResult<SnapStateBatch>> + Unpin
+{
+    /// Sets the state root to download and the hashed address of the first account to download.
+    ///
+    /// Any download in progress is discarded.
+    fn set_target(&mut self, root: B256, start: B256);
+}
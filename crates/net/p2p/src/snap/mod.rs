@@ -1,2 +1,5 @@
 /// SNAP related traits.
 pub mod client;
+
+/// A downloader for the state at a given state root.
+pub mod downloader;
@@ -4,8 +4,9 @@ use alloy_primitives::Address;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::TxTy;
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit,
-    TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
+    blobstore::{BlobArchive, BlobArchiveConfig, DiskFileBlobStore},
+    CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit, TransactionPool,
+    TransactionValidationTaskExecutor, TransactionValidator,
 };
 use std::{collections::HashSet, future::Future};
 
@@ -155,6 +156,10 @@ where
             pool_config.clone(),
         );
 
+        if let Some(blob_archive) = open_blob_archive(ctx)? {
+            transaction_pool.set_blob_archive(blob_archive);
+        }
+
         // Spawn maintenance tasks using standalone functions
         spawn_maintenance_tasks(ctx, transaction_pool.clone(), &pool_config)?;
 
@@ -189,25 +194,44 @@ pub fn create_blob_store_with_cache<Node: FullNodeTypes>(
     Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
+/// Open the blob archive if enabled via `--txpool.blob-archive`.
+pub fn open_blob_archive<Node: FullNodeTypes>(
+    ctx: &BuilderContext<Node>,
+) -> eyre::Result<Option<BlobArchive>> {
+    let txpool_args = &ctx.config().txpool;
+    if !txpool_args.blob_archive {
+        return Ok(None)
+    }
+    let config = BlobArchiveConfig { retention_blocks: Some(txpool_args.blob_archive_retention) };
+    Ok(Some(BlobArchive::open(ctx.config().datadir().blob_archive(), config)?))
+}
+
 /// Spawn local transaction backup task if enabled.
+///
+/// This also persists the entire pool if `--txpool.persist-all` is set.
 fn spawn_local_backup_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
 where
     Node: FullNodeTypes,
     Pool: TransactionPool + Clone + 'static,
 {
-    if !ctx.config().txpool.disable_transactions_backup {
+    let txpool_args = &ctx.config().txpool;
+    if !txpool_args.disable_transactions_backup || txpool_args.persist_all {
         let data_dir = ctx.config().datadir();
-        let transactions_path = ctx
-            .config()
-            .txpool
-            .transactions_backup_path
-            .clone()
-            .unwrap_or_else(|| data_dir.txpool_transactions());
-
-        let transactions_backup_config =
-            reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(
-                transactions_path,
+
+        let mut transactions_backup_config =
+            reth_transaction_pool::maintain::LocalTransactionBackupConfig::default();
+        if !txpool_args.disable_transactions_backup {
+            transactions_backup_config.transactions_path = Some(
+                txpool_args
+                    .transactions_backup_path
+                    .clone()
+                    .unwrap_or_else(|| data_dir.txpool_transactions()),
             );
+        }
+        if txpool_args.persist_all {
+            transactions_backup_config =
+                transactions_backup_config.with_pool_snapshot(data_dir.txpool_snapshot());
+        }
 
         ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
             "local transactions backup task",
@@ -246,6 +270,7 @@ where
             ctx.task_executor().clone(),
             reth_transaction_pool::maintain::MaintainPoolConfig {
                 max_tx_lifetime: pool_config.max_queued_lifetime,
+                max_private_tx_blocks: pool_config.max_private_tx_blocks,
                 no_local_exemptions: pool_config.local_transactions_config.no_exemptions,
                 ..Default::default()
             },
@@ -19,7 +19,9 @@ use reth_engine_local::MiningMode;
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{
+    InvalidBlockOpcodeHook, InvalidBlockPreStateHook, InvalidBlockWitnessHook,
+};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
@@ -905,17 +907,27 @@ where
                 let output_directory = output_directory.join(hook.to_string());
                 fs::create_dir_all(&output_directory)?;
 
-                Ok(match hook {
+                let hook: Box<dyn InvalidBlockHook<_>> = match hook {
                     InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
                         self.blockchain_db().clone(),
                         self.components().evm_config().clone(),
                         output_directory,
                         healthy_node_rpc_client.clone(),
                     )),
-                    InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                        eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                    }
-                } as Box<dyn InvalidBlockHook<_>>)
+                    InvalidBlockHookType::PreState => Box::new(InvalidBlockPreStateHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
+                    )),
+                    InvalidBlockHookType::Opcode => Box::new(InvalidBlockOpcodeHook::new(
+                        self.blockchain_db().clone(),
+                        self.components().evm_config().clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
+                    )),
+                };
+                Ok::<_, eyre::Report>(hook)
             })
             .collect::<Result<_, _>>()?;
 
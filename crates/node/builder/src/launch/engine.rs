@@ -1,6 +1,7 @@
 //! Engine node related functionality.
 
 use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
@@ -12,11 +13,11 @@ use reth_engine_tree::{
 };
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
-use reth_network::{NetworkSyncUpdater, SyncState};
+use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
-    PayloadAttributesBuilder, PayloadTypes,
+    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, LocalMinerHandle, NodeTypes,
+    NodeTypesWithDBAdapter, PayloadAttributesBuilder, PayloadTypes,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -24,7 +25,11 @@ use reth_node_core::{
     primitives::Head,
 };
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
-use reth_provider::providers::{BlockchainProvider, NodeTypesForProvider};
+use reth_provider::{
+    providers::{BlockchainProvider, NodeTypesForProvider},
+    ProviderResult, PruneCheckpointReader, StaticFileProviderFactory, StaticFileSegment,
+};
+use reth_prune::PruneSegment;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
@@ -188,12 +193,21 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // dev nodes expose a handle to control the local miner
+        let (local_miner_handle, from_local_miner_handle) = if ctx.is_dev() {
+            let (handle, rx) = LocalMinerHandle::channel();
+            (Some(handle), Some(rx))
+        } else {
+            (None, None)
+        };
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            local_miner_handle,
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -228,6 +242,7 @@ where
                 ctx.dev_mining_mode(ctx.components().pool()),
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
                 ctx.components().evm_config().clone(),
+                from_local_miner_handle.expect("dev node has a local miner handle"),
             );
 
             Either::Left(eth_service)
@@ -294,6 +309,7 @@ where
             .into_built_payload_stream()
             .fuse();
         let chainspec = ctx.chain_spec();
+        let provider = ctx.blockchain_db().clone();
         let (exit, rx) = oneshot::channel();
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
 
@@ -307,6 +323,8 @@ where
             }
 
             let mut res = Ok(());
+            // the latest block of the last advertised eth/69 block range
+            let mut last_advertised_block: Option<BlockNumber> = None;
 
             // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
             loop {
@@ -349,6 +367,18 @@ where
                                         total_difficulty: chainspec.final_paris_total_difficulty().filter(|_| chainspec.is_paris_active_at_block(head.number())).unwrap_or_default(),
                                     };
                                     network_handle.update_status(head_block);
+
+                                    if last_advertised_block.is_none_or(|last| head.number() < last || head.number() >= last + BLOCK_RANGE_UPDATE_INTERVAL) {
+                                        match advertised_block_range(&provider, head.number(), head.hash()) {
+                                            Ok(block_range) => {
+                                                last_advertised_block = Some(block_range.latest);
+                                                network_handle.update_block_range(block_range);
+                                            }
+                                            Err(err) => {
+                                                debug!(target: "reth::cli", %err, "Failed to determine advertised block range");
+                                            }
+                                        }
+                                    }
                                 }
                                 event_sender.notify(ev);
                             }
@@ -390,3 +420,37 @@ where
         Ok(handle)
     }
 }
+
+/// Number of blocks the canonical head has to advance before the advertised eth/69 block range is
+/// updated again.
+const BLOCK_RANGE_UPDATE_INTERVAL: u64 = 32;
+
+/// Returns the eth/69 block range this node can serve up to the given latest block.
+///
+/// The earliest block is the first block whose header and body are still available, taking
+/// pruned segments and expired header and transaction static files into account.
+fn advertised_block_range<P>(
+    provider: &P,
+    latest: BlockNumber,
+    latest_hash: B256,
+) -> ProviderResult<BlockRangeUpdate>
+where
+    P: StaticFileProviderFactory + PruneCheckpointReader,
+{
+    let static_file_provider = provider.static_file_provider();
+    let mut earliest = [StaticFileSegment::Headers, StaticFileSegment::Transactions]
+        .into_iter()
+        .filter_map(|segment| static_file_provider.get_lowest_static_file_block(segment))
+        .max()
+        .unwrap_or_default();
+
+    for segment in [PruneSegment::Headers, PruneSegment::Transactions] {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            earliest = earliest.max(pruned + 1);
+        }
+    }
+
+    Ok(BlockRangeUpdate { earliest: earliest.min(latest), latest, latest_hash })
+}
@@ -7,6 +7,7 @@ use futures::TryFutureExt;
 use jsonrpsee::RpcModule;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_engine_util::bad_blocks::{maintain_bad_block_store, BadBlockStore};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, EngineValidator, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, ReceiptTy,
@@ -478,7 +479,8 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events, .. } =
+            ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -504,6 +506,13 @@ where
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
+        let bad_block_store =
+            BadBlockStore::open(config.datadir().bad_blocks(), config.debug.max_bad_blocks)?;
+        node.task_executor().spawn(Box::pin(maintain_bad_block_store(
+            engine_events.new_listener(),
+            bad_block_store.clone(),
+        )));
+
         let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
@@ -511,6 +520,7 @@ where
             .with_executor(Box::new(node.task_executor().clone()))
             .with_evm_config(node.evm_config().clone())
             .with_consensus(node.consensus().clone())
+            .with_bad_block_store(bad_block_store)
             .build_with_auth_server(module_config, engine_api, eth_api);
 
         // in dev mode we generate 20 random dev-signer accounts
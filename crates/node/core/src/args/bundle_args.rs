@@ -0,0 +1,13 @@
+use clap::Args;
+
+/// Parameters for configuring the bundle pool of the payload builder.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Bundles")]
+pub struct BundleArgs {
+    /// Enable the bundle pool of the payload builder.
+    ///
+    /// Bundles submitted via `eth_sendBundle` and `mev_sendBundle` are included in built
+    /// payloads.
+    #[arg(long = "bundles.enable", default_value_t = false)]
+    pub enabled: bool,
+}
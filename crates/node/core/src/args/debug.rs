@@ -65,6 +65,9 @@ pub struct DebugArgs {
     /// The path to store engine API messages at.
     /// If specified, all of the intercepted engine API messages
     /// will be written to specified location.
+    ///
+    /// The stored messages can be replayed with `reth-bench replay-engine`, which compares the
+    /// replayed statuses with the stored statuses the node responded with.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
@@ -87,6 +90,13 @@ pub struct DebugArgs {
         verbatim_doc_comment
     )]
     pub healthy_node_rpc_url: Option<String>,
+
+    /// The maximum number of blocks rejected by the engine that are kept for
+    /// `debug_getBadBlocks`.
+    ///
+    /// The oldest block is removed once the limit is reached.
+    #[arg(long = "debug.max-bad-blocks", help_heading = "Debug", default_value_t = 10)]
+    pub max_bad_blocks: usize,
 }
 
 impl Default for DebugArgs {
@@ -104,6 +114,7 @@ impl Default for DebugArgs {
             engine_api_store: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            max_bad_blocks: 10,
         }
     }
 }
@@ -14,6 +14,7 @@ use reth_discv5::{
     discv5::ListenConfig, DEFAULT_COUNT_BOOTSTRAP_LOOKUPS, DEFAULT_DISCOVERY_V5_PORT,
     DEFAULT_SECONDS_BOOTSTRAP_LOOKUP_INTERVAL, DEFAULT_SECONDS_LOOKUP_INTERVAL,
 };
+use reth_net_banlist::{IpFilter, IpNet, NetRestrict};
 use reth_net_nat::{NatResolver, DEFAULT_NET_IF_NAME};
 use reth_network::{
     transactions::{
@@ -24,10 +25,11 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             },
             tx_manager::{
-                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
+                DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER, DEFAULT_SENDER_BAN_THRESHOLD,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        SenderReputationConfig, TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
@@ -58,6 +60,19 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub trusted_only: bool,
 
+    /// Comma separated IP networks in CIDR notation the node is restricted to. Peers and
+    /// discovered nodes outside these networks are ignored.
+    ///
+    /// --netrestrict 10.0.0.0/8,192.168.0.0/16
+    #[arg(long, value_name = "CIDR", value_delimiter = ',')]
+    pub netrestrict: Vec<IpNet>,
+
+    /// Comma separated IP networks in CIDR notation the node refuses to communicate with.
+    ///
+    /// --netdeny 10.1.0.0/16
+    #[arg(long, value_name = "CIDR", value_delimiter = ',')]
+    pub netdeny: Vec<IpNet>,
+
     /// Comma separated enode URLs for P2P discovery bootstrap.
     ///
     /// Will fall back to a network-specific default if not specified.
@@ -150,6 +165,14 @@ pub struct NetworkArgs {
     #[arg(long = "max-tx-pending-fetch", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, verbatim_doc_comment)]
     pub max_capacity_cache_txns_pending_fetch: u32,
 
+    /// Score at which the sender of transactions that are repeatedly rejected by the pool is
+    /// temporarily banned. Transactions of banned senders received from peers are removed from
+    /// the pool and no longer imported.
+    ///
+    /// Sender bans are disabled by default, or if the value is 0.
+    #[arg(long = "tx-sender-ban-threshold", value_name = "SCORE", default_value_t = DEFAULT_SENDER_BAN_THRESHOLD, verbatim_doc_comment)]
+    pub tx_sender_ban_threshold: u32,
+
     /// Name of network interface used to communicate with peers.
     ///
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
@@ -203,6 +226,10 @@ impl NetworkArgs {
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            sender_reputation: SenderReputationConfig {
+                ban_threshold: self.tx_sender_ban_threshold,
+                ..Default::default()
+            },
         }
     }
 
@@ -235,7 +262,8 @@ impl NetworkArgs {
             .peers
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
-            .with_max_outbound_opt(self.max_outbound_peers);
+            .with_max_outbound_opt(self.max_outbound_peers)
+            .with_ip_filter(self.ip_filter());
 
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
@@ -274,6 +302,11 @@ impl NetworkArgs {
             ))
     }
 
+    /// Returns the [`IpFilter`] configured by `--netrestrict` and `--netdeny`.
+    pub fn ip_filter(&self) -> IpFilter {
+        IpFilter::new(NetRestrict::new(self.netrestrict.clone(), self.netdeny.clone()))
+    }
+
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
     pub fn persistent_peers_file(&self, peers_file: PathBuf) -> Option<PathBuf> {
         self.no_persist_peers.not().then_some(peers_file)
@@ -322,6 +355,8 @@ impl Default for NetworkArgs {
             discovery: DiscoveryArgs::default(),
             trusted_peers: vec![],
             trusted_only: false,
+            netrestrict: vec![],
+            netdeny: vec![],
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
@@ -340,6 +375,7 @@ impl Default for NetworkArgs {
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+            tx_sender_ban_threshold: DEFAULT_SENDER_BAN_THRESHOLD,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
             tx_propagation_policy: TransactionPropagationKind::default()
@@ -601,6 +637,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_netrestrict_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--netrestrict",
+            "10.0.0.0/8,192.168.0.0/16",
+            "--netdeny",
+            "10.1.0.0/16",
+        ])
+        .args;
+        assert_eq!(
+            args.netrestrict,
+            vec!["10.0.0.0/8".parse().unwrap(), "192.168.0.0/16".parse().unwrap()]
+        );
+        assert_eq!(args.netdeny, vec!["10.1.0.0/16".parse().unwrap()]);
+
+        let filter = args.ip_filter();
+        assert!(filter.is_allowed(&"10.0.0.1".parse().unwrap()));
+        assert!(!filter.is_allowed(&"10.1.0.1".parse().unwrap()));
+        assert!(!filter.is_allowed(&"1.1.1.1".parse().unwrap()));
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--netrestrict",
+            "10.0.0.0/33"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_retry_strategy_args() {
         let tests = vec![0, 10];
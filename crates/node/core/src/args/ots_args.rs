@@ -0,0 +1,13 @@
+use clap::Args;
+
+/// Parameters for configuring the `ots` namespace.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Otterscan")]
+pub struct OtsArgs {
+    /// Maintain the call trace index used by `ots_searchTransactionsBefore` and
+    /// `ots_searchTransactionsAfter`.
+    ///
+    /// Indexing blocks that were synced before requires the state history of all blocks.
+    #[arg(long = "ots.index", default_value_t = false)]
+    pub index: bool,
+}
@@ -0,0 +1,117 @@
+use clap::Args;
+use reth_rpc_server_types::{
+    rate_limit::{MethodCost, MethodRateLimit, RateLimit},
+    RpcRateLimitConfig,
+};
+
+/// Parameters to configure the rate limits of the HTTP and WS RPC servers.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "RPC Rate Limits")]
+pub struct RpcRateLimitArgs {
+    /// Path to a TOML file with the rate limit configuration.
+    ///
+    /// Limits given on the command line are applied on top of the file.
+    #[arg(long = "rpc.ratelimit.config", value_name = "PATH", value_parser = read_rate_limit_config)]
+    pub config_file: Option<RpcRateLimitConfig>,
+
+    /// Limit of a group of methods in credits per second, shared by all clients.
+    ///
+    /// For example `eth_call=100`, or `trace_*=10/20` to allow bursts of 20 credits.
+    #[arg(
+        long = "rpc.ratelimit.method",
+        value_name = "PATTERN=RATE[/BURST]",
+        value_delimiter = ','
+    )]
+    pub methods: Vec<MethodRateLimit>,
+
+    /// Limit of every client IP in credits per second.
+    #[arg(long = "rpc.ratelimit.per-ip", value_name = "RATE[/BURST]")]
+    pub per_ip: Option<RateLimit>,
+
+    /// Cost of methods in credits, for example `eth_getLogs=10`. Other methods cost one credit.
+    #[arg(long = "rpc.ratelimit.cost", value_name = "PATTERN=CREDITS", value_delimiter = ',')]
+    pub costs: Vec<MethodCost>,
+
+    /// HTTP header the client IP is read from, for example `X-Forwarded-For`.
+    ///
+    /// Only set this if the server is behind a proxy that sets this header, otherwise the address
+    /// of the connected peer is used.
+    #[arg(long = "rpc.ratelimit.ip-header", value_name = "HEADER")]
+    pub client_ip_header: Option<String>,
+
+    /// Number of trusted proxies in front of the server that append to the client IP header.
+    ///
+    /// The client IP is the entry appended by the outermost trusted proxy. Defaults to one, the
+    /// last entry of the header.
+    #[arg(long = "rpc.ratelimit.trusted-hops", value_name = "HOPS", requires = "client_ip_header")]
+    pub trusted_hops: Option<usize>,
+}
+
+impl RpcRateLimitArgs {
+    /// Returns the rate limit configuration, or `None` if no limits are configured.
+    pub fn rate_limit_config(&self) -> Option<RpcRateLimitConfig> {
+        let mut config = self.config_file.clone().unwrap_or_default();
+
+        // groups from the command line are matched before the groups from the file
+        config.methods.splice(0..0, self.methods.iter().cloned());
+        if let Some(per_ip) = self.per_ip {
+            config.per_ip = Some(per_ip);
+        }
+        config.costs.extend(self.costs.iter().map(|cost| (cost.pattern.clone(), cost.credits)));
+        if let Some(header) = &self.client_ip_header {
+            config.client_ip_header = Some(header.clone());
+        }
+        if let Some(trusted_hops) = self.trusted_hops {
+            config.trusted_hops = Some(trusted_hops);
+        }
+
+        (!config.is_empty()).then_some(config)
+    }
+}
+
+/// Reads the rate limit configuration from a TOML file.
+fn read_rate_limit_config(path: &str) -> eyre::Result<RpcRateLimitConfig> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|err| eyre::eyre!("failed to read {path}: {err}"))?;
+    toml::from_str(&contents).map_err(|err| eyre::eyre!("failed to parse {path}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_rate_limit_args() {
+        let args = CommandParser::<RpcRateLimitArgs>::parse_from([
+            "reth",
+            "--rpc.ratelimit.method",
+            "eth_call=100,trace_*=10/20",
+            "--rpc.ratelimit.per-ip",
+            "50",
+            "--rpc.ratelimit.cost",
+            "eth_getLogs=10",
+        ])
+        .args;
+
+        let config = args.rate_limit_config().unwrap();
+        assert_eq!(config.methods.len(), 2);
+        assert_eq!(config.methods[1].limit, RateLimit { per_second: 10, burst: Some(20) });
+        assert_eq!(config.per_ip, Some(RateLimit::per_second(50)));
+        assert_eq!(config.cost("eth_getLogs"), 10);
+    }
+
+    #[test]
+    fn test_no_rate_limits() {
+        let args = CommandParser::<RpcRateLimitArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, RpcRateLimitArgs::default());
+        assert!(args.rate_limit_config().is_none());
+    }
+}
@@ -19,7 +19,7 @@ use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
-    GasPriceOracleArgs, RpcStateCacheArgs,
+    GasPriceOracleArgs, RpcRateLimitArgs, RpcStateCacheArgs,
 };
 
 use super::types::MaxOr;
@@ -218,6 +218,10 @@ pub struct RpcServerArgs {
     /// Gas price oracle configuration.
     #[command(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
+
+    /// Rate limit configuration.
+    #[command(flatten)]
+    pub rate_limit: RpcRateLimitArgs,
 }
 
 impl RpcServerArgs {
@@ -352,6 +356,7 @@ impl Default for RpcServerArgs {
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
             builder_disallow: Default::default(),
+            rate_limit: RpcRateLimitArgs::default(),
         }
     }
 }
@@ -0,0 +1,10 @@
+use clap::Args;
+
+/// Parameters for configuring the `snap` subprotocol.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Snap")]
+pub struct SnapArgs {
+    /// Enable serving state to peers over the `snap` subprotocol.
+    #[arg(long = "snap.enable", default_value_t = false)]
+    pub enabled: bool,
+}
@@ -50,4 +50,9 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The snap sync stage within the pipeline.
+    ///
+    /// Downloads the hashed state at the target block from `snap` peers instead of executing
+    /// blocks, the headers up to the target must already be synced.
+    SnapSync,
 }
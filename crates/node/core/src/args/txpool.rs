@@ -6,8 +6,8 @@ use alloy_primitives::Address;
 use clap::Args;
 use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
-    maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
+    blobstore::{disk::DEFAULT_MAX_CACHED_BLOBS, DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS},
+    maintain::{MAX_PRIVATE_TRANSACTION_BLOCKS, MAX_QUEUED_TRANSACTION_LIFETIME},
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
@@ -111,6 +111,14 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
 
+    /// Number of blocks after which private transactions that were not included are removed
+    /// from the pool.
+    ///
+    /// Private transactions are submitted with `eth_sendPrivateRawTransaction` or
+    /// `eth_sendPrivateTransaction` and are never propagated to peers.
+    #[arg(long = "txpool.private-tx-lifetime", default_value_t = MAX_PRIVATE_TRANSACTION_BLOCKS, value_name = "BLOCKS")]
+    pub max_private_tx_blocks: u64,
+
     /// Path to store the local transaction backup at, to survive node restarts.
     #[arg(long = "txpool.transactions-backup", alias = "txpool.journal", value_name = "PATH")]
     pub transactions_backup_path: Option<std::path::PathBuf>,
@@ -122,6 +130,21 @@ pub struct TxPoolArgs {
         conflicts_with = "transactions_backup_path"
     )]
     pub disable_transactions_backup: bool,
+
+    /// Persists all transactions of the pool, including blob sidecars, on shutdown and
+    /// restores them on startup.
+    #[arg(long = "txpool.persist-all")]
+    pub persist_all: bool,
+
+    /// Keeps the blob sidecars of finalized transactions in a compressed archive instead of
+    /// deleting them.
+    #[arg(long = "txpool.blob-archive")]
+    pub blob_archive: bool,
+
+    /// Number of blocks, up to and including the finalized block, to keep archived blob sidecars
+    /// for.
+    #[arg(long = "txpool.blob-archive-retention", value_name = "BLOCKS", default_value_t = DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS)]
+    pub blob_archive_retention: u64,
 }
 
 impl Default for TxPoolArgs {
@@ -151,8 +174,12 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_private_tx_blocks: MAX_PRIVATE_TRANSACTION_BLOCKS,
             transactions_backup_path: None,
             disable_transactions_backup: false,
+            persist_all: false,
+            blob_archive: false,
+            blob_archive_retention: DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS,
         }
     }
 }
@@ -194,6 +221,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            max_private_tx_blocks: self.max_private_tx_blocks,
         }
     }
 }
@@ -323,6 +323,14 @@ impl<D> ChainPath<D> {
         self.data_dir().join("blobstore")
     }
 
+    /// Returns the path to the blob archive directory for this chain where blobs of finalized
+    /// transactions are stored.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blob-archive`
+    pub fn blob_archive(&self) -> PathBuf {
+        self.data_dir().join("blob-archive")
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
@@ -330,6 +338,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the snapshot file of the entire transaction pool
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.rlp`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -351,6 +366,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the directory of blocks rejected by the engine for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bad_blocks`
+    pub fn bad_blocks(&self) -> PathBuf {
+        self.data_dir().join("bad_blocks")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
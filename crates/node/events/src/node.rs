@@ -277,8 +277,8 @@ impl NodeState {
                 let block = executed.sealed_block();
                 info!(number=block.number(), hash=?block.hash(), ?elapsed, "Block added to fork chain");
             }
-            BeaconConsensusEngineEvent::InvalidBlock(block) => {
-                warn!(number=block.number(), hash=?block.hash(), "Encountered invalid block");
+            BeaconConsensusEngineEvent::InvalidBlock(block, reason) => {
+                warn!(number=block.number(), hash=?block.hash(), %reason, "Encountered invalid block");
             }
         }
     }
@@ -1,6 +1,6 @@
 //! Loads and formats OP receipt RPC response.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, SignerRecoverable, TransactionMeta};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
@@ -231,25 +231,30 @@ impl OpReceiptBuilder {
     ) -> Result<Self, OpEthApiError> {
         let timestamp = meta.timestamp;
         let block_number = meta.block_number;
-        let core_receipt =
-            build_receipt(transaction, meta, receipt, all_receipts, None, |receipt_with_bloom| {
-                match receipt {
-                    OpReceipt::Legacy(_) => OpReceiptEnvelope::<Log>::Legacy(receipt_with_bloom),
-                    OpReceipt::Eip2930(_) => OpReceiptEnvelope::<Log>::Eip2930(receipt_with_bloom),
-                    OpReceipt::Eip1559(_) => OpReceiptEnvelope::<Log>::Eip1559(receipt_with_bloom),
-                    OpReceipt::Eip7702(_) => OpReceiptEnvelope::<Log>::Eip7702(receipt_with_bloom),
-                    OpReceipt::Deposit(receipt) => {
-                        OpReceiptEnvelope::<Log>::Deposit(OpDepositReceiptWithBloom::<Log> {
-                            receipt: OpDepositReceipt::<Log> {
-                                inner: receipt_with_bloom.receipt,
-                                deposit_nonce: receipt.deposit_nonce,
-                                deposit_receipt_version: receipt.deposit_receipt_version,
-                            },
-                            logs_bloom: receipt_with_bloom.logs_bloom,
-                        })
-                    }
+        let sender = transaction.recover_signer_unchecked().map_err(EthApiError::from)?;
+        let core_receipt = build_receipt(
+            Recovered::new_unchecked(transaction, sender),
+            meta,
+            receipt,
+            all_receipts,
+            None,
+            |receipt_with_bloom| match receipt {
+                OpReceipt::Legacy(_) => OpReceiptEnvelope::<Log>::Legacy(receipt_with_bloom),
+                OpReceipt::Eip2930(_) => OpReceiptEnvelope::<Log>::Eip2930(receipt_with_bloom),
+                OpReceipt::Eip1559(_) => OpReceiptEnvelope::<Log>::Eip1559(receipt_with_bloom),
+                OpReceipt::Eip7702(_) => OpReceiptEnvelope::<Log>::Eip7702(receipt_with_bloom),
+                OpReceipt::Deposit(receipt) => {
+                    OpReceiptEnvelope::<Log>::Deposit(OpDepositReceiptWithBloom::<Log> {
+                        receipt: OpDepositReceipt::<Log> {
+                            inner: receipt_with_bloom.receipt,
+                            deposit_nonce: receipt.deposit_nonce,
+                            deposit_receipt_version: receipt.deposit_receipt_version,
+                        },
+                        logs_bloom: receipt_with_bloom.logs_bloom,
+                    })
                 }
-            })?;
+            },
+        )?;
 
         let op_receipt_fields = OpReceiptFieldsBuilder::new(timestamp, block_number)
             .l1_block_info(chain_spec, transaction, l1_block_info)?
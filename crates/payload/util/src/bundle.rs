@@ -0,0 +1,628 @@
+//! Support for including bundles of transactions in built payloads.
+
+use alloy_consensus::transaction::Recovered;
+use alloy_primitives::{keccak256, map::HashMap, B256};
+use parking_lot::RwLock;
+use reth_primitives_traits::SignedTransaction;
+use reth_transaction_pool::{
+    error::InvalidPoolTransactionError, BestTransactions, PoolTransaction, ValidPoolTransaction,
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+
+/// Default maximum number of bundles kept in the [`BundlePool`] before the oldest bundles are
+/// evicted.
+pub const DEFAULT_MAX_BUNDLES: usize = 4096;
+
+/// Maximum number of blocks a bundle can target.
+pub const MAX_BUNDLE_BLOCK_RANGE: u64 = 30;
+
+/// Maximum number of transactions in a bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 100;
+
+/// A bundle of transactions that must be included atomically and in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadBundle<T> {
+    /// Hash of the bundle, the keccak256 hash of the concatenated transaction hashes.
+    pub hash: B256,
+    /// The transactions of the bundle.
+    pub transactions: Vec<Recovered<T>>,
+    /// Hashes of the transactions that are allowed to revert.
+    pub reverting_tx_hashes: Vec<B256>,
+    /// The first block the bundle is valid for.
+    pub block_number: u64,
+    /// The last block the bundle is valid for.
+    pub max_block_number: u64,
+    /// Minimum timestamp of the block the bundle is included in.
+    pub min_timestamp: Option<u64>,
+    /// Maximum timestamp of the block the bundle is included in.
+    pub max_timestamp: Option<u64>,
+    /// UUID that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl<T: SignedTransaction> PayloadBundle<T> {
+    /// Creates a new bundle that targets the given block.
+    pub fn new(transactions: Vec<Recovered<T>>, block_number: u64) -> Self {
+        let mut hashes = Vec::with_capacity(transactions.len() * 32);
+        for tx in &transactions {
+            hashes.extend_from_slice(tx.tx_hash().as_slice());
+        }
+
+        Self {
+            hash: keccak256(hashes),
+            transactions,
+            reverting_tx_hashes: Vec::new(),
+            block_number,
+            max_block_number: block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        }
+    }
+
+    /// Sets the last block the bundle is valid for.
+    pub const fn with_max_block_number(mut self, max_block_number: u64) -> Self {
+        self.max_block_number = max_block_number;
+        self
+    }
+
+    /// Sets the timestamp range of the block the bundle can be included in.
+    pub const fn with_timestamps(
+        mut self,
+        min_timestamp: Option<u64>,
+        max_timestamp: Option<u64>,
+    ) -> Self {
+        self.min_timestamp = min_timestamp;
+        self.max_timestamp = max_timestamp;
+        self
+    }
+
+    /// Sets the hashes of the transactions that are allowed to revert.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<B256>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Sets the replacement UUID of the bundle.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+
+    /// Returns `true` if the transaction with the given hash is allowed to revert.
+    pub fn can_revert(&self, tx_hash: &B256) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+
+    /// Returns the total gas limit of all transactions in the bundle.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.gas_limit()).sum()
+    }
+
+    /// Returns `true` if the bundle can be included in the block with the given number and
+    /// timestamp.
+    pub fn is_valid_at(&self, block_number: u64, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.is_none_or(|min| timestamp >= min) &&
+            self.max_timestamp.is_none_or(|max| max == 0 || timestamp <= max)
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle does not contain any transactions.
+    #[error("bundle contains no transactions")]
+    EmptyBundle,
+    /// The bundle contains more than [`MAX_BUNDLE_TRANSACTIONS`] transactions.
+    #[error("bundle contains {0} transactions, at most {MAX_BUNDLE_TRANSACTIONS} are allowed")]
+    TooManyTransactions(usize),
+    /// The block range of the bundle is invalid.
+    #[error("invalid block range {block_number}..={max_block_number}")]
+    InvalidBlockRange {
+        /// The first block the bundle is valid for.
+        block_number: u64,
+        /// The last block the bundle is valid for.
+        max_block_number: u64,
+    },
+    /// The last block the bundle is valid for was already built.
+    #[error("bundle targets past block {max_block_number}, next block is {next_block}")]
+    BlockInPast {
+        /// The last block the bundle is valid for.
+        max_block_number: u64,
+        /// The next block that is built.
+        next_block: u64,
+    },
+}
+
+/// Identifies a bundle in the pool, the same bundle can be submitted for multiple blocks.
+type BundleKey = (u64, B256);
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// All bundles by the first block they are valid for.
+    by_block: BTreeMap<u64, Vec<Arc<PayloadBundle<T>>>>,
+    /// Bundle keys by replacement UUID.
+    by_uuid: HashMap<String, BundleKey>,
+    /// Submission id of every bundle.
+    ids: HashMap<BundleKey, u64>,
+    /// Bundle keys by submission id, used to evict the oldest bundles.
+    by_id: BTreeMap<u64, BundleKey>,
+    /// Submission id of the next bundle.
+    next_id: u64,
+    /// The next block that is built, bundles for earlier blocks are rejected.
+    next_block: u64,
+    /// Maximum number of bundles in the pool.
+    max_bundles: usize,
+}
+
+impl<T> BundlePoolInner<T> {
+    /// Removes the bundle with the given key.
+    fn remove(&mut self, (block_number, hash): BundleKey) -> Option<Arc<PayloadBundle<T>>> {
+        let bundles = self.by_block.get_mut(&block_number)?;
+        let bundle = bundles.remove(bundles.iter().position(|bundle| bundle.hash == hash)?);
+        if bundles.is_empty() {
+            self.by_block.remove(&block_number);
+        }
+
+        if let Some(id) = self.ids.remove(&(block_number, hash)) {
+            self.by_id.remove(&id);
+        }
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_uuid.remove(uuid);
+        }
+        Some(bundle)
+    }
+
+    /// Removes all bundles that can no longer be included in `block_number` or any later block.
+    fn remove_expired(&mut self, block_number: u64) {
+        // bundles that start at `block_number` or later are still valid
+        let expired = self
+            .by_block
+            .range(..block_number)
+            .flat_map(|(_, bundles)| bundles)
+            .filter(|bundle| bundle.max_block_number < block_number)
+            .map(|bundle| (bundle.block_number, bundle.hash))
+            .collect::<Vec<_>>();
+        for key in expired {
+            self.remove(key);
+        }
+    }
+}
+
+/// A shareable pool of bundles that are included by the payload builder.
+///
+/// Bundles are stored by the first block they target and are removed once the chain advances
+/// past their last target block. Submitting a bundle with the replacement UUID of a pending bundle
+/// replaces it. Once the pool is full, the oldest submitted bundles are evicted.
+#[derive(Debug)]
+pub struct BundlePool<T> {
+    inner: Arc<RwLock<BundlePoolInner<T>>>,
+}
+
+impl<T> BundlePool<T> {
+    /// Creates a new pool that holds at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner {
+                by_block: BTreeMap::new(),
+                by_uuid: HashMap::default(),
+                ids: HashMap::default(),
+                by_id: BTreeMap::new(),
+                next_id: 0,
+                next_block: 0,
+                max_bundles,
+            })),
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().ids.len()
+    }
+
+    /// Returns `true` if the pool contains no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// A pending bundle with the same replacement UUID is replaced. If the pool is full, the
+    /// oldest bundle is evicted.
+    pub fn add_bundle(&self, bundle: PayloadBundle<T>) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(BundlePoolError::TooManyTransactions(bundle.transactions.len()))
+        }
+        if bundle.block_number == 0 ||
+            bundle.max_block_number < bundle.block_number ||
+            bundle.max_block_number - bundle.block_number >= MAX_BUNDLE_BLOCK_RANGE
+        {
+            return Err(BundlePoolError::InvalidBlockRange {
+                block_number: bundle.block_number,
+                max_block_number: bundle.max_block_number,
+            })
+        }
+
+        let mut inner = self.inner.write();
+        if bundle.max_block_number < inner.next_block {
+            return Err(BundlePoolError::BlockInPast {
+                max_block_number: bundle.max_block_number,
+                next_block: inner.next_block,
+            })
+        }
+
+        if let Some(replaced) =
+            bundle.replacement_uuid.as_ref().and_then(|uuid| inner.by_uuid.get(uuid).copied())
+        {
+            inner.remove(replaced);
+        }
+
+        // resubmitting an identical bundle is a no-op
+        let hash = bundle.hash;
+        let key = (bundle.block_number, hash);
+        if inner.ids.contains_key(&key) {
+            return Ok(hash)
+        }
+
+        while inner.ids.len() >= inner.max_bundles.max(1) {
+            let Some((_, oldest)) = inner.by_id.pop_first() else { break };
+            inner.remove(oldest);
+        }
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.ids.insert(key, id);
+        inner.by_id.insert(id, key);
+        if let Some(uuid) = &bundle.replacement_uuid {
+            inner.by_uuid.insert(uuid.clone(), key);
+        }
+        inner.by_block.entry(bundle.block_number).or_default().push(Arc::new(bundle));
+
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given replacement UUID.
+    ///
+    /// Returns the removed bundle, if any.
+    pub fn cancel_bundle(&self, replacement_uuid: &str) -> Option<Arc<PayloadBundle<T>>> {
+        let mut inner = self.inner.write();
+        let key = inner.by_uuid.get(replacement_uuid).copied()?;
+        inner.remove(key)
+    }
+
+    /// Removes all bundles with the given hash.
+    ///
+    /// Returns the removed bundles.
+    pub fn remove_bundle(&self, hash: &B256) -> Vec<Arc<PayloadBundle<T>>> {
+        let mut inner = self.inner.write();
+        let keys =
+            inner.ids.keys().filter(|(_, bundle)| bundle == hash).copied().collect::<Vec<_>>();
+        keys.into_iter().filter_map(|key| inner.remove(key)).collect()
+    }
+
+    /// Removes all bundles that can no longer be included in the block with the given number or
+    /// any later block.
+    ///
+    /// Bundles that only target earlier blocks are rejected until this is called with a lower
+    /// block number.
+    pub fn remove_expired(&self, block_number: u64) {
+        let mut inner = self.inner.write();
+        inner.next_block = block_number;
+        inner.remove_expired(block_number);
+    }
+}
+
+impl<T: SignedTransaction> BundlePool<T> {
+    /// Returns all bundles that can be included in the block with the given number and
+    /// timestamp.
+    pub fn bundles_for_block(
+        &self,
+        block_number: u64,
+        timestamp: u64,
+    ) -> Vec<Arc<PayloadBundle<T>>> {
+        self.inner
+            .read()
+            .by_block
+            .range(block_number.saturating_sub(MAX_BUNDLE_BLOCK_RANGE)..=block_number)
+            .flat_map(|(_, bundles)| bundles)
+            .filter(|bundle| bundle.is_valid_at(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T> Clone for BundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<T> PartialEq for BundlePool<T> {
+    /// Pools are equal if they share the same bundles.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> Eq for BundlePool<T> {}
+
+impl<T> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+/// An item yielded by [`BundleBestTransactions`].
+#[derive(Debug, Clone)]
+pub enum BundleOrTransaction<Tx, T> {
+    /// A single transaction.
+    Transaction(Tx),
+    /// A bundle that must be included atomically.
+    Bundle(Arc<PayloadBundle<T>>),
+}
+
+/// Iterator that interleaves bundles with the transactions of a [`BestTransactions`] iterator.
+///
+/// Bundles are ordered by the profit per gas they pay to the coinbase, which includes direct
+/// payments to the coinbase in addition to the priority fees and has to be determined by
+/// simulating the bundles. A bundle is yielded before any transaction that pays a lower tip.
+#[derive(Debug)]
+pub struct BundleBestTransactions<I: Iterator, T> {
+    /// Remaining bundles with their coinbase profit per gas, ordered by descending profit.
+    bundles: VecDeque<(u128, Arc<PayloadBundle<T>>)>,
+    /// The transactions to interleave the bundles with.
+    best: I,
+    /// The next transaction of `best` that has not been yielded yet.
+    next_tx: Option<I::Item>,
+    /// Base fee of the block that is being built.
+    base_fee: u64,
+}
+
+impl<I, Tx, T> BundleBestTransactions<I, T>
+where
+    I: BestTransactions<Item = Arc<ValidPoolTransaction<Tx>>>,
+    Tx: PoolTransaction,
+{
+    /// Creates a new iterator that interleaves the given bundles with the transactions of
+    /// `best`.
+    ///
+    /// Every bundle is given together with the profit per gas it pays to the coinbase.
+    pub fn new(best: I, mut bundles: Vec<(u128, Arc<PayloadBundle<T>>)>, base_fee: u64) -> Self {
+        // stable sort to keep the submission order for bundles with the same profit
+        bundles.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        Self { bundles: bundles.into(), best, next_tx: None, base_fee }
+    }
+
+    /// Marks the transaction as invalid, see [`BestTransactions::mark_invalid`].
+    pub fn mark_invalid(&mut self, transaction: &I::Item, kind: InvalidPoolTransactionError) {
+        // a buffered transaction of the same sender depends on the invalid transaction
+        if self.next_tx.as_ref().is_some_and(|tx| tx.sender() == transaction.sender()) {
+            self.next_tx = None;
+        }
+        self.best.mark_invalid(transaction, kind);
+    }
+
+    /// Skips all blob transactions, see [`BestTransactions::skip_blobs`].
+    pub fn skip_blobs(&mut self) {
+        if self.next_tx.as_ref().is_some_and(|tx| tx.is_eip4844()) {
+            self.next_tx = None;
+        }
+        self.best.skip_blobs();
+    }
+}
+
+impl<I, Tx, T> Iterator for BundleBestTransactions<I, T>
+where
+    I: BestTransactions<Item = Arc<ValidPoolTransaction<Tx>>>,
+    Tx: PoolTransaction,
+{
+    type Item = BundleOrTransaction<I::Item, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_tx.is_none() {
+            self.next_tx = self.best.next();
+        }
+
+        let tx_tip = self
+            .next_tx
+            .as_ref()
+            .map(|tx| tx.effective_tip_per_gas(self.base_fee).unwrap_or_default());
+
+        match (self.bundles.front(), tx_tip) {
+            (Some((bundle_profit, _)), Some(tx_tip)) if *bundle_profit < tx_tip => {
+                self.next_tx.take().map(BundleOrTransaction::Transaction)
+            }
+            (Some(_), _) => {
+                self.bundles.pop_front().map(|(_, bundle)| BundleOrTransaction::Bundle(bundle))
+            }
+            (None, _) => self.next_tx.take().map(BundleOrTransaction::Transaction),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::test_utils::{MockTransaction, MockTransactionFactory};
+
+    type Consensus = <MockTransaction as PoolTransaction>::Consensus;
+    type MockValidTx = Arc<ValidPoolTransaction<MockTransaction>>;
+
+    fn bundle(txs: Vec<MockTransaction>, block_number: u64) -> PayloadBundle<Consensus> {
+        PayloadBundle::new(txs.into_iter().map(|tx| tx.into_consensus()).collect(), block_number)
+    }
+
+    /// Yields the given transactions in order.
+    #[derive(Debug, Default)]
+    struct FixedBest {
+        txs: VecDeque<MockValidTx>,
+        skip_blobs: bool,
+    }
+
+    impl Iterator for FixedBest {
+        type Item = MockValidTx;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let skip_blobs = self.skip_blobs;
+            self.txs.retain(|tx| !(skip_blobs && tx.is_eip4844()));
+            self.txs.pop_front()
+        }
+    }
+
+    impl BestTransactions for FixedBest {
+        fn mark_invalid(&mut self, transaction: &Self::Item, _kind: InvalidPoolTransactionError) {
+            self.txs.retain(|tx| tx.sender() != transaction.sender());
+        }
+
+        fn no_updates(&mut self) {}
+
+        fn set_skip_blobs(&mut self, skip_blobs: bool) {
+            self.skip_blobs = skip_blobs;
+        }
+    }
+
+    #[test]
+    fn pool_replaces_and_cancels_by_uuid() {
+        let pool = BundlePool::default();
+
+        let first = bundle(vec![MockTransaction::eip1559()], 10)
+            .with_replacement_uuid(Some("uuid".to_string()));
+        let first_hash = pool.add_bundle(first).unwrap();
+        assert_eq!(pool.len(), 1);
+
+        let second = bundle(vec![MockTransaction::eip1559()], 10)
+            .with_replacement_uuid(Some("uuid".to_string()));
+        let second_hash = pool.add_bundle(second).unwrap();
+        assert_ne!(first_hash, second_hash);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.bundles_for_block(10, 0)[0].hash, second_hash);
+
+        assert!(pool.cancel_bundle("uuid").is_some());
+        assert!(pool.is_empty());
+        assert!(pool.cancel_bundle("uuid").is_none());
+    }
+
+    #[test]
+    fn pool_selects_by_block_and_expires() {
+        let pool = BundlePool::default();
+        pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 10).with_max_block_number(12))
+            .unwrap();
+        pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 11)).unwrap();
+
+        assert_eq!(pool.bundles_for_block(9, 0).len(), 0);
+        assert_eq!(pool.bundles_for_block(10, 0).len(), 1);
+        assert_eq!(pool.bundles_for_block(11, 0).len(), 2);
+        assert_eq!(pool.bundles_for_block(12, 0).len(), 1);
+
+        pool.remove_expired(12);
+        assert_eq!(pool.len(), 1);
+        pool.remove_expired(13);
+        assert!(pool.is_empty());
+
+        assert_eq!(
+            pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 10).with_max_block_number(9)),
+            Err(BundlePoolError::InvalidBlockRange { block_number: 10, max_block_number: 9 })
+        );
+    }
+
+    #[test]
+    fn pool_rejects_past_blocks() {
+        let pool = BundlePool::default();
+        pool.remove_expired(10);
+
+        assert_eq!(
+            pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 8).with_max_block_number(9)),
+            Err(BundlePoolError::BlockInPast { max_block_number: 9, next_block: 10 })
+        );
+        pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 9).with_max_block_number(10))
+            .unwrap();
+        pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 10)).unwrap();
+        assert_eq!(pool.bundles_for_block(10, 0).len(), 2);
+    }
+
+    #[test]
+    fn pool_evicts_oldest_bundles() {
+        let pool = BundlePool::new(2);
+        let first = pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 10)).unwrap();
+        let second = pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 11)).unwrap();
+
+        // the first bundle is evicted to make room for the third one
+        pool.add_bundle(bundle(vec![MockTransaction::eip1559()], 12)).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert!(pool.remove_bundle(&first).is_empty());
+        assert_eq!(pool.remove_bundle(&second).len(), 1);
+    }
+
+    #[test]
+    fn pool_rejects_too_many_transactions() {
+        let pool = BundlePool::default();
+        let txs = vec![MockTransaction::eip1559(); MAX_BUNDLE_TRANSACTIONS + 1];
+        assert_eq!(
+            pool.add_bundle(bundle(txs, 10)),
+            Err(BundlePoolError::TooManyTransactions(MAX_BUNDLE_TRANSACTIONS + 1))
+        );
+        assert!(pool.is_empty());
+
+        let txs = vec![MockTransaction::eip1559(); MAX_BUNDLE_TRANSACTIONS];
+        pool.add_bundle(bundle(txs, 10)).unwrap();
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn interleaves_bundles_by_profit() {
+        let mut factory = MockTransactionFactory::default();
+        let base_fee = 10;
+        let tx = || MockTransaction::eip1559().with_gas_limit(21_000).with_max_fee(100);
+        let cheap = factory.validated_arc(tx().with_priority_fee(1));
+        let expensive = factory.validated_arc(tx().with_priority_fee(50));
+
+        // the bundle's transaction pays a low tip, but the bundle pays the coinbase directly
+        let paying = Arc::new(bundle(vec![tx().with_priority_fee(1)], 1));
+        let bundle = Arc::new(bundle(vec![tx().with_priority_fee(5)], 1));
+        let bundles = vec![(5, bundle.clone()), (60, paying.clone())];
+        let best =
+            FixedBest { txs: vec![expensive.clone(), cheap.clone()].into(), skip_blobs: false };
+        let mut txs = BundleBestTransactions::new(best, bundles, base_fee);
+
+        assert!(
+            matches!(txs.next(), Some(BundleOrTransaction::Bundle(next)) if next.hash == paying.hash)
+        );
+        assert!(
+            matches!(txs.next(), Some(BundleOrTransaction::Transaction(tx)) if Arc::ptr_eq(&tx, &expensive))
+        );
+        assert!(
+            matches!(txs.next(), Some(BundleOrTransaction::Bundle(next)) if next.hash == bundle.hash)
+        );
+        assert!(
+            matches!(txs.next(), Some(BundleOrTransaction::Transaction(tx)) if Arc::ptr_eq(&tx, &cheap))
+        );
+        assert!(txs.next().is_none());
+    }
+
+    #[test]
+    fn forwards_invalid_transactions_and_blobs() {
+        let mut factory = MockTransactionFactory::default();
+        let first = MockTransaction::eip1559().with_priority_fee(1);
+        let dependent = factory.validated_arc(first.next());
+        let first = factory.validated_arc(first);
+        let blob = factory.validated_arc(MockTransaction::eip4844());
+
+        // the bundle is yielded first and the dependent transaction is buffered
+        let bundles = vec![(100, Arc::new(bundle(vec![MockTransaction::eip1559()], 1)))];
+        let best = FixedBest { txs: vec![dependent, blob].into(), skip_blobs: false };
+        let mut txs = BundleBestTransactions::new(best, bundles, 0);
+        assert!(matches!(txs.next(), Some(BundleOrTransaction::Bundle(_))));
+
+        txs.mark_invalid(&first, InvalidPoolTransactionError::Underpriced);
+        txs.skip_blobs();
+        assert!(txs.next().is_none());
+    }
+}
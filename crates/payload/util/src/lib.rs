@@ -8,8 +8,13 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod bundle;
 mod traits;
 mod transaction;
 
+pub use bundle::{
+    BundleBestTransactions, BundleOrTransaction, BundlePool, BundlePoolError, PayloadBundle,
+    DEFAULT_MAX_BUNDLES, MAX_BUNDLE_BLOCK_RANGE, MAX_BUNDLE_TRANSACTIONS,
+};
 pub use traits::{BestPayloadTransactions, NoopPayloadTransactions, PayloadTransactions};
 pub use transaction::{PayloadTransactionsChain, PayloadTransactionsFixed};
@@ -1,4 +1,5 @@
 mod receipts;
+mod sender_recovery;
 mod set;
 mod static_file;
 mod user;
@@ -10,7 +11,7 @@ use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, S
 pub use set::SegmentSet;
 pub use static_file::{
     Headers as StaticFileHeaders, Receipts as StaticFileReceipts,
-    Transactions as StaticFileTransactions,
+    TransactionSenders as StaticFileTransactionSenders, Transactions as StaticFileTransactions,
 };
 use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
@@ -0,0 +1,59 @@
+//! Common transaction senders pruning logic shared between user and static file pruning segments.
+//!
+//! - [`crate::segments::user::SenderRecovery`] is responsible for pruning transaction senders
+//!   according to the user-configured settings (for example, on a full node or with a custom prune
+//!   config)
+//! - [`crate::segments::static_file::TransactionSenders`] is responsible for pruning transaction
+//!   senders on an archive node after static file producer has finished
+
+use crate::{db_ext::DbTxPruneExt, segments::PruneInput, PrunerError};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::{BlockReader, DBProvider, TransactionsProvider};
+use reth_prune_types::{SegmentOutput, SegmentOutputCheckpoint};
+use tracing::trace;
+
+pub(crate) fn prune<Provider>(
+    provider: &Provider,
+    input: PruneInput,
+) -> Result<SegmentOutput, PrunerError>
+where
+    Provider: DBProvider<Tx: DbTxMut> + TransactionsProvider + BlockReader,
+{
+    let tx_range = match input.get_next_tx_num_range(provider)? {
+        Some(range) => range,
+        None => {
+            trace!(target: "pruner", "No transaction senders to prune");
+            return Ok(SegmentOutput::done())
+        }
+    };
+    let tx_range_end = *tx_range.end();
+
+    let mut limiter = input.limiter;
+
+    let mut last_pruned_transaction = tx_range_end;
+    let (pruned, done) = provider.tx_ref().prune_table_with_range::<tables::TransactionSenders>(
+        tx_range,
+        &mut limiter,
+        |_| false,
+        |row| last_pruned_transaction = row.0,
+    )?;
+    trace!(target: "pruner", %pruned, %done, "Pruned transaction senders");
+
+    let last_pruned_block = provider
+        .transaction_block(last_pruned_transaction)?
+        .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+        // If there's more transaction senders to prune, set the checkpoint block number to
+        // previous, so we could finish pruning its transaction senders on the next run.
+        .checked_sub(if done { 0 } else { 1 });
+
+    let progress = limiter.progress(done);
+
+    Ok(SegmentOutput {
+        progress,
+        pruned,
+        checkpoint: Some(SegmentOutputCheckpoint {
+            block_number: last_pruned_block,
+            tx_number: Some(last_pruned_transaction),
+        }),
+    })
+}
@@ -1,7 +1,9 @@
 mod headers;
 mod receipts;
+mod transaction_senders;
 mod transactions;
 
 pub use headers::Headers;
 pub use receipts::Receipts;
+pub use transaction_senders::TransactionSenders;
 pub use transactions::Transactions;
@@ -0,0 +1,153 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db_api::transaction::DbTxMut;
+use reth_provider::{
+    providers::StaticFileProvider, BlockReader, DBProvider, StaticFileProviderFactory,
+    TransactionsProvider,
+};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use reth_static_file_types::StaticFileSegment;
+
+#[derive(Debug)]
+pub struct TransactionSenders<N> {
+    static_file_provider: StaticFileProvider<N>,
+}
+
+impl<N> TransactionSenders<N> {
+    pub const fn new(static_file_provider: StaticFileProvider<N>) -> Self {
+        Self { static_file_provider }
+    }
+}
+
+impl<Provider> Segment<Provider> for TransactionSenders<Provider::Primitives>
+where
+    Provider:
+        StaticFileProviderFactory + DBProvider<Tx: DbTxMut> + TransactionsProvider + BlockReader,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::SenderRecovery
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        self.static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::TransactionSenders)
+            .map(PruneMode::before_inclusive)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::StaticFile
+    }
+
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        crate::segments::sender_recovery::prune(provider, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::B256;
+    use reth_db_api::tables;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::{
+        DatabaseProviderFactory, StaticFileProviderFactory, StaticFileWriter, TransactionsProvider,
+    };
+    use reth_prune_types::{PruneMode, PruneProgress, SegmentOutput, SegmentOutputCheckpoint};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_static_file_types::StaticFileSegment;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    fn segment_mode<Provider>(
+        segment: &impl Segment<Provider>,
+        _provider: &Provider,
+    ) -> Option<PruneMode> {
+        segment.mode()
+    }
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=9,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let mut transaction_senders = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body().transactions {
+                transaction_senders.push((
+                    transaction_senders.len() as u64,
+                    transaction.recover_signer().expect("recover signer"),
+                ));
+            }
+        }
+        db.insert_transaction_senders(transaction_senders.clone())
+            .expect("insert transaction senders");
+
+        let static_file_provider = db.factory.static_file_provider();
+        let segment = super::TransactionSenders::new(static_file_provider.clone());
+
+        // nothing is pruned until the senders are moved to static files
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_eq!(segment_mode(&segment, &provider), None);
+        drop(provider);
+
+        // move the senders of the first blocks to static files
+        let to_block = 5;
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::TransactionSenders).unwrap();
+        let mut senders = transaction_senders.iter();
+        for block in &blocks[..=to_block as usize] {
+            writer.increment_block(block.number).unwrap();
+            for (tx_num, sender) in senders.by_ref().take(block.transaction_count()) {
+                writer.append_transaction_sender(*tx_num, sender).unwrap();
+            }
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        let static_senders = blocks[..=to_block as usize]
+            .iter()
+            .map(|block| block.transaction_count())
+            .sum::<usize>();
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_eq!(segment_mode(&segment, &provider), Some(PruneMode::before_inclusive(to_block)));
+
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+        let result = segment.prune(&provider, input).unwrap();
+        assert_eq!(
+            result,
+            SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned: static_senders,
+                checkpoint: Some(SegmentOutputCheckpoint {
+                    block_number: Some(to_block),
+                    tx_number: Some(static_senders as u64 - 1),
+                }),
+            }
+        );
+        provider.commit().expect("commit");
+
+        // only the senders that are not in static files are left in the database
+        assert_eq!(
+            db.table::<tables::TransactionSenders>().unwrap(),
+            transaction_senders[static_senders..]
+        );
+        // and all senders are still available
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .senders_by_tx_range(0..transaction_senders.len() as u64)
+                .unwrap(),
+            transaction_senders.into_iter().map(|(_, sender)| sender).collect::<Vec<_>>()
+        );
+    }
+}
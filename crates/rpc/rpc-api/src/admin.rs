@@ -1,6 +1,56 @@
+use alloy_primitives::Address;
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_network_peers::{AnyNode, NodeRecord};
+use reth_network_api::BannedSender;
+use reth_network_peers::{AnyNode, NodeRecord, PeerId};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+
+/// An entry of the network's ban list, as returned by `admin_listBans`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BanListEntry {
+    /// The banned peer, if the entry bans a peer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<PeerId>,
+    /// The banned ip address, if the entry bans an ip address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+    /// Number of seconds until the ban is lifted, `None` if the ban is indefinite.
+    pub expires_in: Option<u64>,
+}
+
+/// Reputation of a peer in the peer set, as returned by `admin_peerReputation`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerReputation {
+    /// The identifier of the peer.
+    pub id: PeerId,
+    /// The tcp address of the peer.
+    pub address: SocketAddr,
+    /// The kind of the peer: `basic`, `static` or `trusted`.
+    pub kind: String,
+    /// The current reputation of the peer.
+    pub reputation: i32,
+    /// Whether the reputation is below the ban threshold.
+    pub banned: bool,
+    /// The most recent reputation changes that were applied to the peer, oldest first.
+    pub recent_changes: Vec<String>,
+}
+
+/// Allowed and denied IP networks in CIDR notation, as used by `admin_netRestrict` and
+/// `admin_setNetRestrict`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetRestrict {
+    /// Networks the node is restricted to, e.g. `10.0.0.0/8`. Empty means all networks are
+    /// allowed.
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// Networks the node refuses to communicate with.
+    #[serde(default)]
+    pub denied: Vec<String>,
+}
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -45,4 +95,65 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the transaction senders that are temporarily banned because their transactions
+    /// were repeatedly rejected by the transaction pool.
+    #[method(name = "bannedSenders")]
+    async fn banned_senders(&self) -> RpcResult<Vec<BannedSender>>;
+
+    /// Lifts the ban of the given transaction sender.
+    ///
+    /// Returns true if the sender was banned.
+    #[method(name = "unbanSender")]
+    async fn unban_sender(&self, sender: Address) -> RpcResult<bool>;
+
+    /// Lifts the bans of all transaction senders.
+    #[method(name = "clearSenderBans")]
+    async fn clear_sender_bans(&self) -> RpcResult<()>;
+
+    /// Bans the given peer for the given number of seconds, or indefinitely if no duration is
+    /// given, and disconnects it.
+    ///
+    /// Returns true if the peer was not banned before.
+    #[method(name = "banPeer")]
+    async fn ban_peer(&self, record: AnyNode, duration: Option<u64>) -> RpcResult<bool>;
+
+    /// Lifts the ban of the given peer and resets its reputation.
+    ///
+    /// Returns true if the peer was banned.
+    #[method(name = "unbanPeer")]
+    async fn unban_peer(&self, record: AnyNode) -> RpcResult<bool>;
+
+    /// Bans the given ip address for the given number of seconds, or indefinitely if no duration
+    /// is given, and disconnects all peers at this address.
+    ///
+    /// Returns false if the address is not globally routable and can't be banned.
+    #[method(name = "banIp")]
+    async fn ban_ip(&self, ip: IpAddr, duration: Option<u64>) -> RpcResult<bool>;
+
+    /// Lifts the ban of the given ip address.
+    ///
+    /// Returns true if the ip address was banned.
+    #[method(name = "unbanIp")]
+    async fn unban_ip(&self, ip: IpAddr) -> RpcResult<bool>;
+
+    /// Returns all banned peers and ip addresses.
+    #[method(name = "listBans")]
+    async fn list_bans(&self) -> RpcResult<Vec<BanListEntry>>;
+
+    /// Returns the reputation of all peers in the peer set and the most recent reputation changes
+    /// that were applied to them.
+    #[method(name = "peerReputation")]
+    async fn peer_reputation(&self) -> RpcResult<Vec<PeerReputation>>;
+
+    /// Returns the IP networks the node is restricted to and the networks it refuses to
+    /// communicate with.
+    #[method(name = "netRestrict")]
+    fn net_restrict(&self) -> RpcResult<NetRestrict>;
+
+    /// Replaces the allowed and denied IP networks.
+    ///
+    /// Connected peers outside the allowed networks are disconnected.
+    #[method(name = "setNetRestrict")]
+    fn set_net_restrict(&self, restrict: NetRestrict) -> RpcResult<()>;
 }
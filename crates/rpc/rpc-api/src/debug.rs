@@ -1,5 +1,6 @@
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
+use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
@@ -8,11 +9,109 @@ use alloy_rpc_types_trace::geth::{
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A block that was rejected as invalid, as returned by `debug_getBadBlocks`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlock<B = Block> {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The block, including all transactions.
+    pub block: B,
+    /// The RLP encoded block.
+    pub rlp: Bytes,
+    /// Why the block was rejected.
+    pub reason: String,
+}
+
+/// A blob of a finalized transaction, as returned by `debug_getBlobsByBlock` and
+/// `debug_getBlobsByVersionedHashes`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedBlob {
+    /// The block the transaction was included in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The versioned hash of the blob.
+    pub versioned_hash: B256,
+    /// The blob.
+    pub blob: Bytes,
+    /// The KZG proof of the blob, or its cell proofs for EIP-7594 sidecars.
+    pub proofs: Vec<Bytes>,
+}
+
+/// A page of accounts, as returned by `debug_accountRange`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRangeResult {
+    /// The state root of the block the accounts were dumped at.
+    pub root: B256,
+    /// The dumped accounts.
+    ///
+    /// Accounts are keyed by their address, or by `pre(<hashed address>)` if the address is not
+    /// known.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// The hashed address of the first account of the next page, if there are more accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Bytes>,
+}
+
+/// An account of a state dump.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, in decimal.
+    pub balance: String,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The hash of the account's code.
+    pub code_hash: B256,
+    /// The code of the account, unless code was excluded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The non-zero storage slots of the account keyed by hashed slot, unless storage was
+    /// excluded.
+    ///
+    /// Values are hex encoded without the `0x` prefix.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, String>,
+    /// The address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account.
+    #[serde(rename = "key")]
+    pub hashed_address: B256,
+}
+
+/// A page of storage slots, as returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots keyed by hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The hashed slot of the first slot of the next page, or `None` if there are no more slots.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot of a [`StorageRangeResult`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The preimage of the hashed slot, if known.
+    pub key: Option<B256>,
+    /// The value of the slot.
+    pub value: B256,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
-pub trait DebugApi {
+pub trait DebugApi<B: RpcObject> {
     /// Returns an RLP-encoded header.
     #[method(name = "getRawHeader")]
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes>;
@@ -35,18 +134,44 @@ pub trait DebugApi {
     #[method(name = "getRawReceipts")]
     async fn raw_receipts(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>>;
 
-    /// Returns an array of recent bad blocks that the client has seen on the network.
+    /// Returns an array of recent bad blocks that the client has seen on the network, along with
+    /// the reason each block was rejected.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock<B>>>;
+
+    /// Returns the archived blobs of all blob transactions included in the given block.
+    ///
+    /// Requires the blob archive of the transaction pool to be enabled, only blobs of finalized
+    /// blocks within the retention window of the archive are available.
+    #[method(name = "getBlobsByBlock")]
+    async fn blobs_by_block(&self, block_id: BlockId) -> RpcResult<Vec<ArchivedBlob>>;
+
+    /// Returns the archived blobs for the given versioned hashes.
+    ///
+    /// The response has the same length as the request, blobs that are not archived are `null`.
+    /// At most 128 versioned hashes can be requested at once.
+    #[method(name = "getBlobsByVersionedHashes")]
+    async fn blobs_by_versioned_hashes(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<ArchivedBlob>>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
     /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    ///
+    /// The traces are streamed as one [`BlockTraceResult`] per block in ascending block order.
+    /// For the third parameter see [`GethDebugTracingOptions`] reference.
+    #[subscription(
+        name = "traceChain",
+        unsubscribe = "unsubscribeTraceChain",
+        item = BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -166,16 +291,19 @@ pub trait DebugApi {
     ///
     /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
     /// exist in db are skipped. NB: geth by default does not store preimages.
+    ///
+    /// Reth does not store preimages, so all accounts are returned as incomplete regardless of
+    /// `incompletes`.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
-        block_number: BlockNumberOrTag,
+        block_id: BlockId,
         start: Bytes,
         max_results: u64,
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRangeResult>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -259,17 +387,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -281,8 +410,8 @@ pub trait DebugApi {
     async fn debug_intermediate_roots(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -335,16 +464,18 @@ pub trait DebugApi {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// Replays a block that has been rejected as invalid and writes the trace of each transaction
+    /// to a separate file in the temporary directory.
+    ///
+    /// Returns the paths of the written files.
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Used to obtain info about a block.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
@@ -388,7 +519,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -397,8 +528,8 @@ pub trait DebugApi {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
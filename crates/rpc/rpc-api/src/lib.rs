@@ -31,6 +31,13 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use admin::{BanListEntry, NetRestrict, PeerReputation};
+pub use debug::{
+    AccountRangeResult, ArchivedBlob, BadBlock, DumpAccount, StorageRangeEntry, StorageRangeResult,
+};
+pub use reth_network_api::BannedSender;
+pub use txpool::{TxpoolDiff, TxpoolDiffFilter, TxpoolDiscardReason, TxpoolSubpool};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,8 +45,10 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        hardhat::HardhatApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
@@ -53,7 +62,7 @@ pub mod servers {
     };
     pub use reth_rpc_eth_api::{
         self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthFilterApiServer,
-        EthPubSubApiServer, L2EthApiExtServer,
+        EthPrivateTransactionApiServer, EthPubSubApiServer, L2EthApiExtServer,
     };
 }
 
@@ -84,6 +93,6 @@ pub mod clients {
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
-        L2EthApiExtServer,
+        EthPrivateTransactionApiClient, L2EthApiExtServer,
     };
 }
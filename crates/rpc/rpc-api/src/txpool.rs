@@ -1,7 +1,103 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// The sub-pool a transaction is held in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxpoolSubpool {
+    /// Transactions that are ready to be included in the next block.
+    Pending,
+    /// Transactions whose fee cap is below the current base fee.
+    Basefee,
+    /// Transactions with a nonce gap or insufficient balance.
+    Queued,
+    /// Blob transactions whose blob fee cap is below the current blob fee.
+    Blob,
+}
+
+/// Why a transaction was discarded from the pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolDiscardReason {
+    /// Evicted to enforce the pool limits.
+    Evicted,
+    /// No longer executable on top of the current state.
+    Outdated,
+    /// Explicitly removed from the pool.
+    Removed,
+}
+
+/// A change of the transaction pool, emitted by `txpool_subscribeDiffs`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TxpoolDiff<T> {
+    /// A new transaction was added to the pool.
+    Added {
+        /// The added transaction.
+        transaction: T,
+        /// The sub-pool the transaction was added to.
+        subpool: TxpoolSubpool,
+    },
+    /// A parked transaction was promoted to the pending sub-pool.
+    Promoted {
+        /// The promoted transaction.
+        transaction: T,
+    },
+    /// A transaction was moved to the basefee, blob or queued sub-pool.
+    Parked {
+        /// The parked transaction.
+        transaction: T,
+        /// The sub-pool the transaction was moved to.
+        subpool: TxpoolSubpool,
+    },
+    /// A transaction was replaced by another transaction of the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The replaced transaction.
+        transaction: T,
+        /// The hash of the replacement.
+        replaced_by: B256,
+    },
+    /// A transaction was included in a block.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// The mined transaction.
+        transaction: T,
+        /// The hash of the block the transaction was included in.
+        block_hash: B256,
+    },
+    /// A transaction was discarded.
+    Discarded {
+        /// The discarded transaction.
+        transaction: T,
+        /// Why the transaction was discarded.
+        reason: TxpoolDiscardReason,
+    },
+}
+
+/// Filter for `txpool_subscribeDiffs`.
+///
+/// A transaction matches if its sender is one of `from` and its recipient is one of `to`, empty
+/// lists match all transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TxpoolDiffFilter {
+    /// Senders to match.
+    pub from: Vec<Address>,
+    /// Recipients to match.
+    pub to: Vec<Address>,
+}
+
+impl TxpoolDiffFilter {
+    /// Returns `true` if a transaction with the given sender and recipient matches the filter.
+    pub fn matches(&self, from: &Address, to: Option<&Address>) -> bool {
+        (self.from.is_empty() || self.from.contains(from)) &&
+            (self.to.is_empty() || to.is_some_and(|to| self.to.contains(to)))
+    }
+}
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +130,18 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Subscribes to changes of the txpool.
+    ///
+    /// Emits a [`TxpoolDiff`] with the full transaction whenever a transaction that matches the
+    /// optional filter is added, moved between sub-pools, replaced, mined or discarded.
+    #[subscription(
+        name = "subscribeDiffs",
+        unsubscribe = "unsubscribeDiffs",
+        item = TxpoolDiff<T>
+    )]
+    async fn txpool_subscribe_diffs(
+        &self,
+        filter: Option<TxpoolDiffFilter>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -19,7 +19,12 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use crate::{
+    auth::AuthRpcModule,
+    error::WsHttpSamePortError,
+    metrics::RpcRequestMetrics,
+    rate_limiter::{serve_with_client_ip, RpcRateLimitLayer, RpcRateLimitService, RpcRateLimiter},
+};
 use alloy_provider::{fillers::RecommendedFillers, Provider, ProviderBuilder};
 use core::marker::PhantomData;
 use error::{ConflictingModules, RpcError, ServerKind};
@@ -34,22 +39,26 @@ use jsonrpsee::{
 };
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_consensus::{ConsensusError, FullConsensus};
+use reth_engine_util::bad_blocks::BadBlockStore;
 use reth_evm::ConfigureEvm;
-use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
+use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers, SenderBans};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, EthPrivateTransactions,
+    MinerApi, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig,
+    Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
-    helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
-    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    helpers::{block::LoadBlock, Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
+    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcNodeCore, RpcReceipt,
+    RpcTransaction,
 };
 use reth_rpc_eth_types::{EthConfig, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
-    AccountReader, BlockReader, BlockReaderIdExt, ChangeSetReader, FullRpcProvider, ProviderBlock,
+    AccountReader, BlockReader, BlockReaderIdExt, CallHistoryReader, ChangeSetReader,
+    FullRpcProvider, ModifiedStateReader, ProviderBlock, StateDumpProviderFactory,
     StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
@@ -62,6 +71,7 @@ use std::{
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use tokio::net::TcpListener;
 use tower::Layer;
 use tower_http::cors::CorsLayer;
 
@@ -73,7 +83,7 @@ use jsonrpsee::server::ServerConfigBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection, RpcRateLimitConfig};
 pub use tower::layer::util::{Identity, Stack};
 
 /// Auth server utilities.
@@ -121,7 +131,7 @@ where
         + AccountReader
         + ChangeSetReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SenderBans + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
     EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
 {
@@ -152,6 +162,8 @@ pub struct RpcModuleBuilder<N, Provider, Pool, Network, EvmConfig, Consensus> {
     evm_config: EvmConfig,
     /// The consensus implementation.
     consensus: Consensus,
+    /// The store of bad blocks served by the `debug` namespace.
+    bad_block_store: Option<BadBlockStore>,
     /// Node data primitives.
     _primitives: PhantomData<N>,
 }
@@ -173,7 +185,16 @@ where
         evm_config: EvmConfig,
         consensus: Consensus,
     ) -> Self {
-        Self { provider, pool, network, executor, evm_config, consensus, _primitives: PhantomData }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store: None,
+            _primitives: PhantomData,
+        }
     }
 
     /// Configure the provider instance.
@@ -186,8 +207,26 @@ where
             + StateProviderFactory
             + 'static,
     {
-        let Self { pool, network, executor, evm_config, consensus, _primitives, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, evm_config, consensus, _primitives }
+        let Self {
+            pool,
+            network,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+        }
     }
 
     /// Configure the transaction pool instance.
@@ -198,8 +237,26 @@ where
     where
         P: TransactionPool<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
     {
-        let Self { provider, network, executor, evm_config, consensus, _primitives, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, evm_config, consensus, _primitives }
+        let Self {
+            provider,
+            network,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+        }
     }
 
     /// Configure a [`NoopTransactionPool`] instance.
@@ -210,7 +267,16 @@ where
     pub fn with_noop_pool(
         self,
     ) -> RpcModuleBuilder<N, Provider, NoopTransactionPool, Network, EvmConfig, Consensus> {
-        let Self { provider, executor, network, evm_config, consensus, _primitives, .. } = self;
+        let Self {
+            provider,
+            executor,
+            network,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             executor,
@@ -218,6 +284,7 @@ where
             evm_config,
             pool: NoopTransactionPool::default(),
             consensus,
+            bad_block_store,
             _primitives,
         }
     }
@@ -228,10 +295,28 @@ where
         network: Net,
     ) -> RpcModuleBuilder<N, Provider, Pool, Net, EvmConfig, Consensus>
     where
-        Net: NetworkInfo + Peers + 'static,
+        Net: NetworkInfo + Peers + SenderBans + 'static,
     {
-        let Self { provider, pool, executor, evm_config, consensus, _primitives, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, evm_config, consensus, _primitives }
+        let Self {
+            provider,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+        }
     }
 
     /// Configure a [`NoopNetwork`] instance.
@@ -242,7 +327,16 @@ where
     pub fn with_noop_network(
         self,
     ) -> RpcModuleBuilder<N, Provider, Pool, NoopNetwork, EvmConfig, Consensus> {
-        let Self { provider, pool, executor, evm_config, consensus, _primitives, .. } = self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             pool,
@@ -250,14 +344,33 @@ where
             network: NoopNetwork::default(),
             evm_config,
             consensus,
+            bad_block_store,
             _primitives,
         }
     }
 
     /// Configure the task executor to use for additional tasks.
     pub fn with_executor(self, executor: Box<dyn TaskSpawner + 'static>) -> Self {
-        let Self { pool, network, provider, evm_config, consensus, _primitives, .. } = self;
-        Self { provider, network, pool, executor, evm_config, consensus, _primitives }
+        let Self {
+            pool,
+            network,
+            provider,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
+        Self {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+        }
     }
 
     /// Configure [`TokioTaskExecutor`] as the task executor to use for additional tasks.
@@ -265,7 +378,16 @@ where
     /// This will spawn additional tasks directly via `tokio::task::spawn`, See
     /// [`TokioTaskExecutor`].
     pub fn with_tokio_executor(self) -> Self {
-        let Self { pool, network, provider, evm_config, consensus, _primitives, .. } = self;
+        let Self {
+            pool,
+            network,
+            provider,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
         Self {
             provider,
             network,
@@ -273,6 +395,7 @@ where
             executor: Box::new(TokioTaskExecutor::default()),
             evm_config,
             consensus,
+            bad_block_store,
             _primitives,
         }
     }
@@ -286,8 +409,19 @@ where
         EvmConfig: 'static,
         E: ConfigureEvm + Clone,
     {
-        let Self { provider, pool, executor, network, consensus, _primitives, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, evm_config, consensus, _primitives }
+        let Self {
+            provider, pool, executor, network, consensus, bad_block_store, _primitives, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+        }
     }
 
     /// Configure the consensus implementation.
@@ -295,8 +429,34 @@ where
         self,
         consensus: C,
     ) -> RpcModuleBuilder<N, Provider, Pool, Network, EvmConfig, C> {
-        let Self { provider, network, pool, executor, evm_config, _primitives, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, evm_config, consensus, _primitives }
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            bad_block_store,
+            _primitives,
+            ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            evm_config,
+            consensus,
+            bad_block_store,
+            _primitives,
+        }
+    }
+
+    /// Configure the [`BadBlockStore`] that backs the bad block endpoints of the `debug` namespace.
+    ///
+    /// If not configured, an empty in-memory store is used.
+    pub fn with_bad_block_store(mut self, bad_block_store: BadBlockStore) -> Self {
+        self.bad_block_store = Some(bad_block_store);
+        self
     }
 
     /// Instantiates a new [`EthApiBuilder`] from the configured components.
@@ -346,7 +506,7 @@ where
         + AccountReader
         + ChangeSetReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SenderBans + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
     Consensus: FullConsensus<N, Error = ConsensusError> + Clone + 'static,
 {
@@ -369,13 +529,16 @@ where
     where
         EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
     {
-        let Self { provider, pool, network, executor, consensus, evm_config, .. } = self;
+        let Self {
+            provider, pool, network, executor, consensus, evm_config, bad_block_store, ..
+        } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
 
         let mut registry = RpcRegistryInner::new(
             provider, pool, network, executor, consensus, config, evm_config, eth,
-        );
+        )
+        .with_bad_block_store(bad_block_store.unwrap_or_default());
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -396,8 +559,11 @@ where
     where
         EthApi: EthApiTypes + 'static,
     {
-        let Self { provider, pool, network, executor, consensus, evm_config, .. } = self;
+        let Self {
+            provider, pool, network, executor, consensus, evm_config, bad_block_store, ..
+        } = self;
         RpcRegistryInner::new(provider, pool, network, executor, consensus, config, evm_config, eth)
+            .with_bad_block_store(bad_block_store.unwrap_or_default())
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -412,7 +578,9 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, consensus, evm_config, .. } = self;
+        let Self {
+            provider, pool, network, executor, consensus, evm_config, bad_block_store, ..
+        } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -426,7 +594,8 @@ where
                 config.unwrap_or_default(),
                 evm_config,
                 eth,
-            );
+            )
+            .with_bad_block_store(bad_block_store.unwrap_or_default());
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -546,6 +715,8 @@ pub struct RpcRegistryInner<
     modules: HashMap<RethRpcModule, Methods>,
     /// eth config settings
     eth_config: EthConfig,
+    /// Recent blocks rejected by the engine, served by the `debug` namespace
+    bad_block_store: BadBlockStore,
 }
 
 // === impl RpcRegistryInner ===
@@ -595,8 +766,15 @@ where
             blocking_pool_guard,
             eth_config: config.eth,
             evm_config,
+            bad_block_store: BadBlockStore::default(),
         }
     }
+
+    /// Sets the [`BadBlockStore`] that backs the bad block endpoints of the `debug` namespace.
+    pub fn with_bad_block_store(mut self, bad_block_store: BadBlockStore) -> Self {
+        self.bad_block_store = bad_block_store;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, EthApi, BlockExecutor, Consensus>
@@ -656,7 +834,7 @@ where
     /// Instantiates `AdminApi`
     pub fn admin_api(&self) -> AdminApi<Network, Provider::ChainSpec>
     where
-        Network: Peers,
+        Network: Peers + SenderBans,
     {
         AdminApi::new(self.network.clone(), self.provider.chain_spec())
     }
@@ -669,7 +847,7 @@ where
     /// Register Admin Namespace
     pub fn register_admin(&mut self) -> &mut Self
     where
-        Network: Peers,
+        Network: Peers + SenderBans,
     {
         let adminapi = self.admin_api();
         self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
@@ -696,7 +874,7 @@ where
         > + AccountReader
         + ChangeSetReader
         + CanonStateSubscriptions,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SenderBans + Clone + 'static,
     EthApi: EthApiServer<
             RpcTransaction<EthApi::NetworkTypes>,
             RpcBlock<EthApi::NetworkTypes>,
@@ -723,7 +901,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_ots(&mut self) -> &mut Self
     where
-        EthApi: TraceExt + EthTransactions,
+        EthApi: TraceExt + EthTransactions + RpcNodeCore<Provider: CallHistoryReader>,
     {
         let otterscan_api = self.otterscan_api();
         self.modules.insert(RethRpcModule::Ots, otterscan_api.into_rpc().into());
@@ -737,7 +915,10 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_debug(&mut self) -> &mut Self
     where
-        EthApi: EthApiSpec + EthTransactions + TraceExt,
+        EthApi: EthApiSpec
+            + EthTransactions
+            + TraceExt
+            + RpcNodeCore<Provider: ModifiedStateReader + StateDumpProviderFactory>,
         EvmConfig::Primitives: NodePrimitives<Block = ProviderBlock<EthApi::Provider>>,
     {
         let debug_api = self.debug_api();
@@ -810,7 +991,7 @@ where
             Receipt = N::Receipt,
         > + AccountReader
         + ChangeSetReader,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SenderBans + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
 {
@@ -839,6 +1020,19 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthSimBundle`] Api
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn sim_bundle_api(&self) -> EthSimBundle<EthApi>
+    where
+        EthApi: EthTransactions + LoadBlock + Call,
+    {
+        let eth_api = self.eth_api().clone();
+        EthSimBundle::new(eth_api, self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `DebugApi`
     ///
     /// # Panics
@@ -853,6 +1047,7 @@ where
             self.eth_api().clone(),
             self.blocking_pool_guard.clone(),
             self.evm_config.clone(),
+            self.bad_block_store.clone(),
         )
     }
 
@@ -884,7 +1079,7 @@ where
         + AccountReader
         + ChangeSetReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SenderBans + Clone + 'static,
     EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
     Consensus: FullConsensus<N, Error = ConsensusError> + Clone + 'static,
@@ -974,6 +1169,7 @@ where
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                             self.evm_config.clone(),
+                            self.bad_block_store.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -991,6 +1187,9 @@ where
                                     .into_rpc(),
                                 )
                                 .expect("No conflicts");
+                            module
+                                .merge(EthPrivateTransactions::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
 
                             module.into()
                         }
@@ -1005,9 +1204,10 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => TxPoolApi::new(
+                        RethRpcModule::Txpool => TxPoolApi::with_spawner(
                             self.eth.api.pool().clone(),
                             self.eth.api.tx_resp_builder().clone(),
+                            self.executor.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -1025,10 +1225,13 @@ where
                                 .into_rpc()
                                 .into()
                         }
-                        // only relevant for Ethereum and configured in `EthereumAddOns`
-                        // implementation
+                        // `Flashbots` is only relevant for Ethereum and configured in
+                        // `EthereumAddOns` implementation, `Anvil` and `Hardhat` are only
+                        // available on dev nodes and configured by the node's add-ons
                         // TODO: can we get rid of this here?
-                        RethRpcModule::Flashbots => Default::default(),
+                        RethRpcModule::Flashbots |
+                        RethRpcModule::Anvil |
+                        RethRpcModule::Hardhat => Default::default(),
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                         RethRpcModule::Mev => {
                             EthSimBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -1077,6 +1280,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     jwt_secret: Option<JwtSecret>,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
+    /// Rate limits of the http and ws servers
+    rate_limit: Option<RpcRateLimitConfig>,
 }
 
 // === impl RpcServerConfig ===
@@ -1096,6 +1301,7 @@ impl Default for RpcServerConfig<Identity> {
             ipc_endpoint: None,
             jwt_secret: None,
             rpc_middleware: RpcServiceBuilder::new(),
+            rate_limit: None,
         }
     }
 }
@@ -1160,9 +1366,18 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
             rpc_middleware,
+            rate_limit: self.rate_limit,
         }
     }
 
+    /// Configures the rate limits of the http and ws servers.
+    ///
+    /// The ipc server is not rate limited.
+    pub fn with_rate_limit(mut self, rate_limit: Option<RpcRateLimitConfig>) -> Self {
+        self.rate_limit = rate_limit.filter(|config| !config.is_empty());
+        self
+    }
+
     /// Configure the cors domains for http _and_ ws
     pub fn with_cors(self, cors_domain: Option<String>) -> Self {
         self.with_http_cors(cors_domain.clone()).with_ws_cors(cors_domain)
@@ -1289,8 +1504,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     /// Returns the [`RpcServerHandle`] with the handle to the started servers.
     pub async fn start(self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
     where
-        RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
-        for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
+        RpcMiddleware:
+            Layer<RpcRequestMetricsService<RpcRateLimitService<RpcService>>> + Clone + Send + 'static,
+        for<'a> <RpcMiddleware as Layer<
+            RpcRequestMetricsService<RpcRateLimitService<RpcService>>,
+        >>::Service:
             Send
                 + Sync
                 + 'static
@@ -1314,6 +1532,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
+        let rate_limiter = self.rate_limit.map(RpcRateLimiter::new);
+        let client_ip_layer = rate_limiter.as_ref().and_then(RpcRateLimiter::client_ip_layer);
+        if let Some(rate_limiter) = &rate_limiter {
+            rate_limiter.spawn_pruning();
+        }
+
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
@@ -1349,6 +1573,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(config) = self.http_server_config {
+                let listener = TcpListener::bind(http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
+                let addr = listener.local_addr().map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
                 let server = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
@@ -1356,29 +1586,36 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
-                            )),
+                            ))
+                            .option_layer(client_ip_layer.clone()),
                     )
                     .set_rpc_middleware(
-                        self.rpc_middleware.clone().layer(
-                            modules
-                                .http
-                                .as_ref()
-                                .or(modules.ws.as_ref())
-                                .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
-                        ),
+                        self.rpc_middleware
+                            .clone()
+                            .layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .or(modules.ws.as_ref())
+                                    .map(RpcRequestMetrics::same_port)
+                                    .unwrap_or_default(),
+                            )
+                            .layer(RpcRateLimitLayer::new(rate_limiter.clone())),
                     )
-                    .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
-                let addr = server.local_addr().map_err(|err| {
-                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                })?;
+                    .set_config(config.build());
                 if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                    // rate limits need the client IP, which the stock server doesn't provide
+                    let handle = if rate_limiter.is_some() {
+                        serve_with_client_ip(listener, server.to_service_builder(), module.clone())
+                    } else {
+                        listener
+                            .into_std()
+                            .and_then(|listener| server.build_from_tcp(listener))
+                            .map_err(|err| {
+                                RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                            })?
+                            .start(module.clone())
+                    };
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
@@ -1400,58 +1637,84 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(config) = self.ws_server_config {
+            let listener = TcpListener::bind(ws_socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+            let addr = listener
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
             let server = ServerBuilder::new()
                 .set_config(config.ws_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(client_ip_layer.clone()),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware
                         .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
-                )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
-
-            let addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(RpcRateLimitLayer::new(rate_limiter.clone())),
+                );
 
             ws_local_addr = Some(addr);
-            ws_server = Some(server);
+            ws_server = Some((listener, server));
         }
 
         if let Some(config) = self.http_server_config {
+            let listener = TcpListener::bind(http_socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+            let local_addr = listener
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let server = ServerBuilder::new()
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
+                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression))
+                        .option_layer(client_ip_layer),
                 )
                 .set_rpc_middleware(
-                    self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
-                )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            let local_addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+                    self.rpc_middleware
+                        .clone()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(RpcRateLimitLayer::new(rate_limiter.clone())),
+                );
             http_local_addr = Some(local_addr);
-            http_server = Some(server);
+            http_server = Some((listener, server));
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        // rate limits need the client IP, which the stock server doesn't provide
+        if let Some((listener, server)) = http_server {
+            let module = modules.http.clone().expect("http server error");
+            http_handle = Some(if rate_limiter.is_some() {
+                serve_with_client_ip(listener, server.to_service_builder(), module)
+            } else {
+                listener
+                    .into_std()
+                    .and_then(|listener| server.build_from_tcp(listener))
+                    .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?
+                    .start(module)
+            });
+        }
+        if let Some((listener, server)) = ws_server {
+            let module = modules.ws.clone().expect("ws server error");
+            ws_handle = Some(if rate_limiter.is_some() {
+                serve_with_client_ip(listener, server.to_service_builder(), module)
+            } else {
+                listener
+                    .into_std()
+                    .and_then(|listener| server.build_from_tcp(listener))
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?
+                    .start(module)
+            });
+        }
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
@@ -1690,6 +1953,29 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Replace the given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    ///
+    /// Methods of `other` that are already installed are removed first.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
+
     /// Merge the given [Methods] in the configured http methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -1,132 +1,712 @@
-//! [`jsonrpsee`] helper layer for rate limiting certain methods.
+//! [`jsonrpsee`] helper layers for rate limiting RPC calls.
 
-use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request};
+use http::{HeaderName, Request as HttpRequest, Response as HttpResponse};
+use hyper::body::{Body, Bytes, Incoming};
+use jsonrpsee::{
+    core::{
+        middleware::{Batch, BatchEntry, Notification},
+        BoxError,
+    },
+    server::{
+        middleware::rpc::RpcServiceT, serve_with_graceful_shutdown, stop_channel, HttpBody,
+        ServerHandle, TowerService, TowerServiceBuilder,
+    },
+    types::{ErrorObject, ErrorObjectOwned, Id, Request},
+    Extensions, MethodResponse, Methods,
+};
+use parking_lot::Mutex;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_rpc_server_types::{
+    rate_limit::{method_matches, RateLimit},
+    RpcRateLimitConfig,
+};
+use schnellru::{ByLength, LruMap};
 use std::{
     future::Future,
-    pin::Pin,
-    sync::Arc,
-    task::{ready, Context, Poll},
+    net::{IpAddr, Ipv6Addr},
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+    time::{Duration, Instant},
 };
-use tokio::sync::{OwnedSemaphorePermit, Semaphore};
-use tokio_util::sync::PollSemaphore;
-use tower::Layer;
+use tokio::net::TcpListener;
+use tower::{Layer, Service};
+use tracing::{debug, warn};
+
+/// The JSON-RPC error code of calls that exceed a rate limit, see EIP-1474.
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The maximum number of clients whose buckets are tracked at once.
+///
+/// Once reached, the bucket of the least recently seen client is dropped.
+const MAX_TRACKED_CLIENTS: u32 = 100_000;
+
+/// The interval in which the buckets of idle clients are dropped.
+const CLIENT_PRUNE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long to wait before accepting connections again after accepting failed.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The IP of the client that sent a request.
+///
+/// Inserted into the request extensions by [`serve_with_client_ip`] with the address of the
+/// connected peer, and replaced by the [`ClientIpLayer`] if the server is behind a proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Serves the given methods on the listener until the returned handle is stopped.
+///
+/// Behaves like [`jsonrpsee::server::Server`], but inserts the address of the connected peer into
+/// the extensions of every request as its [`ClientIp`].
+pub fn serve_with_client_ip<RpcMiddleware, HttpMiddleware, ResponseBody>(
+    listener: TcpListener,
+    service_builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+    methods: impl Into<Methods>,
+) -> ServerHandle
+where
+    RpcMiddleware: Clone + Send + 'static,
+    HttpMiddleware: Clone + Send + 'static,
+    TowerService<RpcMiddleware, HttpMiddleware>: Service<
+            HttpRequest<HttpBody>,
+            Response = HttpResponse<ResponseBody>,
+            Error = BoxError,
+            Future: Send,
+        > + Send,
+    ResponseBody: Body<Data = Bytes, Error: Into<BoxError>> + Send + 'static,
+{
+    let methods = methods.into();
+    let (stop_handle, server_handle) = stop_channel();
+
+    tokio::spawn(async move {
+        loop {
+            let accepted = tokio::select! {
+                res = listener.accept() => res,
+                _ = stop_handle.clone().shutdown() => break,
+            };
+            let (socket, peer_addr) = match accepted {
+                Ok(conn) => conn,
+                Err(err) => {
+                    // accepting mostly fails if we ran out of file descriptors, retrying right away
+                    // would spin until connections are closed
+                    debug!(target: "rpc", %err, "Failed to accept connection");
+                    tokio::select! {
+                        _ = tokio::time::sleep(ACCEPT_ERROR_BACKOFF) => continue,
+                        _ = stop_handle.clone().shutdown() => break,
+                    }
+                }
+            };
+            if let Err(err) = socket.set_nodelay(true) {
+                debug!(target: "rpc", %err, "Failed to set TCP_NODELAY");
+            }
+
+            let service = service_builder.clone().build(methods.clone(), stop_handle.clone());
+            let service = tower::service_fn(move |req: HttpRequest<Incoming>| {
+                let mut req = req.map(HttpBody::new);
+                req.extensions_mut().insert(ClientIp(peer_addr.ip()));
+                service.clone().call(req)
+            });
+            let stopped = stop_handle.clone().shutdown();
+            tokio::spawn(async move {
+                if let Err(err) = serve_with_graceful_shutdown(socket, service, stopped).await {
+                    debug!(target: "rpc", %err, %peer_addr, "Failed to serve connection");
+                }
+            });
+        }
+    });
+
+    server_handle
+}
+
+/// HTTP middleware that reads the client IP from a request header set by proxies, e.g.
+/// `X-Forwarded-For`.
+///
+/// Every proxy appends the address it received the request from to the header, so only the
+/// entries appended by the trusted proxies in front of the server can be relied on. The client IP
+/// is the entry appended by the outermost trusted proxy, or the first entry if there are fewer
+/// entries than trusted proxies. Requests without a valid entry keep the address of the connected
+/// peer.
+#[derive(Debug, Clone)]
+pub struct ClientIpLayer {
+    header: HeaderName,
+    trusted_hops: usize,
+}
+
+impl ClientIpLayer {
+    /// Creates a new layer that reads the client IP from the given header, which is set by the
+    /// given number of trusted proxies.
+    pub fn new(header: HeaderName, trusted_hops: usize) -> Self {
+        Self { header, trusted_hops: trusted_hops.max(1) }
+    }
+}
+
+impl<S> Layer<S> for ClientIpLayer {
+    type Service = ClientIpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientIpService { header: self.header.clone(), trusted_hops: self.trusted_hops, inner }
+    }
+}
+
+/// The service of the [`ClientIpLayer`].
+#[derive(Debug, Clone)]
+pub struct ClientIpService<S> {
+    header: HeaderName,
+    trusted_hops: usize,
+    inner: S,
+}
 
-/// Rate limiter for the RPC server.
+impl<S, B> Service<HttpRequest<B>> for ClientIpService<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        let entries = req
+            .headers()
+            .get_all(&self.header)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        let client_ip = entries
+            .iter()
+            .rev()
+            .nth(self.trusted_hops - 1)
+            .or_else(|| entries.first())
+            .and_then(|ip| ip.parse().ok());
+        if let Some(ip) = client_ip {
+            req.extensions_mut().insert(ClientIp(ip));
+        }
+        self.inner.call(req)
+    }
+}
+
+/// Credit based rate limiter for the public RPC servers.
 ///
-/// Rate limits expensive calls such as debug_ and trace_.
+/// Calls are counted against the limit of the first method group that matches the method, and
+/// against the limit of the client IP if known. Calls that exceed a limit are rejected with a
+/// [`RATE_LIMIT_EXCEEDED_CODE`] error that tells the client when to retry.
 #[derive(Debug, Clone)]
-pub struct RpcRequestRateLimiter {
-    inner: Arc<RpcRequestRateLimiterInner>,
+pub struct RpcRateLimiter {
+    inner: Arc<RpcRateLimiterInner>,
 }
 
-impl RpcRequestRateLimiter {
-    /// Create a new rate limit layer with the given number of permits.
-    pub fn new(rate_limit: usize) -> Self {
+impl RpcRateLimiter {
+    /// Creates a new rate limiter with the given configuration.
+    pub fn new(config: RpcRateLimitConfig) -> Self {
+        let now = Instant::now();
+        let groups = config
+            .methods
+            .iter()
+            .map(|group| MethodGroup {
+                pattern: group.pattern.clone(),
+                bucket: Mutex::new(TokenBucket::new(group.limit, now)),
+                metrics: RpcRateLimitGroupMetrics::new_with_labels(&[(
+                    "methods",
+                    group.pattern.clone(),
+                )]),
+            })
+            .collect();
+        let per_ip = config
+            .per_ip
+            .map(|limit| (limit, Mutex::new(LruMap::new(ByLength::new(MAX_TRACKED_CLIENTS)))));
+
         Self {
-            inner: Arc::new(RpcRequestRateLimiterInner {
-                call_guard: PollSemaphore::new(Arc::new(Semaphore::new(rate_limit))),
+            inner: Arc::new(RpcRateLimiterInner {
+                config,
+                groups,
+                per_ip,
+                metrics: Default::default(),
             }),
         }
     }
+
+    /// Spawns a task that drops the buckets of idle clients until the rate limiter is dropped.
+    ///
+    /// Does nothing if there is no per IP limit.
+    pub fn spawn_pruning(&self) {
+        if self.inner.per_ip.is_none() {
+            return
+        }
+
+        let inner = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLIENT_PRUNE_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(inner) = Weak::upgrade(&inner) else { break };
+                inner.prune_idle_clients(Instant::now());
+            }
+        });
+    }
+
+    /// Returns the HTTP middleware that provides the client IP for the per IP limits, if
+    /// configured.
+    pub fn client_ip_layer(&self) -> Option<ClientIpLayer> {
+        let header = self.inner.config.client_ip_header.as_ref()?;
+        match HeaderName::from_bytes(header.as_bytes()) {
+            Ok(header) => {
+                Some(ClientIpLayer::new(header, self.inner.config.trusted_hops.unwrap_or(1)))
+            }
+            Err(err) => {
+                warn!(target: "rpc", %header, %err, "Invalid client IP header, the peer address is used as client IP");
+                None
+            }
+        }
+    }
+
+    /// Spends the credits of the given calls.
+    ///
+    /// Returns the error to respond with if a limit is exceeded, in which case no credits are
+    /// spent.
+    fn check<'a>(
+        &self,
+        methods: impl IntoIterator<Item = &'a str>,
+        extensions: Option<&Extensions>,
+    ) -> Result<(), ErrorObjectOwned> {
+        let now = Instant::now();
+        let client_ip = extensions.and_then(|ext| ext.get::<ClientIp>()).map(|ip| ip.0);
+
+        // the credits spent from the group buckets, refunded if a later limit is exceeded
+        let mut spent = Vec::new();
+        let refund = |spent: Vec<(&MethodGroup, u32)>| {
+            for (group, credits) in spent {
+                group.bucket.lock().refund(credits);
+            }
+        };
+
+        let mut ip_credits = 0u32;
+        for method in methods {
+            let credits = self.inner.config.cost(method);
+            ip_credits = ip_credits.saturating_add(credits);
+
+            if let Some(group) =
+                self.inner.groups.iter().find(|group| method_matches(&group.pattern, method))
+            {
+                let res = group.bucket.lock().try_spend(credits, now);
+                if let Err(retry_after) = res {
+                    refund(spent);
+                    self.inner.metrics.method_limited_total.increment(1);
+                    group.metrics.limited_total.increment(1);
+                    return Err(rate_limit_exceeded(
+                        &format!("{} calls", group.pattern),
+                        retry_after,
+                    ))
+                }
+                spent.push((group, credits));
+            }
+        }
+
+        if let (Some((limit, clients)), Some(ip)) = (&self.inner.per_ip, client_ip) {
+            let mut clients = clients.lock();
+            let bucket = clients.get_or_insert(client_key(ip), || TokenBucket::new(*limit, now));
+            if let Some(Err(retry_after)) = bucket.map(|bucket| bucket.try_spend(ip_credits, now)) {
+                drop(clients);
+                refund(spent);
+                self.inner.metrics.ip_limited_total.increment(1);
+                return Err(rate_limit_exceeded("client", retry_after))
+            }
+        }
+
+        Ok(())
+    }
 }
 
-impl<S> Layer<S> for RpcRequestRateLimiter {
-    type Service = RpcRequestRateLimitingService<S>;
+/// Layer that applies the limits of a [`RpcRateLimiter`], if configured.
+#[derive(Debug, Clone, Default)]
+pub struct RpcRateLimitLayer {
+    rate_limiter: Option<RpcRateLimiter>,
+}
+
+impl RpcRateLimitLayer {
+    /// Creates a new layer with the given rate limiter.
+    pub const fn new(rate_limiter: Option<RpcRateLimiter>) -> Self {
+        Self { rate_limiter }
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimitService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        RpcRequestRateLimitingService::new(inner, self.clone())
+        RpcRateLimitService::new(inner, self.rate_limiter.clone())
     }
 }
 
-/// Rate Limiter for the RPC server
-#[derive(Debug, Clone)]
-struct RpcRequestRateLimiterInner {
-    /// Semaphore to rate limit calls
-    call_guard: PollSemaphore,
+/// Returns the key of the bucket of the given client IP.
+///
+/// IPv6 clients are usually assigned an entire /64 network, so they are limited by their /64
+/// prefix.
+fn client_key(ip: IpAddr) -> IpAddr {
+    match ip.to_canonical() {
+        IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from_bits(ip.to_bits() & !u128::from(u64::MAX))),
+        ip => ip,
+    }
+}
+
+/// Returns the error of a call that exceeded the limit of the given scope.
+fn rate_limit_exceeded(scope: &str, retry_after: Duration) -> ErrorObjectOwned {
+    let retry_after_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+    ErrorObject::owned(
+        RATE_LIMIT_EXCEEDED_CODE,
+        format!("rate limit of {scope} exceeded, retry in {retry_after_ms}ms"),
+        Some(serde_json::json!({ "retryAfterMs": retry_after_ms })),
+    )
+}
+
+#[derive(Debug)]
+struct RpcRateLimiterInner {
+    /// The configured limits and costs.
+    config: RpcRateLimitConfig,
+    /// The buckets of the method groups, in the configured order.
+    groups: Vec<MethodGroup>,
+    /// The limit of every client and the buckets of the clients, keyed by [`client_key`].
+    per_ip: Option<(RateLimit, Mutex<LruMap<IpAddr, TokenBucket>>)>,
+    /// Metrics of rejected calls.
+    metrics: RpcRateLimitMetrics,
+}
+
+impl RpcRateLimiterInner {
+    /// Drops the buckets of the least recently seen clients until a client is found that has
+    /// spent credits it didn't get back yet.
+    fn prune_idle_clients(&self, now: Instant) {
+        let Some((_, clients)) = &self.per_ip else { return };
+        let mut clients = clients.lock();
+        while clients.peek_oldest().is_some_and(|(_, bucket)| bucket.is_full(now)) {
+            clients.pop_oldest();
+        }
+    }
+}
+
+/// The bucket of a method group.
+#[derive(Debug)]
+struct MethodGroup {
+    pattern: String,
+    bucket: Mutex<TokenBucket>,
+    metrics: RpcRateLimitGroupMetrics,
+}
+
+/// A token bucket of credits.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The maximum number of credits in the bucket.
+    capacity: f64,
+    /// The number of credits refilled every second.
+    per_second: f64,
+    /// The number of credits in the bucket.
+    credits: f64,
+    /// When the bucket was last refilled.
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        let capacity = limit.capacity().max(1) as f64;
+        Self { capacity, per_second: limit.per_second as f64, credits: capacity, refilled_at: now }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.credits = elapsed.mul_add(self.per_second, self.credits).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    /// Takes the given number of credits from the bucket.
+    ///
+    /// Returns how long it takes until enough credits are available if there are not enough
+    /// credits.
+    fn try_spend(&mut self, credits: u32, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+
+        // calls that cost more than the capacity can be made with a full bucket
+        let credits = (credits as f64).min(self.capacity);
+        if self.credits >= credits {
+            self.credits -= credits;
+            return Ok(())
+        }
+
+        if self.per_second <= 0.0 {
+            return Err(Duration::MAX)
+        }
+        Err(Duration::from_secs_f64((credits - self.credits) / self.per_second))
+    }
+
+    /// Returns the given number of spent credits to the bucket.
+    fn refund(&mut self, credits: u32) {
+        self.credits = (self.credits + (credits as f64).min(self.capacity)).min(self.capacity);
+    }
+
+    /// Returns `true` if the bucket would be full at the given time.
+    fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        elapsed.mul_add(self.per_second, self.credits) >= self.capacity
+    }
 }
 
-/// A [`RpcServiceT`] middleware that rate limits RPC calls to the server.
+/// A [`RpcServiceT`] middleware that rejects calls that exceed the limits of a
+/// [`RpcRateLimiter`].
+///
+/// Does nothing if no rate limiter is configured.
 #[derive(Debug, Clone)]
-pub struct RpcRequestRateLimitingService<S> {
-    /// The rate limiter for RPC requests
-    rate_limiter: RpcRequestRateLimiter,
+pub struct RpcRateLimitService<S> {
+    /// The rate limiter, if configured
+    rate_limiter: Option<RpcRateLimiter>,
     /// The inner service being wrapped
     inner: S,
 }
 
-impl<S> RpcRequestRateLimitingService<S> {
-    /// Create a new rate limited service.
-    pub const fn new(service: S, rate_limiter: RpcRequestRateLimiter) -> Self {
+impl<S> RpcRateLimitService<S> {
+    /// Creates a new service with the given rate limiter.
+    pub const fn new(service: S, rate_limiter: Option<RpcRateLimiter>) -> Self {
         Self { inner: service, rate_limiter }
     }
 }
 
-impl<S> RpcServiceT for RpcRequestRateLimitingService<S>
+impl<S> RpcServiceT for RpcRateLimitService<S>
 where
-    S: RpcServiceT + Send + Sync + Clone + 'static,
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
 {
     type MethodResponse = S::MethodResponse;
     type NotificationResponse = S::NotificationResponse;
     type BatchResponse = S::BatchResponse;
 
     fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
-        let method_name = req.method_name();
-        if method_name.starts_with("trace_") || method_name.starts_with("debug_") {
-            RateLimitingRequestFuture {
-                fut: self.inner.call(req),
-                guard: Some(self.rate_limiter.inner.call_guard.clone()),
-                permit: None,
+        let rejected = self.rate_limiter.as_ref().and_then(|rate_limiter| {
+            let res = rate_limiter.check([req.method_name()], Some(req.extensions()));
+            res.err().map(|err| MethodResponse::error(req.id(), err))
+        });
+        let fut = rejected.is_none().then(|| self.inner.call(req));
+
+        async move {
+            match fut {
+                Some(fut) => fut.await,
+                None => rejected.expect("rejected if not called"),
             }
-        } else {
-            // if we don't need to rate limit, then there
-            // is no need to get a semaphore permit
-            RateLimitingRequestFuture { fut: self.inner.call(req), guard: None, permit: None }
         }
     }
 
     fn batch<'a>(
         &self,
-        requests: jsonrpsee::core::middleware::Batch<'a>,
+        mut requests: Batch<'a>,
     ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
-        self.inner.batch(requests)
+        // the whole batch is rejected if any of its calls exceeds a limit
+        let rejected = self.rate_limiter.as_ref().and_then(|rate_limiter| {
+            let mut methods = Vec::new();
+            let mut extensions = None;
+            for entry in requests.iter_mut() {
+                match entry {
+                    Ok(BatchEntry::Call(req)) => {
+                        extensions.get_or_insert_with(|| req.extensions().clone());
+                        methods.push(req.method_name().to_string());
+                    }
+                    Ok(BatchEntry::Notification(n)) => methods.push(n.method_name().to_string()),
+                    Err(_) => {}
+                }
+            }
+            let res = rate_limiter.check(methods.iter().map(String::as_str), extensions.as_ref());
+            res.err().map(|err| MethodResponse::error(Id::Null, err))
+        });
+        let fut = rejected.is_none().then(|| self.inner.batch(requests));
+
+        async move {
+            match fut {
+                Some(fut) => fut.await,
+                None => rejected.expect("rejected if not called"),
+            }
+        }
     }
 
     fn notification<'a>(
         &self,
-        n: jsonrpsee::core::middleware::Notification<'a>,
+        n: Notification<'a>,
     ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
         self.inner.notification(n)
     }
 }
 
-/// Response future.
-#[pin_project::pin_project]
-pub struct RateLimitingRequestFuture<F> {
-    #[pin]
-    fut: F,
-    guard: Option<PollSemaphore>,
-    permit: Option<OwnedSemaphorePermit>,
+/// Metrics of the calls rejected by the [`RpcRateLimiter`].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.rate_limit")]
+struct RpcRateLimitMetrics {
+    /// The number of calls rejected because the limit of a method group was exceeded
+    method_limited_total: Counter,
+    /// The number of calls rejected because the limit of the client IP was exceeded
+    ip_limited_total: Counter,
 }
 
-impl<F> std::fmt::Debug for RateLimitingRequestFuture<F> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("RateLimitingRequestFuture")
-    }
+/// Metrics of the calls rejected by the limit of a method group.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.rate_limit.group")]
+struct RpcRateLimitGroupMetrics {
+    /// The number of calls rejected because the limit of the group was exceeded
+    limited_total: Counter,
 }
 
-impl<F: Future> Future for RateLimitingRequestFuture<F> {
-    type Output = F::Output;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_server_types::rate_limit::MethodRateLimit;
+    use std::collections::BTreeMap;
 
-    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let this = self.project();
-        if let Some(guard) = this.guard.as_mut() {
-            *this.permit = ready!(guard.poll_acquire(cx));
-            *this.guard = None;
-        }
-        let res = this.fut.poll(cx);
-        if res.is_ready() {
-            *this.permit = None;
+    #[test]
+    fn token_bucket_refills() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit { per_second: 10, burst: Some(20) }, now);
+
+        assert!(bucket.try_spend(15, now).is_ok());
+        assert_eq!(bucket.try_spend(10, now), Err(Duration::from_millis(500)));
+
+        let later = now + Duration::from_millis(500);
+        assert!(bucket.try_spend(10, later).is_ok());
+        assert!(!bucket.is_full(later));
+        assert!(bucket.is_full(later + Duration::from_secs(2)));
+
+        // calls that cost more than the burst need a full bucket
+        assert!(bucket.try_spend(100, later + Duration::from_secs(2)).is_ok());
+    }
+
+    #[test]
+    fn limits_method_groups_and_clients() {
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            methods: vec![MethodRateLimit {
+                pattern: "trace_*".to_string(),
+                limit: RateLimit::per_second(1),
+            }],
+            per_ip: Some(RateLimit::per_second(3)),
+            costs: BTreeMap::from([("eth_getLogs".to_string(), 2)]),
+            client_ip_header: None,
+            trusted_hops: None,
+        });
+
+        assert!(rate_limiter.check(["trace_block"], None).is_ok());
+        let err = rate_limiter.check(["trace_filter"], None).unwrap_err();
+        assert_eq!(err.code(), RATE_LIMIT_EXCEEDED_CODE);
+
+        let mut extensions = Extensions::new();
+        extensions.insert(ClientIp(IpAddr::from([127, 0, 0, 1])));
+        assert!(rate_limiter.check(["eth_getLogs"], Some(&extensions)).is_ok());
+        assert!(rate_limiter.check(["eth_getLogs"], Some(&extensions)).is_err());
+        assert!(rate_limiter.check(["eth_call"], Some(&extensions)).is_ok());
+
+        // other clients have their own budget, calls without a known client IP are not limited
+        let mut extensions = Extensions::new();
+        extensions.insert(ClientIp(IpAddr::from([127, 0, 0, 2])));
+        assert!(rate_limiter.check(["eth_getLogs"], Some(&extensions)).is_ok());
+        assert!(rate_limiter.check(["eth_getLogs", "eth_getLogs"], None).is_ok());
+    }
+
+    #[test]
+    fn limits_ipv6_clients_by_prefix() {
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            per_ip: Some(RateLimit::per_second(1)),
+            ..Default::default()
+        });
+        let check = |ip: &str| {
+            let mut extensions = Extensions::new();
+            extensions.insert(ClientIp(ip.parse().unwrap()));
+            rate_limiter.check(["eth_call"], Some(&extensions))
+        };
+
+        // addresses of the same /64 network share a bucket
+        assert!(check("2001:db8:1:1::1").is_ok());
+        assert!(check("2001:db8:1:1:ffff::2").is_err());
+        assert!(check("2001:db8:1:2::1").is_ok());
+
+        // IPv4 mapped addresses are limited like their IPv4 address
+        assert!(check("10.0.0.1").is_ok());
+        assert!(check("::ffff:10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn prunes_idle_clients() {
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            per_ip: Some(RateLimit::per_second(1)),
+            ..Default::default()
+        });
+        let clients = || rate_limiter.inner.per_ip.as_ref().unwrap().1.lock().len();
+        for ip in [[127, 0, 0, 1], [127, 0, 0, 2]] {
+            let mut extensions = Extensions::new();
+            extensions.insert(ClientIp(IpAddr::from(ip)));
+            assert!(rate_limiter.check(["eth_call"], Some(&extensions)).is_ok());
         }
-        res
+        assert_eq!(clients(), 2);
+
+        // clients that still wait for credits are kept
+        rate_limiter.inner.prune_idle_clients(Instant::now());
+        assert_eq!(clients(), 2);
+
+        rate_limiter.inner.prune_idle_clients(Instant::now() + Duration::from_secs(2));
+        assert_eq!(clients(), 0);
+    }
+
+    #[test]
+    fn rejected_calls_spend_no_credits() {
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            methods: vec![
+                MethodRateLimit { pattern: "eth_*".to_string(), limit: RateLimit::per_second(2) },
+                MethodRateLimit { pattern: "trace_*".to_string(), limit: RateLimit::per_second(1) },
+            ],
+            per_ip: Some(RateLimit::per_second(2)),
+            ..Default::default()
+        });
+
+        // the second trace call exceeds the group limit, so the whole batch is refunded
+        assert!(rate_limiter.check(["eth_call", "trace_block", "trace_block"], None).is_err());
+        assert!(rate_limiter.check(["eth_call", "eth_call"], None).is_ok());
+        assert!(rate_limiter.check(["trace_block"], None).is_ok());
+
+        // the client limit is exceeded after the group credits were spent
+        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig {
+            methods: vec![MethodRateLimit {
+                pattern: "eth_*".to_string(),
+                limit: RateLimit::per_second(3),
+            }],
+            per_ip: Some(RateLimit::per_second(2)),
+            ..Default::default()
+        });
+        let mut extensions = Extensions::new();
+        extensions.insert(ClientIp(IpAddr::from([127, 0, 0, 1])));
+        assert!(rate_limiter.check(["eth_call"], Some(&extensions)).is_ok());
+        assert!(rate_limiter.check(["eth_call"; 2], Some(&extensions)).is_err());
+        assert!(rate_limiter.check(["eth_call"; 2], None).is_ok());
+    }
+
+    #[test]
+    fn client_ip_from_trusted_hops() {
+        let client_ip = |trusted_hops, forwarded_for: &[&str]| {
+            let mut service =
+                ClientIpLayer::new(HeaderName::from_static("x-forwarded-for"), trusted_hops).layer(
+                    tower::service_fn(|req: HttpRequest<()>| {
+                        std::future::ready(Ok::<_, ()>(req.extensions().get::<ClientIp>().copied()))
+                    }),
+                );
+            let mut req = HttpRequest::new(());
+            for value in forwarded_for {
+                req.headers_mut().append("x-forwarded-for", value.parse().unwrap());
+            }
+            req.extensions_mut().insert(ClientIp(IpAddr::from([10, 0, 0, 1])));
+            service.call(req).into_inner().unwrap().map(|ip| ip.0.to_string())
+        };
+
+        // the entries before the one appended by the proxy are sent by the client
+        assert_eq!(client_ip(1, &["1.1.1.1, 2.2.2.2"]).unwrap(), "2.2.2.2");
+        assert_eq!(client_ip(1, &["1.1.1.1", "2.2.2.2"]).unwrap(), "2.2.2.2");
+        assert_eq!(client_ip(2, &["1.1.1.1, 2.2.2.2, 3.3.3.3"]).unwrap(), "2.2.2.2");
+        assert_eq!(client_ip(3, &["2.2.2.2"]).unwrap(), "2.2.2.2");
+
+        // requests without a valid entry keep the peer address
+        assert_eq!(client_ip(1, &[]).unwrap(), "10.0.0.1");
+        assert_eq!(client_ip(1, &["1.1.1.1, unknown"]).unwrap(), "10.0.0.1");
     }
 }
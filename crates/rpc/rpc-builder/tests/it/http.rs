@@ -8,6 +8,7 @@ use alloy_rpc_types_eth::{
     transaction::TransactionRequest, Block, FeeHistory, Filter, Header, Index, Log,
     PendingTransactionFilterKind, SyncStatus, Transaction, TransactionReceipt,
 };
+use alloy_rpc_types_mev::CancelPrivateTransactionRequest;
 use alloy_rpc_types_trace::filter::TraceFilter;
 use jsonrpsee::{
     core::{
@@ -22,8 +23,8 @@ use reth_ethereum_primitives::Receipt;
 use reth_network_peers::NodeRecord;
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, NetApiClient, OtterscanClient,
-    TraceApiClient, Web3ApiClient,
+    DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, EthPrivateTransactionApiClient,
+    NetApiClient, OtterscanClient, TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_server_types::RethRpcModule;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -146,6 +147,11 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::banned_senders(client).await.unwrap();
+    AdminApiClient::clear_sender_bans(client).await.unwrap();
+    AdminApiClient::net_restrict(client).await.unwrap();
+    AdminApiClient::list_bans(client).await.unwrap();
+    AdminApiClient::peer_reputation(client).await.unwrap();
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -356,6 +362,12 @@ where
         .unwrap()
     ));
     EthCallBundleApiClient::call_bundle(client, Default::default()).await.unwrap_err();
+    assert!(!EthPrivateTransactionApiClient::cancel_private_transaction(
+        client,
+        CancelPrivateTransactionRequest { tx_hash: B256::default() }
+    )
+    .await
+    .unwrap());
 }
 
 async fn test_basic_debug_calls<C>(client: &C)
@@ -364,11 +376,38 @@ where
 {
     let block_id = BlockId::Number(BlockNumberOrTag::default());
 
-    DebugApiClient::raw_header(client, block_id).await.unwrap();
-    DebugApiClient::raw_block(client, block_id).await.unwrap_err();
-    DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
-    DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    DebugApiClient::bad_blocks(client).await.unwrap();
+    DebugApiClient::<Block>::raw_header(client, block_id).await.unwrap();
+    DebugApiClient::<Block>::raw_block(client, block_id).await.unwrap_err();
+    DebugApiClient::<Block>::raw_transaction(client, B256::default()).await.unwrap();
+    DebugApiClient::<Block>::raw_receipts(client, block_id).await.unwrap();
+    DebugApiClient::<Block>::bad_blocks(client).await.unwrap();
+    DebugApiClient::<Block>::blobs_by_block(client, block_id).await.unwrap_err();
+    DebugApiClient::<Block>::blobs_by_versioned_hashes(client, vec![B256::default()])
+        .await
+        .unwrap_err();
+    DebugApiClient::<Block>::debug_trace_bad_block(client, B256::default(), None)
+        .await
+        .unwrap_err();
+    DebugApiClient::<Block>::debug_account_range(
+        client,
+        block_id,
+        Bytes::default(),
+        10,
+        false,
+        false,
+        true,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::<Block>::debug_get_modified_accounts_by_number(client, 0, None)
+        .await
+        .unwrap_err();
+    DebugApiClient::<Block>::debug_get_modified_accounts_by_hash(client, B256::default(), None)
+        .await
+        .unwrap_err();
+    DebugApiClient::<Block>::debug_intermediate_roots(client, B256::default(), None)
+        .await
+        .unwrap_err();
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -478,28 +517,23 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    // the call trace index is not maintained
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
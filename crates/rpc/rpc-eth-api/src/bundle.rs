@@ -22,6 +22,36 @@ pub trait EthCallBundleApi {
     ) -> jsonrpsee::core::RpcResult<EthCallBundleResponse>;
 }
 
+/// A subset of the [EthBundleApi] API interface that only supports private transactions.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthPrivateTransactionApi {
+    /// `eth_sendPrivateTransaction` is used to send a single transaction to Flashbots. Flashbots will attempt to build a block including the transaction for the next 25 blocks. See [Private Transactions](https://docs.flashbots.net/flashbots-protect/additional-documentation/eth-sendPrivateTransaction) for more info.
+    #[method(name = "sendPrivateTransaction")]
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// The `eth_sendPrivateRawTransaction` method can be used to send private transactions to
+    /// the RPC endpoint. Private transactions are protected from frontrunning and kept
+    /// private until included in a block. A request to this endpoint needs to follow
+    /// the standard `eth_sendRawTransaction`
+    #[method(name = "sendPrivateRawTransaction")]
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// The `eth_cancelPrivateTransaction` method stops private transactions from being
+    /// submitted for future blocks.
+    ///
+    /// A transaction can only be cancelled if the request is signed by the same key as the
+    /// `eth_sendPrivateTransaction` call submitting the transaction in first place.
+    #[method(name = "cancelPrivateTransaction")]
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> jsonrpsee::core::RpcResult<bool>;
+}
+
 /// The __full__ Eth bundle rpc interface.
 ///
 /// See also <https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint>
@@ -482,12 +482,28 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
                     {
                         None => Ok(None),
                         Some((tx, meta)) => {
+                            // The recorded sender is preferred, it differs from the signer of
+                            // impersonated transactions on dev nodes.
+                            let sender = match this
+                                .provider()
+                                .transaction_id(hash)
+                                .map_err(Self::Error::from_eth_err)?
+                            {
+                                Some(id) => this
+                                    .provider()
+                                    .transaction_sender(id)
+                                    .map_err(Self::Error::from_eth_err)?,
+                                None => None,
+                            };
                             // Note: we assume this transaction is valid, because it's mined (or
                             // part of pending block) and already. We don't need to
                             // check for pre EIP-2 because this transaction could be pre-EIP-2.
-                            let transaction = tx
-                                .try_into_recovered_unchecked()
-                                .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                            let transaction = match sender {
+                                Some(sender) => tx.with_signer(sender),
+                                None => tx
+                                    .try_into_recovered_unchecked()
+                                    .map_err(|_| EthApiError::InvalidTransactionSignature)?,
+                            };
 
                             let tx = TransactionSource::Block {
                                 transaction,
@@ -1,7 +1,10 @@
 //! RPC receipt response builder, extends a layer one receipt with layer two data.
 
 use super::EthResult;
-use alloy_consensus::{transaction::TransactionMeta, ReceiptEnvelope, TxReceipt};
+use alloy_consensus::{
+    transaction::{Recovered, TransactionMeta},
+    ReceiptEnvelope, TxReceipt,
+};
 use alloy_eips::eip7840::BlobParams;
 use alloy_primitives::{Address, TxKind};
 use alloy_rpc_types_eth::{Log, ReceiptWithBloom, TransactionReceipt};
@@ -9,8 +12,10 @@ use reth_ethereum_primitives::{Receipt, TransactionSigned, TxType};
 use reth_primitives_traits::SignedTransaction;
 
 /// Builds an [`TransactionReceipt`] obtaining the inner receipt envelope from the given closure.
+///
+/// The sender of the receipt is the recorded signer of the given transaction.
 pub fn build_receipt<R, T, E>(
-    transaction: &T,
+    transaction: Recovered<&T>,
     meta: TransactionMeta,
     receipt: &R,
     all_receipts: &[R],
@@ -21,9 +26,7 @@ where
     R: TxReceipt<Log = alloy_primitives::Log>,
     T: SignedTransaction,
 {
-    // Note: we assume this transaction is valid, because it's mined (or part of pending block)
-    // and we don't need to check for pre EIP-2
-    let from = transaction.recover_signer_unchecked()?;
+    let from = transaction.signer();
 
     // get the previous transaction cumulative gas used
     let gas_used = if meta.index == 0 {
@@ -106,7 +109,7 @@ impl EthReceiptBuilder {
     /// Note: This requires _all_ block receipts because we need to calculate the gas used by the
     /// transaction.
     pub fn new(
-        transaction: &TransactionSigned,
+        transaction: Recovered<&TransactionSigned>,
         meta: TransactionMeta,
         receipt: &Receipt,
         all_receipts: &[Receipt],
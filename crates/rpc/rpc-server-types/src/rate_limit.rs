@@ -0,0 +1,240 @@
+//! Rate limit configuration of the RPC servers.
+
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, num::ParseIntError, str::FromStr};
+
+/// Rate limits of the public RPC servers.
+///
+/// Every call spends credits, one credit unless configured otherwise in
+/// [`costs`](Self::costs). Limits are token buckets that hold up to `burst` credits and are
+/// refilled with `per_second` credits every second.
+///
+/// Methods are matched by patterns, which are either a method name like `eth_call` or a prefix
+/// followed by `*` like `trace_*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RpcRateLimitConfig {
+    /// Limits of groups of methods, shared by all clients.
+    ///
+    /// A call is only counted against the first group that matches its method.
+    pub methods: Vec<MethodRateLimit>,
+    /// Limit of every client IP, applies to all methods.
+    pub per_ip: Option<RateLimit>,
+    /// The cost of methods in credits, keyed by method pattern.
+    ///
+    /// A method name takes precedence over patterns, and longer patterns take precedence over
+    /// shorter ones.
+    pub costs: BTreeMap<String, u32>,
+    /// The HTTP header the client IP is read from if the server is behind proxies, e.g.
+    /// `X-Forwarded-For`.
+    ///
+    /// Without a header, the address of the connected peer is the client IP.
+    pub client_ip_header: Option<String>,
+    /// The number of trusted proxies in front of the server that append to the client IP header,
+    /// defaults to one.
+    ///
+    /// The client IP is the entry appended by the outermost trusted proxy, entries before it may
+    /// have been sent by the client.
+    pub trusted_hops: Option<usize>,
+}
+
+impl RpcRateLimitConfig {
+    /// Returns `true` if no limits are configured.
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty() && self.per_ip.is_none()
+    }
+
+    /// Returns the cost of the given method in credits.
+    pub fn cost(&self, method: &str) -> u32 {
+        if let Some(cost) = self.costs.get(method) {
+            return *cost
+        }
+        self.costs
+            .iter()
+            .filter(|(pattern, _)| pattern.ends_with('*') && method_matches(pattern, method))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map_or(1, |(_, cost)| *cost)
+    }
+}
+
+/// A token bucket limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// The number of credits that are refilled every second.
+    pub per_second: u32,
+    /// The maximum number of credits that can be spent at once, defaults to `per_second`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+impl RateLimit {
+    /// Creates a new limit of the given number of credits per second.
+    pub const fn per_second(per_second: u32) -> Self {
+        Self { per_second, burst: None }
+    }
+
+    /// Returns the maximum number of credits that can be spent at once.
+    pub fn capacity(&self) -> u32 {
+        self.burst.unwrap_or(self.per_second)
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = ParseIntError;
+
+    /// Parses `<PER_SECOND>` or `<PER_SECOND>/<BURST>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((per_second, burst)) => Ok(Self {
+                per_second: per_second.trim().parse()?,
+                burst: Some(burst.trim().parse()?),
+            }),
+            None => Ok(Self::per_second(s.trim().parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.per_second)?;
+        if let Some(burst) = self.burst {
+            write!(f, "/{burst}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The limit of a group of methods.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodRateLimit {
+    /// The pattern of the methods in the group.
+    pub pattern: String,
+    /// The limit of the group.
+    #[serde(flatten)]
+    pub limit: RateLimit,
+}
+
+impl MethodRateLimit {
+    /// Returns `true` if the given method belongs to the group.
+    pub fn matches(&self, method: &str) -> bool {
+        method_matches(&self.pattern, method)
+    }
+}
+
+impl FromStr for MethodRateLimit {
+    type Err = RateLimitParseError;
+
+    /// Parses `<PATTERN>=<PER_SECOND>[/<BURST>]`, e.g. `trace_*=10/20`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern, limit) = split_method_value(s)?;
+        Ok(Self { pattern, limit: limit.parse()? })
+    }
+}
+
+/// The cost of the methods that match a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodCost {
+    /// The pattern of the methods.
+    pub pattern: String,
+    /// The cost of a call in credits.
+    pub credits: u32,
+}
+
+impl FromStr for MethodCost {
+    type Err = RateLimitParseError;
+
+    /// Parses `<PATTERN>=<CREDITS>`, e.g. `eth_getLogs=10`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern, credits) = split_method_value(s)?;
+        Ok(Self { pattern, credits: credits.trim().parse()? })
+    }
+}
+
+/// Error returned when parsing a method limit or cost fails.
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitParseError {
+    /// The value is not of the form `<PATTERN>=<VALUE>`.
+    #[error("expected <PATTERN>=<VALUE>, got {0:?}")]
+    MissingValue(String),
+    /// The value is not a number.
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+}
+
+/// Splits `<PATTERN>=<VALUE>`.
+fn split_method_value(s: &str) -> Result<(String, &str), RateLimitParseError> {
+    match s.split_once('=') {
+        Some((pattern, value)) if !pattern.trim().is_empty() => {
+            Ok((pattern.trim().to_string(), value))
+        }
+        _ => Err(RateLimitParseError::MissingValue(s.to_string())),
+    }
+}
+
+/// Returns `true` if the method matches the pattern.
+///
+/// Patterns ending with `*` match all methods with the preceding prefix, other patterns only match
+/// the method with the same name.
+pub fn method_matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_method_limits() {
+        assert_eq!(
+            "eth_call=100".parse::<MethodRateLimit>().unwrap(),
+            MethodRateLimit { pattern: "eth_call".to_string(), limit: RateLimit::per_second(100) }
+        );
+        assert_eq!(
+            "trace_*=10/20".parse::<MethodRateLimit>().unwrap(),
+            MethodRateLimit {
+                pattern: "trace_*".to_string(),
+                limit: RateLimit { per_second: 10, burst: Some(20) }
+            }
+        );
+        assert!("eth_call".parse::<MethodRateLimit>().is_err());
+        assert!("=10".parse::<MethodRateLimit>().is_err());
+        assert!("eth_call=fast".parse::<MethodRateLimit>().is_err());
+    }
+
+    #[test]
+    fn method_costs() {
+        let config = RpcRateLimitConfig {
+            costs: BTreeMap::from([
+                ("debug_*".to_string(), 20),
+                ("debug_traceCall*".to_string(), 30),
+                ("debug_traceCallMany".to_string(), 50),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(config.cost("eth_call"), 1);
+        assert_eq!(config.cost("debug_traceBlock"), 20);
+        assert_eq!(config.cost("debug_traceCall"), 30);
+        assert_eq!(config.cost("debug_traceCallMany"), 50);
+    }
+
+    #[test]
+    fn deserialize_config() {
+        let config: RpcRateLimitConfig = serde_json::from_str(
+            r#"{
+                "methods": [{ "pattern": "eth_getLogs", "per_second": 5, "burst": 10 }],
+                "per_ip": { "per_second": 100 },
+                "costs": { "trace_*": 10 },
+                "client_ip_header": "X-Forwarded-For",
+                "trusted_hops": 2
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.methods[0].limit, RateLimit { per_second: 5, burst: Some(10) });
+        assert_eq!(config.per_ip, Some(RateLimit::per_second(100)));
+        assert_eq!(config.cost("trace_block"), 10);
+        assert_eq!(config.trusted_hops, Some(2));
+    }
+}
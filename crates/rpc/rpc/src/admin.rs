@@ -1,6 +1,11 @@
-use std::sync::Arc;
+use std::{
+    net::IpAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use alloy_genesis::ChainConfig;
+use alloy_primitives::Address;
 use alloy_rpc_types_admin::{
     EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
     Ports, ProtocolInfo,
@@ -8,11 +13,11 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{BanTarget, IpNet, NetworkInfo, Peers, SenderBans};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_api::{AdminApiServer, BanListEntry, BannedSender, NetRestrict, PeerReputation};
+use reth_rpc_server_types::{result::invalid_params_rpc_err, ToRpcResult};
 
 /// `admin` API implementation.
 ///
@@ -34,7 +39,7 @@ impl<N, ChainSpec> AdminApi<N, ChainSpec> {
 #[async_trait]
 impl<N, ChainSpec> AdminApiServer for AdminApi<N, ChainSpec>
 where
-    N: NetworkInfo + Peers + 'static,
+    N: NetworkInfo + Peers + SenderBans + 'static,
     ChainSpec: EthChainSpec + EthereumHardforks + Send + Sync + 'static,
 {
     /// Handler for `admin_addPeer`
@@ -189,6 +194,115 @@ where
     ) -> jsonrpsee::core::SubscriptionResult {
         Err("admin_peerEvents is not implemented yet".into())
     }
+
+    /// Handler for `admin_bannedSenders`
+    async fn banned_senders(&self) -> RpcResult<Vec<BannedSender>> {
+        self.network.banned_senders().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_unbanSender`
+    async fn unban_sender(&self, sender: Address) -> RpcResult<bool> {
+        self.network.unban_sender(sender).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_clearSenderBans`
+    async fn clear_sender_bans(&self) -> RpcResult<()> {
+        self.network.clear_sender_bans().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_banPeer`
+    async fn ban_peer(&self, record: AnyNode, duration: Option<u64>) -> RpcResult<bool> {
+        self.network
+            .ban_peer(record.peer_id(), duration.map(Duration::from_secs))
+            .await
+            .to_rpc_result()
+    }
+
+    /// Handler for `admin_unbanPeer`
+    async fn unban_peer(&self, record: AnyNode) -> RpcResult<bool> {
+        self.network.unban_peer(record.peer_id()).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_banIp`
+    async fn ban_ip(&self, ip: IpAddr, duration: Option<u64>) -> RpcResult<bool> {
+        self.network.ban_ip(ip, duration.map(Duration::from_secs)).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_unbanIp`
+    async fn unban_ip(&self, ip: IpAddr) -> RpcResult<bool> {
+        self.network.unban_ip(ip).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_listBans`
+    async fn list_bans(&self) -> RpcResult<Vec<BanListEntry>> {
+        let now = Instant::now();
+        let bans = self.network.bans().await.to_rpc_result()?;
+        Ok(bans
+            .into_iter()
+            .map(|ban| {
+                let (id, ip) = match ban.target {
+                    BanTarget::Peer(peer_id) => (Some(peer_id), None),
+                    BanTarget::Ip(ip) => (None, Some(ip)),
+                };
+                BanListEntry {
+                    id,
+                    ip,
+                    expires_in: ban
+                        .banned_until
+                        .map(|until| until.saturating_duration_since(now).as_secs()),
+                }
+            })
+            .collect())
+    }
+
+    /// Handler for `admin_peerReputation`
+    async fn peer_reputation(&self) -> RpcResult<Vec<PeerReputation>> {
+        let reputations = self.network.peer_reputations().await.to_rpc_result()?;
+        Ok(reputations
+            .into_iter()
+            .map(|peer| PeerReputation {
+                id: peer.peer_id,
+                address: peer.addr,
+                kind: match peer.kind {
+                    PeerKind::Basic => "basic",
+                    PeerKind::Static => "static",
+                    PeerKind::Trusted => "trusted",
+                }
+                .to_string(),
+                reputation: peer.reputation,
+                banned: peer.is_banned,
+                recent_changes: peer
+                    .recent_changes
+                    .iter()
+                    .map(|change| format!("{change:?}"))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Handler for `admin_netRestrict`
+    fn net_restrict(&self) -> RpcResult<NetRestrict> {
+        let restrict = self.network.net_restrict();
+        Ok(NetRestrict {
+            allowed: restrict.allowed.iter().map(ToString::to_string).collect(),
+            denied: restrict.denied.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Handler for `admin_setNetRestrict`
+    fn set_net_restrict(&self, restrict: NetRestrict) -> RpcResult<()> {
+        let parse = |nets: Vec<String>| {
+            nets.iter()
+                .map(|net| {
+                    net.parse::<IpNet>().map_err(|err| invalid_params_rpc_err(err.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let restrict =
+            reth_network_api::NetRestrict::new(parse(restrict.allowed)?, parse(restrict.denied)?);
+        self.network.set_net_restrict(restrict);
+        Ok(())
+    }
 }
 
 impl<N, ChainSpec> std::fmt::Debug for AdminApi<N, ChainSpec> {
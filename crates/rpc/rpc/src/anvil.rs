@@ -0,0 +1,559 @@
+use alloy_dyn_abi::TypedData;
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    BlockId,
+};
+use alloy_network::{EthereumWallet, TransactionBuilder};
+use alloy_primitives::{keccak256, map::HashMap, Address, Bytes, Signature, B256, U256};
+use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
+use alloy_rpc_types_eth::{Block, Transaction, TransactionRequest};
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use parking_lot::RwLock;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_engine_primitives::{LocalMinerError, LocalMinerHandle};
+use reth_evm::state_override::{AccountOverride, StateOverrideLayer};
+use reth_rpc_api::AnvilApiServer;
+use reth_rpc_eth_api::{
+    helpers::{signer::Result as SignResult, EthBlocks, EthSigner, EthTransactions, SpawnBlocking},
+    types::RpcTypes,
+    EthApiTypes, FromEthApiError, FullEthApiTypes, RpcNodeCore,
+};
+use reth_rpc_eth_types::{EthApiError, SignError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{
+    AccountReader, BlockNumReader, HeaderProvider, ModifiedStateReader, StateProvider,
+    StateProviderFactory,
+};
+use reth_transaction_pool::TransactionPool;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// `anvil` API implementation for dev nodes.
+///
+/// State modifications such as `anvil_setBalance` are recorded and applied at the start of the
+/// next mined block, they become visible once that block has been mined, e.g. with `anvil_mine`.
+///
+/// Impersonated accounts can send transactions with `eth_sendTransaction`. These transactions are
+/// signed with a throwaway key of the impersonated account, they are added to the pool and
+/// included in blocks with the impersonated account as their sender.
+pub struct AnvilApi<Eth> {
+    inner: Arc<AnvilApiInner<Eth>>,
+}
+
+impl<Eth: EthTransactions> AnvilApi<Eth> {
+    /// Creates a new instance of `AnvilApi`.
+    ///
+    /// State modifying methods and impersonation are unavailable if no [`StateOverrideLayer`] is
+    /// configured. Otherwise, a signer for impersonated accounts is added to the `eth` API.
+    pub fn new(
+        eth_api: Eth,
+        miner: LocalMinerHandle,
+        state_overrides: Option<StateOverrideLayer>,
+    ) -> Self {
+        let impersonation = Arc::<Impersonation>::default();
+        if let Some(layer) = &state_overrides {
+            EthTransactions::signers(&eth_api).write().push(Box::new(ImpersonationSigner {
+                impersonation: impersonation.clone(),
+                seed: B256::random(),
+                layer: layer.clone(),
+            }));
+        }
+        Self { inner: Arc::new(AnvilApiInner { eth_api, miner, state_overrides, impersonation }) }
+    }
+}
+
+impl<Eth> AnvilApi<Eth> {
+    /// Returns the configured [`StateOverrideLayer`].
+    fn state_overrides(&self) -> RpcResult<&StateOverrideLayer> {
+        self.inner
+            .state_overrides
+            .as_ref()
+            .ok_or_else(|| EthApiError::Unsupported("state overrides are not configured").into())
+    }
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: EthBlocks
+        + SpawnBlocking
+        + FullEthApiTypes
+        + EthApiTypes<NetworkTypes: RpcTypes<Transaction = Transaction>>
+        + RpcNodeCore<
+            Provider: ModifiedStateReader
+                          + StateProviderFactory
+                          + HeaderProvider<Header = alloy_consensus::Header>
+                          + BlockNumReader
+                          + ChainSpecProvider<ChainSpec: EthChainSpec>,
+            Pool: TransactionPool,
+        > + 'static,
+{
+    /// Records the given account overrides, they are applied by the next mined block.
+    fn apply_overrides(
+        &self,
+        overrides: impl IntoIterator<Item = (Address, AccountOverride)>,
+    ) -> RpcResult<()> {
+        self.state_overrides()?.extend(overrides);
+        Ok(())
+    }
+
+    /// Mines the given number of blocks and returns their hashes.
+    async fn mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<Vec<B256>> {
+        let blocks = blocks.unwrap_or_else(|| U256::from(1)).saturating_to();
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.inner.miner.mine(blocks, interval).await.map_err(into_rpc_err)
+    }
+
+    /// Collects all accounts that exist in the latest state, see
+    /// [`AnvilApiServer::anvil_dump_state`].
+    async fn dump_state(&self) -> Result<SerializableState, Eth::Error> {
+        self.inner
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+
+                // collect all accounts and storage slots that were ever touched
+                let mut touched = BTreeMap::<Address, BTreeSet<B256>>::new();
+                for (address, account) in &provider.chain_spec().genesis().alloc {
+                    touched
+                        .entry(*address)
+                        .or_default()
+                        .extend(account.storage.iter().flatten().map(|(slot, _)| *slot));
+                }
+                let best = provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+                if best > 0 {
+                    for address in
+                        provider.modified_accounts(1..=best).map_err(Eth::Error::from_eth_err)?
+                    {
+                        touched.entry(address).or_default();
+                    }
+                    for (address, slots) in
+                        provider.modified_storage(1..=best).map_err(Eth::Error::from_eth_err)?
+                    {
+                        touched.entry(address).or_default().extend(slots);
+                    }
+                }
+
+                let state = provider.latest().map_err(Eth::Error::from_eth_err)?;
+                let mut accounts = BTreeMap::new();
+                for (address, slots) in touched {
+                    let Some(account) =
+                        state.basic_account(&address).map_err(Eth::Error::from_eth_err)?
+                    else {
+                        continue
+                    };
+                    let code = state
+                        .account_code(&address)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .map(|code| code.original_bytes())
+                        .unwrap_or_default();
+                    let mut storage = BTreeMap::new();
+                    for slot in slots {
+                        let value = state
+                            .storage(address, slot)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .unwrap_or_default();
+                        if !value.is_zero() {
+                            storage.insert(slot.into(), value);
+                        }
+                    }
+                    accounts.insert(
+                        address,
+                        SerializableAccount {
+                            nonce: account.nonce,
+                            balance: account.balance,
+                            code,
+                            storage,
+                        },
+                    );
+                }
+
+                Ok(SerializableState { accounts })
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilApiServer for AnvilApi<Eth>
+where
+    Eth: EthBlocks
+        + SpawnBlocking
+        + FullEthApiTypes
+        + EthApiTypes<NetworkTypes: RpcTypes<Transaction = Transaction>>
+        + RpcNodeCore<
+            Provider: ModifiedStateReader
+                          + StateProviderFactory
+                          + HeaderProvider<Header = alloy_consensus::Header>
+                          + BlockNumReader
+                          + ChainSpecProvider<ChainSpec: EthChainSpec>,
+            Pool: TransactionPool,
+        > + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.state_overrides()?;
+        self.inner.impersonation.accounts.write().insert(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.state_overrides()?;
+        self.inner.impersonation.accounts.write().remove(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.state_overrides()?;
+        self.inner.impersonation.auto.store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.inner.miner.automine().await.map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        self.inner.miner.set_automine(enabled).map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        self.inner.miner.set_interval_mining(interval).map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        let removed = self.inner.eth_api.pool().remove_transactions(vec![tx_hash]);
+        Ok(removed.first().map(|tx| *tx.hash()))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_reset is not supported").into())
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setRpcUrl is not supported").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.apply_overrides([(
+            address,
+            AccountOverride { balance: Some(balance), ..Default::default() },
+        )])
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.apply_overrides([(
+            address,
+            AccountOverride { code: Some(code), ..Default::default() },
+        )])
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = nonce
+            .try_into()
+            .map_err(|_| invalid_params_rpc_err("nonce does not fit into a u64"))?;
+        self.apply_overrides([(
+            address,
+            AccountOverride { nonce: Some(nonce), ..Default::default() },
+        )])
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        self.apply_overrides([(
+            address,
+            AccountOverride {
+                storage: HashMap::from_iter([(B256::from(slot), value)]),
+                ..Default::default()
+            },
+        )])?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setCoinbase is not supported").into())
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setChainId is not supported").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setLoggingEnabled is not supported").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setMinGasPrice is not supported").into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, _base_fee: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setNextBlockBaseFeePerGas is not supported").into())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        let offset = self.inner.miner.set_time(timestamp).await.map_err(into_rpc_err)?;
+        Ok(offset.unsigned_abs())
+    }
+
+    /// Handler for `anvil_dumpState`
+    ///
+    /// Returns the JSON encoded accounts of the latest state.
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        let state = self.dump_state().await.map_err(Into::into)?;
+        let json = serde_json::to_vec(&state).map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(json.into())
+    }
+
+    /// Handler for `anvil_loadState`
+    ///
+    /// Accepts the output of `anvil_dumpState`, all accounts are applied by the next mined block.
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        let state: SerializableState = serde_json::from_slice(&state)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid state: {err}")))?;
+        self.apply_overrides(state.accounts.into_iter().map(|(address, account)| {
+            (
+                address,
+                AccountOverride {
+                    balance: Some(account.balance),
+                    nonce: Some(account.nonce),
+                    code: Some(account.code),
+                    storage: account
+                        .storage
+                        .into_iter()
+                        .map(|(slot, value)| (slot.into(), value.into()))
+                        .collect(),
+                },
+            )
+        }))?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        Err(EthApiError::Unsupported("anvil_nodeInfo is not supported").into())
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        Err(EthApiError::Unsupported("anvil_metadata is not supported").into())
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        self.inner.miner.snapshot().await.map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        self.inner.miner.revert(id).await.map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.inner.miner.increase_time(seconds.saturating_to()).await.map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.inner.miner.set_next_block_timestamp(seconds).await.map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, _gas_limit: U256) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_setBlockGasLimit is not supported").into())
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.inner.miner.set_block_timestamp_interval(seconds).map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        self.inner.miner.remove_block_timestamp_interval().await.map_err(into_rpc_err)
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        let (blocks, timestamp) = match opts {
+            Some(MineOptions::Options { timestamp, blocks }) => (blocks, timestamp),
+            Some(MineOptions::Timestamp(timestamp)) => (None, timestamp),
+            None => (None, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.inner.miner.set_next_block_timestamp(timestamp).await.map_err(into_rpc_err)?;
+        }
+
+        let hashes = self.mine(blocks.map(U256::from), None).await?;
+        let mut blocks = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let block = self
+                .inner
+                .eth_api
+                .rpc_block(BlockId::from(hash), true)
+                .await
+                .map_err(Into::into)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_enableTraces is not supported").into())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.inner.eth_api.pool().remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+impl<Eth> std::fmt::Debug for AnvilApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for AnvilApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct AnvilApiInner<Eth> {
+    /// The `eth` API.
+    eth_api: Eth,
+    /// Handle to the local miner.
+    miner: LocalMinerHandle,
+    /// State overrides applied to the next mined block.
+    state_overrides: Option<StateOverrideLayer>,
+    /// Accounts that are currently impersonated.
+    impersonation: Arc<Impersonation>,
+}
+
+/// Accounts that can send transactions without their private key.
+#[derive(Debug, Default)]
+struct Impersonation {
+    /// Explicitly impersonated accounts.
+    accounts: RwLock<HashSet<Address>>,
+    /// Whether all accounts are impersonated.
+    auto: AtomicBool,
+}
+
+/// An [`EthSigner`] that signs transactions of impersonated accounts with a throwaway key.
+///
+/// Every impersonated account gets its own key, so identical requests of different accounts
+/// result in different transactions. The actual sender of every signed transaction is recorded in
+/// the [`StateOverrideLayer`], which resolves it when blocks are validated and executed.
+#[derive(Debug, Clone)]
+struct ImpersonationSigner {
+    impersonation: Arc<Impersonation>,
+    /// Seed the throwaway keys are derived from.
+    seed: B256,
+    layer: StateOverrideLayer,
+}
+
+impl ImpersonationSigner {
+    /// Returns the throwaway key of the given impersonated account.
+    fn key(&self, address: &Address) -> SignResult<PrivateKeySigner> {
+        let secret = keccak256([self.seed.as_slice(), address.as_slice()].concat());
+        PrivateKeySigner::from_bytes(&secret).map_err(|_| SignError::CouldNotSign)
+    }
+}
+
+#[async_trait]
+impl<T: Decodable2718> EthSigner<T> for ImpersonationSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.impersonation.accounts.read().iter().copied().collect()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.impersonation.auto.load(Ordering::Relaxed) ||
+            self.impersonation.accounts.read().contains(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> SignResult<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    async fn sign_transaction(
+        &self,
+        mut request: TransactionRequest,
+        address: &Address,
+    ) -> SignResult<T> {
+        let key = self.key(address)?;
+        request.from = Some(key.address());
+        let wallet = EthereumWallet::from(key);
+        let envelope =
+            request.build(&wallet).await.map_err(|_| SignError::InvalidTransactionRequest)?;
+        self.layer.impersonate_transaction(*envelope.tx_hash(), *address);
+
+        let encoded = envelope.encoded_2718();
+        T::decode_2718(&mut encoded.as_ref()).map_err(|_| SignError::InvalidTransactionRequest)
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> SignResult<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
+/// Converts a [`LocalMinerError`] into an RPC error.
+fn into_rpc_err(err: LocalMinerError) -> jsonrpsee_types::ErrorObject<'static> {
+    match err {
+        err @ LocalMinerError::TimestampTooLow { .. } => invalid_params_rpc_err(err.to_string()),
+        err => internal_rpc_err(err.to_string()),
+    }
+}
+
+/// State dumped by `anvil_dumpState`, compatible with the `accounts` of anvil's state dumps.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SerializableState {
+    accounts: BTreeMap<Address, SerializableAccount>,
+}
+
+/// An account of a [`SerializableState`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SerializableAccount {
+    nonce: u64,
+    balance: U256,
+    code: Bytes,
+    storage: BTreeMap<U256, U256>,
+}
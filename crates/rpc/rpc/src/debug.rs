@@ -1,12 +1,12 @@
-use alloy_consensus::{transaction::SignerRecoverable, BlockHeader};
+use alloy_consensus::{constants::KECCAK_EMPTY, transaction::SignerRecoverable, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{hex, keccak256, Address, Bytes, B256, U256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    state::EvmOverrides, transaction::TransactionRequest, BlockError, BlockTransactionsKind,
+    Bundle, StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
@@ -14,36 +14,81 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionError},
+    PendingSubscriptionSink, SubscriptionMessage,
+};
+use jsonrpsee_types::ErrorObject;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
-use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
+use reth_engine_util::bad_blocks::{BadBlockStore, StoredBadBlock};
+use reth_errors::RethError;
+use reth_evm::{execute::Executor, ConfigureEvm, Evm as _, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
     Block as _, BlockBody, NodePrimitives, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
 };
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{AccountState, CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountRangeResult, ArchivedBlob, BadBlock, DebugApiServer, DumpAccount, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    EthApiTypes, FromEthApiError, RpcNodeCore,
+    EthApiTypes, FromEthApiError, FromEvmError, RpcBlock, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
+use reth_rpc_types_compat::block::from_block;
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReaderIdExt, HashedPostStateProvider,
+    HeaderProvider, ModifiedStateReader, ProviderBlock, ReceiptProviderIdExt,
+    StateDumpProviderFactory, StateProofProvider, StateProvider, StateProviderFactory,
+    StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
-use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
+use reth_transaction_pool::{blobstore::BlobArchive, TransactionPool};
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, HashedStorage};
+use revm::{
+    context_interface::Transaction, database::states::bundle_state::BundleRetention,
+    state::EvmState, DatabaseCommit,
+};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::{debug, warn};
+
+/// The maximum number of blocks that are traced concurrently for a `debug_traceChain`
+/// subscription.
+///
+/// Tracing is additionally limited by the tracing permits shared by all tracing calls.
+const TRACE_CHAIN_CONCURRENCY: usize = 8;
+
+/// The maximum number of accounts returned by a single `debug_accountRange` call, same as geth.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// The maximum number of storage slots returned per account by a single `debug_accountRange`
+/// call.
+const ACCOUNT_RANGE_MAX_STORAGE: usize = 1024;
+
+/// The maximum number of storage slots returned by a single `debug_storageRangeAt` call.
+const STORAGE_RANGE_MAX_RESULTS: usize = 1024;
+
+/// The maximum number of blocks a single `debug_getModifiedAccountsByNumber` or
+/// `debug_getModifiedAccountsByHash` call may span.
+const MODIFIED_ACCOUNTS_MAX_BLOCKS: u64 = 100;
+
+/// The maximum number of versioned hashes accepted by a single `debug_getBlobsByVersionedHashes`
+/// call, same as `engine_getBlobs`.
+const MAX_BLOB_VERSIONED_HASHES: usize = 128;
 
 /// `debug` API implementation.
 ///
@@ -56,8 +101,16 @@ pub struct DebugApi<Eth, BlockExecutor> {
 
 impl<Eth, Evm> DebugApi<Eth, Evm> {
     /// Create a new instance of the [`DebugApi`]
-    pub fn new(eth: Eth, blocking_task_guard: BlockingTaskGuard, evm_config: Evm) -> Self {
-        let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config });
+    ///
+    /// The bad block endpoints are served from the given [`BadBlockStore`].
+    pub fn new(
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        evm_config: Evm,
+        bad_blocks: BadBlockStore,
+    ) -> Self {
+        let inner =
+            Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config, bad_blocks });
         Self { inner }
     }
 
@@ -137,6 +190,51 @@ where
             .await
     }
 
+    /// Resolves the range of blocks to trace for `debug_traceChain`.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<RangeInclusive<u64>, Eth::Error> {
+        let start = self
+            .provider()
+            .convert_block_number(start_exclusive)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(start_exclusive.into()))?;
+        let end = self
+            .provider()
+            .convert_block_number(end_inclusive)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(end_inclusive.into()))?;
+
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block #{end} needs to come after start block #{start}"
+            ))
+            .into())
+        }
+
+        Ok(start + 1..=end)
+    }
+
+    /// Replays the block with the given number for `debug_traceChain`.
+    ///
+    /// Waits for a tracing permit before the block is replayed.
+    async fn trace_chain_block(
+        &self,
+        number: u64,
+        opts: GethDebugTracingOptions,
+    ) -> Result<BlockTraceResult, Eth::Error> {
+        let _permit = self.acquire_trace_permit().await;
+        let hash = self
+            .provider()
+            .block_hash(number)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+        let traces = self.debug_trace_block(hash.into(), opts).await?;
+        Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+    }
+
     /// Replays the given block and returns the trace of each transaction.
     ///
     /// This expects a rlp encoded block
@@ -147,12 +245,21 @@ where
         rlp_block: Bytes,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let block = self.recover_raw_block(&rlp_block)?;
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        self.trace_block(Arc::new(block), evm_env, opts).await
+    }
+
+    /// Decodes the rlp encoded block and recovers the senders of its transactions.
+    fn recover_raw_block(
+        &self,
+        rlp_block: &Bytes,
+    ) -> Result<RecoveredBlock<ProviderBlock<Eth::Provider>>, Eth::Error> {
         let block: ProviderBlock<Eth::Provider> = Decodable::decode(&mut rlp_block.as_ref())
             .map_err(BlockError::RlpDecodeRawBlock)
             .map_err(Eth::Error::from_eth_err)?;
 
-        let evm_env = self.eth_api().evm_config().evm_env(block.header());
-
         // Depending on EIP-2 we need to recover the transactions differently
         let senders =
             if self.provider().chain_spec().is_homestead_active_at_block(block.header().number()) {
@@ -175,7 +282,71 @@ where
                     .collect()
             };
 
-        self.trace_block(Arc::new(block.into_recovered_with_signers(senders)), evm_env, opts).await
+        Ok(block.into_recovered_with_signers(senders))
+    }
+
+    /// Returns the block with the given hash from the bad block store.
+    fn bad_block(&self, block_hash: B256) -> Result<StoredBadBlock, Eth::Error> {
+        self.inner
+            .bad_blocks
+            .get(&block_hash)
+            .ok_or_else(|| Eth::Error::from_eth_err(EthApiError::HeaderNotFound(block_hash.into())))
+    }
+
+    /// Returns the blob archive of the transaction pool.
+    fn blob_archive(&self) -> Result<BlobArchive, EthApiError> {
+        self.eth_api()
+            .pool()
+            .blob_archive()
+            .ok_or(EthApiError::Unsupported("blob archive is not enabled"))
+    }
+
+    /// Converts a block from the bad block store into the `debug_getBadBlocks` response.
+    fn rpc_bad_block(
+        &self,
+        bad_block: StoredBadBlock,
+    ) -> Result<BadBlock<RpcBlock<Eth::NetworkTypes>>, Eth::Error> {
+        let block = self.recover_raw_block(&bad_block.rlp)?;
+        let block =
+            from_block(block, BlockTransactionsKind::Full, self.eth_api().tx_resp_builder())?;
+
+        Ok(BadBlock { hash: bad_block.hash, block, rlp: bad_block.rlp, reason: bad_block.reason })
+    }
+
+    /// Replays a block from the bad block store and writes the trace of each transaction to a
+    /// separate file in the temporary directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let bad_block = self.bad_block(block_hash)?;
+        let traces = self.debug_trace_raw_block(bad_block.rlp, opts).await?;
+
+        // the traces are written on a blocking task
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let dir = std::env::temp_dir();
+                let mut files = Vec::with_capacity(traces.len());
+                for (index, trace) in traces.iter().enumerate() {
+                    let tx_hash = match trace {
+                        TraceResult::Success { tx_hash, .. } |
+                        TraceResult::Error { tx_hash, .. } => tx_hash.unwrap_or_default(),
+                    };
+                    let file = dir.join(format!("block_{block_hash}-{index}-{tx_hash}.json"));
+                    let json = serde_json::to_vec(trace).map_err(|err| {
+                        Eth::Error::from_eth_err(EthApiError::Internal(RethError::msg(err)))
+                    })?;
+                    std::fs::write(&file, json).map_err(|err| {
+                        Eth::Error::from_eth_err(EthApiError::Internal(RethError::msg(err)))
+                    })?;
+                    files.push(file.display().to_string());
+                }
+                Ok(files)
+            })
+            .await
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -695,6 +866,48 @@ where
             .map(|b| b.original_bytes()))
     }
 
+    /// Replays the block with the given hash and returns the state root after each of its
+    /// transactions.
+    ///
+    /// Blocks that failed validation are looked up in the bad block store.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let block = match self.eth_api().recovered_block(block_hash.into()).await? {
+            Some(block) => block,
+            None => Arc::new(self.recover_raw_block(&self.bad_block(block_hash)?.rlp)?),
+        };
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state))
+                    .with_bundle_update()
+                    .build();
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                let evm_config = this.eth_api().evm_config();
+                let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+                let mut roots = Vec::with_capacity(block.body().transaction_count());
+                for tx in block.transactions_recovered() {
+                    evm.transact_commit(evm_config.tx_env(tx)).map_err(Eth::Error::from_evm_err)?;
+
+                    let db = evm.db_mut();
+                    db.merge_transitions(BundleRetention::PlainState);
+                    let root = state
+                        .state_root(state.hashed_post_state(&db.bundle_state))
+                        .map_err(Eth::Error::from_eth_err)?;
+                    roots.push(root);
+                }
+                Ok(roots)
+            })
+            .await
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// It optionally takes fused inspector ([`TracingInspector::fused`]) to avoid re-creating the
@@ -884,10 +1097,294 @@ where
     }
 }
 
+impl<Eth, Evm> DebugApi<Eth, Evm>
+where
+    Eth: EthApiTypes + TraceExt + RpcNodeCore<Provider: ModifiedStateReader> + 'static,
+{
+    /// Returns the addresses of all accounts that changed after the start block, up to and
+    /// including the end block.
+    ///
+    /// If no end block is given, the accounts changed in the start block are returned. The range
+    /// may span at most [`MODIFIED_ACCOUNTS_MAX_BLOCKS`] blocks.
+    pub async fn debug_get_modified_accounts_by_number(
+        &self,
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let (start, end) = match end_number {
+            Some(end) => (start_number, end),
+            None => {
+                let parent = start_number.checked_sub(1).ok_or_else(|| {
+                    EthApiError::InvalidParams("genesis block has no parent".to_string())
+                })?;
+                (parent, start_number)
+            }
+        };
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({start}) must be less than end block height ({end})"
+            ))
+            .into())
+        }
+        if end - start > MODIFIED_ACCOUNTS_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large; currently limited to {MODIFIED_ACCOUNTS_MAX_BLOCKS} blocks"
+            ))
+            .into())
+        }
+        let best = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if end > best {
+            return Err(EthApiError::HeaderNotFound(end.into()).into())
+        }
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let accounts = this
+                    .provider()
+                    .modified_accounts(start + 1..=end)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(accounts.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Same as [`Self::debug_get_modified_accounts_by_number`], with the blocks given by hash.
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let block_number = |hash: B256| {
+            self.provider()
+                .block_number(hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| Eth::Error::from_eth_err(EthApiError::HeaderNotFound(hash.into())))
+        };
+        let start = block_number(start_hash)?;
+        let end = end_hash.map(block_number).transpose()?;
+
+        self.debug_get_modified_accounts_by_number(start, end).await
+    }
+}
+
+impl<Eth, Evm> DebugApi<Eth, Evm>
+where
+    Eth: EthApiTypes + TraceExt + RpcNodeCore<Provider: StateDumpProviderFactory> + 'static,
+    Evm: ConfigureEvm<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>> + 'static,
+{
+    /// Dumps up to `max_results` accounts of the state at the given block, starting at the first
+    /// account whose hashed address is greater than or equal to `start`.
+    ///
+    /// `start` may be shorter than a hash, in which case it is treated as a prefix. Unless `nocode`
+    /// or `nostorage` are set, the code and up to [`ACCOUNT_RANGE_MAX_STORAGE`] storage slots of
+    /// each account are included.
+    ///
+    /// Accounts are keyed by their address. Accounts whose address can't be resolved are skipped,
+    /// unless `incompletes` is set, in which case they are keyed by their hashed address.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<AccountRangeResult, Eth::Error> {
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key must be at most {} bytes",
+                B256::len_bytes()
+            ))
+            .into())
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let max_results = match max_results as usize {
+            0 => ACCOUNT_RANGE_MAX_RESULTS,
+            max => max.min(ACCOUNT_RANGE_MAX_RESULTS),
+        };
+
+        let header = self
+            .provider()
+            .sealed_header_by_id(block_id)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let (root, hash) = (header.state_root(), header.hash());
+        let max_storage = if nostorage { 0 } else { ACCOUNT_RANGE_MAX_STORAGE };
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let dump = this
+                    .provider()
+                    .state_dump_by_block_hash(hash)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut result = AccountRangeResult { root, ..Default::default() };
+                let mut start = Some(start_key);
+                // skipped accounts don't count towards the page, so accounts are fetched until the
+                // page is full or the state is exhausted
+                'pages: while let Some(from) = start {
+                    // fetch one more account to determine the start of the next page
+                    let limit = max_results - result.accounts.len() + 1;
+                    let accounts = dump
+                        .account_range(Default::default(), from, limit, max_storage)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    start = accounts
+                        .last()
+                        .filter(|_| accounts.len() == limit)
+                        .and_then(|(hashed_address, ..)| {
+                            U256::from_be_bytes(hashed_address.0).checked_add(U256::from(1))
+                        })
+                        .map(B256::from);
+
+                    let hashed_addresses = accounts
+                        .iter()
+                        .map(|(hashed_address, ..)| *hashed_address)
+                        .collect::<Vec<_>>();
+                    let addresses = dump
+                        .account_addresses(&hashed_addresses)
+                        .map_err(Eth::Error::from_eth_err)?;
+
+                    for (hashed_address, account, storage) in accounts {
+                        if result.accounts.len() == max_results {
+                            result.next = Some(Bytes::copy_from_slice(hashed_address.as_slice()));
+                            break 'pages
+                        }
+
+                        let address = addresses.get(&hashed_address).copied();
+                        let key = match address {
+                            Some(address) => address.to_string(),
+                            None if incompletes => format!("pre({hashed_address})"),
+                            None => continue,
+                        };
+                        let code = if nocode || account.code_hash == KECCAK_EMPTY {
+                            None
+                        } else {
+                            dump.bytecode(&account.code_hash)
+                                .map_err(Eth::Error::from_eth_err)?
+                                .map(|code| code.original_bytes())
+                        };
+                        let storage = storage
+                            .into_iter()
+                            .map(|(slot, value)| {
+                                (slot, hex::encode(value.to_be_bytes_trimmed_vec()))
+                            })
+                            .collect();
+
+                        result.accounts.insert(
+                            key,
+                            DumpAccount {
+                                balance: account.balance.to_string(),
+                                nonce: account.nonce,
+                                root: account.storage_root,
+                                code_hash: account.code_hash,
+                                code,
+                                storage,
+                                address,
+                                hashed_address,
+                            },
+                        );
+                    }
+                }
+                Ok(result)
+            })
+            .await
+    }
+
+    /// Returns up to `max_result` storage slots of the given contract, starting at the first slot
+    /// whose hashed key is greater than or equal to `key_start`. At most
+    /// [`STORAGE_RANGE_MAX_RESULTS`] slots are returned.
+    ///
+    /// The storage is read from the state right before the transaction at `tx_idx` in the given
+    /// block is executed.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        if tx_idx > 0 && tx_idx >= block.body().transaction_count() {
+            return Err(EthApiError::UnknownBlockOrTxIndex.into())
+        }
+        let (evm_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let max_result = (max_result as usize).min(STORAGE_RANGE_MAX_RESULTS);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(&state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                if let Some(target) = block.body().transactions().get(tx_idx) {
+                    this.eth_api().replay_transactions_until(
+                        &mut db,
+                        evm_env,
+                        block.transactions_recovered(),
+                        *target.tx_hash(),
+                    )?;
+                }
+
+                // the storage changed by the replayed transactions is overlaid on the parent state
+                let storage = db
+                    .cache
+                    .accounts
+                    .get(&contract_address)
+                    .map(|account| {
+                        HashedStorage::from_iter(
+                            matches!(
+                                account.account_state,
+                                AccountState::NotExisting | AccountState::StorageCleared
+                            ),
+                            account
+                                .storage
+                                .iter()
+                                .map(|(slot, value)| (keccak256(B256::from(*slot)), *value)),
+                        )
+                    })
+                    .unwrap_or_default();
+
+                // fetch one more slot to determine the start of the next page
+                let mut slots = this
+                    .provider()
+                    .state_dump_by_block_hash(block.parent_hash())
+                    .and_then(|dump| {
+                        dump.storage_range(contract_address, storage, key_start, max_result + 1)
+                    })
+                    .map_err(EthApiError::from)?;
+                let next_key =
+                    (slots.len() > max_result).then(|| slots.pop()).flatten().map(|(slot, _)| slot);
+
+                let storage = slots
+                    .into_iter()
+                    .map(|(slot, value)| {
+                        (slot, StorageRangeEntry { key: None, value: B256::from(value) })
+                    })
+                    .collect();
+                Ok(StorageRangeResult { storage, next_key })
+            })
+            .await
+    }
+}
+
 #[async_trait]
-impl<Eth, Evm> DebugApiServer for DebugApi<Eth, Evm>
+impl<Eth, Evm> DebugApiServer<RpcBlock<Eth::NetworkTypes>> for DebugApi<Eth, Evm>
 where
-    Eth: EthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth: EthApiTypes
+        + EthTransactions
+        + TraceExt
+        + RpcNodeCore<Provider: ModifiedStateReader + StateDumpProviderFactory>
+        + 'static,
     Evm: ConfigureEvm<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>> + 'static,
 {
     /// Handler for `debug_getRawHeader`
@@ -959,17 +1456,106 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Ok(vec![])
+    ///
+    /// Stored blocks that can't be decoded are skipped.
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock<RpcBlock<Eth::NetworkTypes>>>> {
+        Ok(self
+            .inner
+            .bad_blocks
+            .blocks()
+            .into_iter()
+            .filter_map(|block| {
+                let (number, hash) = (block.number, block.hash);
+                self.rpc_bad_block(block)
+                    .inspect_err(|err| {
+                        warn!(target: "rpc::debug", number, %hash, %err, "Skipping bad block");
+                    })
+                    .ok()
+            })
+            .collect())
+    }
+
+    /// Handler for `debug_getBlobsByBlock`
+    async fn blobs_by_block(&self, block_id: BlockId) -> RpcResult<Vec<ArchivedBlob>> {
+        let archive = self.blob_archive()?;
+        let block_number = self
+            .provider()
+            .block_number_for_id(block_id)
+            .to_rpc_result()?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let blobs =
+            archive.get_block(block_number).map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(blobs.into_iter().map(rpc_archived_blob).collect())
+    }
+
+    /// Handler for `debug_getBlobsByVersionedHashes`
+    async fn blobs_by_versioned_hashes(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<ArchivedBlob>>> {
+        if versioned_hashes.len() > MAX_BLOB_VERSIONED_HASHES {
+            return Err(invalid_params_rpc_err(format!(
+                "requested {} versioned hashes, at most {MAX_BLOB_VERSIONED_HASHES} are allowed",
+                versioned_hashes.len()
+            )))
+        }
+        let blobs = self
+            .blob_archive()?
+            .get_by_versioned_hashes(&versioned_hashes)
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(blobs.into_iter().map(|blob| blob.map(rpc_archived_blob)).collect())
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let range = match self.trace_chain_range(start_exclusive, end_inclusive) {
+            Ok(range) => range,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+        let sink = pending.accept().await?;
+
+        // blocks are traced concurrently, but results are sent in block order
+        let opts = opts.unwrap_or_default();
+        let mut traces = futures::stream::iter(range)
+            .map(|number| self.trace_chain_block(number, opts.clone()))
+            .buffered(TRACE_CHAIN_CONCURRENCY);
+
+        loop {
+            let trace = tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    break
+                }
+                maybe_trace = traces.next() => match maybe_trace {
+                    Some(Ok(trace)) => trace,
+                    Some(Err(err)) => {
+                        debug!(target: "rpc::debug", %err, "Failed to trace chain");
+                        // the subscription is closed with an error notification, so that the
+                        // subscriber can tell a failed trace apart from a completed range
+                        let err: ErrorObject<'static> = err.into();
+                        return Err(SubscriptionError::from_json(serde_json::value::to_raw_value(
+                            &err,
+                        )?))
+                    }
+                    None => break,
+                }
+            };
+            let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &trace)?;
+            if sink.send(msg).await.is_err() {
+                break
+            }
+        }
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1065,16 +1651,28 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(
+            self,
+            block_id,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1141,32 +1739,45 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_hash(self, start_hash, end_hash)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_number(self, start_number, end_number)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
+    ///
+    /// Tracing options are not supported and rejected if set.
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        if opts.is_some_and(|opts| opts != GethDebugTracingOptions::default()) {
+            return Err(invalid_params_rpc_err("tracing options are not supported"))
+        }
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1213,12 +1824,16 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_standard_trace_bad_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_standard_trace_block_to_file(
@@ -1253,23 +1868,39 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
-    }
-
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        let bad_block = self.bad_block(block_hash).map_err(Into::into)?;
+        Self::debug_trace_raw_block(self, bad_block.rlp, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1293,6 +1924,21 @@ where
     }
 }
 
+/// Converts a blob of the [`BlobArchive`] into the `debug_getBlobsBy*` response.
+fn rpc_archived_blob(blob: reth_transaction_pool::blobstore::ArchivedBlob) -> ArchivedBlob {
+    ArchivedBlob {
+        block_number: blob.block_number,
+        transaction_hash: blob.tx_hash,
+        versioned_hash: blob.versioned_hash,
+        blob: Bytes::copy_from_slice(blob.blob.as_slice()),
+        proofs: blob
+            .proofs
+            .into_iter()
+            .map(|proof| Bytes::copy_from_slice(proof.as_slice()))
+            .collect(),
+    }
+}
+
 impl<Eth, Evm> std::fmt::Debug for DebugApi<Eth, Evm> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1312,4 +1958,210 @@ struct DebugApiInner<Eth, Evm> {
     blocking_task_guard: BlockingTaskGuard,
     /// block executor for debug & trace apis
     evm_config: Evm,
+    /// Recent blocks that were rejected by the engine
+    bad_blocks: BadBlockStore,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthApi, EthApiBuilder};
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::TxKind;
+    use reth_engine_util::bad_blocks::StoredBadBlock;
+    use reth_ethereum_primitives::{Block, BlockBody};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_testing_utils::generators;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use serde_json::Value;
+
+    type TestDebugApi =
+        DebugApi<EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>, EthEvmConfig>;
+
+    fn build_debug_api(provider: MockEthProvider, bad_blocks: BadBlockStore) -> TestDebugApi {
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let eth_api = EthApiBuilder::new(
+            provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            evm_config.clone(),
+        )
+        .build();
+        DebugApi::new(eth_api, BlockingTaskGuard::new(4), evm_config, bad_blocks)
+    }
+
+    /// Adds empty blocks `0..=last` to the provider.
+    fn add_empty_blocks(provider: &MockEthProvider, last: u64) {
+        let mut parent_hash = B256::ZERO;
+        for number in 0..=last {
+            let block = Block {
+                header: Header { number, parent_hash, ..Default::default() },
+                ..Default::default()
+            };
+            let hash = block.header.hash_slow();
+            provider.add_block(hash, block);
+            parent_hash = hash;
+        }
+    }
+
+    /// Subscribes to `debug_traceChain` and returns the response and all notifications.
+    async fn trace_chain(api: TestDebugApi, start: u64, end: u64) -> (Value, Vec<Value>) {
+        let module = api.into_rpc();
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "debug_traceChain",
+            "params": [U256::from(start), U256::from(end)],
+        });
+        let (response, mut rx) = module.raw_json_request(&request.to_string(), 16).await.unwrap();
+        let response = serde_json::from_str(response.get()).unwrap();
+
+        let mut notifications = Vec::new();
+        while let Some(notification) = rx.recv().await {
+            notifications.push(serde_json::from_str(notification.get()).unwrap());
+        }
+        (response, notifications)
+    }
+
+    #[tokio::test]
+    async fn trace_chain_rejects_empty_range() {
+        let provider = MockEthProvider::default();
+        add_empty_blocks(&provider, 2);
+
+        let (response, notifications) =
+            trace_chain(build_debug_api(provider, BadBlockStore::default()), 2, 1).await;
+        assert_eq!(response["error"]["code"], jsonrpsee_types::error::INVALID_PARAMS_CODE);
+        assert!(notifications.is_empty());
+    }
+
+    #[tokio::test]
+    async fn trace_chain_ends_with_error_notification() {
+        let provider = MockEthProvider::default();
+        add_empty_blocks(&provider, 0);
+
+        // block 1 is missing, so the subscription is accepted but tracing fails
+        let (response, notifications) =
+            trace_chain(build_debug_api(provider, BadBlockStore::default()), 0, 3).await;
+        let subscription = &response["result"];
+        assert!(!subscription.is_null());
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(&notifications[0]["params"]["subscription"], subscription);
+
+        let expected: ErrorObject<'static> = EthApiError::HeaderNotFound(1.into()).into();
+        let error = &notifications[0]["params"]["error"];
+        assert_eq!(error["code"], expected.code());
+        assert_eq!(error["message"], expected.message());
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_caps_block_range() {
+        let provider = MockEthProvider::default();
+        add_empty_blocks(&provider, MODIFIED_ACCOUNTS_MAX_BLOCKS + 1);
+        let api = build_debug_api(provider, BadBlockStore::default());
+
+        let accounts = api
+            .debug_get_modified_accounts_by_number(0, Some(MODIFIED_ACCOUNTS_MAX_BLOCKS))
+            .await
+            .unwrap();
+        assert!(accounts.is_empty());
+
+        let err = api
+            .debug_get_modified_accounts_by_number(0, Some(MODIFIED_ACCOUNTS_MAX_BLOCKS + 1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn intermediate_roots_rejects_tracing_options() {
+        let api = build_debug_api(MockEthProvider::default(), BadBlockStore::default());
+        let opts = GethDebugTracingOptions::default()
+            .with_tracer(GethDebugBuiltInTracerType::CallTracer.into());
+        let err = DebugApiServer::debug_intermediate_roots(&api, B256::ZERO, Some(opts))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+    }
+
+    #[tokio::test]
+    async fn intermediate_roots_of_bad_block() {
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let txs = (0..2)
+            .map(|nonce| {
+                generators::sign_tx_with_key_pair(
+                    key_pair,
+                    TxLegacy {
+                        nonce,
+                        gas_price: 1,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(Address::repeat_byte(0x22)),
+                        value: U256::from(1),
+                        ..Default::default()
+                    }
+                    .into(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let sender = txs[0].recover_signer().unwrap();
+        let block = Block {
+            header: Header { number: 1, gas_limit: 1_000_000, timestamp: 1, ..Default::default() },
+            body: BlockBody { transactions: txs, ..Default::default() },
+        }
+        .seal_slow();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000_000)));
+        // the mock pops the state roots from the back
+        let roots = [B256::repeat_byte(1), B256::repeat_byte(2)];
+        provider.state_roots.lock().extend(roots.iter().rev());
+
+        // the block is unknown to the provider and replayed from the bad block store
+        let bad_blocks = BadBlockStore::new(1);
+        bad_blocks.insert(&block, "invalid state root".to_string()).unwrap();
+        let api = build_debug_api(provider.clone(), bad_blocks);
+
+        let result = DebugApiServer::debug_intermediate_roots(
+            &api,
+            block.hash(),
+            Some(GethDebugTracingOptions::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result, roots);
+        assert!(provider.state_roots.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn bad_blocks_skips_undecodable_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let block =
+            Block { header: Header { number: 1, ..Default::default() }, ..Default::default() }
+                .seal_slow();
+        let store = BadBlockStore::open(dir.path().to_path_buf(), 2).unwrap();
+        store.insert(&block, "invalid state root".to_string()).unwrap();
+
+        // a stored block that is not valid rlp
+        let corrupt = StoredBadBlock {
+            hash: B256::repeat_byte(1),
+            number: 2,
+            rlp: Bytes::from_static(&[0xff]),
+            reason: String::new(),
+            rejected_at: u64::MAX,
+            sequence: 1,
+        };
+        std::fs::write(dir.path().join("2-corrupt.json"), serde_json::to_vec(&corrupt).unwrap())
+            .unwrap();
+        let store = BadBlockStore::open(dir.path().to_path_buf(), 2).unwrap();
+        assert_eq!(store.len(), 2);
+
+        let api = build_debug_api(MockEthProvider::default(), store);
+        let bad_blocks = DebugApiServer::bad_blocks(&api).await.unwrap();
+        assert_eq!(bad_blocks.len(), 1);
+        assert_eq!(bad_blocks[0].hash, block.hash());
+        assert_eq!(bad_blocks[0].block.header.hash, block.hash());
+        assert_eq!(bad_blocks[0].reason, "invalid state root");
+    }
 }
@@ -307,4 +307,7 @@ pub enum EthBundleError {
     /// Thrown when the blob gas usage of the blob transactions in a bundle exceed the maximum.
     #[error("blob gas usage exceeds the limit of {0} gas per block.")]
     Eip4844BlobGasExceeded(u64),
+    /// Thrown if a bundle submitted for inclusion contains blob transactions.
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransactionsNotSupported,
 }
@@ -0,0 +1,229 @@
+//! `Eth` and `mev` bundle submission backed by a [`BundlePool`].
+
+use crate::eth::{
+    bundle::EthBundleError, private_tx::EthPrivateTransactions, sim_bundle::EthSimBundle, EthBundle,
+};
+use alloy_consensus::Typed2718;
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_mev::{
+    CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthSendBundle, PrivateTransactionRequest, SendBundleRequest,
+    SendBundleResponse, SimBundleOverrides, SimBundleResponse,
+};
+use jsonrpsee::core::RpcResult;
+use reth_payload_util::{BundlePool, PayloadBundle};
+use reth_primitives_traits::SignedTransaction;
+use reth_rpc_api::{MevFullApiServer, MevSimApiServer};
+use reth_rpc_eth_api::{
+    helpers::{block::LoadBlock, Call, EthTransactions, LoadPendingBlock},
+    EthBundleApiServer, EthPrivateTransactionApiServer,
+};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_storage_api::{BlockNumReader, ProviderTx};
+use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
+use tracing::trace;
+
+/// `Eth` and `mev` bundle implementation that submits bundles to a [`BundlePool`].
+///
+/// Bundles in the pool are picked up by the payload builder and included atomically in the
+/// blocks they target. Simulation requests are served by [`EthBundle`] and [`EthSimBundle`].
+///
+/// Private transactions are served by [`EthPrivateTransactions`].
+pub struct EthFullBundle<Eth: EthTransactions> {
+    /// Handler for `eth_callBundle`.
+    bundle: EthBundle<Eth>,
+    /// Handler for `mev_simBundle`.
+    sim_bundle: EthSimBundle<Eth>,
+    /// Handler for private transactions.
+    private_txs: EthPrivateTransactions<Eth>,
+    /// The pool submitted bundles are added to.
+    pool: BundlePool<ProviderTx<Eth::Provider>>,
+}
+
+impl<Eth: EthTransactions> EthFullBundle<Eth> {
+    /// Creates a new `EthFullBundle` instance.
+    pub fn new(
+        bundle: EthBundle<Eth>,
+        sim_bundle: EthSimBundle<Eth>,
+        pool: BundlePool<ProviderTx<Eth::Provider>>,
+    ) -> Self {
+        let private_txs = EthPrivateTransactions::new(bundle.eth_api().clone());
+        Self { bundle, sim_bundle, private_txs, pool }
+    }
+
+    /// Returns the pool submitted bundles are added to.
+    pub const fn pool(&self) -> &BundlePool<ProviderTx<Eth::Provider>> {
+        &self.pool
+    }
+
+    /// Adds the bundle to the pool and returns its hash.
+    ///
+    /// Bundles that only target blocks that are already part of the chain are rejected.
+    fn add_bundle(&self, bundle: PayloadBundle<ProviderTx<Eth::Provider>>) -> RpcResult<B256> {
+        trace!(target: "rpc::eth", hash=%bundle.hash, block=bundle.block_number, "Adding bundle");
+        let next_block =
+            self.bundle.eth_api().provider().best_block_number().map_err(EthApiError::from)? + 1;
+        self.pool.remove_expired(next_block);
+        self.pool
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()).into())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthFullBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    /// Handler for `eth_sendBundle`
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+            ..
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            )
+            .into())
+        }
+        if block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            )
+            .into())
+        }
+
+        let transactions = txs
+            .iter()
+            .map(|tx| {
+                let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx)?;
+                if tx.is_eip4844() {
+                    return Err(EthApiError::InvalidParams(
+                        EthBundleError::BlobTransactionsNotSupported.to_string(),
+                    ))
+                }
+                Ok(tx.map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bundle = PayloadBundle::new(transactions, block_number)
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes)
+            .with_replacement_uuid(replacement_uuid);
+
+        let bundle_hash = self.add_bundle(bundle)?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Handler for `eth_callBundle`
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        EthBundle::call_bundle(&self.bundle, request).await.map_err(Into::into)
+    }
+
+    /// Handler for `eth_cancelBundle`
+    ///
+    /// The `bundleHash` of the request is the replacement UUID the bundle was submitted with.
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        trace!(target: "rpc::eth", uuid=%request.bundle_hash, "Cancelling bundle");
+        self.pool.cancel_bundle(&request.bundle_hash);
+        Ok(())
+    }
+
+    /// Handler for `eth_sendPrivateTransaction`
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        EthPrivateTransactionApiServer::send_private_transaction(&self.private_txs, request).await
+    }
+
+    /// Handler for `eth_sendPrivateRawTransaction`
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        EthPrivateTransactionApiServer::send_private_raw_transaction(&self.private_txs, bytes).await
+    }
+
+    /// Handler for `eth_cancelPrivateTransaction`
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        EthPrivateTransactionApiServer::cancel_private_transaction(&self.private_txs, request).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevFullApiServer for EthFullBundle<Eth>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
+    /// Handler for `mev_sendBundle`
+    ///
+    /// Nested bundles are flattened and included as a single bundle at the inclusion range of the
+    /// outermost bundle. Refund and privacy settings are ignored.
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        let items = self.sim_bundle.parse_and_flatten_bundle(&request)?;
+        if items.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            )
+            .into())
+        }
+        if items.iter().any(|item| item.tx.is_eip4844()) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlobTransactionsNotSupported.to_string(),
+            )
+            .into())
+        }
+
+        let block_number = request.inclusion.block_number();
+        let max_block_number = request.inclusion.max_block_number().unwrap_or(block_number);
+
+        let mut reverting_tx_hashes = Vec::new();
+        let mut transactions = Vec::with_capacity(items.len());
+        for item in items {
+            if item.can_revert {
+                reverting_tx_hashes.push(*item.tx.tx_hash());
+            }
+            transactions.push(item.tx);
+        }
+
+        let bundle = PayloadBundle::new(transactions, block_number)
+            .with_max_block_number(max_block_number)
+            .with_reverting_tx_hashes(reverting_tx_hashes);
+
+        let bundle_hash = self.add_bundle(bundle)?;
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    /// Handler for `mev_simBundle`
+    async fn sim_bundle(
+        &self,
+        bundle: SendBundleRequest,
+        sim_overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        MevSimApiServer::sim_bundle(&self.sim_bundle, bundle, sim_overrides).await
+    }
+}
+
+impl<Eth: EthTransactions> std::fmt::Debug for EthFullBundle<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthFullBundle").field("pool", &self.pool).finish_non_exhaustive()
+    }
+}
+
+impl<Eth: EthTransactions> Clone for EthFullBundle<Eth> {
+    fn clone(&self) -> Self {
+        Self {
+            bundle: self.bundle.clone(),
+            sim_bundle: self.sim_bundle.clone(),
+            private_txs: self.private_txs.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Builds an RPC receipt response w.r.t. data layout of network.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, SignerRecoverable, TransactionMeta};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_rpc_eth_api::{helpers::LoadReceipt, FromEthApiError, RpcNodeCoreExt, RpcReceipt};
@@ -24,15 +24,26 @@ where
         receipt: Receipt,
     ) -> Result<RpcReceipt<Self::NetworkTypes>, Self::Error> {
         let hash = meta.block_hash;
-        // get all receipts for the block
-        let all_receipts = self
+        // get the block for the recorded sender and all receipts of the block
+        let (block, all_receipts) = self
             .cache()
-            .get_receipts(hash)
+            .get_block_and_receipts(hash)
             .await
             .map_err(Self::Error::from_eth_err)?
             .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+        let sender = match block.senders().get(meta.index as usize) {
+            Some(sender) => *sender,
+            None => tx.recover_signer_unchecked().map_err(EthApiError::from)?,
+        };
         let blob_params = self.provider().chain_spec().blob_params_at_timestamp(meta.timestamp);
 
-        Ok(EthReceiptBuilder::new(&tx, meta, &receipt, &all_receipts, blob_params)?.build())
+        Ok(EthReceiptBuilder::new(
+            Recovered::new_unchecked(&tx, sender),
+            meta,
+            &receipt,
+            &all_receipts,
+            blob_params,
+        )?
+        .build())
     }
 }
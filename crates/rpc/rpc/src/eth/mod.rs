@@ -4,7 +4,9 @@ pub mod builder;
 pub mod bundle;
 pub mod core;
 pub mod filter;
+pub mod full_bundle;
 pub mod helpers;
+pub mod private_tx;
 pub mod pubsub;
 pub mod sim_bundle;
 
@@ -13,6 +15,8 @@ pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
 pub use core::{EthApi, EthApiFor};
 pub use filter::EthFilter;
+pub use full_bundle::EthFullBundle;
+pub use private_tx::EthPrivateTransactions;
 pub use pubsub::EthPubSub;
 
 pub use helpers::{
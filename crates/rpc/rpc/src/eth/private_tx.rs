@@ -0,0 +1,78 @@
+//! `Eth` private transaction submission.
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_mev::{CancelPrivateTransactionRequest, PrivateTransactionRequest};
+use jsonrpsee::core::RpcResult;
+use reth_rpc_eth_api::{helpers::EthTransactions, EthPrivateTransactionApiServer};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use tracing::trace;
+
+/// `Eth` private transaction implementation.
+///
+/// Private transactions are added to the transaction pool as [`TransactionOrigin::Private`], they
+/// are included in locally built blocks but never propagated to peers.
+#[derive(Debug, Clone)]
+pub struct EthPrivateTransactions<Eth> {
+    /// The `Eth` API the transactions are submitted to.
+    eth_api: Eth,
+}
+
+impl<Eth> EthPrivateTransactions<Eth> {
+    /// Creates a new `EthPrivateTransactions` instance.
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthPrivateTransactionApiServer for EthPrivateTransactions<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Handler for `eth_sendPrivateTransaction`
+    ///
+    /// The transaction is kept in the pool for the configured number of blocks, the requested
+    /// `maxBlockNumber` is ignored.
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        self.send_private_raw_transaction(request.tx).await
+    }
+
+    /// Handler for `eth_sendPrivateRawTransaction`
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        let recovered = recover_raw_transaction(&bytes)?;
+        let pool_transaction = <Eth::Pool as TransactionPool>::Transaction::from_pooled(recovered);
+
+        // private transactions are only included in locally built blocks and never propagated
+        let hash = self
+            .eth_api
+            .pool()
+            .add_transaction(TransactionOrigin::Private, pool_transaction)
+            .await
+            .map_err(EthApiError::from)?;
+        trace!(target: "rpc::eth", ?hash, "Added private transaction");
+        Ok(hash)
+    }
+
+    /// Handler for `eth_cancelPrivateTransaction`
+    ///
+    /// Only transactions that were submitted as private transactions can be cancelled.
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        let pool = self.eth_api.pool();
+        if !pool.get(&request.tx_hash).is_some_and(|tx| tx.origin.is_private()) {
+            return Ok(false)
+        }
+        Ok(!pool.remove_transactions(vec![request.tx_hash]).is_empty())
+    }
+}
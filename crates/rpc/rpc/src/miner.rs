@@ -1,25 +1,86 @@
+use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_primitives::{Bytes, U128};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_ethereum_payload_builder::EthereumBuilderConfigHandle;
 use reth_rpc_api::MinerApiServer;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
+use tracing::debug;
 
 /// `miner` API implementation.
 ///
 /// This type provides the functionality for handling `miner` related requests.
+///
+/// If it is configured with the payload builder's [`EthereumBuilderConfigHandle`], all settings
+/// are applied to the next payload that is built. Otherwise, all requests are no-ops that return
+/// `false`.
 #[derive(Clone, Debug, Default)]
-pub struct MinerApi {}
+pub struct MinerApi {
+    builder_config: Option<EthereumBuilderConfigHandle>,
+}
+
+impl MinerApi {
+    /// Creates a new instance of `MinerApi` that updates the given builder configuration.
+    pub const fn new(builder_config: EthereumBuilderConfigHandle) -> Self {
+        Self { builder_config: Some(builder_config) }
+    }
+}
 
 #[async_trait]
 impl MinerApiServer for MinerApi {
-    fn set_extra(&self, _record: Bytes) -> RpcResult<bool> {
-        Ok(false)
+    fn set_extra(&self, record: Bytes) -> RpcResult<bool> {
+        let Some(builder_config) = &self.builder_config else { return Ok(false) };
+        if record.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "extra data exceeds {MAXIMUM_EXTRA_DATA_SIZE}-byte limit"
+            )))
+        }
+
+        debug!(target: "rpc::miner", extra_data = %record, "Setting extra data");
+        builder_config.set_extra_data(record);
+        Ok(true)
     }
 
-    fn set_gas_price(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_price(&self, gas_price: U128) -> RpcResult<bool> {
+        let Some(builder_config) = &self.builder_config else { return Ok(false) };
+
+        debug!(target: "rpc::miner", %gas_price, "Setting minimum priority fee");
+        builder_config.set_min_priority_fee(gas_price.to());
+        Ok(true)
     }
 
-    fn set_gas_limit(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_limit(&self, gas_limit: U128) -> RpcResult<bool> {
+        let Some(builder_config) = &self.builder_config else { return Ok(false) };
+        let gas_limit = u64::try_from(gas_limit)
+            .map_err(|_| invalid_params_rpc_err("gas limit exceeds u64::MAX"))?;
+
+        debug!(target: "rpc::miner", gas_limit, "Setting desired gas limit");
+        builder_config.set_gas_limit(gas_limit);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_payload_builder::EthereumBuilderConfig;
+
+    #[test]
+    fn updates_builder_config() {
+        let builder_config = EthereumBuilderConfigHandle::new(EthereumBuilderConfig::new());
+        let api = MinerApi::new(builder_config.clone());
+
+        assert!(api.set_extra(Bytes::from_static(b"reth")).unwrap());
+        assert!(api.set_gas_price(U128::from(7)).unwrap());
+        assert!(api.set_gas_limit(U128::from(45_000_000)).unwrap());
+        assert!(api.set_extra(Bytes::from(vec![0u8; MAXIMUM_EXTRA_DATA_SIZE + 1])).is_err());
+        assert!(api.set_gas_limit(U128::MAX).is_err());
+
+        let config = builder_config.config();
+        assert_eq!(config.extra_data, Some(Bytes::from_static(b"reth")));
+        assert_eq!(config.min_priority_fee, 7);
+        assert_eq!(config.desired_gas_limit, 45_000_000);
+
+        assert!(!MinerApi::default().set_gas_limit(U128::from(1)).unwrap());
     }
 }
@@ -1,5 +1,5 @@
 use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
+use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId, BlockNumberOrTag};
 use alloy_network::{ReceiptResponse, TransactionResponse};
 use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
@@ -15,10 +15,11 @@ use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    FullEthApiTypes, RpcBlock, RpcHeader, RpcNodeCore, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
-use reth_rpc_server_types::result::internal_rpc_err;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::CallHistoryReader;
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
@@ -27,6 +28,12 @@ use revm_inspectors::{
 
 const API_LEVEL: u64 = 8;
 
+/// The number of blocks that are looked up in the call trace index at once.
+const ADDRESS_HISTORY_WINDOW: u64 = 100_000;
+
+/// The maximum number of transactions returned by a single `ots_searchTransactions*` call.
+const MAX_SEARCH_PAGE_SIZE: usize = 100;
+
 /// Otterscan API.
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
@@ -62,6 +69,130 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + TraceExt
+        + RpcNodeCore<Provider: CallHistoryReader>
+        + 'static,
+{
+    /// Returns the transactions of the block that interact with the given address, along with
+    /// their receipts.
+    ///
+    /// A transaction interacts with the address if the address is the caller or the callee of
+    /// any call frame of the transaction.
+    async fn address_transactions_in_block(
+        &self,
+        address: Address,
+        block_number: u64,
+    ) -> RpcResult<Vec<(RpcTransaction<Eth::NetworkTypes>, OtsTransactionReceipt)>> {
+        let block_id = BlockId::from(block_number);
+        let matches = self
+            .eth
+            .trace_block_with(
+                block_id,
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |_tx_info, inspector, _, _, _| {
+                    Ok(inspector.traces().nodes().iter().any(|node| {
+                        node.trace.caller == address ||
+                            node.trace.address == address ||
+                            node.trace.selfdestruct_refund_target == Some(address)
+                    }))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .unwrap_or_default();
+        if !matches.contains(&true) {
+            return Ok(Vec::new())
+        }
+
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+
+        let timestamp = Some(block.header.timestamp());
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        transactions
+            .into_iter()
+            .zip(receipts)
+            .zip(matches)
+            .filter(|(_, matches)| *matches)
+            .map(|((tx, receipt), _)| {
+                let receipt = ots_receipt(receipt, tx.ty(), timestamp);
+                Ok((tx, receipt))
+            })
+            .collect()
+    }
+
+    /// Collects at least `page_size` transactions interacting with the address from the given
+    /// blocks, or all of them if there are fewer. The page size is capped at
+    /// [`MAX_SEARCH_PAGE_SIZE`].
+    ///
+    /// Blocks covered by the call trace index are only searched if the address was the caller or
+    /// the callee of a call frame in them, newer blocks are traced directly. Fails if the index
+    /// is not maintained by the node.
+    ///
+    /// Blocks are walked in the order of the given windows and the transactions of a block are
+    /// never split across pages. Returns the transactions in the order they were found and whether
+    /// there are no more blocks to search.
+    async fn search_address_transactions(
+        &self,
+        address: Address,
+        windows: impl Iterator<Item = (u64, u64)> + Send,
+        descending: bool,
+        page_size: usize,
+    ) -> RpcResult<(Vec<(RpcTransaction<Eth::NetworkTypes>, OtsTransactionReceipt)>, bool)> {
+        let page_size = page_size.min(MAX_SEARCH_PAGE_SIZE);
+        let provider = self.eth.provider();
+        let Some(checkpoint) = provider.call_history_checkpoint().map_err(EthApiError::from)?
+        else {
+            return Err(invalid_params_rpc_err(
+                "call trace index is not available, the node must be started with --ots.index",
+            ))
+        };
+
+        let mut found = Vec::new();
+        for (from, to) in windows {
+            let mut blocks = if from <= checkpoint {
+                provider
+                    .call_history_blocks(address, from..=to.min(checkpoint))
+                    .map_err(EthApiError::from)?
+            } else {
+                Vec::new()
+            };
+            // blocks that are not indexed yet
+            blocks.extend(from.max(checkpoint.saturating_add(1))..=to);
+            if descending {
+                blocks.reverse();
+            }
+
+            for block_number in blocks {
+                if found.len() >= page_size {
+                    return Ok((found, false))
+                }
+                let mut block_txs =
+                    self.address_transactions_in_block(address, block_number).await?;
+                if descending {
+                    block_txs.reverse();
+                }
+                found.extend(block_txs);
+            }
+        }
+        Ok((found, true))
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -73,6 +204,7 @@ where
             RpcHeader<Eth::NetworkTypes>,
         > + EthTransactions
         + TraceExt
+        + RpcNodeCore<Provider: CallHistoryReader>
         + 'static,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
@@ -245,32 +377,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -280,23 +387,61 @@ where
     }
 
     /// Handler for `ots_searchTransactionsBefore`
+    ///
+    /// Returns the transactions interacting with the address in blocks before the given block,
+    /// newest first. Block `0` starts the search at the latest block.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let latest = self.eth.block_number()?.saturating_to::<u64>();
+        let (end, first_page) = match block_number.into_inner() {
+            BlockNumberOrTag::Number(0) => (Some(latest), true),
+            BlockNumberOrTag::Number(number) => {
+                (number.checked_sub(1).map(|n| n.min(latest)), false)
+            }
+            _ => (Some(latest), true),
+        };
+
+        // search windows from `end` down to genesis
+        let windows = std::iter::successors(end, |to| to.checked_sub(ADDRESS_HISTORY_WINDOW))
+            .map(|to| (to.saturating_sub(ADDRESS_HISTORY_WINDOW - 1), to));
+
+        let (found, last_page) =
+            self.search_address_transactions(address, windows, true, page_size).await?;
+        let (txs, receipts) = found.into_iter().unzip();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `ots_searchTransactionsAfter`
+    ///
+    /// Returns the transactions interacting with the address in blocks after the given block,
+    /// newest first.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let latest = self.eth.block_number()?.saturating_to::<u64>();
+        let (start, last_page) = match block_number.into_inner() {
+            BlockNumberOrTag::Number(number) => (number.saturating_add(1), number == 0),
+            _ => (latest.saturating_add(1), false),
+        };
+
+        // search windows from `start` up to the latest block
+        let windows = std::iter::successors(Some(start).filter(|start| *start <= latest), |from| {
+            from.checked_add(ADDRESS_HISTORY_WINDOW).filter(|from| *from <= latest)
+        })
+        .map(|from| (from, from.saturating_add(ADDRESS_HISTORY_WINDOW - 1).min(latest)));
+
+        let (mut found, first_page) =
+            self.search_address_transactions(address, windows, false, page_size).await?;
+        found.reverse();
+        let (txs, receipts) = found.into_iter().unzip();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -377,3 +522,35 @@ where
         Ok(found)
     }
 }
+
+/// Converts an RPC receipt into the receipt format of otterscan, which omits the logs.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
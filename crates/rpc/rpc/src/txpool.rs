@@ -1,18 +1,30 @@
 use core::fmt;
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::RpcResult, server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink,
+    SubscriptionSink,
+};
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{
+    TxPoolApiServer, TxpoolDiff, TxpoolDiffFilter, TxpoolDiscardReason, TxpoolSubpool,
+};
+use reth_rpc_server_types::result::internal_rpc_err;
 use reth_rpc_types_compat::TransactionCompat;
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, DiscardReason, PoolConsensusTx, PoolDiffEvent, PoolTransaction, SubPool,
+    TransactionPool, ValidPoolTransaction,
 };
 use tracing::trace;
 
@@ -24,12 +36,26 @@ pub struct TxPoolApi<Pool, Eth> {
     /// An interface to interact with the pool
     pool: Pool,
     tx_resp_builder: Eth,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 impl<Pool, Eth> TxPoolApi<Pool, Eth> {
     /// Creates a new instance of `TxpoolApi`.
-    pub const fn new(pool: Pool, tx_resp_builder: Eth) -> Self {
-        Self { pool, tx_resp_builder }
+    ///
+    /// Subscription tasks are spawned via [`tokio::task::spawn`]
+    pub fn new(pool: Pool, tx_resp_builder: Eth) -> Self {
+        Self::with_spawner(pool, tx_resp_builder, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new instance of `TxpoolApi` that spawns subscription tasks with the given
+    /// spawner.
+    pub fn with_spawner(
+        pool: Pool,
+        tx_resp_builder: Eth,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { pool, tx_resp_builder, subscription_task_spawner }
     }
 }
 
@@ -69,6 +95,183 @@ where
 
         Ok(content)
     }
+
+    /// Converts the pool transaction into its RPC representation.
+    fn rpc_transaction(
+        &self,
+        tx: &ValidPoolTransaction<Pool::Transaction>,
+    ) -> Result<Eth::Transaction, ErrorObject<'static>> {
+        self.tx_resp_builder.fill_pending(tx.transaction.clone_into_consensus()).map_err(Into::into)
+    }
+
+    /// Converts the diff of a pool transaction into its RPC representation.
+    fn rpc_diff(
+        &self,
+        diff: TxpoolDiff<Arc<ValidPoolTransaction<Pool::Transaction>>>,
+    ) -> Result<TxpoolDiff<Eth::Transaction>, ErrorObject<'static>> {
+        Ok(match diff {
+            TxpoolDiff::Added { transaction, subpool } => {
+                TxpoolDiff::Added { transaction: self.rpc_transaction(&transaction)?, subpool }
+            }
+            TxpoolDiff::Promoted { transaction } => {
+                TxpoolDiff::Promoted { transaction: self.rpc_transaction(&transaction)? }
+            }
+            TxpoolDiff::Parked { transaction, subpool } => {
+                TxpoolDiff::Parked { transaction: self.rpc_transaction(&transaction)?, subpool }
+            }
+            TxpoolDiff::Replaced { transaction, replaced_by } => TxpoolDiff::Replaced {
+                transaction: self.rpc_transaction(&transaction)?,
+                replaced_by,
+            },
+            TxpoolDiff::Mined { transaction, block_hash } => {
+                TxpoolDiff::Mined { transaction: self.rpc_transaction(&transaction)?, block_hash }
+            }
+            TxpoolDiff::Discarded { transaction, reason } => {
+                TxpoolDiff::Discarded { transaction: self.rpc_transaction(&transaction)?, reason }
+            }
+        })
+    }
+
+    /// Streams the changes of all transactions that match the filter to the subscription sink.
+    async fn pipe_diffs(
+        self,
+        sink: SubscriptionSink,
+        filter: TxpoolDiffFilter,
+    ) -> Result<(), ErrorObject<'static>> {
+        // subscribe first, so that no change is missed
+        let mut events = self.pool.pool_diff_listener();
+        let mut tracker = DiffTracker::new(&self.pool, filter);
+
+        loop {
+            let event = tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    break Ok(())
+                }
+                maybe_event = events.next() => match maybe_event {
+                    Some(event) => event,
+                    None => {
+                        // stream ended
+                        break Ok(())
+                    }
+                },
+            };
+
+            let Some(diff) = tracker.on_event(&self.pool, event) else { continue };
+            let diff = self.rpc_diff(diff)?;
+            let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &diff)
+                .map_err(|err| internal_rpc_err(err.to_string()))?;
+            if sink.send(msg).await.is_err() {
+                break Ok(())
+            }
+        }
+    }
+}
+
+/// Turns the [`PoolDiffEvent`]s of the transactions that match a [`TxpoolDiffFilter`] into
+/// [`TxpoolDiff`]s.
+///
+/// The matching transactions that are in the pool are tracked, so that the full transaction can
+/// also be included in diffs of transactions that left the pool.
+#[derive(Debug)]
+struct DiffTracker<T: PoolTransaction> {
+    filter: TxpoolDiffFilter,
+    tracked: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+}
+
+impl<T: PoolTransaction> DiffTracker<T> {
+    /// Creates a new tracker for the matching transactions that are currently in the pool.
+    fn new<Pool>(pool: &Pool, filter: TxpoolDiffFilter) -> Self
+    where
+        Pool: TransactionPool<Transaction = T>,
+    {
+        let mut this = Self { filter, tracked: HashMap::default() };
+        this.resync(pool);
+        this
+    }
+
+    /// Returns `true` if the transaction matches the filter.
+    fn matches(&self, tx: &ValidPoolTransaction<T>) -> bool {
+        self.filter.matches(&tx.sender(), tx.transaction.to().as_ref())
+    }
+
+    /// Replaces the tracked transactions with the matching transactions that are currently in the
+    /// pool.
+    fn resync<Pool>(&mut self, pool: &Pool)
+    where
+        Pool: TransactionPool<Transaction = T>,
+    {
+        let AllPoolTransactions { pending, queued } = pool.all_transactions();
+        self.tracked = pending
+            .into_iter()
+            .chain(queued)
+            .filter(|tx| self.matches(tx))
+            .map(|tx| (*tx.hash(), tx))
+            .collect();
+    }
+
+    /// Returns the diff for the event if it belongs to a matching transaction.
+    fn on_event<Pool>(
+        &mut self,
+        pool: &Pool,
+        event: PoolDiffEvent,
+    ) -> Option<TxpoolDiff<Arc<ValidPoolTransaction<T>>>>
+    where
+        Pool: TransactionPool<Transaction = T>,
+    {
+        let diff = match event {
+            PoolDiffEvent::SubPool { tx_hash, subpool } => {
+                let rpc_subpool = rpc_subpool(subpool);
+                if let Some(tx) = self.tracked.get(&tx_hash) {
+                    let transaction = Arc::clone(tx);
+                    if subpool.is_pending() {
+                        TxpoolDiff::Promoted { transaction }
+                    } else {
+                        TxpoolDiff::Parked { transaction, subpool: rpc_subpool }
+                    }
+                } else {
+                    let tx = pool.get(&tx_hash).filter(|tx| self.matches(tx))?;
+                    self.tracked.insert(tx_hash, Arc::clone(&tx));
+                    TxpoolDiff::Added { transaction: tx, subpool: rpc_subpool }
+                }
+            }
+            PoolDiffEvent::Replaced { tx_hash, replaced_by } => {
+                TxpoolDiff::Replaced { transaction: self.tracked.remove(&tx_hash)?, replaced_by }
+            }
+            PoolDiffEvent::Mined { tx_hash, block_hash } => {
+                TxpoolDiff::Mined { transaction: self.tracked.remove(&tx_hash)?, block_hash }
+            }
+            PoolDiffEvent::Discarded { tx_hash, reason } => TxpoolDiff::Discarded {
+                transaction: self.tracked.remove(&tx_hash)?,
+                reason: rpc_discard_reason(reason),
+            },
+            PoolDiffEvent::Lagged => {
+                // events were dropped, so the tracked transactions may no longer match the pool
+                self.resync(pool);
+                return None
+            }
+        };
+        Some(diff)
+    }
+}
+
+/// Converts the sub-pool into its RPC representation.
+const fn rpc_subpool(subpool: SubPool) -> TxpoolSubpool {
+    match subpool {
+        SubPool::Pending => TxpoolSubpool::Pending,
+        SubPool::BaseFee => TxpoolSubpool::Basefee,
+        SubPool::Queued => TxpoolSubpool::Queued,
+        SubPool::Blob => TxpoolSubpool::Blob,
+    }
+}
+
+/// Converts the discard reason into its RPC representation.
+const fn rpc_discard_reason(reason: DiscardReason) -> TxpoolDiscardReason {
+    match reason {
+        DiscardReason::Evicted => TxpoolDiscardReason::Evicted,
+        DiscardReason::Outdated => TxpoolDiscardReason::Outdated,
+        DiscardReason::Removed => TxpoolDiscardReason::Removed,
+    }
 }
 
 #[async_trait]
@@ -143,6 +346,20 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Handler for `txpool_subscribeDiffs`
+    async fn txpool_subscribe_diffs(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: Option<TxpoolDiffFilter>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let this = self.clone();
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = this.pipe_diffs(sink, filter.unwrap_or_default()).await;
+        }));
+        Ok(())
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -150,3 +367,94 @@ impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    #[test]
+    fn filter_matches_sender_and_recipient() {
+        let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+        assert!(TxpoolDiffFilter::default().matches(&alice, None));
+
+        let filter = TxpoolDiffFilter { from: vec![alice], to: vec![] };
+        assert!(filter.matches(&alice, Some(&bob)));
+        assert!(!filter.matches(&bob, Some(&alice)));
+
+        let filter = TxpoolDiffFilter { from: vec![alice], to: vec![bob] };
+        assert!(filter.matches(&alice, Some(&bob)));
+        assert!(!filter.matches(&alice, Some(&carol)));
+        // contract creations have no recipient
+        assert!(!filter.matches(&alice, None));
+    }
+
+    #[tokio::test]
+    async fn tracks_matching_transactions() {
+        let pool = testing_pool();
+        let tx_0 = MockTransaction::eip1559();
+        let tx_1 = tx_0.next();
+        let other = MockTransaction::eip1559();
+
+        let mut events = pool.pool_diff_listener();
+        let filter = TxpoolDiffFilter { from: vec![tx_0.sender()], to: vec![] };
+        let mut tracker = DiffTracker::new(&pool, filter);
+
+        let mut diffs = Vec::new();
+
+        // nonce gap
+        pool.add_transaction(TransactionOrigin::External, tx_1.clone()).await.unwrap();
+        diffs.extend(tracker.on_event(&pool, events.next().await.unwrap()));
+
+        pool.add_transaction(TransactionOrigin::External, other.clone()).await.unwrap();
+        diffs.extend(tracker.on_event(&pool, events.next().await.unwrap()));
+
+        // closes the nonce gap and promotes the queued transaction
+        pool.add_transaction(TransactionOrigin::External, tx_0.clone()).await.unwrap();
+        diffs.extend(tracker.on_event(&pool, events.next().await.unwrap()));
+        diffs.extend(tracker.on_event(&pool, events.next().await.unwrap()));
+
+        pool.remove_transactions(vec![*tx_1.hash()]);
+        diffs.extend(tracker.on_event(&pool, events.next().await.unwrap()));
+
+        assert_eq!(diffs.len(), 4);
+        assert!(matches!(
+            &diffs[0],
+            TxpoolDiff::Added { transaction, subpool: TxpoolSubpool::Queued }
+                if transaction.hash() == tx_1.hash()
+        ));
+        assert!(matches!(
+            &diffs[1],
+            TxpoolDiff::Added { transaction, subpool: TxpoolSubpool::Pending }
+                if transaction.hash() == tx_0.hash()
+        ));
+        assert!(matches!(
+            &diffs[2],
+            TxpoolDiff::Promoted { transaction } if transaction.hash() == tx_1.hash()
+        ));
+        assert!(matches!(
+            &diffs[3],
+            TxpoolDiff::Discarded { transaction, reason: TxpoolDiscardReason::Removed }
+                if transaction.hash() == tx_1.hash()
+        ));
+    }
+
+    #[tokio::test]
+    async fn resyncs_after_lag() {
+        let pool = testing_pool();
+        let tx = MockTransaction::eip1559();
+        pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+
+        let mut tracker = DiffTracker::new(&pool, TxpoolDiffFilter::default());
+        assert!(tracker.tracked.contains_key(tx.hash()));
+
+        // the removal of the transaction was missed
+        pool.remove_transactions(vec![*tx.hash()]);
+        assert!(tracker.on_event(&pool, PoolDiffEvent::Lagged).is_none());
+        assert!(tracker.tracked.is_empty());
+    }
+}
@@ -21,6 +21,8 @@ mod prune;
 mod s3;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap sync stage.
+mod snap;
 /// The transaction lookup stage
 mod tx_lookup;
 
@@ -36,6 +38,7 @@ pub use merkle::*;
 pub use prune::*;
 pub use s3::*;
 pub use sender_recovery::*;
+pub use snap::*;
 pub use tx_lookup::*;
 
 mod utils;
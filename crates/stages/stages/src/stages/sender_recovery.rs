@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxNumber};
 use reth_config::config::SenderRecoveryConfig;
 use reth_consensus::ConsensusError;
 use reth_db::static_file::TransactionMask;
@@ -11,6 +11,7 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{GotExpected, NodePrimitives, SignedTransaction};
 use reth_provider::{
+    providers::{StaticFileProviderRWRefMut, StaticFileWriter},
     BlockReader, DBProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
     StaticFileProviderFactory, StatsReader,
 };
@@ -20,7 +21,12 @@ use reth_stages_api::{
     StageId, UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
-use std::{fmt::Debug, ops::Range, sync::mpsc};
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    ops::{Range, RangeInclusive},
+    sync::mpsc,
+};
 use thiserror::Error;
 use tracing::*;
 
@@ -38,6 +44,10 @@ type RecoveryResultSender = mpsc::Sender<Result<(u64, Address), Box<SenderRecove
 /// The sender recovery stage iterates over existing transactions,
 /// recovers the transaction signer and stores them
 /// in [`TransactionSenders`][reth_db_api::tables::TransactionSenders] table.
+///
+/// If sender recovery is not pruned and the
+/// [`StaticFileSegment::TransactionSenders`] static files are up to date with the stage
+/// checkpoint, the senders are written to static files instead.
 #[derive(Clone, Debug)]
 pub struct SenderRecoveryStage {
     /// The size of inserted items after which the control
@@ -58,6 +68,69 @@ impl Default for SenderRecoveryStage {
     }
 }
 
+impl SenderRecoveryStage {
+    /// Performs consistency check on static files and returns `true` if the senders of the blocks
+    /// after `checkpoint` should be written to static files.
+    ///
+    /// If the highest block in the static files is higher than the checkpoint, because of an
+    /// unexpected shutdown after committing to static files but **NOT** to the database, the
+    /// static files are rolled back to the checkpoint.
+    ///
+    /// If the static files are behind the checkpoint, the senders are written to the database and
+    /// moved to static files later by the static file producer.
+    fn ensure_consistency<Provider>(
+        &self,
+        provider: &Provider,
+        checkpoint: BlockNumber,
+    ) -> Result<bool, StageError>
+    where
+        Provider: StaticFileProviderFactory + DBProvider + BlockReader,
+    {
+        // If there's any sender recovery pruning configured, senders are written directly to
+        // database.
+        if provider.prune_modes_ref().sender_recovery.is_some() {
+            return Ok(false)
+        }
+
+        let static_file_provider = provider.static_file_provider();
+        let Some(highest_static_file_block) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::TransactionSenders)
+        else {
+            return Ok(false)
+        };
+
+        if highest_static_file_block < checkpoint {
+            return Ok(false)
+        }
+
+        if highest_static_file_block > checkpoint {
+            // Get next expected sender number
+            let next_sender_num = provider
+                .block_body_indices(checkpoint)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(checkpoint))?
+                .next_tx_num();
+
+            // Get next expected sender number in static files
+            let next_static_file_sender_num = static_file_provider
+                .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+                .map(|num| num + 1)
+                .unwrap_or(0);
+
+            let mut static_file_producer =
+                static_file_provider.latest_writer(StaticFileSegment::TransactionSenders)?;
+            static_file_producer.prune_transaction_senders(
+                next_static_file_sender_num.saturating_sub(next_sender_num),
+                checkpoint,
+            )?;
+            // Since this is a database <-> static file inconsistency, we commit the change
+            // straight away.
+            static_file_producer.commit()?;
+        }
+
+        Ok(true)
+    }
+}
+
 impl<Provider> Stage<Provider> for SenderRecoveryStage
 where
     Provider: DBProvider<Tx: DbTxMut>
@@ -84,9 +157,19 @@ where
             input.next_block_range_with_transaction_threshold(provider, self.commit_threshold)?;
         let end_block = *block_range.end();
 
+        let static_file_provider = provider.static_file_provider();
+        let mut senders_writer = if self
+            .ensure_consistency(provider, input.checkpoint().block_number)?
+        {
+            SendersWriter::static_file(provider, &static_file_provider, block_range)?
+        } else {
+            SendersWriter::Database(provider.tx_ref().cursor_write::<tables::TransactionSenders>()?)
+        };
+
         // No transactions to walk over
         if tx_range.is_empty() {
             info!(target: "sync::stages::sender_recovery", ?tx_range, "Target transaction already reached");
+            senders_writer.finish()?;
             return Ok(ExecOutput {
                 checkpoint: StageCheckpoint::new(end_block)
                     .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
@@ -94,9 +177,6 @@ where
             })
         }
 
-        // Acquire the cursor for inserting elements
-        let mut senders_cursor = provider.tx_ref().cursor_write::<tables::TransactionSenders>()?;
-
         info!(target: "sync::stages::sender_recovery", ?tx_range, "Recovering senders");
 
         // Iterate over transactions in batches, recover the senders and append them
@@ -109,8 +189,9 @@ where
         let tx_batch_sender = setup_range_recovery(provider);
 
         for range in batch {
-            recover_range(range, provider, tx_batch_sender.clone(), &mut senders_cursor)?;
+            recover_range(range, provider, tx_batch_sender.clone(), &mut senders_writer)?;
         }
+        senders_writer.finish()?;
 
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(end_block)
@@ -128,11 +209,31 @@ where
         let (_, unwind_to, _) = input.unwind_block_range_with_threshold(self.commit_threshold);
 
         // Lookup latest tx id that we should unwind to
-        let latest_tx_id = provider
+        let body_indices = provider
             .block_body_indices(unwind_to)?
-            .ok_or(ProviderError::BlockBodyIndicesNotFound(unwind_to))?
-            .last_tx_num();
-        provider.tx_ref().unwind_table_by_num::<tables::TransactionSenders>(latest_tx_id)?;
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(unwind_to))?;
+        provider
+            .tx_ref()
+            .unwind_table_by_num::<tables::TransactionSenders>(body_indices.last_tx_num())?;
+
+        // Unwind the senders that were written to static files
+        let static_file_provider = provider.static_file_provider();
+        if static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::TransactionSenders)
+            .is_some_and(|block| block > unwind_to)
+        {
+            let next_static_file_sender_num = static_file_provider
+                .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+                .map(|num| num + 1)
+                .unwrap_or(0);
+
+            static_file_provider
+                .latest_writer(StaticFileSegment::TransactionSenders)?
+                .prune_transaction_senders(
+                    next_static_file_sender_num.saturating_sub(body_indices.next_tx_num()),
+                    unwind_to,
+                )?;
+        }
 
         Ok(UnwindOutput {
             checkpoint: StageCheckpoint::new(unwind_to)
@@ -141,15 +242,88 @@ where
     }
 }
 
-fn recover_range<Provider, CURSOR>(
+/// Destination of the recovered senders.
+enum SendersWriter<'a, CURSOR, N> {
+    /// Senders are appended to the [`TransactionSenders`](tables::TransactionSenders) table.
+    Database(CURSOR),
+    /// Senders are appended to the [`StaticFileSegment::TransactionSenders`] static files.
+    StaticFile {
+        writer: StaticFileProviderRWRefMut<'a, N>,
+        /// Blocks that haven't been incremented in the static file yet, with the exclusive end of
+        /// their transaction range.
+        pending_blocks: VecDeque<(BlockNumber, TxNumber)>,
+        /// The exclusive end of the transaction range of the current block in the static file.
+        current_block_end: TxNumber,
+    },
+}
+
+impl<'a, CURSOR, N> SendersWriter<'a, CURSOR, N>
+where
+    CURSOR: DbCursorRW<tables::TransactionSenders>,
+    N: NodePrimitives,
+{
+    /// Creates a writer that appends the senders of the given block range to static files.
+    fn static_file<Provider, P>(
+        provider: &Provider,
+        static_file_provider: &'a P,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Result<Self, StageError>
+    where
+        Provider: BlockReader,
+        P: StaticFileWriter<Primitives = N>,
+    {
+        let writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::TransactionSenders)?;
+        let pending_blocks = provider
+            .block_body_indices_range(block_range.clone())?
+            .into_iter()
+            .zip(block_range)
+            .map(|(indices, block)| (block, indices.next_tx_num()))
+            .collect();
+        Ok(Self::StaticFile { writer, pending_blocks, current_block_end: 0 })
+    }
+
+    /// Appends the sender of the given transaction.
+    fn append(&mut self, tx_id: TxNumber, sender: &Address) -> Result<(), StageError> {
+        match self {
+            Self::Database(cursor) => cursor.append(tx_id, sender)?,
+            Self::StaticFile { writer, pending_blocks, current_block_end } => {
+                // Increment the blocks until reaching the block of the transaction, skipping
+                // empty blocks along the way.
+                while tx_id >= *current_block_end {
+                    let (block, block_end) = pending_blocks
+                        .pop_front()
+                        .ok_or(ProviderError::BlockNumberForTransactionIndexNotFound)?;
+                    writer.increment_block(block)?;
+                    *current_block_end = block_end;
+                }
+                writer.append_transaction_sender(tx_id, sender)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Increments the remaining blocks of the range in the static file.
+    fn finish(self) -> Result<(), StageError> {
+        if let Self::StaticFile { mut writer, pending_blocks, .. } = self {
+            for (block, _) in pending_blocks {
+                writer.increment_block(block)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn recover_range<Provider, CURSOR, N>(
     tx_range: Range<u64>,
     provider: &Provider,
     tx_batch_sender: mpsc::Sender<Vec<(Range<u64>, RecoveryResultSender)>>,
-    senders_cursor: &mut CURSOR,
+    senders_writer: &mut SendersWriter<'_, CURSOR, N>,
 ) -> Result<(), StageError>
 where
     Provider: DBProvider + HeaderProvider + StaticFileProviderFactory,
     CURSOR: DbCursorRW<tables::TransactionSenders>,
+    N: NodePrimitives,
 {
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Sending batch for processing");
 
@@ -169,7 +343,7 @@ where
         return Err(StageError::Fatal(err.into()));
     }
 
-    debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending recovered senders to the database");
+    debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending recovered senders");
 
     let mut processed_transactions = 0;
     for channel in receivers {
@@ -209,7 +383,7 @@ where
                     }
                 }
             };
-            senders_cursor.append(tx_id, &sender)?;
+            senders_writer.append(tx_id, &sender)?;
             processed_transactions += 1;
         }
     }
@@ -330,11 +504,16 @@ where
         .get_prune_checkpoint(PruneSegment::SenderRecovery)?
         .and_then(|checkpoint| checkpoint.tx_number)
         .unwrap_or_default();
+    let static_file_entries =
+        provider.static_file_provider().count_entries::<tables::TransactionSenders>()? as u64;
     Ok(EntitiesCheckpoint {
         // If `TransactionSenders` table was pruned, we will have a number of entries in it not
         // matching the actual number of processed transactions. To fix that, we add the
-        // number of pruned `TransactionSenders` entries.
-        processed: provider.count_entries::<tables::TransactionSenders>()? as u64 + pruned_entries,
+        // number of pruned `TransactionSenders` entries. Entries that were pruned from the
+        // database after being moved to static files are already counted there.
+        processed: provider.count_entries::<tables::TransactionSenders>()? as u64 +
+            static_file_entries +
+            pruned_entries.saturating_sub(static_file_entries),
         // Count only static files entries. If we count the database entries too, we may have
         // duplicates. We're sure that the static files have all entries that database has,
         // because we run the `StaticFileProducer` before starting the pipeline.
@@ -520,6 +699,59 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    /// Execute the stage with transaction senders static files that are up to date with the
+    /// checkpoint, so that the senders are written to static files
+    #[tokio::test]
+    async fn execute_to_static_files() {
+        let mut rng = generators::rng();
+        let runner = SenderRecoveryTestRunner::default();
+        let (stage_progress, previous_stage) = (0, 100);
+
+        let genesis = random_block(
+            &mut rng,
+            stage_progress,
+            BlockParams { tx_count: Some(0), ..Default::default() },
+        );
+        let blocks = random_block_range(
+            &mut rng,
+            stage_progress + 1..=previous_stage,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..4, ..Default::default() },
+        );
+        runner
+            .db
+            .insert_blocks(std::iter::once(&genesis).chain(blocks.iter()), StorageKind::Static)
+            .expect("failed to insert blocks");
+
+        let static_file_provider = runner.db.factory.static_file_provider();
+        {
+            let mut senders_writer = static_file_provider
+                .latest_writer(StaticFileSegment::TransactionSenders)
+                .expect("get static file writer for transaction senders");
+            senders_writer.increment_block(stage_progress).unwrap();
+            senders_writer.commit().unwrap();
+        }
+
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+        static_file_provider.commit().unwrap();
+
+        assert!(runner.db.table_is_empty::<tables::TransactionSenders>().unwrap());
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            Some(previous_stage)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::Transactions)
+        );
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
+    }
+
     #[test]
     fn stage_checkpoint_pruned() {
         let db = TestStageDB::default();
@@ -0,0 +1,385 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{B256, U256};
+use futures_util::TryStreamExt;
+use reth_db_api::{cursor::DbCursorRW, tables, transaction::DbTxMut};
+use reth_network_p2p::snap::downloader::{SnapStateBatch, StateDownloader};
+use reth_primitives_traits::StorageEntry;
+use reth_provider::{
+    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_stages_api::{
+    EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use std::task::{ready, Context, Poll};
+use tracing::*;
+
+/// The snap sync stage downloads the state at the target block from peers.
+///
+/// Instead of executing all blocks up to the target, the stage downloads the hashed state at the
+/// state root of the target block over the `snap` protocol.
+///
+/// # Tables
+///
+/// The downloaded state is inserted into these tables:
+///
+/// - [`HashedAccounts`][reth_db_api::tables::HashedAccounts]
+/// - [`HashedStorages`][reth_db_api::tables::HashedStorages]
+/// - [`Bytecodes`][reth_db_api::tables::Bytecodes]
+///
+/// The tables are cleared whenever a download for a new state root starts and when the stage is
+/// unwound. The state root and the next account to download are persisted as the stage progress,
+/// so an interrupted download resumes where it left off.
+///
+/// # Limitations
+///
+/// The `snap` protocol only serves hashed keys, so the plain state tables and the state history
+/// can't be populated by this stage. It is therefore not part of the default pipeline and is run
+/// with `reth stage run snap-sync` instead. It is meant to be followed by the
+/// [`MerkleStage`][crate::stages::MerkleStage], which rebuilds the trie from the hashed state and
+/// verifies it against the state root of the target block, and must not be combined with the
+/// hashing stages, which rebuild the hashed state from the plain state.
+///
+/// Peers only serve the state of their most recent blocks, so the target block has to be close
+/// to the tip of the chain.
+#[derive(Debug)]
+pub struct SnapSyncStage<D> {
+    /// The state downloader.
+    downloader: D,
+    /// The state root that is currently being downloaded.
+    root: Option<B256>,
+    /// The downloaded batch that has not been written yet.
+    buffer: Option<SnapStateBatch>,
+    /// Whether the downloader has downloaded the entire state.
+    finished: bool,
+    /// The number of accounts written for the current state root.
+    accounts: u64,
+}
+
+impl<D> SnapSyncStage<D> {
+    /// The id of the snap sync stage.
+    pub const ID: StageId = StageId::Other("SnapSync");
+
+    /// Create new snap sync stage from downloader.
+    pub const fn new(downloader: D) -> Self {
+        Self { downloader, root: None, buffer: None, finished: false, accounts: 0 }
+    }
+
+    /// Returns the progress of the current download.
+    ///
+    /// The total number of accounts is not known upfront.
+    const fn entities_checkpoint(&self) -> EntitiesCheckpoint {
+        EntitiesCheckpoint { processed: self.accounts, total: self.accounts }
+    }
+
+    /// Gets the persisted download progress.
+    pub fn get_download_progress(
+        &self,
+        provider: &impl StageCheckpointReader,
+    ) -> Result<Option<SnapSyncProgress>, StageError> {
+        let buf = provider.get_stage_checkpoint_progress(Self::ID)?.unwrap_or_default();
+        Ok(SnapSyncProgress::decode(&buf))
+    }
+
+    /// Saves the download progress.
+    pub fn save_download_progress(
+        &self,
+        provider: &impl StageCheckpointWriter,
+        progress: Option<SnapSyncProgress>,
+    ) -> Result<(), StageError> {
+        let buf = progress.map(|progress| progress.encode()).unwrap_or_default();
+        Ok(provider.save_stage_checkpoint_progress(Self::ID, buf)?)
+    }
+}
+
+/// The persisted progress of a state download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapSyncProgress {
+    /// The state root that is being downloaded.
+    pub root: B256,
+    /// The hashed address of the next account to download.
+    pub next_account: B256,
+}
+
+impl SnapSyncProgress {
+    /// Encodes the progress as the state root followed by the next account.
+    fn encode(&self) -> Vec<u8> {
+        [self.root.as_slice(), self.next_account.as_slice()].concat()
+    }
+
+    /// Decodes the progress, returns `None` if there is no valid progress.
+    fn decode(buf: &[u8]) -> Option<Self> {
+        (buf.len() == 64).then(|| Self {
+            root: B256::from_slice(&buf[..32]),
+            next_account: B256::from_slice(&buf[32..]),
+        })
+    }
+}
+
+/// Clears the tables the stage writes to.
+fn clear_state<TX: DbTxMut>(tx: &TX) -> Result<(), StageError> {
+    tx.clear::<tables::HashedAccounts>()?;
+    tx.clear::<tables::HashedStorages>()?;
+    tx.clear::<tables::Bytecodes>()?;
+    Ok(())
+}
+
+impl<Provider, D> Stage<Provider> for SnapSyncStage<D>
+where
+    Provider:
+        DBProvider<Tx: DbTxMut> + HeaderProvider + StageCheckpointReader + StageCheckpointWriter,
+    D: StateDownloader,
+{
+    fn id(&self) -> StageId {
+        Self::ID
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        // The download target is only set on execution, since it requires the header of the
+        // target block.
+        if input.target_reached() || self.root.is_none() || self.buffer.is_some() || self.finished {
+            return Poll::Ready(Ok(()))
+        }
+
+        let result = match ready!(self.downloader.try_poll_next_unpin(cx)) {
+            Some(Ok(batch)) => {
+                self.buffer = Some(batch);
+                Ok(())
+            }
+            Some(Err(err)) => Err(err.into()),
+            None => {
+                self.finished = true;
+                Ok(())
+            }
+        };
+        Poll::Ready(result)
+    }
+
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let target = input.target();
+        let root = provider
+            .header_by_number(target)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(target.into()))?
+            .state_root();
+
+        if self.root != Some(root) {
+            let progress =
+                self.get_download_progress(provider)?.filter(|progress| progress.root == root);
+            let start = if let Some(progress) = progress {
+                debug!(target: "sync::stages::snap", target_block = target, %root, next_account = %progress.next_account, "Resuming state download");
+                self.accounts = input
+                    .checkpoint()
+                    .entities_stage_checkpoint()
+                    .map(|checkpoint| checkpoint.processed)
+                    .unwrap_or_default();
+                progress.next_account
+            } else {
+                debug!(target: "sync::stages::snap", target_block = target, %root, "Starting state download");
+                clear_state(provider.tx_ref())?;
+                self.save_download_progress(
+                    provider,
+                    Some(SnapSyncProgress { root, next_account: B256::ZERO }),
+                )?;
+                self.accounts = 0;
+                B256::ZERO
+            };
+
+            self.downloader.set_target(root, start);
+            self.root = Some(root);
+            self.buffer = None;
+            self.finished = false;
+
+            return Ok(ExecOutput {
+                checkpoint: input
+                    .checkpoint()
+                    .with_entities_stage_checkpoint(self.entities_checkpoint()),
+                done: false,
+            })
+        }
+
+        if self.finished && self.buffer.is_none() {
+            info!(target: "sync::stages::snap", target_block = target, %root, accounts = self.accounts, "State download finished");
+            self.root = None;
+            self.save_download_progress(provider, None)?;
+            return Ok(ExecOutput {
+                checkpoint: StageCheckpoint::new(target)
+                    .with_entities_stage_checkpoint(self.entities_checkpoint()),
+                done: true,
+            })
+        }
+
+        let batch = self.buffer.take().ok_or(StageError::MissingDownloadBuffer)?;
+        let last_account = batch.last_account();
+        trace!(target: "sync::stages::snap", accounts = batch.accounts.len(), last = ?last_account, "Writing state");
+        self.accounts += batch.accounts.len() as u64;
+
+        let tx = provider.tx_ref();
+        let mut accounts_cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+        for (hashed_address, account) in batch.accounts {
+            accounts_cursor.upsert(hashed_address, &account)?;
+        }
+
+        let mut storages_cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+        for (hashed_address, slots) in batch.storages {
+            for (key, value) in slots.into_iter().filter(|(_, value)| !value.is_zero()) {
+                storages_cursor.upsert(hashed_address, &StorageEntry { key, value })?;
+            }
+        }
+
+        for (code_hash, bytecode) in batch.bytecodes {
+            tx.put::<tables::Bytecodes>(code_hash, bytecode)?;
+        }
+
+        if let Some(last_account) = last_account {
+            let next_account = U256::from_be_bytes(last_account.0).saturating_add(U256::from(1));
+            self.save_download_progress(
+                provider,
+                Some(SnapSyncProgress { root, next_account: next_account.into() }),
+            )?;
+        }
+
+        Ok(ExecOutput {
+            checkpoint: input
+                .checkpoint()
+                .with_entities_stage_checkpoint(self.entities_checkpoint()),
+            done: false,
+        })
+    }
+
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        // The downloaded state belongs to a single state root and can't be unwound, it is
+        // cleared and downloaded again for the next target.
+        clear_state(provider.tx_ref())?;
+        self.save_download_progress(provider, None)?;
+        self.root = None;
+        self.buffer = None;
+        self.finished = false;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use futures_util::Stream;
+    use reth_db_api::transaction::DbTx;
+    use reth_network_p2p::error::DownloadResult;
+    use reth_primitives_traits::{Account, Bytecode};
+    use reth_provider::DatabaseProviderFactory;
+    use reth_stages_api::StageExt;
+    use reth_testing_utils::generators::{self, random_header};
+    use std::{collections::VecDeque, pin::Pin};
+
+    /// A downloader that yields the given batches and records its targets.
+    #[derive(Debug, Default)]
+    struct TestDownloader {
+        batches: VecDeque<SnapStateBatch>,
+        targets: Vec<(B256, B256)>,
+    }
+
+    impl Stream for TestDownloader {
+        type Item = DownloadResult<SnapStateBatch>;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.get_mut().batches.pop_front().map(Ok))
+        }
+    }
+
+    impl StateDownloader for TestDownloader {
+        fn set_target(&mut self, root: B256, start: B256) {
+            self.targets.push((root, start));
+        }
+    }
+
+    /// Waits until the stage is ready to execute with the given provider.
+    async fn execute_ready<Provider>(
+        stage: &mut SnapSyncStage<TestDownloader>,
+        _provider: &Provider,
+        input: ExecInput,
+    ) where
+        SnapSyncStage<TestDownloader>: Stage<Provider>,
+    {
+        StageExt::<Provider>::execute_ready(stage, input).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn resumes_download_and_clears_state_on_unwind() {
+        let db = TestStageDB::default();
+        let header = random_header(&mut generators::rng(), 1, None);
+        db.insert_headers(std::iter::once(&header)).unwrap();
+        let root = header.state_root;
+
+        let bytecode = Bytecode::new_raw(vec![0x60, 0x00].into());
+        let code_hash = bytecode.hash_slow();
+        let batch = SnapStateBatch {
+            accounts: vec![(
+                B256::with_last_byte(1),
+                Account { bytecode_hash: Some(code_hash), ..Default::default() },
+            )],
+            storages: vec![(B256::with_last_byte(1), vec![(B256::ZERO, U256::from(1))])],
+            bytecodes: vec![(code_hash, bytecode)],
+        };
+        let input = ExecInput { target: Some(1), checkpoint: None };
+
+        // start the download and write the first batch
+        let mut stage = SnapSyncStage::new(TestDownloader {
+            batches: VecDeque::from([batch, SnapStateBatch::default()]),
+            ..Default::default()
+        });
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = stage.execute(&provider, input).unwrap();
+        assert!(!output.done);
+        assert_eq!(stage.downloader.targets, vec![(root, B256::ZERO)]);
+
+        execute_ready(&mut stage, &provider, input).await;
+        let output = stage.execute(&provider, input).unwrap();
+        assert!(!output.done);
+        assert_eq!(output.checkpoint.entities_stage_checkpoint().unwrap().processed, 1);
+        provider.commit().unwrap();
+
+        // a restarted stage resumes after the last written account
+        let mut stage = SnapSyncStage::new(TestDownloader::default());
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(1), checkpoint: Some(output.checkpoint) };
+        stage.execute(&provider, input).unwrap();
+        assert_eq!(stage.downloader.targets, vec![(root, B256::with_last_byte(2))]);
+        assert!(provider
+            .tx_ref()
+            .get::<tables::HashedAccounts>(B256::with_last_byte(1))
+            .unwrap()
+            .is_some());
+        assert!(provider.tx_ref().get::<tables::Bytecodes>(code_hash).unwrap().is_some());
+
+        // the download is complete once the downloader is exhausted
+        execute_ready(&mut stage, &provider, input).await;
+        let output = stage.execute(&provider, input).unwrap();
+        assert!(output.done);
+        assert_eq!(output.checkpoint.block_number, 1);
+        assert_eq!(stage.get_download_progress(&provider).unwrap(), None);
+
+        // unwinding drops the downloaded state
+        stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: output.checkpoint, unwind_to: 0, bad_block: None },
+            )
+            .unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::HashedAccounts>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::HashedStorages>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::Bytecodes>().unwrap(), 0);
+    }
+}
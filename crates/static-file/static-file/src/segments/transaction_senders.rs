@@ -0,0 +1,49 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+use reth_provider::{
+    providers::StaticFileWriter, BlockReader, DBProvider, StaticFileProviderFactory,
+};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::TransactionSenders`] part of data.
+#[derive(Debug, Default)]
+pub struct TransactionSenders;
+
+impl<Provider> Segment<Provider> for TransactionSenders
+where
+    Provider: StaticFileProviderFactory + DBProvider + BlockReader,
+{
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::TransactionSenders
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::TransactionSenders)?;
+
+        let mut senders_cursor = provider.tx_ref().cursor_read::<tables::TransactionSenders>()?;
+
+        for block in block_range {
+            static_file_writer.increment_block(block)?;
+
+            let block_body_indices = provider
+                .block_body_indices(block)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?;
+
+            for entry in senders_cursor.walk_range(block_body_indices.tx_num_range())? {
+                let (tx_number, sender) = entry?;
+                static_file_writer.append_transaction_sender(tx_number, &sender)?;
+            }
+        }
+
+        Ok(())
+    }
+}
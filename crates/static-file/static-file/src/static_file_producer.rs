@@ -140,6 +140,9 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.transaction_senders.clone() {
+            segments.push((Box::new(segments::TransactionSenders), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -178,16 +181,20 @@ where
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let stages_checkpoints =
+            [StageId::Headers, StageId::Execution, StageId::Bodies, StageId::SenderRecovery]
+                .into_iter()
+                .map(|stage| {
+                    provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
             block_meta: stages_checkpoints[2],
+            transaction_senders: stages_checkpoints[3],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -230,6 +237,18 @@ where
             block_meta: finalized_block_numbers.block_meta.and_then(|finalized_block_number| {
                 self.get_static_file_target(highest_static_files.block_meta, finalized_block_number)
             }),
+            // StaticFile transaction senders only if they're not pruned according to the user
+            // configuration
+            transaction_senders: if self.prune_modes.sender_recovery.is_none() {
+                finalized_block_numbers.transaction_senders.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.transaction_senders,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -262,6 +281,7 @@ mod tests {
     use alloy_primitives::{B256, U256};
     use assert_matches::assert_matches;
     use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_primitives_traits::SignerRecoverable;
     use reth_provider::{
         providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
         ProviderFactory, StaticFileProviderFactory,
@@ -310,6 +330,17 @@ mod tests {
         }
         db.insert_receipts(receipts).expect("insert receipts");
 
+        let mut senders = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body().transactions {
+                senders.push((
+                    senders.len() as u64,
+                    transaction.recover_signer().expect("recover signer"),
+                ));
+            }
+        }
+        db.insert_transaction_senders(senders).expect("insert transaction senders");
+
         let provider_factory = db.factory;
         (provider_factory, db.temp_static_files_dir)
     }
@@ -327,6 +358,7 @@ mod tests {
                 receipts: Some(1),
                 transactions: Some(1),
                 block_meta: None,
+                transaction_senders: Some(1),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -335,7 +367,8 @@ mod tests {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
                 transactions: Some(0..=1),
-                block_meta: None
+                block_meta: None,
+                transaction_senders: Some(0..=1)
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -345,7 +378,8 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
-                block_meta: None
+                block_meta: None,
+                transaction_senders: Some(1)
             }
         );
 
@@ -355,6 +389,7 @@ mod tests {
                 receipts: Some(3),
                 transactions: Some(3),
                 block_meta: None,
+                transaction_senders: Some(3),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -363,7 +398,8 @@ mod tests {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
                 transactions: Some(2..=3),
-                block_meta: None
+                block_meta: None,
+                transaction_senders: Some(2..=3)
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -373,7 +409,8 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                transaction_senders: Some(3)
             }
         );
 
@@ -383,6 +420,7 @@ mod tests {
                 receipts: Some(4),
                 transactions: Some(4),
                 block_meta: None,
+                transaction_senders: Some(4),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -391,7 +429,8 @@ mod tests {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
                 transactions: Some(4..=4),
-                block_meta: None
+                block_meta: None,
+                transaction_senders: Some(4..=4)
             }
         );
         assert_matches!(
@@ -404,7 +443,8 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                transaction_senders: Some(3)
             }
         );
     }
@@ -434,6 +474,7 @@ mod tests {
                         receipts: Some(1),
                         transactions: Some(1),
                         block_meta: None,
+                        transaction_senders: Some(1),
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -39,6 +39,9 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub block_meta: Option<BlockNumber>,
+    /// Highest static file block of transaction senders, inclusive.
+    /// If [`None`], no static file is available.
+    pub transaction_senders: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -49,6 +52,7 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
             StaticFileSegment::BlockMeta => self.block_meta,
+            StaticFileSegment::TransactionSenders => self.transaction_senders,
         }
     }
 
@@ -59,12 +63,14 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
             StaticFileSegment::BlockMeta => &mut self.block_meta,
+            StaticFileSegment::TransactionSenders => &mut self.transaction_senders,
         }
     }
 
     /// Returns an iterator over all static file segments
     fn iter(&self) -> impl Iterator<Item = Option<BlockNumber>> {
-        [self.headers, self.transactions, self.receipts, self.block_meta].into_iter()
+        [self.headers, self.transactions, self.receipts, self.block_meta, self.transaction_senders]
+            .into_iter()
     }
 
     /// Returns the minimum block of all segments.
@@ -89,6 +95,8 @@ pub struct StaticFileTargets {
     pub transactions: Option<RangeInclusive<BlockNumber>>,
     /// Targeted range of block meta.
     pub block_meta: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of transaction senders.
+    pub transaction_senders: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
@@ -97,7 +105,8 @@ impl StaticFileTargets {
         self.headers.is_some() ||
             self.receipts.is_some() ||
             self.transactions.is_some() ||
-            self.block_meta.is_some()
+            self.block_meta.is_some() ||
+            self.transaction_senders.is_some()
     }
 
     /// Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -108,6 +117,7 @@ impl StaticFileTargets {
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
             (self.block_meta.as_ref(), static_files.block_meta),
+            (self.transaction_senders.as_ref(), static_files.transaction_senders),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -142,6 +152,7 @@ mod tests {
             receipts: Some(200),
             transactions: None,
             block_meta: None,
+            transaction_senders: None,
         };
 
         // Test for headers segment
@@ -182,6 +193,7 @@ mod tests {
             receipts: Some(100),
             transactions: None,
             block_meta: None,
+            transaction_senders: None,
         };
 
         // Minimum value among the available segments
@@ -199,6 +211,7 @@ mod tests {
             receipts: Some(100),
             transactions: Some(500),
             block_meta: Some(500),
+            transaction_senders: None,
         };
 
         // Maximum value among the available segments
@@ -41,6 +41,9 @@ pub enum StaticFileSegment {
     /// Static File segment responsible for the `BlockBodyIndices`, `BlockOmmers`,
     /// `BlockWithdrawals` tables.
     BlockMeta,
+    #[strum(serialize = "transactionsenders")]
+    /// Static File segment responsible for the `TransactionSenders` table.
+    TransactionSenders,
 }
 
 impl StaticFileSegment {
@@ -51,6 +54,7 @@ impl StaticFileSegment {
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
             Self::BlockMeta => "blockmeta",
+            Self::TransactionSenders => "transactionsenders",
         }
     }
 
@@ -58,7 +62,14 @@ impl StaticFileSegment {
     pub fn iter() -> impl Iterator<Item = Self> {
         // The order of segments is significant and must be maintained to ensure correctness. For
         // example, Transactions require BlockBodyIndices from Blockmeta to be sound.
-        [Self::Headers, Self::BlockMeta, Self::Transactions, Self::Receipts].into_iter()
+        [
+            Self::Headers,
+            Self::BlockMeta,
+            Self::Transactions,
+            Self::Receipts,
+            Self::TransactionSenders,
+        ]
+        .into_iter()
     }
 
     /// Returns the default configuration of the segment.
@@ -70,7 +81,7 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers | Self::BlockMeta => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions | Self::Receipts | Self::TransactionSenders => 1,
         }
     }
 
@@ -143,9 +154,14 @@ impl StaticFileSegment {
         matches!(self, Self::Receipts)
     }
 
+    /// Returns `true` if the segment is `StaticFileSegment::TransactionSenders`.
+    pub const fn is_transaction_senders(&self) -> bool {
+        matches!(self, Self::TransactionSenders)
+    }
+
     /// Returns `true` if a segment row is linked to a transaction.
     pub const fn is_tx_based(&self) -> bool {
-        matches!(self, Self::Receipts | Self::Transactions)
+        matches!(self, Self::Receipts | Self::Transactions | Self::TransactionSenders)
     }
 
     /// Returns `true` if a segment row is linked to a block.
@@ -454,6 +454,26 @@ tables! {
         type SubKey = B256;
     }
 
+    /// Stores the addresses that were the caller or the callee of a call frame in a block.
+    ///
+    /// Only populated if the call trace index is enabled, it is used to unwind the
+    /// [`CallTracesHistory`] index.
+    table CallTraceSet {
+        type Key = BlockNumber;
+        type Value = Address;
+        type SubKey = Address;
+    }
+
+    /// Stores pointers to the blocks in which an address was the caller or the callee of a call
+    /// frame.
+    ///
+    /// Sharded the same way as [`AccountsHistory`]. Only populated if the call trace index is
+    /// enabled.
+    table CallTracesHistory {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the current state of an [`Account`] indexed with `keccak256Address`
     /// This table is in preparation for merklization and calculation of state root.
     /// We are saving whole account data as it is needed for partial update when
@@ -151,6 +151,9 @@ where
     let segment = StaticFileSegment::Transactions;
     static_file_provider.latest_writer(segment)?.increment_block(0)?;
 
+    let segment = StaticFileSegment::TransactionSenders;
+    static_file_provider.latest_writer(segment)?.increment_block(0)?;
+
     // `commit_unwind`` will first commit the DB and then the static file provider, which is
     // necessary on `init_genesis`.
     UnifiedStorageWriter::commit_unwind(provider_rw)?;
@@ -3,7 +3,7 @@ use crate::{
     static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo},
     BlockBodyIndices, HeaderTerminalDifficulties,
 };
-use alloy_primitives::BlockHash;
+use alloy_primitives::{Address, BlockHash};
 use reth_db_api::{
     models::{StaticFileBlockWithdrawals, StoredBlockOmmers},
     table::Table,
@@ -46,6 +46,12 @@ add_static_file_mask! {
     TransactionMask<T>, T, 0b1
 }
 
+// TRANSACTION SENDER MASKS
+add_static_file_mask! {
+    #[doc = "Mask for selecting a single sender from `TransactionSenders` static file segment"]
+    TransactionSenderMask, Address, 0b1
+}
+
 // BLOCK_META MASKS
 add_static_file_mask! {
     #[doc = "Mask for a `StoredBlockBodyIndices` from `BlockMeta` static file segment"]
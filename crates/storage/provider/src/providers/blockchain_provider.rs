@@ -18,7 +18,7 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumb
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
     BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider,
+    MemoryOverlayStateDump, MemoryOverlayStateProvider,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db_api::{
@@ -36,7 +36,9 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StateCommitmentProvider,
+    BlockBodyIndicesProvider, CallHistoryReader, DBProvider, ModifiedStateReader,
+    NodePrimitivesProvider, StateCommitmentProvider, StateDumpProviderBox,
+    StateDumpProviderFactory, StateRangeProviderBox, StateRangeProviderFactory,
     StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
@@ -44,6 +46,7 @@ use reth_trie::HashedPostState;
 use reth_trie_db::StateCommitment;
 use revm_database::BundleState;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     ops::{Add, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
     time::Instant,
@@ -711,6 +714,22 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> ModifiedStateReader for BlockchainProvider<N> {
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.consistent_provider()?.modified_accounts(range)
+    }
+
+    fn modified_storage(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
+        self.consistent_provider()?.modified_storage(range)
+    }
+}
+
 impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     fn account_block_changeset(
         &self,
@@ -720,6 +739,20 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> CallHistoryReader for BlockchainProvider<N> {
+    fn call_history_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.call_history_checkpoint()
+    }
+
+    fn call_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.call_history_blocks(address, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -727,6 +760,36 @@ impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StateDumpProviderFactory for BlockchainProvider<N> {
+    fn state_dump_by_block_hash(
+        &self,
+        block_hash: BlockHash,
+    ) -> ProviderResult<StateDumpProviderBox> {
+        trace!(target: "providers::blockchain", ?block_hash, "Getting state dump by block hash");
+
+        self.consistent_provider()?.get_in_memory_or_storage_by_block(
+            block_hash.into(),
+            |_| self.database.state_dump_by_block_hash(block_hash),
+            |block_state| {
+                let historical =
+                    self.database.state_dump_by_block_hash(block_state.anchor().hash)?;
+                let in_memory = block_state.chain().map(|state| state.block()).collect::<Vec<_>>();
+                Ok(Box::new(MemoryOverlayStateDump::new(historical, &in_memory)))
+            },
+        )
+    }
+}
+
+impl<N: ProviderNodeTypes> StateRangeProviderFactory for BlockchainProvider<N> {
+    /// Only the state of blocks on disk is served, see [`ProviderFactory::state_range_by_root`].
+    fn state_range_by_root(
+        &self,
+        state_root: B256,
+    ) -> ProviderResult<Option<StateRangeProviderBox>> {
+        self.database.state_range_by_root(state_root)
+    }
+}
+
 impl<N: ProviderNodeTypes> StateReader for BlockchainProvider<N> {
     type Receipt = ReceiptTy<N>;
 
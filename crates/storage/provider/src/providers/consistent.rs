@@ -26,12 +26,13 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader,
+    BlockBodyIndicesProvider, CallHistoryReader, DatabaseProviderFactory, ModifiedStateReader,
+    NodePrimitivesProvider, StateProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
 };
@@ -594,6 +595,26 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
 }
 
 impl<N: ProviderNodeTypes> ConsistentProvider<N> {
+    /// Returns an error if the changesets of the start of the given range have been pruned, in
+    /// which case results derived from them would be incomplete.
+    fn ensure_changesets_available(
+        &self,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if let Some(pruned) = self
+                .storage_provider
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+            {
+                if *range.start() <= pruned {
+                    return Err(ProviderError::StateAtBlockPruned(*range.start()))
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Ensures that the given block number is canonical (synced)
     ///
     /// This is a helper for guarding the `HistoricalStateProvider` against block numbers that are
@@ -1354,6 +1375,81 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> ModifiedStateReader for ConsistentProvider<N> {
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.ensure_changesets_available(&range)?;
+
+        let last_persisted = self.storage_provider.last_block_number()?;
+        let mut accounts = if *range.start() <= last_persisted {
+            self.storage_provider
+                .modified_accounts(*range.start()..=last_persisted.min(*range.end()))?
+        } else {
+            BTreeSet::new()
+        };
+
+        // blocks that are not persisted yet have no changesets, so their bundle state is used. The
+        // bundle state also contains accounts that were only touched, so they are skipped
+        if let Some(head_block) = &self.head_block {
+            for state in head_block.chain().filter(|state| range.contains(&state.number())) {
+                accounts.extend(
+                    state
+                        .block_ref()
+                        .execution_output
+                        .bundle
+                        .state
+                        .iter()
+                        .filter(|(_, account)| {
+                            account.is_info_changed() ||
+                                account.was_destroyed() ||
+                                account.storage.values().any(|slot| slot.is_changed())
+                        })
+                        .map(|(address, _)| *address),
+                );
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    fn modified_storage(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
+        self.ensure_changesets_available(&range)?;
+
+        let last_persisted = self.storage_provider.last_block_number()?;
+        let mut storage = if *range.start() <= last_persisted {
+            self.storage_provider
+                .modified_storage(*range.start()..=last_persisted.min(*range.end()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        // blocks that are not persisted yet have no changesets, so their bundle state is used
+        if let Some(head_block) = &self.head_block {
+            for state in head_block.chain().filter(|state| range.contains(&state.number())) {
+                for (address, account) in &state.block_ref().execution_output.bundle.state {
+                    // the bundle state also contains slots that were only read
+                    let mut slots = account
+                        .storage
+                        .iter()
+                        .filter(|(_, slot)| slot.is_changed())
+                        .map(|(key, _)| B256::from(*key))
+                        .peekable();
+                    if slots.peek().is_some() {
+                        storage.entry(*address).or_default().extend(slots);
+                    }
+                }
+            }
+        }
+
+        Ok(storage)
+    }
+}
+
 impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     fn account_block_changeset(
         &self,
@@ -1400,6 +1496,20 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> CallHistoryReader for ConsistentProvider<N> {
+    fn call_history_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.storage_provider.call_history_checkpoint()
+    }
+
+    fn call_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.storage_provider.call_history_blocks(address, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -1441,7 +1551,7 @@ mod tests {
         test_utils::create_test_provider_factory, BlockWriter,
     };
     use alloy_eips::BlockHashOrNumber;
-    use alloy_primitives::B256;
+    use alloy_primitives::{Address, B256, U256};
     use itertools::Itertools;
     use rand::Rng;
     use reth_chain_state::{
@@ -1450,13 +1560,14 @@ mod tests {
     use reth_db_api::models::AccountBeforeTx;
     use reth_ethereum_primitives::Block;
     use reth_execution_types::ExecutionOutcome;
-    use reth_primitives_traits::{RecoveredBlock, SealedBlock};
-    use reth_storage_api::{BlockReader, BlockSource, ChangeSetReader};
+    use reth_primitives_traits::{Account, RecoveredBlock, SealedBlock};
+    use reth_storage_api::{BlockReader, BlockSource, ChangeSetReader, ModifiedStateReader};
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
     };
     use revm_database::BundleState;
     use std::{
+        collections::{BTreeMap, BTreeSet},
         ops::{Bound, Range, RangeBounds},
         sync::Arc,
     };
@@ -1812,4 +1923,86 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_modified_state_skips_unchanged_state() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let (database_blocks, in_memory_blocks) =
+            random_blocks(&mut rng, TEST_BLOCKS_COUNT, 1, None, None, 0..1);
+        let first_database_block = database_blocks.first().map(|block| block.number).unwrap();
+        let in_memory_block = in_memory_blocks.first().unwrap();
+
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.append_blocks_with_state(
+            database_blocks
+                .into_iter()
+                .map(|b| b.try_recover().expect("failed to seal block with senders"))
+                .collect(),
+            &ExecutionOutcome { first_block: first_database_block, ..Default::default() },
+            Default::default(),
+            Default::default(),
+        )?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory)?;
+
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let changed = Address::with_last_byte(1);
+        let touched = Address::with_last_byte(2);
+        let storage_changed = Address::with_last_byte(3);
+
+        let senders = in_memory_block.senders().expect("failed to recover senders");
+        let chain = NewCanonicalChain::Commit {
+            new: vec![ExecutedBlockWithTrieUpdates::new(
+                Arc::new(RecoveredBlock::new_sealed(in_memory_block.clone(), senders)),
+                Arc::new(ExecutionOutcome {
+                    bundle: BundleState::new(
+                        [
+                            (changed, None, Some(account.into()), Default::default()),
+                            (
+                                touched,
+                                Some(account.into()),
+                                Some(account.into()),
+                                Default::default(),
+                            ),
+                            (
+                                storage_changed,
+                                Some(account.into()),
+                                Some(account.into()),
+                                [
+                                    (U256::from(1), (U256::ZERO, U256::from(5))),
+                                    (U256::from(2), (U256::from(7), U256::from(7))),
+                                ]
+                                .into_iter()
+                                .collect(),
+                            ),
+                        ],
+                        [[(changed, Some(None), Vec::new())]],
+                        [],
+                    ),
+                    first_block: in_memory_block.number,
+                    ..Default::default()
+                }),
+                Default::default(),
+                ExecutedTrieUpdates::empty(),
+            )],
+        };
+        provider.canonical_in_memory_state.update_chain(chain);
+
+        let consistent_provider = provider.consistent_provider()?;
+        assert_eq!(
+            consistent_provider
+                .modified_accounts(in_memory_block.number..=in_memory_block.number)?,
+            BTreeSet::from([changed, storage_changed])
+        );
+        assert_eq!(
+            consistent_provider
+                .modified_storage(in_memory_block.number..=in_memory_block.number)?,
+            BTreeMap::from([(storage_changed, BTreeSet::from([B256::with_last_byte(1)]))])
+        );
+
+        Ok(())
+    }
 }
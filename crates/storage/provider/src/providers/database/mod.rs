@@ -24,7 +24,8 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
     BlockBodyIndicesProvider, NodePrimitivesProvider, StateCommitmentProvider,
-    TryIntoHistoricalStateProvider,
+    StateDumpProviderBox, StateDumpProviderFactory, StateRangeProviderBox,
+    StateRangeProviderFactory, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -39,7 +40,9 @@ use std::{
 use tracing::trace;
 
 mod provider;
-pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
+pub use provider::{
+    DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, MAX_STATE_RANGE_BLOCKS,
+};
 
 use super::ProviderNodeTypes;
 
@@ -598,6 +601,32 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StateDumpProviderFactory for ProviderFactory<N> {
+    fn state_dump_by_block_hash(
+        &self,
+        block_hash: BlockHash,
+    ) -> ProviderResult<StateDumpProviderBox> {
+        let provider = self.provider()?;
+
+        let block_number = provider
+            .block_number(block_hash)?
+            .ok_or(ProviderError::BlockHashNotFound(block_hash))?;
+
+        trace!(target: "providers::db", ?block_number, %block_hash, "Returning state dump provider for block hash");
+        provider.try_into_state_dump_at_block(block_number)
+    }
+}
+
+impl<N: ProviderNodeTypes> StateRangeProviderFactory for ProviderFactory<N> {
+    fn state_range_by_root(
+        &self,
+        state_root: B256,
+    ) -> ProviderResult<Option<StateRangeProviderBox>> {
+        trace!(target: "providers::db", %state_root, "Returning state range provider for state root");
+        self.provider()?.try_into_state_range_by_root(state_root)
+    }
+}
+
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
         HashedPostState::from_bundle_state::<<N::StateCommitment as StateCommitment>::KeyHasher>(
@@ -640,8 +669,8 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockReader, BlockWriter, DBProvider,
+        HeaderSyncGapProvider, StorageLocation, TransactionsProvider,
     };
     use alloy_primitives::{TxNumber, B256, U256};
     use assert_matches::assert_matches;
@@ -650,7 +679,7 @@ mod tests {
         mdbx::DatabaseArguments,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_db_api::tables;
+    use reth_db_api::{tables, transaction::DbTx};
     use reth_primitives_traits::SignerRecoverable;
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
@@ -779,6 +808,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_and_unwind_blocks_with_static_file_senders() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+
+        let mut rng = generators::rng();
+        let genesis =
+            random_block(&mut rng, 0, BlockParams { tx_count: Some(0), ..Default::default() });
+        let mut parent = genesis.hash();
+        let blocks = (1..=2)
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { parent: Some(parent), tx_count: Some(2), ..Default::default() },
+                );
+                parent = block.hash();
+                block
+            })
+            .collect::<Vec<_>>();
+        let senders = blocks
+            .iter()
+            .flat_map(|block| &block.body().transactions)
+            .map(|tx| tx.recover_signer().unwrap())
+            .collect::<Vec<_>>();
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(genesis.try_recover().unwrap(), StorageLocation::Both).unwrap();
+        // senders are only written to static files once the segment is initialized
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::TransactionSenders).unwrap();
+        writer.increment_block(0).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+        for block in &blocks {
+            provider
+                .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Both)
+                .unwrap();
+        }
+        static_file_provider.commit().unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            Some(2)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            Some(3)
+        );
+
+        // senders are read from the static files
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::TransactionSenders>().unwrap(), 0);
+        assert_eq!(provider.senders_by_tx_range(0..4).unwrap(), senders);
+        assert_eq!(provider.senders_by_tx_range(1..=2).unwrap(), senders[1..=2]);
+        assert_eq!(provider.transaction_sender(3).unwrap(), Some(senders[3]));
+        assert_eq!(static_file_provider.senders_by_tx_range(0..4).unwrap(), senders);
+        assert_eq!(static_file_provider.transaction_sender(2).unwrap(), Some(senders[2]));
+        assert_eq!(
+            provider
+                .recovered_block_range(1..=2)
+                .unwrap()
+                .iter()
+                .flat_map(|block| block.senders().to_vec())
+                .collect::<Vec<_>>(),
+            senders
+        );
+        drop(provider);
+
+        // unwinding removes the senders of the unwound block from the static files
+        let provider = factory.provider_rw().unwrap();
+        provider.remove_blocks_above(1, StorageLocation::Both).unwrap();
+        provider.commit().unwrap();
+        static_file_provider.commit().unwrap();
+
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
+            Some(1)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::TransactionSenders),
+            Some(1)
+        );
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.senders_by_tx_range(0..4).unwrap(), senders[..2]);
+        assert_eq!(provider.transaction_sender(2).unwrap(), None);
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
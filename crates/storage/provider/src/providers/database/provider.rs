@@ -3,7 +3,7 @@ use crate::{
     providers::{
         database::{chain::ChainStorage, metrics},
         static_file::StaticFileWriter,
-        NodeTypesForProvider, StaticFileProvider,
+        HashedStateRangeProvider, NodeTypesForProvider, StaticFileProvider,
     },
     to_range,
     traits::{
@@ -55,16 +55,20 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, CallHistoryReader, CallHistoryWriter,
+    ModifiedStateReader, NodePrimitivesProvider, StateDumpProviderBox, StateProvider,
+    StateRangeProviderBox, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    CALL_TRACE_INDEX_ID,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostState, HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+};
+use reth_trie_db::{
+    DatabaseHashedPostState, DatabaseStateRoot, DatabaseStorageTrieCursor, StateCommitment,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm_database::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -77,6 +81,10 @@ use std::{
 };
 use tracing::{debug, trace};
 
+/// The number of most recent blocks on disk whose state can be served as ranges, see
+/// [`DatabaseProvider::try_into_state_range_by_root`].
+pub const MAX_STATE_RANGE_BLOCKS: u64 = 128;
+
 /// A [`DatabaseProvider`] that holds a read-only database transaction.
 pub type DatabaseProviderRO<DB, N> = DatabaseProvider<<DB as Database>::TX, N>;
 
@@ -371,7 +379,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for DatabaseProvider<TX, N> {
     fn try_into_history_at_block(
         self,
-        mut block_number: BlockNumber,
+        block_number: BlockNumber,
     ) -> ProviderResult<StateProviderBox> {
         // if the block number is the same as the currently best block number on disk we can use the
         // latest state provider here
@@ -379,6 +387,29 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
             return Ok(Box::new(LatestStateProvider::new(self)))
         }
 
+        Ok(Box::new(self.into_historical_state_provider(block_number)?))
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns a [`StateDumpProviderBox`] for the state after the given block has been executed.
+    pub fn try_into_state_dump_at_block(
+        self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<StateDumpProviderBox> {
+        if block_number == self.best_block_number().unwrap_or_default() {
+            return Ok(Box::new(LatestStateProvider::new(self)))
+        }
+
+        Ok(Box::new(self.into_historical_state_provider(block_number)?))
+    }
+
+    /// Returns a [`HistoricalStateProvider`] for the state after the given block has been
+    /// executed, capped at the prune checkpoints of the history segments.
+    fn into_historical_state_provider(
+        self,
+        mut block_number: BlockNumber,
+    ) -> ProviderResult<HistoricalStateProvider<Self>> {
         // +1 as the changeset that we want is the one that was applied after this block.
         block_number += 1;
 
@@ -406,7 +437,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
             );
         }
 
-        Ok(Box::new(state_provider))
+        Ok(state_provider)
     }
 }
 
@@ -680,11 +711,22 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
             let senders = if tx_range.is_empty() {
                 Vec::new()
             } else {
-                // fetch senders from the senders table
-                let known_senders =
-                    senders_cursor
-                        .walk_range(tx_range.clone())?
-                        .collect::<Result<HashMap<_, _>, _>>()?;
+                // fetch senders from the static files or the senders table
+                let known_senders = self
+                    .static_file_provider
+                    .get_range_with_static_file_or_database(
+                        StaticFileSegment::TransactionSenders,
+                        tx_range.clone(),
+                        |static_file, range, _| {
+                            Ok(range.clone().zip(static_file.senders_by_tx_range(range)?).collect())
+                        },
+                        |range, _| {
+                            Ok(senders_cursor.walk_range(range)?.collect::<Result<Vec<_>, _>>()?)
+                        },
+                        |_| true,
+                    )?
+                    .into_iter()
+                    .collect::<HashMap<_, _>>();
 
                 let mut senders = Vec::with_capacity(body.transactions().len());
                 for (tx_num, tx) in tx_range.zip(body.transactions()) {
@@ -902,6 +944,41 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> CallHistoryReader for DatabaseProvider<TX, N> {
+    fn call_history_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_stage_checkpoint(CALL_TRACE_INDEX_ID)?
+            .map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn call_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let (from, to) = (*range.start(), *range.end());
+        let mut blocks = Vec::new();
+
+        // Shards are keyed by their highest block number, so the first shard that can contain
+        // blocks of the range is the first one at or after the range start.
+        let mut cursor = self.tx.cursor_read::<tables::CallTracesHistory>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(address, from)))? {
+            let (key, list) = entry?;
+            if key.key != address {
+                break
+            }
+            blocks.extend(
+                list.iter().skip_while(|block| *block < from).take_while(|block| *block <= to),
+            );
+            if key.highest_block_number >= to {
+                break
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
@@ -917,6 +994,44 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> ModifiedStateReader for DatabaseProvider<TX, N> {
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut accounts = self.changed_accounts_with_range(range.clone())?;
+
+        // accounts whose storage changed without any change to the account itself only show up
+        // in the storage changesets
+        for entry in self
+            .tx
+            .cursor_dup_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(range))?
+        {
+            let (key, _) = entry?;
+            accounts.insert(key.address());
+        }
+
+        Ok(accounts)
+    }
+
+    fn modified_storage(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
+        let mut storage = BTreeMap::<Address, BTreeSet<B256>>::new();
+        for entry in self
+            .tx
+            .cursor_dup_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(range))?
+        {
+            let (key, entry) = entry?;
+            storage.entry(key.address()).or_default().insert(entry.key);
+        }
+        Ok(storage)
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
     fn account_block_changeset(
         &self,
@@ -1504,11 +1619,22 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.cursor_read_collect::<tables::TransactionSenders>(range)
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::TransactionSenders,
+            to_range(range),
+            |static_file, range, _| static_file.senders_by_tx_range(range),
+            |range, _| self.cursor_read_collect::<tables::TransactionSenders>(range),
+            |_| true,
+        )
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.tx.get::<tables::TransactionSenders>(id)?)
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::TransactionSenders,
+            id,
+            |static_file| static_file.transaction_sender(id),
+            || Ok(self.tx.get::<tables::TransactionSenders>(id)?),
+        )
     }
 }
 
@@ -2606,6 +2732,63 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> CallHistoryWriter for DatabaseProvider<TX, N> {
+    fn insert_call_history(
+        &self,
+        block_number: BlockNumber,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> ProviderResult<()> {
+        let addresses = addresses.into_iter().collect::<BTreeSet<_>>();
+
+        let mut cursor = self.tx.cursor_dup_write::<tables::CallTraceSet>()?;
+        for address in &addresses {
+            cursor.append_dup(block_number, *address)?;
+        }
+
+        self.append_history_index::<_, tables::CallTracesHistory>(
+            addresses.into_iter().map(|address| (address, [block_number])),
+            ShardedKey::new,
+        )?;
+        self.save_stage_checkpoint(CALL_TRACE_INDEX_ID, StageCheckpoint::new(block_number))
+    }
+
+    fn unwind_call_history(&self, from: BlockNumber) -> ProviderResult<usize> {
+        let entries = self.take::<tables::CallTraceSet>(from..)?;
+
+        // entries are sorted by block, so the first entry of an address is the lowest block to
+        // remove from its shards
+        let mut last_indices = BTreeMap::new();
+        for (block_number, address) in &entries {
+            last_indices.entry(*address).or_insert(*block_number);
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::CallTracesHistory>()?;
+        for (address, rem_index) in last_indices {
+            let partial_shard = unwind_history_shards::<_, tables::CallTracesHistory, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        self.save_stage_checkpoint(
+            CALL_TRACE_INDEX_ID,
+            StageCheckpoint::new(from.saturating_sub(1)),
+        )?;
+        Ok(entries.len())
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvider<TX, N> {
     fn unwind_account_history_indices<'a>(
         &self,
@@ -2867,11 +3050,30 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         let tx_count = block.body().transaction_count() as u64;
 
+        // Write the senders to static files if they're not pruned and the static files are up to
+        // date with the parent block, otherwise to the database.
+        let mut senders_static_writer = None;
+        if write_to.static_files() &&
+            self.prune_modes.sender_recovery.is_none() &&
+            self.static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders)
+                .is_some()
+        {
+            let mut writer =
+                self.static_file_provider.latest_writer(StaticFileSegment::TransactionSenders)?;
+            if writer.next_block_number() == block_number {
+                writer.increment_block(block_number)?;
+                senders_static_writer = Some(writer);
+            }
+        }
+
         // Ensures we have all the senders for the block's transactions.
         for (transaction, sender) in block.body().transactions_iter().zip(block.senders_iter()) {
             let hash = transaction.tx_hash();
 
-            if self.prune_modes.sender_recovery.as_ref().is_none_or(|m| !m.is_full()) {
+            if let Some(writer) = senders_static_writer.as_mut() {
+                writer.append_transaction_sender(next_tx_num, sender)?;
+            } else if self.prune_modes.sender_recovery.as_ref().is_none_or(|m| !m.is_full()) {
                 self.tx.put::<tables::TransactionSenders>(next_tx_num, *sender)?;
             }
 
@@ -3009,6 +3211,22 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         self.remove::<tables::TransactionSenders>(unwind_tx_from..)?;
 
+        if remove_from.static_files() &&
+            self.static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders)
+                .is_some_and(|highest| highest > block)
+        {
+            let to_delete = self
+                .static_file_provider
+                .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+                .map(|static_tx| (static_tx + 1).saturating_sub(unwind_tx_from))
+                .unwrap_or_default();
+
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::TransactionSenders)?
+                .prune_transaction_senders(to_delete, block)?;
+        }
+
         self.remove_bodies_above(block, remove_from)?;
 
         Ok(())
@@ -3138,6 +3356,59 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> StatsReader for DatabaseProvid
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns a [`StateRangeProviderBox`] for the state with the given root, or `None` if it is
+    /// not the state after one of the last [`MAX_STATE_RANGE_BLOCKS`] blocks on disk.
+    ///
+    /// The state of earlier blocks is restored by applying the reverts of all following blocks, so
+    /// it can't be served if the account or storage history of these blocks has been pruned.
+    /// Nothing is served while the pipeline is still hashing or merkleizing blocks past the tip.
+    pub fn try_into_state_range_by_root(
+        self,
+        state_root: B256,
+    ) -> ProviderResult<Option<StateRangeProviderBox>> {
+        let tip = self.best_block_number()?;
+
+        // While the pipeline is running, the hashed state and the tries may be ahead of the tip.
+        for stage in [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute] {
+            if self.get_stage_checkpoint(stage)?.map(|checkpoint| checkpoint.block_number) !=
+                Some(tip)
+            {
+                return Ok(None)
+            }
+        }
+
+        let lowest = tip.saturating_sub(MAX_STATE_RANGE_BLOCKS - 1);
+        let Some(block_number) = self
+            .headers_range(lowest..=tip)?
+            .iter()
+            .rposition(|header| header.state_root() == state_root)
+            .map(|idx| lowest + idx as u64)
+        else {
+            return Ok(None)
+        };
+
+        if block_number == tip {
+            return Ok(Some(Box::new(HashedStateRangeProvider::new(self, Default::default()))))
+        }
+
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if self
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| pruned > block_number)
+            {
+                return Ok(None)
+            }
+        }
+
+        let revert_state = HashedPostState::from_reverts::<
+            <N::StateCommitment as StateCommitment>::KeyHasher,
+        >(&self.tx, block_number + 1)?;
+        Ok(Some(Box::new(HashedStateRangeProvider::new(self, revert_state))))
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypes> ChainStateBlockReader for DatabaseProvider<TX, N> {
     fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
         let mut finalized_blocks = self
@@ -3205,7 +3476,10 @@ mod tests {
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
         BlockWriter,
     };
+    use alloy_primitives::{keccak256, Bytes};
     use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use reth_trie::{root::state_root_unsorted, KeccakKeyHasher, StateRoot};
+    use reth_trie_db::DatabaseStateRoot;
 
     #[test]
     fn test_receipts_by_block_range_empty_range() {
@@ -3450,4 +3724,109 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn call_history_insert_and_unwind() {
+        let factory = create_test_provider_factory();
+        let caller = Address::with_last_byte(1);
+        let callee = Address::with_last_byte(2);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        assert_eq!(provider_rw.call_history_checkpoint().unwrap(), None);
+        provider_rw.insert_call_history(1, [caller, callee]).unwrap();
+        provider_rw.insert_call_history(2, []).unwrap();
+        provider_rw.insert_call_history(3, [callee]).unwrap();
+        provider_rw.insert_call_history(4, [caller, callee]).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.call_history_checkpoint().unwrap(), Some(4));
+        assert_eq!(provider.call_history_blocks(caller, 0..=10).unwrap(), vec![1, 4]);
+        assert_eq!(provider.call_history_blocks(callee, 0..=10).unwrap(), vec![1, 3, 4]);
+        assert_eq!(provider.call_history_blocks(callee, 2..=3).unwrap(), vec![3]);
+        assert!(provider.call_history_blocks(Address::ZERO, 0..=10).unwrap().is_empty());
+        drop(provider);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        assert_eq!(provider_rw.unwind_call_history(3).unwrap(), 3);
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.call_history_checkpoint().unwrap(), Some(2));
+        assert_eq!(provider.call_history_blocks(caller, 0..=10).unwrap(), vec![1]);
+        assert_eq!(provider.call_history_blocks(callee, 0..=10).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn state_dump_resolves_addresses_from_plain_state() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let bytecode = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let code_hash = bytecode.hash_slow();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let account = Account { bytecode_hash: Some(code_hash), ..Default::default() };
+        provider_rw.tx_ref().put::<tables::PlainAccountState>(address, account).unwrap();
+        provider_rw.tx_ref().put::<tables::Bytecodes>(code_hash, bytecode.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let dump = factory.provider().unwrap().try_into_state_dump_at_block(0).unwrap();
+        let unknown = keccak256(Address::with_last_byte(2));
+        let addresses = dump.account_addresses(&[keccak256(address), unknown]).unwrap();
+        assert_eq!(addresses.len(), 1);
+        assert_eq!(addresses.get(&keccak256(address)), Some(&address));
+        let code = dump.bytecode(&code_hash).unwrap().map(|code| code.original_bytes());
+        assert_eq!(code, Some(bytecode.original_bytes()));
+        assert!(dump.bytecode(&B256::ZERO).unwrap().is_none());
+    }
+
+    #[test]
+    fn state_range_by_root() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        for (block, execution_outcome) in data.blocks.iter().take(2) {
+            let hashed_state = HashedPostState::from_bundle_state::<KeccakKeyHasher>(
+                execution_outcome.state().state(),
+            );
+            let (state_root, trie_updates) =
+                StateRoot::overlay_root_with_updates(provider_rw.tx_ref(), hashed_state.clone())
+                    .unwrap();
+            assert_eq!(state_root, block.state_root());
+            provider_rw
+                .append_blocks_with_state(
+                    vec![block.clone()],
+                    execution_outcome,
+                    hashed_state.into_sorted(),
+                    trie_updates,
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // both the tip and the state before it are served
+        for (block, _) in data.blocks.iter().take(2) {
+            let state_root = block.state_root();
+            let state = factory.provider().unwrap().try_into_state_range_by_root(state_root);
+            let state = state.unwrap().expect("state is served");
+
+            let accounts = state.hashed_account_range(B256::ZERO, B256::repeat_byte(0xff), 10);
+            let accounts = accounts.unwrap();
+            assert_eq!(accounts.len(), 2);
+            assert_eq!(state_root_unsorted(accounts.iter().copied()), state_root);
+
+            let proof = state.range_proof(None, &[accounts[0].0]).unwrap();
+            assert_eq!(keccak256(&proof[0]), state_root);
+        }
+
+        let provider = factory.provider().unwrap();
+        assert!(provider.try_into_state_range_by_root(B256::random()).unwrap().is_none());
+    }
 }
@@ -1,9 +1,12 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    providers::state::{latest::plain_state_addresses, macros::delegate_provider_impls},
+    AccountReader, BlockHashReader, HashedPostStateProvider, ProviderError, StateProvider,
+    StateRootProvider,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    map::B256Map, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
     models::{storage_sharded_key::StorageShardedKey, ShardedKey},
@@ -14,19 +17,21 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
-    BlockNumReader, DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    BlockNumReader, DBProvider, StateCommitmentProvider, StateDumpProvider, StateProofProvider,
+    StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, MultiProofTargets,
+    StateRoot, StorageMultiProof, StorageRoot, TrieAccount, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedPostState, DatabaseHashedStateRange, DatabaseHashedStorage, DatabaseProof,
+    DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
+    StateCommitment,
 };
 use std::fmt::Debug;
 
@@ -391,6 +396,54 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
     }
 }
 
+impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateDumpProvider
+    for HistoricalStateProviderRef<'_, Provider>
+{
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        max: usize,
+        max_storage: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        revert_state
+            .account_range(self.tx(), start, max, max_storage)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut revert_storage = self.revert_storage(address)?;
+        revert_storage.extend(&hashed_storage);
+        let hashed_address =
+            <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(address);
+        Ok(HashedPostState::from_hashed_storage(hashed_address, revert_storage).storage_range(
+            self.tx(),
+            hashed_address,
+            start,
+            max,
+        )?)
+    }
+
+    fn account_addresses(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        plain_state_addresses::<_, <Provider::StateCommitment as StateCommitment>::KeyHasher>(
+            self.tx(),
+            hashed_addresses,
+        )
+    }
+
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash).map_err(Into::into)
+    }
+}
+
 impl<Provider: StateCommitmentProvider> HashedPostStateProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
@@ -2,23 +2,30 @@ use crate::{
     providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
     HashedPostStateProvider, StateProvider, StateRootProvider,
 };
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
-use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
-    DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    DBProvider, StateCommitmentProvider, StateDumpProvider, StateProofProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, MultiProofTargets,
+    StateRoot, StorageMultiProof, StorageRoot, TrieAccount, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedStateRange, DatabaseProof, DatabaseStateRoot, DatabaseStorageProof,
+    DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
 
 /// State provider over latest state that takes tx reference.
@@ -150,6 +157,50 @@ impl<Provider: DBProvider + StateCommitmentProvider> StateProofProvider
     }
 }
 
+impl<Provider: DBProvider + StateCommitmentProvider> StateDumpProvider
+    for LatestStateProviderRef<'_, Provider>
+{
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        max: usize,
+        max_storage: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>> {
+        hashed_state
+            .account_range(self.tx(), start, max, max_storage)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let hashed_address =
+            <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(address);
+        Ok(HashedPostState::from_hashed_storage(hashed_address, hashed_storage).storage_range(
+            self.tx(),
+            hashed_address,
+            start,
+            max,
+        )?)
+    }
+
+    fn account_addresses(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        plain_state_addresses::<_, <Provider::StateCommitment as StateCommitment>::KeyHasher>(
+            self.tx(),
+            hashed_addresses,
+        )
+    }
+
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash).map_err(Into::into)
+    }
+}
+
 impl<Provider: DBProvider + StateCommitmentProvider> HashedPostStateProvider
     for LatestStateProviderRef<'_, Provider>
 {
@@ -212,6 +263,29 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider for LatestStateP
 }
 
 // Delegates all provider impls to [LatestStateProviderRef]
+/// Resolves the given hashed addresses by hashing the addresses of the plain account state.
+///
+/// The plain state is scanned until all addresses are resolved, so resolving an address of an
+/// account that is not in the plain state scans the whole state.
+pub(crate) fn plain_state_addresses<TX: DbTx, KH: KeyHasher>(
+    tx: &TX,
+    hashed_addresses: &[B256],
+) -> ProviderResult<B256Map<Address>> {
+    let mut unresolved = hashed_addresses.iter().copied().collect::<B256Set>();
+    let mut addresses = B256Map::default();
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        if unresolved.is_empty() {
+            break
+        }
+        let (address, _) = entry?;
+        let hashed_address = KH::hash_key(address);
+        if unresolved.remove(&hashed_address) {
+            addresses.insert(hashed_address, address);
+        }
+    }
+    Ok(addresses)
+}
+
 delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvider + BlockHashReader + StateCommitmentProvider]);
 
 #[cfg(test)]
@@ -57,6 +57,12 @@ macro_rules! delegate_provider_impls {
                 fn multiproof(&self, input: reth_trie::TrieInput, targets: reth_trie::MultiProofTargets) -> reth_storage_errors::provider::ProviderResult<reth_trie::MultiProof>;
                 fn witness(&self, input: reth_trie::TrieInput, target: reth_trie::HashedPostState) -> reth_storage_errors::provider::ProviderResult<Vec<alloy_primitives::Bytes>>;
             }
+            StateDumpProvider $(where [$($generics)*])? {
+                fn account_range(&self, state: reth_trie::HashedPostState, start: alloy_primitives::B256, max: usize, max_storage: usize) -> reth_storage_errors::provider::ProviderResult<Vec<(alloy_primitives::B256, reth_trie::TrieAccount, Vec<(alloy_primitives::B256, alloy_primitives::U256)>)>>;
+                fn storage_range(&self, address: alloy_primitives::Address, storage: reth_trie::HashedStorage, start: alloy_primitives::B256, max: usize) -> reth_storage_errors::provider::ProviderResult<Vec<(alloy_primitives::B256, alloy_primitives::U256)>>;
+                fn account_addresses(&self, hashed_addresses: &[alloy_primitives::B256]) -> reth_storage_errors::provider::ProviderResult<alloy_primitives::map::B256Map<alloy_primitives::Address>>;
+                fn bytecode(&self, code_hash: &alloy_primitives::B256) -> reth_storage_errors::provider::ProviderResult<Option<reth_primitives_traits::Bytecode>>;
+            }
             HashedPostStateProvider $(where [$($generics)*])? {
                 fn hashed_post_state(&self, bundle_state: &revm_database::BundleState) -> reth_trie::HashedPostState;
             }
@@ -0,0 +1,201 @@
+use alloy_primitives::{
+    map::{B256Set, HashMap},
+    Bytes, B256, U256,
+};
+use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_storage_api::{DBProvider, StateRangeProvider};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    prefix_set::TriePrefixSetsMut,
+    proof::{Proof, StorageProof},
+    HashedPostState, HashedPostStateSorted, MultiProofTargets, Nibbles, StorageRoot,
+    StoredNibblesSubKey, TrieAccount,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStorageRoot, DatabaseTrieCursorFactory};
+
+/// [`StateRangeProvider`] over the hashed state in the database.
+///
+/// The state of an earlier block is served by applying the reverts of all following blocks on top
+/// of the hashed state. All ranges and proofs are read through the transaction of the wrapped
+/// [`DBProvider`], so they are consistent with each other.
+#[derive(Debug)]
+pub struct HashedStateRangeProvider<Provider> {
+    /// Database provider
+    provider: Provider,
+    /// The reverts applied on top of the hashed state, empty for the latest state.
+    revert_state: HashedPostStateSorted,
+    /// The trie paths changed by the reverts.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl<Provider: DBProvider> HashedStateRangeProvider<Provider> {
+    /// Create new state range provider with the given reverts applied on top of the latest state.
+    pub fn new(provider: Provider, revert_state: HashedPostState) -> Self {
+        let prefix_sets = revert_state.construct_prefix_sets();
+        Self { provider, revert_state: revert_state.into_sorted(), prefix_sets }
+    }
+
+    fn tx(&self) -> &Provider::Tx {
+        self.provider.tx_ref()
+    }
+
+    fn hashed_cursor_factory(
+        &self,
+    ) -> HashedPostStateCursorFactory<'_, DatabaseHashedCursorFactory<'_, Provider::Tx>> {
+        HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(self.tx()),
+            &self.revert_state,
+        )
+    }
+
+    /// Returns the root of the storage trie of the given account.
+    fn storage_root(
+        &self,
+        trie_cursor: &mut impl DbDupCursorRO<tables::StoragesTrie>,
+        hashed_address: B256,
+    ) -> ProviderResult<B256> {
+        let storage_root = StorageRoot::from_tx_hashed(self.tx(), hashed_address)
+            .with_hashed_cursor_factory(self.hashed_cursor_factory());
+
+        // Storage tries changed by the reverts have to be rehashed.
+        if let Some(prefix_set) = self.prefix_sets.storage_prefix_sets.get(&hashed_address) {
+            return storage_root
+                .with_prefix_set(prefix_set.clone().freeze())
+                .root()
+                .map_err(|err| ProviderError::Database(err.into()))
+        }
+
+        // The root node of a storage trie carries the root hash. Tries without a stored root
+        // node only have a handful of leaves and are cheap to hash.
+        let root_node = trie_cursor
+            .seek_by_key_subkey(hashed_address, StoredNibblesSubKey(Nibbles::default()))?
+            .filter(|entry| entry.nibbles.0.is_empty());
+        match root_node.and_then(|entry| entry.node.root_hash) {
+            Some(root) => Ok(root),
+            None => storage_root.root().map_err(|err| ProviderError::Database(err.into())),
+        }
+    }
+
+    /// Returns the proof nodes for the given keys, ordered by path.
+    ///
+    /// If `hashed_address` is set, the keys are proven in the storage trie of that account.
+    fn proof_nodes(
+        &self,
+        hashed_address: Option<B256>,
+        keys: B256Set,
+    ) -> ProviderResult<Vec<(Nibbles, Bytes)>> {
+        let trie_cursor_factory = DatabaseTrieCursorFactory::new(self.tx());
+        let nodes = if let Some(hashed_address) = hashed_address {
+            StorageProof::new_hashed(
+                trie_cursor_factory,
+                self.hashed_cursor_factory(),
+                hashed_address,
+            )
+            .with_prefix_set_mut(
+                self.prefix_sets
+                    .storage_prefix_sets
+                    .get(&hashed_address)
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+            .storage_multiproof(keys)?
+            .subtree
+        } else {
+            Proof::new(trie_cursor_factory, self.hashed_cursor_factory())
+                .with_prefix_sets_mut(self.prefix_sets.clone())
+                .multiproof(
+                    keys.into_iter()
+                        .map(|key| (key, B256Set::default()))
+                        .collect::<MultiProofTargets>(),
+                )?
+                .account_subtree
+        };
+        Ok(nodes.into_nodes_sorted())
+    }
+}
+
+impl<Provider: DBProvider + Send + Sync> StateRangeProvider for HashedStateRangeProvider<Provider> {
+    fn hashed_account_range(
+        &self,
+        start: B256,
+        limit: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount)>> {
+        let mut accounts = Vec::new();
+        let mut cursor = self.hashed_cursor_factory().hashed_account_cursor()?;
+        let mut trie_cursor = self.tx().cursor_dup_read::<tables::StoragesTrie>()?;
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if accounts.len() >= max {
+                break
+            }
+
+            let storage_root = self.storage_root(&mut trie_cursor, hashed_address)?;
+            accounts.push((hashed_address, account.into_trie_account(storage_root)));
+
+            // the first account past the limit is included so the range can be proven
+            if hashed_address >= limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+        Ok(accounts)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut slots = Vec::new();
+        let mut cursor = self.hashed_cursor_factory().hashed_storage_cursor(hashed_address)?;
+        let mut entry = cursor.seek(start)?;
+        while let Some((key, value)) = entry {
+            if slots.len() >= max {
+                break
+            }
+
+            slots.push((key, value));
+
+            // the first slot past the limit is included so the range can be proven
+            if key >= limit {
+                break
+            }
+            entry = cursor.next()?;
+        }
+        Ok(slots)
+    }
+
+    fn range_proof(
+        &self,
+        hashed_address: Option<B256>,
+        keys: &[B256],
+    ) -> ProviderResult<Vec<Bytes>> {
+        let nodes = self.proof_nodes(hashed_address, keys.iter().copied().collect())?;
+        Ok(nodes.into_iter().map(|(_, node)| node).collect())
+    }
+
+    fn trie_nodes(
+        &self,
+        hashed_address: Option<B256>,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Vec<Option<Bytes>>> {
+        // Proofs retain every node on the path towards a target key, so the node located at a
+        // path is part of the proof for any key that has the path as prefix.
+        let keys = paths
+            .iter()
+            .filter(|path| path.len() <= 64)
+            .map(|path| {
+                let mut nibbles = path.clone();
+                nibbles.extend_from_slice_unchecked(&[0; 64][path.len()..]);
+                B256::from_slice(&nibbles.pack())
+            })
+            .collect();
+
+        let nodes = self.proof_nodes(hashed_address, keys)?.into_iter().collect::<HashMap<_, _>>();
+        Ok(paths.iter().map(|path| nodes.get(path).cloned()).collect())
+    }
+}
@@ -12,7 +12,7 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
 use reth_chainspec::ChainInfo;
 use reth_db::static_file::{
     BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask, ReceiptMask, StaticFileCursor,
-    TDWithHashMask, TotalDifficultyMask, TransactionMask,
+    TDWithHashMask, TotalDifficultyMask, TransactionMask, TransactionSenderMask,
 };
 use reth_db_api::{
     models::StoredBlockBodyIndices,
@@ -296,11 +296,28 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
+        if self.segment().is_transaction_senders() {
+            let range = to_range(range);
+            let mut cursor = self.cursor()?;
+            let mut senders = Vec::with_capacity((range.end - range.start) as usize);
+
+            for num in range {
+                if let Some(sender) = cursor.get_one::<TransactionSenderMask>(num.into())? {
+                    senders.push(sender)
+                }
+            }
+            return Ok(senders)
+        }
+
         let txs = self.transactions_by_tx_range(range)?;
         Ok(reth_primitives_traits::transaction::recover::recover_signers(&txs)?)
     }
 
     fn transaction_sender(&self, num: TxNumber) -> ProviderResult<Option<Address>> {
+        if self.segment().is_transaction_senders() {
+            return self.cursor()?.get_one::<TransactionSenderMask>(num.into())
+        }
+
         Ok(self
             .cursor()?
             .get_one::<TransactionMask<Self::Transaction>>(num.into())?
@@ -23,7 +23,7 @@ use reth_db::{
     lockfile::StorageLock,
     static_file::{
         iter_static_files, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
-        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
+        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask, TransactionSenderMask,
     },
 };
 use reth_db_api::{
@@ -229,6 +229,8 @@ pub struct StaticFileProviderInner<N> {
     /// Maintains a map which allows for concurrent access to different `NippyJars`, over different
     /// segments and ranges.
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
+    /// Min static file block for each segment
+    static_files_min_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Available static file block ranges on disk indexed by max transactions.
@@ -261,6 +263,7 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
         let provider = Self {
             map: Default::default(),
             writers: Default::default(),
+            static_files_min_block: Default::default(),
             static_files_max_block: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
@@ -526,6 +529,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
+        let mut min_block = self.static_files_min_block.write();
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
@@ -535,6 +539,12 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 max_block.insert(segment, segment_max_block);
                 let fixed_range = self.find_fixed_range(segment_max_block);
 
+                // Update the min block for the segment, if this is its first static file
+                min_block
+                    .entry(segment)
+                    .and_modify(|min| *min = (*min).min(fixed_range.start()))
+                    .or_insert_with(|| fixed_range.start());
+
                 let jar = NippyJar::<SegmentHeader>::load(
                     &self.path.join(segment.filename(&fixed_range)),
                 )
@@ -588,6 +598,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             }
             None => {
                 tx_index.remove(&segment);
+                min_block.remove(&segment);
                 max_block.remove(&segment);
             }
         };
@@ -597,13 +608,20 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
+        let mut min_block = self.static_files_min_block.write();
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
+        min_block.clear();
         max_block.clear();
         tx_index.clear();
 
         for (segment, ranges) in iter_static_files(&self.path).map_err(ProviderError::other)? {
+            // Update first block for each segment
+            if let Some((block_range, _)) = ranges.first() {
+                min_block.insert(segment, block_range.start());
+            }
+
             // Update last block for each segment
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
@@ -706,6 +724,16 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 continue
             }
 
+            if segment.is_transaction_senders() &&
+                (provider.prune_modes_ref().sender_recovery.is_some() ||
+                    self.get_highest_static_file_block(segment).is_none())
+            {
+                // Nodes that prune sender recovery do not store transaction senders as static
+                // files, and nodes initialized before the segment was introduced keep them in the
+                // database until they're moved by the static file producer.
+                continue
+            }
+
             let initial_highest_block = self.get_highest_static_file_block(segment);
 
             //  File consistency is broken if:
@@ -739,7 +767,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 update_unwind_target(highest_block.unwrap_or_default());
             }
 
-            // Only applies to transaction-based static files. (Receipts & Transactions)
+            // Only applies to transaction-based static files. (Receipts, Transactions &
+            // TransactionSenders)
             //
             // Make sure the last transaction matches the last block from its indices, since a heal
             // from a pruning interruption might have decreased the number of transactions without
@@ -804,6 +833,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_block,
                         highest_block,
                     )?,
+                StaticFileSegment::TransactionSenders => self
+                    .ensure_invariants::<_, tables::TransactionSenders>(
+                        provider,
+                        segment,
+                        highest_tx,
+                        highest_block,
+                    )?,
             } {
                 update_unwind_target(unwind);
             }
@@ -891,6 +927,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions | StaticFileSegment::BlockMeta => StageId::Bodies,
                 StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::TransactionSenders => StageId::SenderRecovery,
             })?
             .unwrap_or_default()
             .block_number;
@@ -928,6 +965,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 let number = highest_static_file_entry - block.last_tx_num();
                 if segment.is_receipts() {
                     writer.prune_receipts(number, checkpoint_block_number)?;
+                } else if segment.is_transaction_senders() {
+                    writer.prune_transaction_senders(number, checkpoint_block_number)?;
                 } else {
                     writer.prune_transactions(number, checkpoint_block_number)?;
                 }
@@ -945,6 +984,16 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         self.static_files_max_block.read().get(&segment).copied()
     }
 
+    /// Gets the lowest static file block if it exists for a static file segment.
+    ///
+    /// This is the first block of the lowest static file on disk, static files below it may have
+    /// been removed, e.g. by history expiry.
+    ///
+    /// If there is nothing on disk for the given segment, this will return [`None`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min_block.read().get(&segment).copied()
+    }
+
     /// Gets the highest static file transaction.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -962,6 +1011,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
             block_meta: self.get_highest_static_file_block(StaticFileSegment::BlockMeta),
+            transaction_senders: self
+                .get_highest_static_file_block(StaticFileSegment::TransactionSenders),
         }
     }
 
@@ -1589,11 +1640,48 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        let txes = self.transactions_by_tx_range(range)?;
-        Ok(reth_primitives_traits::transaction::recover::recover_signers(&txes)?)
+        let mut range = to_range(range);
+        let mut senders = Vec::new();
+
+        // Read the senders that are available in the transaction senders segment, and recover the
+        // remaining ones from the transactions segment.
+        if let Some(highest_sender_tx) =
+            self.get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+        {
+            if range.start <= highest_sender_tx {
+                let end = range.end.min(highest_sender_tx + 1);
+                senders.extend(self.fetch_range_with_predicate(
+                    StaticFileSegment::TransactionSenders,
+                    range.start..end,
+                    |cursor, number| cursor.get_one::<TransactionSenderMask>(number.into()),
+                    |_| true,
+                )?);
+                range.start = end;
+            }
+        }
+
+        if range.end > range.start {
+            let txes = self.transactions_by_tx_range(range)?;
+            senders.extend(reth_primitives_traits::transaction::recover::recover_signers(&txes)?);
+        }
+
+        Ok(senders)
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
+        if self
+            .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+            .is_some_and(|highest_sender_tx| highest_sender_tx >= id)
+        {
+            return self
+                .get_segment_provider_from_transaction(
+                    StaticFileSegment::TransactionSenders,
+                    id,
+                    None,
+                )?
+                .transaction_sender(id)
+        }
+
         match self.transaction_by_id_unhashed(id)? {
             Some(tx) => Ok(tx.recover_signer().ok()),
             None => Ok(None),
@@ -1744,6 +1832,10 @@ impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
                 .map(|txs| txs + 1)
                 .unwrap_or_default()
                 as usize),
+            tables::TransactionSenders::NAME => Ok(self
+                .get_highest_static_file_tx(StaticFileSegment::TransactionSenders)
+                .map(|txs| txs + 1)
+                .unwrap_or_default() as usize),
             _ => Err(ProviderError::UnsupportedProvider),
         }
     }
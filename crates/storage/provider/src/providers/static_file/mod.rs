@@ -58,7 +58,7 @@ mod tests {
         test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
-    use alloy_primitives::{BlockHash, Signature, TxNumber, B256, U256};
+    use alloy_primitives::{Address, BlockHash, Signature, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::test_utils::create_test_static_files_dir;
     use reth_db_api::{
@@ -271,6 +271,7 @@ mod tests {
                 .with_custom_blocks_per_file(blocks_per_file);
 
             assert_eq!(sf_rw.get_highest_static_file_block(StaticFileSegment::Headers), Some(tip));
+            assert_eq!(sf_rw.get_lowest_static_file_block(StaticFileSegment::Headers), Some(0));
             assert_eq!(
                 count_files_without_lockfile(static_dir.as_ref()).unwrap(),
                 initial_file_count as usize
@@ -298,9 +299,9 @@ mod tests {
     /// 3 block ranges are built
     ///
     /// for `blocks_per_file = 10`:
-    /// * `0..=9` : except genesis, every block has a tx/receipt
-    /// * `10..=19`: no txs/receipts
-    /// * `20..=29`: only one tx/receipt
+    /// * `0..=9` : except genesis, every block has a tx/receipt/sender
+    /// * `10..=19`: no txs/receipts/senders
+    /// * `20..=29`: only one tx/receipt/sender
     fn setup_tx_based_scenario(
         sf_rw: &StaticFileProvider<EthPrimitives>,
         segment: StaticFileSegment,
@@ -320,12 +321,16 @@ mod tests {
             for block in block_range.clone() {
                 writer.increment_block(block).unwrap();
 
-                // Append transaction/receipt if there's still a transaction count to append
+                // Append transaction/receipt/sender if there's still a transaction count to append
                 if tx_count > 0 {
                     if segment.is_receipts() {
                         // Used as ID for validation
                         receipt.cumulative_gas_used = *next_tx_num;
                         writer.append_receipt(*next_tx_num, &receipt).unwrap();
+                    } else if segment.is_transaction_senders() {
+                        // Used as ID for validation
+                        let sender = Address::left_padding_from(&next_tx_num.to_be_bytes());
+                        writer.append_transaction_sender(*next_tx_num, &sender).unwrap();
                     } else {
                         // Used as ID for validation
                         tx.nonce = *next_tx_num;
@@ -407,7 +412,11 @@ mod tests {
 
     #[test]
     fn test_tx_based_truncation() {
-        let segments = [StaticFileSegment::Transactions, StaticFileSegment::Receipts];
+        let segments = [
+            StaticFileSegment::Transactions,
+            StaticFileSegment::Receipts,
+            StaticFileSegment::TransactionSenders,
+        ];
         let blocks_per_file = 10; // Number of blocks per file
         let files_per_range = 3; // Number of files per range (data/conf/offset files)
         let file_set_count = 3; // Number of sets of files to create
@@ -426,9 +435,11 @@ mod tests {
         ) -> eyre::Result<()> {
             let mut writer = sf_rw.latest_writer(segment)?;
 
-            // Prune transactions or receipts based on the segment type
+            // Prune transactions, receipts or senders based on the segment type
             if segment.is_receipts() {
                 writer.prune_receipts(prune_count, last_block)?;
+            } else if segment.is_transaction_senders() {
+                writer.prune_transaction_senders(prune_count, last_block)?;
             } else {
                 writer.prune_transactions(prune_count, last_block)?;
             }
@@ -442,8 +453,8 @@ mod tests {
             )?;
             assert_eyre(sf_rw.get_highest_static_file_tx(segment), expected_tx_tip, "tx mismatch")?;
 
-            // Verify that transactions and receipts are returned correctly. Uses
-            // cumulative_gas_used & nonce as ids.
+            // Verify that transactions, receipts and senders are returned correctly. Uses
+            // cumulative_gas_used, nonce & the sender address as ids.
             if let Some(id) = expected_tx_tip {
                 if segment.is_receipts() {
                     assert_eyre(
@@ -451,6 +462,12 @@ mod tests {
                         sf_rw.receipt(id)?.map(|r| r.cumulative_gas_used),
                         "tx mismatch",
                     )?;
+                } else if segment.is_transaction_senders() {
+                    assert_eyre(
+                        Some(Address::left_padding_from(&id.to_be_bytes())),
+                        sf_rw.transaction_sender(id)?,
+                        "tx mismatch",
+                    )?;
                 } else {
                     assert_eyre(
                         expected_tx_tip,
@@ -3,7 +3,7 @@ use super::{
 };
 use crate::providers::static_file::metrics::StaticFileProviderOperation;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
 use reth_db_api::models::{
@@ -32,6 +32,7 @@ pub(crate) struct StaticFileWriters<N> {
     transactions: RwLock<Option<StaticFileProviderRW<N>>>,
     receipts: RwLock<Option<StaticFileProviderRW<N>>>,
     block_meta: RwLock<Option<StaticFileProviderRW<N>>>,
+    transaction_senders: RwLock<Option<StaticFileProviderRW<N>>>,
 }
 
 impl<N> Default for StaticFileWriters<N> {
@@ -41,6 +42,7 @@ impl<N> Default for StaticFileWriters<N> {
             transactions: Default::default(),
             receipts: Default::default(),
             block_meta: Default::default(),
+            transaction_senders: Default::default(),
         }
     }
 }
@@ -56,6 +58,7 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
             StaticFileSegment::Transactions => self.transactions.write(),
             StaticFileSegment::Receipts => self.receipts.write(),
             StaticFileSegment::BlockMeta => self.block_meta.write(),
+            StaticFileSegment::TransactionSenders => self.transaction_senders.write(),
         };
 
         if write_guard.is_none() {
@@ -66,7 +69,9 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
     }
 
     pub(crate) fn commit(&self) -> ProviderResult<()> {
-        for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
+        for writer_lock in
+            [&self.headers, &self.transactions, &self.receipts, &self.transaction_senders]
+        {
             let mut writer = writer_lock.write();
             if let Some(writer) = writer.as_mut() {
                 writer.commit()?;
@@ -232,6 +237,10 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                     self.prune_receipt_data(to_delete, last_block_number.expect("should exist"))?
                 }
                 StaticFileSegment::BlockMeta => todo!(),
+                StaticFileSegment::TransactionSenders => self.prune_transaction_sender_data(
+                    to_delete,
+                    last_block_number.expect("should exist"),
+                )?,
             }
         }
 
@@ -359,18 +368,22 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(())
     }
 
-    /// Verifies if the incoming block number matches the next expected block number
-    /// for a static file. This ensures data continuity when adding new blocks.
-    fn check_next_block_number(&self, expected_block_number: u64) -> ProviderResult<()> {
+    /// Returns the next block number expected by the static file, including uncommitted blocks.
+    pub fn next_block_number(&self) -> BlockNumber {
         // The next static file block number can be found by checking the one after block_end.
         // However if it's a new file that hasn't been added any data, its block range will actually
         // be None. In that case, the next block will be found on `expected_block_start`.
-        let next_static_file_block = self
-            .writer
+        self.writer
             .user_header()
             .block_end()
             .map(|b| b + 1)
-            .unwrap_or_else(|| self.writer.user_header().expected_block_start());
+            .unwrap_or_else(|| self.writer.user_header().expected_block_start())
+    }
+
+    /// Verifies if the incoming block number matches the next expected block number
+    /// for a static file. This ensures data continuity when adding new blocks.
+    fn check_next_block_number(&self, expected_block_number: u64) -> ProviderResult<()> {
+        let next_static_file_block = self.next_block_number();
 
         if expected_block_number != next_static_file_block {
             return Err(ProviderError::UnexpectedStaticFileBlockNumber(
@@ -431,7 +444,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             }
         }
 
-        // Only Transactions and Receipts
+        // Only transaction based segments
         if let Some(last_block) = last_block {
             let mut expected_block_start = self.writer.user_header().expected_block_start();
 
@@ -698,6 +711,32 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(Some(tx_number))
     }
 
+    /// Appends transaction sender to static file.
+    ///
+    /// It **DOES NOT** call `increment_block()`, it should be handled elsewhere. There might be
+    /// empty blocks and this function wouldn't be called.
+    pub fn append_transaction_sender(
+        &mut self,
+        tx_num: TxNumber,
+        sender: &Address,
+    ) -> ProviderResult<()> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::TransactionSenders);
+        self.append_with_tx_number(tx_num, *sender)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::TransactionSenders,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Adds an instruction to prune `to_delete`transactions during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at.
@@ -722,6 +761,21 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         self.queue_prune(to_delete, Some(last_block))
     }
 
+    /// Adds an instruction to prune `to_delete` transaction senders during commit.
+    ///
+    /// Note: `last_block` refers to the block the unwinds ends at.
+    pub fn prune_transaction_senders(
+        &mut self,
+        to_delete: u64,
+        last_block: BlockNumber,
+    ) -> ProviderResult<()> {
+        debug_assert_eq!(
+            self.writer.user_header().segment(),
+            StaticFileSegment::TransactionSenders
+        );
+        self.queue_prune(to_delete, Some(last_block))
+    }
+
     /// Adds an instruction to prune `to_delete` headers during commit.
     pub fn prune_headers(&mut self, to_delete: u64) -> ProviderResult<()> {
         debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::Headers);
@@ -804,6 +858,29 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(())
     }
 
+    /// Prunes the last `to_delete` transaction senders from the data file.
+    fn prune_transaction_sender_data(
+        &mut self,
+        to_delete: u64,
+        last_block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::TransactionSenders);
+
+        self.truncate(to_delete, Some(last_block))?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::TransactionSenders,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Prunes the last `to_delete` headers from the data file.
     fn prune_header_data(&mut self, to_delete: u64) -> ProviderResult<()> {
         let start = Instant::now();
@@ -8,8 +8,9 @@ use crate::{
 use alloy_consensus::{constants::EMPTY_ROOT_HASH, transaction::TransactionMeta, Header};
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
-    keccak256, map::HashMap, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue,
-    TxHash, TxNumber, B256, U256,
+    keccak256,
+    map::{B256Map, HashMap},
+    Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256, U256,
 };
 use parking_lot::Mutex;
 use reth_chain_state::{CanonStateNotifications, CanonStateSubscriptions};
@@ -29,18 +30,19 @@ use reth_primitives_traits::{
 use reth_prune_types::PruneModes;
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, DatabaseProviderFactory, HashedPostStateProvider,
-    NodePrimitivesProvider, StageCheckpointReader, StateCommitmentProvider, StateProofProvider,
-    StorageRootProvider,
+    BlockBodyIndicesProvider, CallHistoryReader, DBProvider, DatabaseProviderFactory,
+    HashedPostStateProvider, ModifiedStateReader, NodePrimitivesProvider, StageCheckpointReader,
+    StateCommitmentProvider, StateDumpProvider, StateDumpProviderBox, StateDumpProviderFactory,
+    StateProofProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieAccount, TrieInput,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
@@ -859,6 +861,69 @@ where
     }
 }
 
+impl<T, ChainSpec> StateDumpProvider for MockEthProvider<T, ChainSpec>
+where
+    T: NodePrimitives,
+    ChainSpec: Send + Sync,
+{
+    fn account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _max: usize,
+        _max_storage: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::default())
+    }
+
+    fn account_addresses(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        let lock = self.accounts.lock();
+        Ok(hashed_addresses
+            .iter()
+            .filter_map(|hashed_address| {
+                lock.keys()
+                    .find(|address| keccak256(address) == *hashed_address)
+                    .map(|address| (*hashed_address, *address))
+            })
+            .collect())
+    }
+
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        let lock = self.accounts.lock();
+        Ok(lock.values().find_map(|account| {
+            match (account.account.bytecode_hash.as_ref(), account.bytecode.as_ref()) {
+                (Some(bytecode_hash), Some(bytecode)) if bytecode_hash == code_hash => {
+                    Some(bytecode.clone())
+                }
+                _ => None,
+            }
+        }))
+    }
+}
+
+impl<T, ChainSpec> StateDumpProviderFactory for MockEthProvider<T, ChainSpec>
+where
+    T: NodePrimitives,
+    ChainSpec: Send + Sync + 'static,
+{
+    fn state_dump_by_block_hash(
+        &self,
+        _block_hash: BlockHash,
+    ) -> ProviderResult<StateDumpProviderBox> {
+        Ok(Box::new(self.clone()))
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: EthChainSpec + 'static> HashedPostStateProvider
     for MockEthProvider<T, ChainSpec>
 {
@@ -962,6 +1027,22 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockBodyIndicesProvider
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> CallHistoryReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn call_history_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn call_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvider<T, ChainSpec> {
     fn account_block_changeset(
         &self,
@@ -971,6 +1052,24 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvi
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> ModifiedStateReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn modified_accounts(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::new())
+    }
+
+    fn modified_storage(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
+        Ok(BTreeMap::new())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StateReader for MockEthProvider<T, ChainSpec> {
     type Receipt = Receipt;
 
@@ -1,8 +1,9 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    AccountReader, BlockReaderIdExt, CallHistoryReader, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, ModifiedStateReader, StageCheckpointReader, StateDumpProviderFactory,
+    StateProviderFactory, StateReader, StaticFileProviderFactory,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
@@ -20,7 +21,11 @@ pub trait FullProvider<N: NodeTypesWithDB>:
         Receipt = ReceiptTy<N>,
         Header = HeaderTy<N>,
     > + AccountReader
+    + ModifiedStateReader
+    + CallHistoryReader
     + StateProviderFactory
+    + StateDumpProviderFactory
+    + StateReader<Receipt = ReceiptTy<N>>
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
     + CanonStateSubscriptions
@@ -43,7 +48,11 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
             Receipt = ReceiptTy<N>,
             Header = HeaderTy<N>,
         > + AccountReader
+        + ModifiedStateReader
+        + CallHistoryReader
         + StateProviderFactory
+        + StateDumpProviderFactory
+        + StateReader<Receipt = ReceiptTy<N>>
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
         + CanonStateSubscriptions
@@ -2,7 +2,7 @@ use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockNumber, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_models::AccountBeforeTx;
@@ -55,3 +55,20 @@ pub trait ChangeSetReader {
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
 }
+
+/// Reader for the accounts and storage slots that changed in a block range
+#[auto_impl(&, Arc, Box)]
+pub trait ModifiedStateReader: Send + Sync {
+    /// Returns the addresses of all accounts whose info or storage changed in the given block
+    /// range.
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
+
+    /// Returns the storage slots that changed in the given block range, grouped by account.
+    fn modified_storage(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>>;
+}
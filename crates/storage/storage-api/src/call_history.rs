@@ -0,0 +1,51 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use auto_impl::auto_impl;
+use core::ops::RangeInclusive;
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The id under which the checkpoint of the call trace index is stored.
+///
+/// The index is not built by a pipeline stage, the checkpoint is only used to track the highest
+/// indexed block.
+pub const CALL_TRACE_INDEX_ID: StageId = StageId::Other("CallTraceIndex");
+
+/// Call trace history reader
+#[auto_impl(&, Arc, Box)]
+pub trait CallHistoryReader: Send + Sync {
+    /// Returns the highest block the call trace index has been built for, or `None` if the index
+    /// is not built.
+    fn call_history_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the numbers of all blocks in the given range in which the address was the caller
+    /// or the callee of a call frame, in ascending order.
+    ///
+    /// NOTE: Blocks are looked up in the call trace index, blocks above the
+    /// [checkpoint](Self::call_history_checkpoint) of the index are not returned.
+    fn call_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// Call trace history writer
+#[auto_impl(&, Arc, Box)]
+pub trait CallHistoryWriter: Send + Sync {
+    /// Inserts the addresses that were the caller or the callee of a call frame in the given
+    /// block into the call trace index and advances the checkpoint of the index to the block.
+    ///
+    /// Blocks must be inserted in ascending order.
+    fn insert_call_history(
+        &self,
+        block_number: BlockNumber,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> ProviderResult<()>;
+
+    /// Removes all blocks starting at the given block from the call trace index and resets the
+    /// checkpoint of the index to the block before it.
+    ///
+    /// Returns the number of removed entries.
+    fn unwind_call_history(&self, from: BlockNumber) -> ProviderResult<usize>;
+}
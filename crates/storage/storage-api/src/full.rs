@@ -3,8 +3,8 @@
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 
 use crate::{
-    BlockReaderIdExt, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    TransactionsProvider,
+    BlockReaderIdExt, CallHistoryReader, HeaderProvider, ModifiedStateReader,
+    StageCheckpointReader, StateDumpProviderFactory, StateProviderFactory, TransactionsProvider,
 };
 
 /// Helper trait to unify all provider traits required to support `eth` RPC server behaviour, for
@@ -16,6 +16,9 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + ModifiedStateReader
+    + CallHistoryReader
+    + StateDumpProviderFactory
     + Clone
     + Unpin
     + 'static
@@ -29,6 +32,9 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + ModifiedStateReader
+        + CallHistoryReader
+        + StateDumpProviderFactory
         + Clone
         + Unpin
         + 'static
@@ -2,17 +2,26 @@
 
 use crate::{
     AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChangeSetReader, HashedPostStateProvider,
-    HeaderProvider, NodePrimitivesProvider, PruneCheckpointReader, ReceiptProvider,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider, StateProvider,
-    StateProviderBox, StateProviderFactory, StateRootProvider, StorageRootProvider,
+    BlockReader, BlockReaderIdExt, BlockSource, CallHistoryReader, ChangeSetReader,
+    HashedPostStateProvider, HeaderProvider, ModifiedStateReader, NodePrimitivesProvider,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
+    StateDumpProvider, StateDumpProviderBox, StateDumpProviderFactory, StateProofProvider,
+    StateProvider, StateProviderBox, StateProviderFactory, StateRangeProvider,
+    StateRangeProviderBox, StateRangeProviderFactory, StateRootProvider, StorageRootProvider,
     TransactionVariant, TransactionsProvider,
 };
-use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
-    Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256, U256,
+    map::B256Map, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash,
+    TxNumber, B256, U256,
 };
 use core::{
     fmt::Debug,
@@ -30,7 +39,7 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    MultiProofTargets, Nibbles, StorageMultiProof, StorageProof, TrieAccount, TrieInput,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -361,6 +370,20 @@ impl<C: Send + Sync, N: NodePrimitives> AccountReader for NoopProvider<C, N> {
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> CallHistoryReader for NoopProvider<C, N> {
+    fn call_history_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn call_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     fn account_block_changeset(
         &self,
@@ -370,6 +393,22 @@ impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> ModifiedStateReader for NoopProvider<C, N> {
+    fn modified_accounts(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::new())
+    }
+
+    fn modified_storage(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
+        Ok(BTreeMap::new())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StateRootProvider for NoopProvider<C, N> {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -445,6 +484,91 @@ impl<C: Send + Sync, N: NodePrimitives> StateProofProvider for NoopProvider<C, N
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> StateDumpProvider for NoopProvider<C, N> {
+    fn account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _max: usize,
+        _max_storage: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::default())
+    }
+
+    fn account_addresses(&self, _hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        Ok(B256Map::default())
+    }
+
+    fn bytecode(&self, _code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync + 'static, N: NodePrimitives> StateDumpProviderFactory for NoopProvider<C, N> {
+    fn state_dump_by_block_hash(
+        &self,
+        _block_hash: BlockHash,
+    ) -> ProviderResult<StateDumpProviderBox> {
+        Ok(Box::new(self.clone()))
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> StateRangeProvider for NoopProvider<C, N> {
+    fn hashed_account_range(
+        &self,
+        _start: B256,
+        _limit: B256,
+        _max: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount)>> {
+        Ok(Vec::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: B256,
+        _max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::default())
+    }
+
+    fn range_proof(
+        &self,
+        _hashed_address: Option<B256>,
+        _keys: &[B256],
+    ) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::default())
+    }
+
+    fn trie_nodes(
+        &self,
+        _hashed_address: Option<B256>,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Vec<Option<Bytes>>> {
+        Ok(alloc::vec![None; paths.len()])
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> StateRangeProviderFactory for NoopProvider<C, N> {
+    fn state_range_by_root(
+        &self,
+        _state_root: B256,
+    ) -> ProviderResult<Option<StateRangeProviderBox>> {
+        Ok(None)
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> HashedPostStateProvider for NoopProvider<C, N> {
     fn hashed_post_state(&self, _bundle_state: &revm_database::BundleState) -> HashedPostState {
         HashedPostState::default()
@@ -1,10 +1,11 @@
-use alloc::vec::Vec;
-use alloy_primitives::{map::B256Map, Address, Bytes, B256};
+use alloc::{boxed::Box, vec::Vec};
+use alloy_primitives::{map::B256Map, Address, BlockHash, Bytes, B256, U256};
+use reth_primitives_traits::Bytecode;
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-    StorageProof, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, Nibbles,
+    StorageMultiProof, StorageProof, TrieAccount, TrieInput,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -89,6 +90,128 @@ pub trait StateProofProvider: Send + Sync {
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
 }
 
+/// A type that can dump ranges of the hashed state on top of a given post state.
+///
+/// The ranges are served from the state the provider was opened at, which allows dumping
+/// historical state. Providers are opened with a [`StateDumpProviderFactory`].
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait StateDumpProvider: Send + Sync {
+    /// Returns up to `max` accounts of the `HashedPostState` on top of the current state, ordered
+    /// by hashed address and starting at `start`.
+    ///
+    /// Each account is returned together with the root of its storage trie and up to
+    /// `max_storage` of its non-zero storage slots ordered by hashed slot.
+    #[expect(clippy::type_complexity)]
+    fn account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        max: usize,
+        max_storage: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>>;
+
+    /// Returns up to `max` non-zero storage slots of the `HashedStorage` for target address on
+    /// top of the current state, ordered by hashed slot and starting at `start`.
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Returns the addresses of the given hashed addresses that can be resolved.
+    ///
+    /// There are no preimages of hashed addresses, the addresses are resolved from the plain
+    /// state instead. Accounts that are not in the plain state are missing from the result.
+    fn account_addresses(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>>;
+
+    /// Returns the bytecode with the given hash.
+    fn bytecode(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>>;
+}
+
+/// Type alias of boxed [`StateDumpProvider`].
+pub type StateDumpProviderBox = Box<dyn StateDumpProvider>;
+
+/// A type that can open a [`StateDumpProvider`] at a given block.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StateDumpProviderFactory: Send + Sync {
+    /// Returns a [`StateDumpProvider`] for the state after the block with the given hash has been
+    /// executed.
+    fn state_dump_by_block_hash(
+        &self,
+        block_hash: BlockHash,
+    ) -> ProviderResult<StateDumpProviderBox>;
+}
+
+/// A type that can serve contiguous ranges of the hashed state together with the merkle proofs
+/// required to verify them.
+///
+/// This is the data source for serving the `snap` protocol. All ranges are served from the state
+/// the provider was opened at. Providers are opened with a [`StateRangeProviderFactory`].
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait StateRangeProvider: Send + Sync {
+    /// Returns up to `max` accounts ordered by hashed address, starting at `start` and stopping
+    /// after the first account with a hashed address greater than or equal to `limit`.
+    ///
+    /// Each account is returned together with the root of its storage trie.
+    fn hashed_account_range(
+        &self,
+        start: B256,
+        limit: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, TrieAccount)>>;
+
+    /// Returns up to `max` storage slots of the given account ordered by hashed slot, starting at
+    /// `start` and stopping after the first slot with a hashed key greater than or equal to
+    /// `limit`.
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: B256,
+        max: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Returns the RLP encoded trie nodes proving the given keys, ordered by path and
+    /// deduplicated.
+    ///
+    /// If `hashed_address` is set, the keys are hashed storage slots and the proof is generated
+    /// against the storage trie of that account. Otherwise, the keys are hashed addresses in the
+    /// account trie.
+    fn range_proof(
+        &self,
+        hashed_address: Option<B256>,
+        keys: &[B256],
+    ) -> ProviderResult<Vec<Bytes>>;
+
+    /// Returns the RLP encoded trie nodes located at the given paths, or `None` for paths that
+    /// do not point at a node.
+    ///
+    /// If `hashed_address` is set, the paths are looked up in the storage trie of that account.
+    fn trie_nodes(
+        &self,
+        hashed_address: Option<B256>,
+        paths: &[Nibbles],
+    ) -> ProviderResult<Vec<Option<Bytes>>>;
+}
+
+/// Type alias of boxed [`StateRangeProvider`].
+pub type StateRangeProviderBox = Box<dyn StateRangeProvider>;
+
+/// A type that can open a [`StateRangeProvider`] at a given state root.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StateRangeProviderFactory: Send + Sync {
+    /// Returns a [`StateRangeProvider`] for the state with the given root, or `None` if the state
+    /// is not served.
+    ///
+    /// Only the state of recently persisted blocks is served.
+    fn state_range_by_root(
+        &self,
+        state_root: B256,
+    ) -> ProviderResult<Option<StateRangeProviderBox>>;
+}
+
 /// Trie Writer
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait TrieWriter: Send + Sync {
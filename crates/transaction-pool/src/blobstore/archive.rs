@@ -0,0 +1,490 @@
+//! An archive for blob sidecars of finalized transactions.
+
+use crate::blobstore::BlobStoreError;
+use alloy_eips::{
+    eip4844::{Blob, Bytes48},
+    eip7594::BlobTransactionSidecarVariant,
+};
+use alloy_primitives::{BlockNumber, TxHash, B256};
+use parking_lot::RwLock;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::{debug, trace};
+
+/// Number of blocks the [`BlobArchive`] retains by default.
+///
+/// This matches the number of slots consensus clients are required to serve blob sidecars for:
+/// 4096 epochs.
+pub const DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS: u64 = 4096 * 32;
+
+/// The zstd compression level used for archived sidecars.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// File extension of archived blocks.
+const BLOCK_FILE_EXTENSION: &str = "blobs";
+
+/// Configuration for a [`BlobArchive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobArchiveConfig {
+    /// Number of blocks, up to and including the finalized block, to keep sidecars for.
+    ///
+    /// `None` keeps sidecars of all archived blocks.
+    pub retention_blocks: Option<u64>,
+}
+
+impl Default for BlobArchiveConfig {
+    fn default() -> Self {
+        Self { retention_blocks: Some(DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS) }
+    }
+}
+
+/// A blob of a transaction that was included in a block, as stored in the [`BlobArchive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedBlob {
+    /// The block the transaction was included in.
+    pub block_number: BlockNumber,
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The versioned hash of the blob.
+    pub versioned_hash: B256,
+    /// The blob.
+    pub blob: Box<Blob>,
+    /// The KZG proof of the blob for EIP-4844 sidecars, or the cell proofs of the blob for
+    /// EIP-7594 sidecars.
+    pub proofs: Vec<Bytes48>,
+}
+
+/// An archive that keeps blob sidecars of transactions after their blocks were finalized.
+///
+/// The [`BlobStore`](crate::blobstore::BlobStore) of the pool only keeps sidecars until the
+/// blocks that include the transactions are finalized. If an archive is configured, the pool
+/// maintenance task moves the sidecars of finalized blocks into the archive instead, where they
+/// are kept for the configured retention window.
+///
+/// Sidecars are stored compressed, in one file per block. The versioned hashes of all archived
+/// blobs are kept in memory, so that blobs can be looked up by versioned hash.
+///
+/// Note: only sidecars of transactions that were in the pool can be archived.
+#[derive(Clone, Debug)]
+pub struct BlobArchive {
+    inner: Arc<BlobArchiveInner>,
+}
+
+impl BlobArchive {
+    /// Opens the archive in the given directory and indexes the blocks that are already archived.
+    pub fn open(
+        archive_dir: impl Into<PathBuf>,
+        config: BlobArchiveConfig,
+    ) -> Result<Self, BlobArchiveError> {
+        let archive_dir = archive_dir.into();
+        fs::create_dir_all(&archive_dir)
+            .map_err(|err| BlobArchiveError::Open(archive_dir.clone(), err))?;
+
+        let mut index = ArchiveIndex::default();
+        let entries = fs::read_dir(&archive_dir)
+            .map_err(|err| BlobArchiveError::Open(archive_dir.clone(), err))?;
+        for entry in entries {
+            let path =
+                entry.map_err(|err| BlobArchiveError::Open(archive_dir.clone(), err))?.path();
+            if path.extension().is_none_or(|ext| ext != BLOCK_FILE_EXTENSION) {
+                continue
+            }
+            let Some(block_number) =
+                path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok())
+            else {
+                continue
+            };
+            let file = fs::File::open(&path)
+                .map_err(|err| BlobArchiveError::ReadFile(block_number, path.clone(), err))?;
+            let header = read_header(&mut io::BufReader::new(file))
+                .map_err(|err| BlobArchiveError::ReadFile(block_number, path.clone(), err))?;
+            index.insert(block_number, header);
+        }
+        debug!(target: "txpool::blob", ?archive_dir, blocks = index.blocks.len(), "Opened blob archive");
+
+        Ok(Self {
+            inner: Arc::new(BlobArchiveInner { archive_dir, config, index: RwLock::new(index) }),
+        })
+    }
+
+    /// Returns the configuration of the archive.
+    pub fn config(&self) -> BlobArchiveConfig {
+        self.inner.config
+    }
+
+    /// Returns the lowest and highest archived block.
+    pub fn block_range(&self) -> Option<(BlockNumber, BlockNumber)> {
+        let index = self.inner.index.read();
+        Some((*index.blocks.first_key_value()?.0, *index.blocks.last_key_value()?.0))
+    }
+
+    /// Archives the sidecars of the transactions included in the given block.
+    ///
+    /// Replaces previously archived sidecars of the block.
+    pub fn insert_block(
+        &self,
+        block_number: BlockNumber,
+        sidecars: Vec<(TxHash, Arc<BlobTransactionSidecarVariant>)>,
+    ) -> Result<(), BlobArchiveError> {
+        if sidecars.is_empty() {
+            return Ok(())
+        }
+
+        let header = sidecars
+            .iter()
+            .map(|(tx_hash, sidecar)| (*tx_hash, sidecar.versioned_hashes().collect()))
+            .collect::<Vec<_>>();
+
+        let mut body = Vec::new();
+        for (_, sidecar) in &sidecars {
+            let mut buf = Vec::with_capacity(sidecar.rlp_encoded_fields_length());
+            sidecar.rlp_encode_fields(&mut buf);
+            body.extend_from_slice(&(buf.len() as u32).to_be_bytes());
+            body.extend_from_slice(&buf);
+        }
+
+        let mut data = Vec::new();
+        write_header(&mut data, &header);
+        let path = self.inner.block_file(block_number);
+        let compressed = zstd::encode_all(body.as_slice(), COMPRESSION_LEVEL)
+            .map_err(|err| BlobArchiveError::WriteFile(block_number, path.clone(), err))?;
+        data.extend_from_slice(&compressed);
+
+        // write to a temporary file first, so that readers never observe a partially written block
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, &data)
+            .and_then(|_| fs::rename(&tmp_path, &path))
+            .map_err(|err| BlobArchiveError::WriteFile(block_number, path, err))?;
+        trace!(target: "txpool::blob", block_number, txs = sidecars.len(), "Archived blob sidecars");
+
+        self.inner.index.write().insert(block_number, header);
+        Ok(())
+    }
+
+    /// Returns all archived blobs of the given block.
+    pub fn get_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Vec<ArchivedBlob>, BlobStoreError> {
+        if !self.inner.index.read().blocks.contains_key(&block_number) {
+            return Ok(Vec::new())
+        }
+
+        let mut blobs = Vec::new();
+        for (tx_hash, sidecar) in self.inner.read_block(block_number)? {
+            let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+            blobs.extend(
+                archived_blobs(block_number, tx_hash, &sidecar, &versioned_hashes)
+                    .into_iter()
+                    .flatten(),
+            );
+        }
+        Ok(blobs)
+    }
+
+    /// Returns the archived blobs for the given versioned hashes.
+    ///
+    /// The response has the same length as the request, blobs that are not archived are `None`.
+    pub fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<ArchivedBlob>>, BlobStoreError> {
+        let mut blocks = {
+            let index = self.inner.index.read();
+            versioned_hashes
+                .iter()
+                .filter_map(|hash| index.versioned_hashes.get(hash).copied())
+                .collect::<Vec<_>>()
+        };
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        let mut result = vec![None; versioned_hashes.len()];
+        for block_number in blocks {
+            for (tx_hash, sidecar) in self.inner.read_block(block_number)? {
+                for (idx, blob) in archived_blobs(block_number, tx_hash, &sidecar, versioned_hashes)
+                    .into_iter()
+                    .enumerate()
+                {
+                    if blob.is_some() {
+                        result[idx] = blob;
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Removes all archived blocks below the given block number.
+    ///
+    /// Returns the number of removed blocks.
+    pub fn prune_below(&self, block_number: BlockNumber) -> usize {
+        let pruned = {
+            let mut index = self.inner.index.write();
+            let retained = index.blocks.split_off(&block_number);
+            let pruned = std::mem::replace(&mut index.blocks, retained);
+            for versioned_hash in pruned.values().flatten() {
+                index.versioned_hashes.remove(versioned_hash);
+            }
+            pruned
+        };
+
+        for block_number in pruned.keys() {
+            let path = self.inner.block_file(*block_number);
+            if let Err(err) = fs::remove_file(&path) {
+                debug!(target: "txpool::blob", %err, ?path, "Failed to remove archived blob file");
+            }
+        }
+        pruned.len()
+    }
+
+    /// Removes all blocks that are outside of the retention window of the given finalized block.
+    ///
+    /// The retention window consists of the finalized block and the `retention_blocks - 1` blocks
+    /// below it.
+    ///
+    /// Returns the number of removed blocks.
+    pub fn prune(&self, finalized_block: BlockNumber) -> usize {
+        let Some(retention_blocks) = self.inner.config.retention_blocks else { return 0 };
+        self.prune_below(finalized_block.saturating_add(1).saturating_sub(retention_blocks))
+    }
+}
+
+/// Returns the blobs of the sidecar that match the given versioned hashes, in the order of the
+/// versioned hashes.
+fn archived_blobs(
+    block_number: BlockNumber,
+    tx_hash: TxHash,
+    sidecar: &BlobTransactionSidecarVariant,
+    versioned_hashes: &[B256],
+) -> Vec<Option<ArchivedBlob>> {
+    let mut result = vec![None; versioned_hashes.len()];
+    let blob = |idx: usize, blob: Box<Blob>, proofs: Vec<Bytes48>| ArchivedBlob {
+        block_number,
+        tx_hash,
+        versioned_hash: versioned_hashes[idx],
+        blob,
+        proofs,
+    };
+    match sidecar {
+        BlobTransactionSidecarVariant::Eip4844(sidecar) => {
+            for (idx, matched) in sidecar.match_versioned_hashes(versioned_hashes) {
+                result[idx] = Some(blob(idx, matched.blob, vec![matched.proof]));
+            }
+        }
+        BlobTransactionSidecarVariant::Eip7594(sidecar) => {
+            for (idx, matched) in sidecar.match_versioned_hashes(versioned_hashes) {
+                result[idx] = Some(blob(idx, matched.blob, matched.proofs));
+            }
+        }
+    }
+    result
+}
+
+/// The transaction hashes and versioned hashes of an archived block.
+type BlockHeader = Vec<(TxHash, Vec<B256>)>;
+
+/// Writes the uncompressed header of a block file.
+///
+/// The header contains the number of transactions, followed by the hash, the number of blobs and
+/// the versioned hashes of each transaction.
+fn write_header(out: &mut Vec<u8>, header: &BlockHeader) {
+    out.extend_from_slice(&(header.len() as u32).to_be_bytes());
+    for (tx_hash, versioned_hashes) in header {
+        out.extend_from_slice(tx_hash.as_slice());
+        out.extend_from_slice(&(versioned_hashes.len() as u32).to_be_bytes());
+        for versioned_hash in versioned_hashes {
+            out.extend_from_slice(versioned_hash.as_slice());
+        }
+    }
+}
+
+/// Reads the header of a block file.
+fn read_header(reader: &mut impl Read) -> io::Result<BlockHeader> {
+    fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    fn read_hash(reader: &mut impl Read) -> io::Result<B256> {
+        let mut hash = B256::ZERO;
+        reader.read_exact(hash.as_mut_slice())?;
+        Ok(hash)
+    }
+
+    let num_txs = read_u32(reader)?;
+    let mut header = Vec::with_capacity(num_txs as usize);
+    for _ in 0..num_txs {
+        let tx_hash = read_hash(reader)?;
+        let num_blobs = read_u32(reader)?;
+        let versioned_hashes =
+            (0..num_blobs).map(|_| read_hash(reader)).collect::<io::Result<Vec<_>>>()?;
+        header.push((tx_hash, versioned_hashes));
+    }
+    Ok(header)
+}
+
+struct BlobArchiveInner {
+    archive_dir: PathBuf,
+    config: BlobArchiveConfig,
+    index: RwLock<ArchiveIndex>,
+}
+
+impl BlobArchiveInner {
+    /// Returns the path to the file of the given block.
+    #[inline]
+    fn block_file(&self, block_number: BlockNumber) -> PathBuf {
+        self.archive_dir.join(format!("{block_number}.{BLOCK_FILE_EXTENSION}"))
+    }
+
+    /// Reads and decodes all sidecars of the given block.
+    fn read_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Vec<(TxHash, BlobTransactionSidecarVariant)>, BlobStoreError> {
+        let path = self.block_file(block_number);
+        let read_err = |err| BlobArchiveError::ReadFile(block_number, path.clone(), err);
+
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            // the block was pruned concurrently
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(read_err(err).into()),
+        };
+        let mut reader = data.as_slice();
+        let header = read_header(&mut reader).map_err(read_err)?;
+        let body = zstd::decode_all(reader).map_err(read_err)?;
+
+        let mut body = body.as_slice();
+        let mut sidecars = Vec::with_capacity(header.len());
+        for (tx_hash, _) in header {
+            let (len, rest) =
+                body.split_first_chunk::<4>().ok_or(alloy_rlp::Error::InputTooShort)?;
+            let len = u32::from_be_bytes(*len) as usize;
+            if rest.len() < len {
+                return Err(alloy_rlp::Error::InputTooShort.into())
+            }
+            let (mut fields, rest) = rest.split_at(len);
+            sidecars
+                .push((tx_hash, BlobTransactionSidecarVariant::rlp_decode_fields(&mut fields)?));
+            body = rest;
+        }
+        Ok(sidecars)
+    }
+}
+
+impl fmt::Debug for BlobArchiveInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlobArchiveInner")
+            .field("archive_dir", &self.archive_dir)
+            .field("config", &self.config)
+            .field("archived_blocks", &self.index.try_read().map(|index| index.blocks.len()))
+            .finish()
+    }
+}
+
+/// In memory index of the archived blocks.
+#[derive(Debug, Default)]
+struct ArchiveIndex {
+    /// The versioned hashes of the blobs of all archived blocks.
+    blocks: BTreeMap<BlockNumber, Vec<B256>>,
+    /// The block each archived blob is stored in.
+    versioned_hashes: HashMap<B256, BlockNumber>,
+}
+
+impl ArchiveIndex {
+    /// Adds the block with the given header to the index.
+    fn insert(&mut self, block_number: BlockNumber, header: BlockHeader) {
+        let versioned_hashes = header
+            .into_iter()
+            .flat_map(|(_, versioned_hashes)| versioned_hashes)
+            .collect::<Vec<_>>();
+        for versioned_hash in &versioned_hashes {
+            self.versioned_hashes.insert(*versioned_hash, block_number);
+        }
+        self.blocks.insert(block_number, versioned_hashes);
+    }
+}
+
+/// Errors that can occur when interacting with a [`BlobArchive`].
+#[derive(Debug, thiserror::Error)]
+pub enum BlobArchiveError {
+    /// Thrown during [`BlobArchive::open`] if the archive directory cannot be opened.
+    #[error("failed to open blob archive at {0}: {1}")]
+    Open(PathBuf, io::Error),
+    /// Failure while reading the file of an archived block.
+    #[error("[{0}] failed to read archived blob file at {1}: {2}")]
+    ReadFile(BlockNumber, PathBuf, io::Error),
+    /// Failure while writing the file of an archived block.
+    #[error("[{0}] failed to write archived blob file at {1}: {2}")]
+    WriteFile(BlockNumber, PathBuf, io::Error),
+}
+
+impl From<BlobArchiveError> for BlobStoreError {
+    fn from(value: BlobArchiveError) -> Self {
+        Self::Other(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::BlobTransactionSidecar;
+
+    fn rng_sidecar() -> Arc<BlobTransactionSidecarVariant> {
+        Arc::new(BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar {
+            blobs: vec![Blob::random()],
+            commitments: vec![Bytes48::random()],
+            proofs: vec![Bytes48::random()],
+        }))
+    }
+
+    #[test]
+    fn archive_and_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = BlobArchive::open(dir.path(), Default::default()).unwrap();
+
+        let tx_hash = TxHash::random();
+        let sidecar = rng_sidecar();
+        let versioned_hash = sidecar.versioned_hashes().next().unwrap();
+        archive.insert_block(10, vec![(tx_hash, sidecar.clone())]).unwrap();
+
+        let blobs = archive.get_block(10).unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].tx_hash, tx_hash);
+        assert_eq!(blobs[0].versioned_hash, versioned_hash);
+        assert_eq!(blobs[0].blob.as_slice(), sidecar.as_eip4844().unwrap().blobs[0].as_slice());
+        assert!(archive.get_block(11).unwrap().is_empty());
+
+        // the index is restored on open
+        let archive = BlobArchive::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(archive.block_range(), Some((10, 10)));
+        let blobs = archive.get_by_versioned_hashes(&[B256::random(), versioned_hash]).unwrap();
+        assert!(blobs[0].is_none());
+        assert_eq!(blobs[1].as_ref().unwrap().block_number, 10);
+    }
+
+    #[test]
+    fn prune_outside_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive =
+            BlobArchive::open(dir.path(), BlobArchiveConfig { retention_blocks: Some(5) }).unwrap();
+
+        let sidecar = rng_sidecar();
+        let versioned_hash = sidecar.versioned_hashes().next().unwrap();
+        archive.insert_block(1, vec![(TxHash::random(), sidecar)]).unwrap();
+        archive.insert_block(4, vec![(TxHash::random(), rng_sidecar())]).unwrap();
+
+        assert_eq!(archive.prune(5), 0);
+        assert_eq!(archive.prune(6), 1);
+        assert_eq!(archive.block_range(), Some((4, 4)));
+        assert!(archive.get_by_versioned_hashes(&[versioned_hash]).unwrap()[0].is_none());
+        assert!(!dir.path().join("1.blobs").exists());
+    }
+}
@@ -5,6 +5,10 @@ use alloy_eips::{
     eip7594::BlobTransactionSidecarVariant,
 };
 use alloy_primitives::B256;
+pub use archive::{
+    ArchivedBlob, BlobArchive, BlobArchiveConfig, BlobArchiveError,
+    DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS,
+};
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
@@ -17,6 +21,7 @@ use std::{
 };
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
+pub mod archive;
 pub mod disk;
 mod mem;
 mod noop;
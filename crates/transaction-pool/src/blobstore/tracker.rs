@@ -60,20 +60,35 @@ impl BlobStoreCanonTracker {
     ///
     /// This returns all blob transactions that were included in blocks that are now finalized.
     pub fn on_finalized_block(&mut self, finalized_block: BlockNumber) -> BlobStoreUpdates {
+        let finalized = self
+            .take_finalized_blocks(finalized_block)
+            .into_iter()
+            .flat_map(|(_, blob_txs)| blob_txs)
+            .collect::<Vec<_>>();
+
+        if finalized.is_empty() {
+            BlobStoreUpdates::None
+        } else {
+            BlobStoreUpdates::Finalized(finalized)
+        }
+    }
+
+    /// Removes all blocks that are now finalized from the tracker.
+    ///
+    /// This returns the blob transactions of each finalized block, in ascending block order.
+    pub fn take_finalized_blocks(
+        &mut self,
+        finalized_block: BlockNumber,
+    ) -> Vec<(BlockNumber, Vec<B256>)> {
         let mut finalized = Vec::new();
         while let Some(entry) = self.blob_txs_in_blocks.first_entry() {
             if *entry.key() <= finalized_block {
-                finalized.extend(entry.remove_entry().1);
+                finalized.push(entry.remove_entry());
             } else {
                 break
             }
         }
-
-        if finalized.is_empty() {
-            BlobStoreUpdates::None
-        } else {
-            BlobStoreUpdates::Finalized(finalized)
-        }
+        finalized
     }
 }
 
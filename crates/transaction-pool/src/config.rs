@@ -1,5 +1,5 @@
 use crate::{
-    maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
+    maintain::{MAX_PRIVATE_TRANSACTION_BLOCKS, MAX_QUEUED_TRANSACTION_LIFETIME},
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
 };
@@ -63,6 +63,8 @@ pub struct PoolConfig {
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// Number of blocks after which private transactions are removed from the pool
+    pub max_private_tx_blocks: u64,
 }
 
 impl PoolConfig {
@@ -93,6 +95,7 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_private_tx_blocks: MAX_PRIVATE_TRANSACTION_BLOCKS,
         }
     }
 }
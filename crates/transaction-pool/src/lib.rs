@@ -153,7 +153,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 pub use crate::{
-    blobstore::{BlobStore, BlobStoreError},
+    blobstore::{BlobArchive, BlobStore, BlobStoreError},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
@@ -162,9 +162,11 @@ pub use crate::{
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    policy::{DefaultPoolPolicy, PolicyContext, PoolPolicy, RemovalReason},
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        NewTransactionEvent, TransactionEvent, TransactionEvents, TransactionListenerKind,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DiscardReason,
+        FullTransactionEvent, NewTransactionEvent, PoolDiffEvent, PoolDiffEvents, TransactionEvent,
+        TransactionEvents, TransactionListenerKind,
     },
     traits::*,
     validate::{
@@ -193,12 +195,14 @@ pub mod maintain;
 pub mod metrics;
 pub mod noop;
 pub mod pool;
+pub mod simulation;
 pub mod validate;
 
 pub mod blobstore;
 mod config;
 pub mod identifier;
 mod ordering;
+mod policy;
 mod traits;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -232,6 +236,21 @@ where
         Self { pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)) }
     }
 
+    /// Sets the [`PoolPolicy`] the pool consults for admission and eviction decisions.
+    ///
+    /// By default the pool uses the [`DefaultPoolPolicy`].
+    pub fn set_policy(&self, policy: Arc<dyn PoolPolicy<V::Transaction>>) {
+        self.inner().set_policy(policy)
+    }
+
+    /// Sets the [`BlobArchive`] sidecars of finalized blob transactions are moved to.
+    ///
+    /// By default sidecars are deleted once the blocks that include their transactions are
+    /// finalized.
+    pub fn set_blob_archive(&self, archive: BlobArchive) {
+        self.inner().set_blob_archive(archive)
+    }
+
     /// Returns the wrapped pool.
     pub(crate) fn inner(&self) -> &PoolInner<V, T, S> {
         &self.pool
@@ -395,6 +414,10 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn pool_diff_listener(&self) -> PoolDiffEvents {
+        self.pool.add_pool_diff_listener()
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -623,6 +646,10 @@ where
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
         self.pool.blob_store().get_by_versioned_hashes_v2(versioned_hashes)
     }
+
+    fn blob_archive(&self) -> Option<BlobArchive> {
+        self.pool.blob_archive()
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
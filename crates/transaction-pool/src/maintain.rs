@@ -1,16 +1,19 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::{BlobArchive, BlobStoreCanonTracker, BlobStoreUpdates},
     error::PoolError,
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
+    traits::{
+        AllPoolTransactions, CanonicalStateUpdate, EthPoolTransaction, TransactionOrigin,
+        TransactionPool, TransactionPoolExt,
+    },
     BlockInfo, PoolTransaction, PoolUpdateKind,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Address, BlockHash, BlockNumber};
-use alloy_rlp::Encodable;
+use alloy_primitives::{map::HashMap, Address, BlockHash, BlockNumber, TxHash};
+use alloy_rlp::{Decodable, Encodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -32,7 +35,7 @@ use std::{
     sync::Arc,
 };
 use tokio::{
-    sync::oneshot,
+    sync::{mpsc, oneshot},
     time::{self, Duration},
 };
 use tracing::{debug, error, info, trace, warn};
@@ -40,6 +43,9 @@ use tracing::{debug, error, info, trace, warn};
 /// Maximum amount of time non-executable transaction are queued.
 pub const MAX_QUEUED_TRANSACTION_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
 
+/// Maximum number of blocks private transactions are kept in the pool.
+pub const MAX_PRIVATE_TRANSACTION_BLOCKS: u64 = 25;
+
 /// Additional settings for maintaining the transaction pool
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MaintainPoolConfig {
@@ -57,6 +63,10 @@ pub struct MaintainPoolConfig {
     /// Default: 3 hours
     pub max_tx_lifetime: Duration,
 
+    /// Number of blocks after which private transactions that were not included are removed.
+    /// Default: 25
+    pub max_private_tx_blocks: u64,
+
     /// Apply no exemptions to the locally received transactions.
     ///
     /// This includes:
@@ -71,22 +81,37 @@ impl Default for MaintainPoolConfig {
             max_update_depth: 64,
             max_reload_accounts: 100,
             max_tx_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_private_tx_blocks: MAX_PRIVATE_TRANSACTION_BLOCKS,
             no_local_exemptions: false,
         }
     }
 }
 
+/// Number of transactions that are revalidated at once when restoring a pool snapshot.
+pub const POOL_SNAPSHOT_RESTORE_BATCH_SIZE: usize = 1_000;
+
 /// Settings for local transaction backup task
 #[derive(Debug, Clone, Default)]
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Path to the snapshot file of the entire pool, if enabled.
+    ///
+    /// Unlike the local transactions backup, the snapshot includes all transactions of the pool
+    /// and the sidecars of blob transactions.
+    pub pool_snapshot_path: Option<PathBuf>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self { transactions_path: Some(transactions_path), pool_snapshot_path: None }
+    }
+
+    /// Enables the snapshot of the entire pool at the given path.
+    pub fn with_pool_snapshot(mut self, pool_snapshot_path: PathBuf) -> Self {
+        self.pool_snapshot_path = Some(pool_snapshot_path);
+        self
     }
 }
 
@@ -128,7 +153,8 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, max_private_tx_blocks, .. } =
+        config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = SealedHeader::seal_slow(latest);
@@ -149,10 +175,18 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     // keeps track of mined blob transaction so we can clean finalized transactions
     let mut blob_store_tracker = BlobStoreCanonTracker::default();
 
+    // moves the sidecars of finalized blocks into the blob archive, if enabled
+    let blob_archive_tx = pool
+        .blob_archive()
+        .map(|archive| spawn_blob_archive_worker(pool.clone(), archive, &task_spawner));
+
     // keeps track of the latest finalized block
     let mut last_finalized_block =
         FinalizedBlockTracker::new(client.finalized_block_number().ok().flatten());
 
+    // keeps track of private transactions so they can be removed once they expire
+    let mut private_tx_tracker = PrivateTransactionTracker::default();
+
     // keeps track of any dirty accounts that we know of are out of sync with the pool
     let mut dirty_addresses = HashSet::default();
 
@@ -222,7 +256,14 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
-            if let BlobStoreUpdates::Finalized(blobs) =
+            if let Some(blob_archive_tx) = &blob_archive_tx {
+                let blocks = blob_store_tracker.take_finalized_blocks(finalized);
+                metrics.inc_deleted_tracked_blobs(
+                    blocks.iter().map(|(_, blob_txs)| blob_txs.len()).sum(),
+                );
+                // move the sidecars of all finalized blocks into the archive
+                let _ = blob_archive_tx.send((finalized, blocks));
+            } else if let BlobStoreUpdates::Finalized(blobs) =
                 blob_store_tracker.on_finalized_block(finalized)
             {
                 metrics.inc_deleted_tracked_blobs(blobs.len());
@@ -298,6 +339,17 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 
         // handle the new block or reorg
         let Some(event) = event else { continue };
+
+        // remove private transactions that were not included in time
+        let expired = private_tx_tracker.on_new_block(
+            event.tip().number(),
+            pool.get_private_transactions().iter().map(|tx| *tx.hash()),
+            max_private_tx_blocks,
+        );
+        if !expired.is_empty() {
+            debug!(target: "txpool", count=%expired.len(), "removing expired private transactions");
+            pool.remove_transactions(expired);
+        }
         match event {
             CanonStateNotification::Reorg { old, new } => {
                 let (old_blocks, old_state) = old.inner();
@@ -515,6 +567,38 @@ impl FinalizedBlockTracker {
     }
 }
 
+/// Keeps track of the block at which private transactions were first seen in the pool.
+#[derive(Debug, Default)]
+struct PrivateTransactionTracker {
+    first_seen: HashMap<TxHash, BlockNumber>,
+}
+
+impl PrivateTransactionTracker {
+    /// Records the private transactions currently in the pool and returns the ones that have been
+    /// in the pool for at least `max_blocks` blocks.
+    ///
+    /// Transactions that are no longer in the pool are no longer tracked.
+    fn on_new_block(
+        &mut self,
+        block_number: BlockNumber,
+        private_txs: impl IntoIterator<Item = TxHash>,
+        max_blocks: u64,
+    ) -> Vec<TxHash> {
+        let mut first_seen = HashMap::default();
+        let mut expired = Vec::new();
+        for hash in private_txs {
+            let seen = self.first_seen.get(&hash).copied().unwrap_or(block_number);
+            if block_number.saturating_sub(seen) >= max_blocks {
+                expired.push(hash);
+            } else {
+                first_seen.insert(hash, seen);
+            }
+        }
+        self.first_seen = first_seen;
+        expired
+    }
+}
+
 /// Keeps track of the pool's state, whether the accounts in the pool are in sync with the actual
 /// state.
 #[derive(Debug, PartialEq, Eq)]
@@ -597,6 +681,61 @@ where
     Ok(res)
 }
 
+/// Finalized blocks whose blob sidecars should be moved into the [`BlobArchive`], together with
+/// the finalized block number.
+type FinalizedBlobBlocks = (BlockNumber, Vec<(BlockNumber, Vec<TxHash>)>);
+
+/// Spawns the worker that moves the sidecars of finalized blocks into the archive.
+///
+/// All archive updates are processed by this single worker, so that blocks are archived and
+/// pruned in order and slow disk writes never pile up concurrent blocking tasks.
+fn spawn_blob_archive_worker<P, Tasks>(
+    pool: P,
+    archive: BlobArchive,
+    task_spawner: &Tasks,
+) -> mpsc::UnboundedSender<FinalizedBlobBlocks>
+where
+    P: TransactionPoolExt + 'static,
+    Tasks: TaskSpawner,
+{
+    let (tx, mut rx) = mpsc::unbounded_channel::<FinalizedBlobBlocks>();
+    task_spawner.spawn_blocking(Box::pin(async move {
+        while let Some((finalized, blocks)) = rx.recv().await {
+            archive_finalized_blobs(&pool, &archive, blocks);
+            archive.prune(finalized);
+            debug!(target: "txpool", finalized_block = %finalized, "cleaning up blob store");
+            pool.cleanup_blobs();
+        }
+    }));
+    tx
+}
+
+/// Moves the sidecars of the blob transactions included in the given finalized blocks from the
+/// blob store of the pool into the archive.
+///
+/// Sidecars are removed from the blob store even if archiving fails.
+fn archive_finalized_blobs<P>(
+    pool: &P,
+    archive: &BlobArchive,
+    blocks: Vec<(BlockNumber, Vec<TxHash>)>,
+) where
+    P: TransactionPoolExt,
+{
+    for (block_number, blob_txs) in blocks {
+        match pool.get_all_blobs(blob_txs.clone()) {
+            Ok(sidecars) => {
+                if let Err(err) = archive.insert_block(block_number, sidecars) {
+                    warn!(target: "txpool", %err, block_number, "failed to archive blob sidecars");
+                }
+            }
+            Err(err) => {
+                warn!(target: "txpool", %err, block_number, "failed to load blob sidecars for archive")
+            }
+        }
+        pool.delete_blobs(blob_txs);
+    }
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
@@ -668,6 +807,165 @@ where
     }
 }
 
+/// Snapshot of all transactions in the pool, grouped by their origin.
+///
+/// Transactions are stored in their pooled format, so blob transactions include their sidecar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PoolSnapshot<T> {
+    local: Vec<T>,
+    external: Vec<T>,
+    private: Vec<T>,
+}
+
+impl<T> Default for PoolSnapshot<T> {
+    fn default() -> Self {
+        Self { local: Vec::new(), external: Vec::new(), private: Vec::new() }
+    }
+}
+
+impl<T> PoolSnapshot<T> {
+    /// Returns the total number of transactions in the snapshot.
+    fn len(&self) -> usize {
+        self.local.len() + self.external.len() + self.private.len()
+    }
+
+    /// Adds a transaction with the given origin to the snapshot.
+    fn push(&mut self, origin: TransactionOrigin, tx: T) {
+        match origin {
+            TransactionOrigin::Local => self.local.push(tx),
+            TransactionOrigin::External => self.external.push(tx),
+            TransactionOrigin::Private => self.private.push(tx),
+        }
+    }
+}
+
+impl<T: Encodable> Encodable for PoolSnapshot<T> {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        let payload_length = self.local.length() + self.external.length() + self.private.length();
+        alloy_rlp::Header { list: true, payload_length }.encode(out);
+        self.local.encode(out);
+        self.external.encode(out);
+        self.private.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.local.length() + self.external.length() + self.private.length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl<T: Decodable> Decodable for PoolSnapshot<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = alloy_rlp::Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+        let this = Self {
+            local: Decodable::decode(buf)?,
+            external: Decodable::decode(buf)?,
+            private: Decodable::decode(buf)?,
+        };
+        if started_len - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: started_len - buf.len(),
+            })
+        }
+        Ok(this)
+    }
+}
+
+/// Loads the pool snapshot from the given file and revalidates the transactions against the
+/// current state in batches of [`POOL_SNAPSHOT_RESTORE_BATCH_SIZE`].
+///
+/// The file is removed after the transactions have been processed.
+async fn load_and_restore_pool_snapshot<P>(
+    pool: P,
+    file_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Restoring transaction pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+    reth_fs_util::remove_file(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    let snapshot: PoolSnapshot<<P::Transaction as PoolTransaction>::Pooled> =
+        Decodable::decode(&mut data.as_slice())?;
+    let total = snapshot.len();
+
+    let mut restored = 0;
+    for (origin, txs) in [
+        (TransactionOrigin::Local, snapshot.local),
+        (TransactionOrigin::External, snapshot.external),
+        (TransactionOrigin::Private, snapshot.private),
+    ] {
+        let mut txs = txs
+            .into_iter()
+            .filter_map(|tx| tx.try_into_recovered().ok())
+            .map(<P::Transaction as PoolTransaction>::from_pooled)
+            .peekable();
+
+        while txs.peek().is_some() {
+            let batch = txs.by_ref().take(POOL_SNAPSHOT_RESTORE_BATCH_SIZE).collect();
+            restored += pool
+                .add_transactions(origin, batch)
+                .await
+                .into_iter()
+                .filter(|outcome| outcome.is_ok())
+                .count();
+        }
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, total, restored, "Restored transaction pool snapshot");
+    Ok(())
+}
+
+/// Writes all transactions of the pool, including blob sidecars, to the given file.
+fn save_pool_snapshot<P>(pool: P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+
+    let mut snapshot = PoolSnapshot::default();
+    for tx in pending.into_iter().chain(queued) {
+        // this also fetches the sidecar of blob transactions from the blob store
+        if let Some(pooled) = pool.get_pooled_transaction_element(*tx.hash()) {
+            snapshot.push(tx.origin, pooled.into_inner());
+        }
+    }
+
+    let num_txs = snapshot.len();
+    if num_txs == 0 {
+        trace!(target: "txpool", "no transactions to snapshot");
+        return
+    }
+
+    let mut buf = Vec::new();
+    snapshot.encode(&mut buf);
+    info!(target: "txpool", snapshot_file=?file_path, num_txs, "Saving transaction pool snapshot");
+    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
+
+    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+        Ok(_) => {
+            info!(target: "txpool", snapshot_file=?file_path, "Wrote transaction pool snapshot to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file=?file_path, "Failed to write transaction pool snapshot to file");
+        }
+    }
+}
+
 /// Errors possible during txs backup load and decode
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionsBackupError {
@@ -684,6 +982,9 @@ pub enum TransactionsBackupError {
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
+///
+/// If a [pool snapshot](LocalTransactionBackupConfig::pool_snapshot_path) is configured, all
+/// transactions of the pool are saved and restored as well.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -691,19 +992,33 @@ pub async fn backup_local_transactions_task<P>(
 ) where
     P: TransactionPool<Transaction: PoolTransaction<Consensus: SignedTransaction>> + Clone,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let LocalTransactionBackupConfig { transactions_path, pool_snapshot_path } = config;
+    if transactions_path.is_none() && pool_snapshot_path.is_none() {
         // nothing to do
         return
-    };
+    }
 
-    if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
-        error!(target: "txpool", "{}", err)
+    if let Some(transactions_path) = &transactions_path {
+        if let Err(err) = load_and_reinsert_transactions(pool.clone(), transactions_path).await {
+            error!(target: "txpool", "{}", err)
+        }
+    }
+
+    if let Some(pool_snapshot_path) = &pool_snapshot_path {
+        if let Err(err) = load_and_restore_pool_snapshot(pool.clone(), pool_snapshot_path).await {
+            error!(target: "txpool", "{}", err)
+        }
     }
 
     let graceful_guard = shutdown.await;
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    if let Some(transactions_path) = &transactions_path {
+        save_local_txs_backup(pool.clone(), transactions_path);
+    }
+    if let Some(pool_snapshot_path) = &pool_snapshot_path {
+        save_pool_snapshot(pool, pool_snapshot_path);
+    }
 
     drop(graceful_guard)
 }
@@ -722,6 +1037,20 @@ mod tests {
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
+    #[test]
+    fn private_tx_expiry() {
+        let mut tracker = PrivateTransactionTracker::default();
+        let (a, b) = (TxHash::with_last_byte(1), TxHash::with_last_byte(2));
+
+        assert!(tracker.on_new_block(10, [a], 2).is_empty());
+        assert!(tracker.on_new_block(11, [a, b], 2).is_empty());
+        assert_eq!(tracker.on_new_block(12, [a, b], 2), vec![a]);
+        assert_eq!(tracker.on_new_block(13, [b], 2), vec![b]);
+
+        // transactions that left the pool are no longer tracked
+        assert!(tracker.on_new_block(14, [a], 2).is_empty());
+    }
+
     #[test]
     fn changed_acc_entry() {
         let changed_acc = ChangedAccountEntry(ChangedAccount::empty(Address::random()));
@@ -782,6 +1111,45 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pool_snapshot_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("test_pool_snapshot").with_extension(EXTENSION);
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let provider = MockEthProvider::default();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+
+        let new_pool = || {
+            let blob_store = InMemoryBlobStore::default();
+            let validator =
+                EthTransactionValidatorBuilder::new(provider.clone()).build(blob_store.clone());
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+        };
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        save_pool_snapshot(txpool, &snapshot_path);
+
+        let data = fs::read(&snapshot_path).unwrap();
+        let snapshot: PoolSnapshot<PooledTransactionVariant> =
+            Decodable::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(snapshot.external.len(), 1);
+        assert!(snapshot.local.is_empty());
+
+        let restored = new_pool();
+        load_and_restore_pool_snapshot(restored.clone(), &snapshot_path).await.unwrap();
+        let tx = restored.get(transaction.hash()).expect("transaction should be restored");
+        assert!(tx.origin.is_external());
+        assert!(!snapshot_path.exists());
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -60,6 +60,11 @@ pub struct TxPoolMetrics {
     pub(crate) blob_transactions_evicted: Counter,
     /// Counter for the number of queued transactions evicted
     pub(crate) queued_transactions_evicted: Counter,
+
+    /// Number of transactions rejected by the pool policy
+    pub(crate) policy_rejected_transactions: Counter,
+    /// Counter for the number of transactions evicted by the pool policy
+    pub(crate) policy_transactions_evicted: Counter,
 }
 
 /// Transaction pool blobstore metrics
@@ -6,7 +6,7 @@
 use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolError},
-    pool::TransactionListenerKind,
+    pool::{PoolDiffEvents, TransactionListenerKind},
     traits::{BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar},
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPoolTransaction,
@@ -106,6 +106,10 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
         AllTransactionsEvents::new(mpsc::channel(1).1)
     }
 
+    fn pool_diff_listener(&self) -> PoolDiffEvents {
+        PoolDiffEvents::new(mpsc::channel(1).1)
+    }
+
     fn pending_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
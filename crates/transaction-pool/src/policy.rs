@@ -0,0 +1,123 @@
+//! Admission and eviction policies for the transaction pool.
+//!
+//! The [`TransactionOrdering`](crate::TransactionOrdering) determines the priority of
+//! transactions, the [`PoolPolicy`] determines which transactions are allowed to enter the pool
+//! and which transactions should be removed from it in addition to the transactions evicted to
+//! enforce the configured sub-pool limits.
+
+use crate::{
+    error::InvalidPoolTransactionError, BlockInfo, PoolSize, PoolTransaction, SubPool,
+    SubPoolLimit, ValidPoolTransaction,
+};
+use alloy_primitives::TxHash;
+use std::{fmt, sync::Arc};
+
+/// Context that is passed to the [`PoolPolicy`] when the pool consults it.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyContext {
+    /// The block the pool is currently tracking.
+    pub block_info: BlockInfo,
+    /// The current size of the pool.
+    pub pool_size: PoolSize,
+}
+
+/// Why a transaction was removed from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalReason {
+    /// The transaction was replaced by a transaction with the same nonce and a higher fee.
+    Replaced,
+    /// The transaction was evicted, either to enforce the sub-pool limits or because the
+    /// [`PoolPolicy`] requested it.
+    Evicted,
+}
+
+/// Policy the pool consults for admission and eviction decisions.
+///
+/// This is used alongside the [`TransactionOrdering`](crate::TransactionOrdering) of the pool and
+/// allows operators to enforce custom rules, for example per origin quotas, penalties for senders
+/// whose transactions are frequently replaced or evicted, dynamic tip floors or blocklists.
+///
+/// All functions are called while the pool is locked and should return quickly.
+///
+/// The default implementations of all functions match the built-in behaviour of the pool, see
+/// [`DefaultPoolPolicy`].
+pub trait PoolPolicy<T: PoolTransaction>: fmt::Debug + Send + Sync + 'static {
+    /// Decides whether the validated transaction may be added to the pool.
+    ///
+    /// `sender_transactions` is the number of transactions of the sender that are currently in
+    /// the pool. Note that the configured `max_account_slots` are still enforced by the pool.
+    ///
+    /// Policy specific errors can be returned via [`InvalidPoolTransactionError::other`].
+    fn admit(
+        &self,
+        transaction: &ValidPoolTransaction<T>,
+        sender_transactions: usize,
+        ctx: &PolicyContext,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let _ = (transaction, sender_transactions, ctx);
+        Ok(())
+    }
+
+    /// Returns the limit that should be enforced for the given sub-pool.
+    ///
+    /// `limit` is the limit configured in the [`PoolConfig`](crate::PoolConfig).
+    fn subpool_limit(&self, subpool: SubPool, limit: &SubPoolLimit) -> SubPoolLimit {
+        let _ = subpool;
+        *limit
+    }
+
+    /// Returns the hashes of additional transactions that should be evicted from the pool.
+    ///
+    /// This is called after the sub-pool limits were enforced, with all transactions that remain
+    /// in the pool. Descendants of the returned transactions are evicted as well.
+    fn evict(
+        &self,
+        transactions: &mut dyn Iterator<Item = &Arc<ValidPoolTransaction<T>>>,
+        ctx: &PolicyContext,
+    ) -> Vec<TxHash> {
+        let _ = (transactions, ctx);
+        Vec::new()
+    }
+
+    /// Invoked when a transaction was removed from the pool for the given reason.
+    fn on_removed(&self, transaction: &ValidPoolTransaction<T>, reason: RemovalReason) {
+        let _ = (transaction, reason);
+    }
+}
+
+impl<T: PoolTransaction, P: PoolPolicy<T>> PoolPolicy<T> for Arc<P> {
+    fn admit(
+        &self,
+        transaction: &ValidPoolTransaction<T>,
+        sender_transactions: usize,
+        ctx: &PolicyContext,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        (**self).admit(transaction, sender_transactions, ctx)
+    }
+
+    fn subpool_limit(&self, subpool: SubPool, limit: &SubPoolLimit) -> SubPoolLimit {
+        (**self).subpool_limit(subpool, limit)
+    }
+
+    fn evict(
+        &self,
+        transactions: &mut dyn Iterator<Item = &Arc<ValidPoolTransaction<T>>>,
+        ctx: &PolicyContext,
+    ) -> Vec<TxHash> {
+        (**self).evict(transactions, ctx)
+    }
+
+    fn on_removed(&self, transaction: &ValidPoolTransaction<T>, reason: RemovalReason) {
+        (**self).on_removed(transaction, reason)
+    }
+}
+
+/// The default [`PoolPolicy`].
+///
+/// Admits all transactions, enforces the configured sub-pool limits and does not evict any
+/// additional transactions.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct DefaultPoolPolicy;
+
+impl<T: PoolTransaction> PoolPolicy<T> for DefaultPoolPolicy {}
@@ -54,6 +54,61 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
     }
 }
 
+/// Why a transaction was discarded from the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiscardReason {
+    /// The transaction was evicted to enforce the configured limits, or by the
+    /// [`PoolPolicy`](crate::PoolPolicy).
+    Evicted,
+    /// The transaction can no longer be executed on top of the current state, e.g. because its
+    /// nonce is too low or the sender can no longer cover its cost.
+    Outdated,
+    /// The transaction was explicitly removed from the pool.
+    Removed,
+}
+
+/// A change of the sub-pool or the status of a transaction in the pool.
+///
+/// Unlike [`FullTransactionEvent`] this also reports which sub-pool a transaction was moved to and
+/// why it was discarded, see
+/// [`TransactionPool::pool_diff_listener`](crate::TransactionPool::pool_diff_listener).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PoolDiffEvent {
+    /// Transaction has been added to the given sub-pool, or was moved there from another one.
+    SubPool {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+        /// The sub-pool the transaction is now held in.
+        subpool: SubPool,
+    },
+    /// Transaction has been replaced by the transaction belonging to the hash.
+    Replaced {
+        /// The hash of the replaced transaction.
+        tx_hash: TxHash,
+        /// The transaction that replaced the event subject.
+        replaced_by: TxHash,
+    },
+    /// Transaction has been included in the block belonging to this hash.
+    Mined {
+        /// The hash of the mined transaction.
+        tx_hash: TxHash,
+        /// The hash of the mined block that contains the transaction.
+        block_hash: B256,
+    },
+    /// Transaction was dropped from the pool for the given reason.
+    Discarded {
+        /// The hash of the discarded transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
+    /// The listener did not keep up and events were dropped since the last event.
+    ///
+    /// The listener should resync with the current content of the pool.
+    Lagged,
+}
+
 /// Various events that describe status changes of a transaction.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
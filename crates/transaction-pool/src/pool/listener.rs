@@ -1,9 +1,11 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent},
+    pool::events::{
+        DiscardReason, FullTransactionEvent, NewTransactionEvent, PoolDiffEvent, TransactionEvent,
+    },
     traits::{NewBlobSidecar, PropagateKind},
-    PoolTransaction, ValidPoolTransaction,
+    PoolTransaction, SubPool, ValidPoolTransaction,
 };
 use alloy_primitives::{TxHash, B256};
 use futures_util::Stream;
@@ -68,6 +70,28 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
     }
 }
 
+/// A Stream that receives [`PoolDiffEvent`] for _all_ transactions.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PoolDiffEvents {
+    pub(crate) events: Receiver<PoolDiffEvent>,
+}
+
+impl PoolDiffEvents {
+    /// Create a new instance of this stream.
+    pub const fn new(events: Receiver<PoolDiffEvent>) -> Self {
+        Self { events }
+    }
+}
+
+impl Stream for PoolDiffEvents {
+    type Item = PoolDiffEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().events.poll_recv(cx)
+    }
+}
+
 /// A type that broadcasts [`TransactionEvent`] to installed listeners.
 ///
 /// This is essentially a multi-producer, multi-consumer channel where each event is broadcast to
@@ -76,6 +100,8 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
 pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     /// All listeners for all transaction events.
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
+    /// All listeners for the sub-pool changes of all transactions.
+    diff_broadcaster: PoolDiffBroadcaster,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
 }
@@ -84,6 +110,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
     fn default() -> Self {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
+            diff_broadcaster: PoolDiffBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
         }
     }
@@ -96,6 +123,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         hash: &TxHash,
         event: TransactionEvent,
         pool_event: FullTransactionEvent<T>,
+        diff: Option<PoolDiffEvent>,
     ) {
         // Broadcast to all listeners for the transaction hash.
         if let Entry::Occupied(mut sink) = self.broadcasters_by_hash.entry(*hash) {
@@ -108,6 +136,10 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
         // Broadcast to all listeners for all transactions.
         self.all_events_broadcaster.broadcast(pool_event);
+
+        if let Some(diff) = diff {
+            self.diff_broadcaster.broadcast(diff);
+        }
     }
 
     /// Create a new subscription for the given transaction hash.
@@ -132,9 +164,27 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         AllTransactionsEvents::new(rx)
     }
 
+    /// Create a new subscription for the sub-pool changes of all transactions.
+    pub(crate) fn subscribe_diffs(&mut self) -> PoolDiffEvents {
+        self.subscribe_diffs_with_capacity(TX_POOL_EVENT_CHANNEL_SIZE)
+    }
+
+    /// Returns a new listener for the sub-pool changes of all transactions that buffers up to
+    /// `capacity` events.
+    pub(crate) fn subscribe_diffs_with_capacity(&mut self, capacity: usize) -> PoolDiffEvents {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        self.diff_broadcaster.listeners.push(PoolDiffListener { sender: tx, lagged: false });
+        PoolDiffEvents::new(rx)
+    }
+
     /// Notify listeners about a transaction that was added to the pending queue.
     pub(crate) fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
-        self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Pending,
+            FullTransactionEvent::Pending(*tx),
+            Some(PoolDiffEvent::SubPool { tx_hash: *tx, subpool: SubPool::Pending }),
+        );
 
         if let Some(replaced) = replaced {
             // notify listeners that this transaction was replaced
@@ -149,12 +199,18 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             tx.hash(),
             TransactionEvent::Replaced(replaced_by),
             FullTransactionEvent::Replaced { transaction, replaced_by },
+            Some(PoolDiffEvent::Replaced { tx_hash: *tx.hash(), replaced_by }),
         );
     }
 
-    /// Notify listeners about a transaction that was added to the queued pool.
-    pub(crate) fn queued(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Queued, FullTransactionEvent::Queued(*tx));
+    /// Notify listeners about a transaction that was added to the given parked pool.
+    pub(crate) fn queued(&mut self, tx: &TxHash, subpool: SubPool) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Queued,
+            FullTransactionEvent::Queued(*tx),
+            Some(PoolDiffEvent::SubPool { tx_hash: *tx, subpool }),
+        );
     }
 
     /// Notify listeners about a transaction that was propagated.
@@ -164,17 +220,39 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             tx,
             TransactionEvent::Propagated(Arc::clone(&peers)),
             FullTransactionEvent::Propagated(peers),
+            None,
         );
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded,
+            FullTransactionEvent::Discarded(*tx),
+            Some(PoolDiffEvent::Discarded { tx_hash: *tx, reason }),
+        );
+    }
+
+    /// Notify listeners about a transaction that could not be validated and never entered the
+    /// pool.
+    pub(crate) fn validation_failed(&mut self, tx: &TxHash) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded,
+            FullTransactionEvent::Discarded(*tx),
+            None,
+        );
     }
 
     /// Notify listeners about a transaction that was invalid.
     pub(crate) fn invalid(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Invalid,
+            FullTransactionEvent::Invalid(*tx),
+            None,
+        );
     }
 
     /// Notify listeners that the transaction was mined
@@ -183,6 +261,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             tx,
             TransactionEvent::Mined(block_hash),
             FullTransactionEvent::Mined { tx_hash: *tx, block_hash },
+            Some(PoolDiffEvent::Mined { tx_hash: *tx, block_hash }),
         );
     }
 }
@@ -212,6 +291,52 @@ impl<T: PoolTransaction> AllPoolEventsBroadcaster<T> {
     }
 }
 
+/// All listeners for the sub-pool changes of all transactions.
+#[derive(Default, Debug)]
+struct PoolDiffBroadcaster {
+    /// Corresponding listener(s) for the event channel
+    listeners: Vec<PoolDiffListener>,
+}
+
+impl PoolDiffBroadcaster {
+    // Broadcast an event to all listeners. Dropped listeners are silently evicted.
+    fn broadcast(&mut self, event: PoolDiffEvent) {
+        self.listeners.retain_mut(|listener| listener.send(event))
+    }
+}
+
+/// An active listener for the sub-pool changes of all transactions.
+#[derive(Debug)]
+struct PoolDiffListener {
+    sender: Sender<PoolDiffEvent>,
+    /// Whether events were dropped because the channel was full.
+    lagged: bool,
+}
+
+impl PoolDiffListener {
+    /// Sends the event to the listener, preceded by [`PoolDiffEvent::Lagged`] if events were
+    /// dropped since the last send.
+    ///
+    /// Returns `false` if the listener was dropped.
+    fn send(&mut self, event: PoolDiffEvent) -> bool {
+        if self.lagged {
+            match self.sender.try_send(PoolDiffEvent::Lagged) {
+                Ok(()) => self.lagged = false,
+                Err(TrySendError::Full(_)) => return true,
+                Err(TrySendError::Closed(_)) => return false,
+            }
+        }
+        match self.sender.try_send(event) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.lagged = true;
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
 /// All Sender half(s) of the event channels for a specific transaction.
 ///
 /// This mimics [`tokio::sync::broadcast`] but uses separate channels and is unbounded.
@@ -337,3 +462,38 @@ impl TransactionListenerKind {
         matches!(self, Self::PropagateOnly)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn diff_listener_reports_lag() {
+        let mut broadcast = PoolEventBroadcast::<MockTransaction>::default();
+        let mut diffs = broadcast.subscribe_diffs_with_capacity(2);
+        let (tx_0, tx_1) = (TxHash::random(), TxHash::random());
+
+        broadcast.queued(&tx_0, SubPool::Queued);
+        broadcast.pending(&tx_0, None);
+        // the channel is full, so this event is dropped
+        broadcast.discarded(&tx_0, DiscardReason::Evicted);
+        assert_eq!(
+            diffs.events.try_recv(),
+            Ok(PoolDiffEvent::SubPool { tx_hash: tx_0, subpool: SubPool::Queued })
+        );
+        assert_eq!(
+            diffs.events.try_recv(),
+            Ok(PoolDiffEvent::SubPool { tx_hash: tx_0, subpool: SubPool::Pending })
+        );
+        assert!(diffs.events.try_recv().is_err());
+
+        // the listener is told about the dropped event before it receives the next one
+        broadcast.mined(&tx_1, B256::ZERO);
+        assert_eq!(diffs.events.try_recv(), Ok(PoolDiffEvent::Lagged));
+        assert_eq!(
+            diffs.events.try_recv(),
+            Ok(PoolDiffEvent::Mined { tx_hash: tx_1, block_hash: B256::ZERO })
+        );
+    }
+}
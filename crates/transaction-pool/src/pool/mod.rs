@@ -66,7 +66,7 @@
 //!    category (2.) and become pending.
 
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobArchive, BlobStore},
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
@@ -84,7 +84,7 @@ use crate::{
         NewBlobSidecar, PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction, ValidTransaction},
-    CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionOrdering,
+    CanonicalStateUpdate, EthPoolTransaction, PoolConfig, PoolPolicy, TransactionOrdering,
     TransactionValidator,
 };
 
@@ -103,8 +103,12 @@ use tracing::{debug, trace, warn};
 mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
-pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
+pub use events::{
+    DiscardReason, FullTransactionEvent, NewTransactionEvent, PoolDiffEvent, TransactionEvent,
+};
+pub use listener::{
+    AllTransactionsEvents, PoolDiffEvents, TransactionEvents, TransactionListenerKind,
+};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 use reth_primitives_traits::Block;
@@ -151,6 +155,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Archive for sidecars of finalized blob transactions, if enabled.
+    blob_archive: RwLock<Option<BlobArchive>>,
 }
 
 // === impl PoolInner ===
@@ -174,9 +180,25 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            blob_archive: Default::default(),
         }
     }
 
+    /// Sets the policy the pool consults for admission and eviction decisions.
+    pub fn set_policy(&self, policy: Arc<dyn PoolPolicy<T::Transaction>>) {
+        self.pool.write().set_policy(policy)
+    }
+
+    /// Sets the archive sidecars of finalized blob transactions are moved to.
+    pub fn set_blob_archive(&self, archive: BlobArchive) {
+        *self.blob_archive.write() = Some(archive);
+    }
+
+    /// Returns the blob archive, if enabled.
+    pub fn blob_archive(&self) -> Option<BlobArchive> {
+        self.blob_archive.read().clone()
+    }
+
     /// Returns the configured blob store.
     pub const fn blob_store(&self) -> &S {
         &self.blob_store
@@ -278,6 +300,17 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a listener for the sub-pool changes of all transactions.
+    pub fn add_pool_diff_listener(&self) -> PoolDiffEvents {
+        self.event_listener.write().subscribe_diffs()
+    }
+
+    /// Adds a listener for the sub-pool changes of all transactions that can buffer the given
+    /// number of events.
+    pub(crate) fn add_pool_diff_listener_with_capacity(&self, capacity: usize) -> PoolDiffEvents {
+        self.event_listener.write().subscribe_diffs_with_capacity(capacity)
+    }
+
     /// Returns a read lock to the pool's data.
     pub fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
         self.pool.read()
@@ -353,7 +386,8 @@ where
         let transactions = self.get_all(tx_hashes);
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
-        for transaction in transactions {
+        // transactions that must not be propagated, e.g. private transactions, are never served
+        for transaction in transactions.into_iter().filter(|tx| tx.propagate) {
             let encoded_len = transaction.encoded_length();
             let Some(pooled) = self.to_pooled_transaction(transaction) else {
                 continue;
@@ -416,15 +450,18 @@ where
     /// This will either promote or discard transactions based on the new account state.
     pub fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
+        let UpdateOutcome { promoted, parked, discarded } =
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
         for tx in &promoted {
             listener.pending(tx.hash(), None);
         }
+        for (tx, subpool) in &parked {
+            listener.queued(tx, *subpool);
+        }
         for tx in &discarded {
-            listener.discarded(tx.hash());
+            listener.discarded(tx.hash(), DiscardReason::Outdated);
         }
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
@@ -516,7 +553,7 @@ where
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.validation_failed(&tx_hash);
                 Err(PoolError::other(tx_hash, err))
             }
         }
@@ -575,7 +612,7 @@ where
             {
                 let mut listener = self.event_listener.write();
                 for hash in &discarded_hashes {
-                    listener.discarded(hash);
+                    listener.discarded(hash, DiscardReason::Evicted);
                 }
             }
 
@@ -666,7 +703,7 @@ where
             listener.send_all(outcome.full_pending_transactions(listener.kind))
         });
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome { mined, promoted, parked, discarded, block_hash } = outcome;
 
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
@@ -677,8 +714,11 @@ where
         for tx in &promoted {
             listener.pending(tx.hash(), None);
         }
+        for (tx, subpool) in &parked {
+            listener.queued(tx, *subpool);
+        }
         for tx in &discarded {
-            listener.discarded(tx.hash());
+            listener.discarded(tx.hash(), DiscardReason::Outdated);
         }
     }
 
@@ -688,21 +728,28 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
+                let AddedPendingTransaction { transaction, promoted, parked, discarded, replaced } =
+                    tx;
 
                 listener.pending(transaction.hash(), replaced.clone());
                 for tx in promoted {
                     listener.pending(tx.hash(), None);
                 }
+                for (tx, subpool) in parked {
+                    listener.queued(tx, *subpool);
+                }
                 for tx in discarded {
-                    listener.discarded(tx.hash());
+                    listener.discarded(tx.hash(), DiscardReason::Outdated);
                 }
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
-                listener.queued(transaction.hash());
+            AddedTransaction::Parked { transaction, replaced, subpool, parked } => {
+                listener.queued(transaction.hash(), *subpool);
                 if let Some(replaced) = replaced {
                     listener.replaced(replaced.clone(), *transaction.hash());
                 }
+                for (tx, subpool) in parked {
+                    listener.queued(tx, *subpool);
+                }
             }
         }
     }
@@ -765,7 +812,7 @@ where
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
-            listener.discarded(tx.hash());
+            listener.discarded(tx.hash(), DiscardReason::Removed);
         }
 
         removed
@@ -785,7 +832,7 @@ where
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
-            listener.discarded(tx.hash());
+            listener.discarded(tx.hash(), DiscardReason::Removed);
         }
 
         removed
@@ -802,7 +849,7 @@ where
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
-            listener.discarded(tx.hash());
+            listener.discarded(tx.hash(), DiscardReason::Removed);
         }
 
         removed
@@ -1008,6 +1055,8 @@ pub struct AddedPendingTransaction<T: PoolTransaction> {
     replaced: Option<Arc<ValidPoolTransaction<T>>>,
     /// transactions promoted to the pending queue
     promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved to a parked pool, and the pool they were moved to
+    parked: Vec<(TxHash, SubPool)>,
     /// transactions that failed and became discarded
     discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
@@ -1096,6 +1145,9 @@ pub enum AddedTransaction<T: PoolTransaction> {
         replaced: Option<Arc<ValidPoolTransaction<T>>>,
         /// The subpool it was moved to.
         subpool: SubPool,
+        /// Other transactions of the sender that were moved to a parked pool, and the pool they
+        /// were moved to.
+        parked: Vec<(TxHash, SubPool)>,
     },
 }
 
@@ -1177,6 +1229,8 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) mined: Vec<TxHash>,
     /// Transactions promoted to the pending pool.
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions moved to a parked pool.
+    pub(crate) parked: Vec<(TxHash, SubPool)>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
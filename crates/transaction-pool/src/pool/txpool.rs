@@ -8,6 +8,7 @@ use crate::{
     },
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
+    policy::{DefaultPoolPolicy, PolicyContext, PoolPolicy, RemovalReason},
     pool::{
         best::BestTransactions,
         blob::BlobTransactions,
@@ -118,6 +119,8 @@ pub struct TxPool<T: TransactionOrdering> {
     metrics: TxPoolMetrics,
     /// The last update kind that was applied to the pool.
     latest_update_kind: Option<PoolUpdateKind>,
+    /// The policy that is consulted for admission and eviction decisions.
+    policy: Arc<dyn PoolPolicy<T::Transaction>>,
 }
 
 // === impl TxPool ===
@@ -138,9 +141,25 @@ impl<T: TransactionOrdering> TxPool<T> {
             config,
             metrics: Default::default(),
             latest_update_kind: None,
+            policy: Arc::new(DefaultPoolPolicy::default()),
         }
     }
 
+    /// Sets the policy that is consulted for admission and eviction decisions.
+    pub fn set_policy(&mut self, policy: Arc<dyn PoolPolicy<T::Transaction>>) {
+        self.policy = policy;
+    }
+
+    /// Returns the policy that is consulted for admission and eviction decisions.
+    pub fn policy(&self) -> &Arc<dyn PoolPolicy<T::Transaction>> {
+        &self.policy
+    }
+
+    /// Returns the context that is passed to the policy.
+    fn policy_context(&self) -> PolicyContext {
+        PolicyContext { block_info: self.block_info(), pool_size: self.size() }
+    }
+
     /// Retrieves the highest nonce for a specific sender from the transaction pool.
     pub fn get_highest_nonce_by_sender(&self, sender: SenderId) -> Option<u64> {
         self.all().txs_iter(sender).last().map(|(_, tx)| tx.transaction.nonce())
@@ -568,7 +587,7 @@ impl<T: TransactionOrdering> TxPool<T> {
         // Update removed transactions metric
         self.metrics.removed_transactions.increment(removed_txs_count);
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, parked, discarded } = self.update_accounts(changed_senders);
 
         self.update_transaction_type_metrics();
         self.metrics.performed_state_updates.increment(1);
@@ -576,7 +595,13 @@ impl<T: TransactionOrdering> TxPool<T> {
         // Update the latest update kind
         self.latest_update_kind = Some(update_kind);
 
-        OnNewCanonicalStateOutcome { block_hash, mined: mined_transactions, promoted, discarded }
+        OnNewCanonicalStateOutcome {
+            block_hash,
+            mined: mined_transactions,
+            promoted,
+            parked,
+            discarded,
+        }
     }
 
     /// Update sub-pools size metrics.
@@ -655,6 +680,12 @@ impl<T: TransactionOrdering> TxPool<T> {
             return Err(PoolError::new(*tx.hash(), PoolErrorKind::AlreadyImported))
         }
 
+        let sender_transactions = self.all_transactions.tx_count(tx.sender_id());
+        if let Err(err) = self.policy.admit(&tx, sender_transactions, &self.policy_context()) {
+            self.metrics.policy_rejected_transactions.increment(1);
+            return Err(PoolError::new(*tx.hash(), PoolErrorKind::InvalidTransaction(err)))
+        }
+
         self.validate_auth(&tx, on_chain_nonce, on_chain_code_hash)?;
 
         // Update sender info with balance and nonce
@@ -669,20 +700,24 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, parked, discarded } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
+                if let Some(replaced) = &replaced {
+                    self.policy.on_removed(replaced, RemovalReason::Replaced);
+                }
 
                 // This transaction was moved to the pending pool.
                 let res = if move_to.is_pending() {
                     AddedTransaction::Pending(AddedPendingTransaction {
                         transaction,
                         promoted,
+                        parked,
                         discarded,
                         replaced,
                     })
                 } else {
-                    AddedTransaction::Parked { transaction, subpool: move_to, replaced }
+                    AddedTransaction::Parked { transaction, subpool: move_to, replaced, parked }
                 };
 
                 // Update size metrics after adding and potentially moving transactions.
@@ -849,6 +884,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                             trace!(target: "txpool", hash=%tx.transaction.hash(), "Promoted transaction to pending");
                             outcome.promoted.push(tx);
                         }
+                    } else if moved.is_some() {
+                        outcome.parked.push((hash, move_to));
                     }
                 }
             }
@@ -1052,7 +1089,8 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Ensures that the transactions in the sub-pools are within the given bounds.
     ///
     /// If the current size exceeds the given bounds, the worst transactions are evicted from the
-    /// pool and returned.
+    /// pool and returned. The bounds are determined by the pool's [`PoolPolicy`], which can also
+    /// request additional evictions.
     ///
     /// This returns all transactions that were removed from the entire pool.
     pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
@@ -1060,28 +1098,29 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident, [$($limit:ident => ($pool:ident, $metric:ident)),* $(,)*]) => {
+            ($this:ident, $removed:ident, [$($limit:ident => ($subpool:expr, $pool:ident, $metric:ident)),* $(,)*]) => {
                 $ (
-                while $this.$pool.exceeds(&$this.config.$limit)
+                let limit = $this.policy.subpool_limit($subpool, &$this.config.$limit);
+                while $this.$pool.exceeds(&limit)
                     {
                         trace!(
                             target: "txpool",
                             "discarding transactions from {}, limit: {:?}, curr size: {}, curr len: {}",
                             stringify!($pool),
-                            $this.config.$limit,
+                            limit,
                             $this.$pool.size(),
                             $this.$pool.len(),
                         );
 
                         // 1. first remove the worst transaction from the subpool
-                        let removed_from_subpool = $this.$pool.truncate_pool($this.config.$limit.clone());
+                        let removed_from_subpool = $this.$pool.truncate_pool(limit.clone());
 
                         trace!(
                             target: "txpool",
                             "removed {} transactions from {}, limit: {:?}, curr size: {}, curr len: {}",
                             removed_from_subpool.len(),
                             stringify!($pool),
-                            $this.config.$limit,
+                            limit,
                             $this.$pool.size(),
                             $this.$pool.len()
                         );
@@ -1107,13 +1146,28 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit => (pending_pool, pending_transactions_evicted),
-                basefee_limit => (basefee_pool, basefee_transactions_evicted),
-                blob_limit    => (blob_pool, blob_transactions_evicted),
-                queued_limit  => (queued_pool, queued_transactions_evicted),
+                pending_limit => (SubPool::Pending, pending_pool, pending_transactions_evicted),
+                basefee_limit => (SubPool::BaseFee, basefee_pool, basefee_transactions_evicted),
+                blob_limit    => (SubPool::Blob, blob_pool, blob_transactions_evicted),
+                queued_limit  => (SubPool::Queued, queued_pool, queued_transactions_evicted),
             ]
         );
 
+        // evict any additional transactions requested by the policy
+        let ctx = self.policy_context();
+        let evict = self
+            .policy
+            .evict(&mut self.all_transactions.txs.values().map(|tx| &tx.transaction), &ctx);
+        if !evict.is_empty() {
+            let evicted = self.remove_transactions_and_descendants(evict);
+            self.metrics.policy_transactions_evicted.increment(evicted.len() as u64);
+            removed.extend(evicted);
+        }
+
+        for tx in &removed {
+            self.policy.on_removed(tx, RemovalReason::Evicted);
+        }
+
         removed
     }
 
@@ -1184,7 +1238,10 @@ impl<T: TransactionOrdering> TxPool<T> {
 
 impl<T: TransactionOrdering> fmt::Debug for TxPool<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("TxPool").field("config", &self.config).finish_non_exhaustive()
+        f.debug_struct("TxPool")
+            .field("config", &self.config)
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
     }
 }
 
@@ -1259,6 +1316,13 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.txs.get(id)
     }
 
+    /// This function retrieves the number of transactions stored in the pool for a specific sender.
+    ///
+    /// If there are no transactions for the given sender, it returns zero by default.
+    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
+        self.tx_counter.get(&sender).copied().unwrap_or_default()
+    }
+
     /// Increments the transaction counter for the sender
     pub(crate) fn tx_inc(&mut self, sender: SenderId) {
         let count = self.tx_counter.entry(sender).or_default();
@@ -1982,16 +2046,6 @@ impl<T: PoolTransaction> AllTransactions<T> {
     }
 }
 
-#[cfg(test)]
-impl<T: PoolTransaction> AllTransactions<T> {
-    /// This function retrieves the number of transactions stored in the pool for a specific sender.
-    ///
-    /// If there are no transactions for the given sender, it returns zero by default.
-    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
-        self.tx_counter.get(&sender).copied().unwrap_or_default()
-    }
-}
-
 impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
@@ -2126,6 +2180,7 @@ impl SenderInfo {
 mod tests {
     use super::*;
     use crate::{
+        error::PoolTransactionError,
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
         SubPoolLimit,
@@ -3171,6 +3226,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discard_with_policy() {
+        #[derive(Debug, thiserror::Error)]
+        #[error("sender is blocked")]
+        struct BlockedSender;
+
+        impl PoolTransactionError for BlockedSender {
+            fn is_bad_transaction(&self) -> bool {
+                false
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        #[derive(Debug, Default)]
+        struct TestPolicy {
+            blocked: Address,
+            evicted: Address,
+            removed: parking_lot::Mutex<Vec<(TxHash, RemovalReason)>>,
+        }
+
+        impl PoolPolicy<MockTransaction> for TestPolicy {
+            fn admit(
+                &self,
+                transaction: &ValidPoolTransaction<MockTransaction>,
+                _sender_transactions: usize,
+                _ctx: &PolicyContext,
+            ) -> Result<(), InvalidPoolTransactionError> {
+                if transaction.sender() == self.blocked {
+                    return Err(InvalidPoolTransactionError::other(BlockedSender))
+                }
+                Ok(())
+            }
+
+            fn subpool_limit(&self, subpool: SubPool, limit: &SubPoolLimit) -> SubPoolLimit {
+                if subpool.is_queued() {
+                    return SubPoolLimit::new(1, usize::MAX)
+                }
+                *limit
+            }
+
+            fn evict(
+                &self,
+                transactions: &mut dyn Iterator<Item = &Arc<ValidPoolTransaction<MockTransaction>>>,
+                _ctx: &PolicyContext,
+            ) -> Vec<TxHash> {
+                transactions.filter(|tx| tx.sender() == self.evicted).map(|tx| *tx.hash()).collect()
+            }
+
+            fn on_removed(
+                &self,
+                transaction: &ValidPoolTransaction<MockTransaction>,
+                reason: RemovalReason,
+            ) {
+                self.removed.lock().push((*transaction.hash(), reason));
+            }
+        }
+
+        let policy = Arc::new(TestPolicy {
+            blocked: address!("0x000000000000000000000000000000000000dead"),
+            evicted: address!("0x000000000000000000000000000000000000beef"),
+            ..Default::default()
+        });
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        pool.set_policy(policy.clone());
+
+        // transactions of blocked senders are rejected
+        let blocked = f.validated(MockTransaction::eip1559().with_sender(policy.blocked));
+        let err = pool.add_transaction(blocked, U256::from(1_000), 0, None).unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::InvalidTransaction(_)));
+
+        // the policy limits the queued pool to a single transaction
+        for _ in 0..2 {
+            let tx = f.validated(MockTransaction::eip1559().inc_nonce());
+            pool.add_transaction(tx, U256::from(1_000), 0, None).unwrap();
+        }
+        assert_eq!(pool.size().queued, 2);
+
+        // transactions of evicted senders are removed together with their descendants
+        let tx = MockTransaction::eip1559().with_sender(policy.evicted);
+        let next = tx.next();
+        for tx in [tx, next] {
+            pool.add_transaction(f.validated(tx), U256::MAX, 0, None).unwrap();
+        }
+        assert_eq!(pool.size().total, 4);
+
+        let removed = pool.discard_worst();
+        pool.assert_invariants();
+        assert_eq!(removed.len(), 3);
+        assert_eq!(pool.size().queued, 1);
+        assert_eq!(pool.size().total, 1);
+
+        let notified = policy.removed.lock().clone();
+        assert_eq!(notified.len(), 3);
+        assert!(notified.iter().all(|(_, reason)| *reason == RemovalReason::Evicted));
+    }
+
     #[test]
     fn account_updates_sender_balance() {
         let mut on_chain_balance = U256::from(100);
@@ -3203,17 +3358,56 @@ mod tests {
             v0.sender_id(),
             SenderInfo { state_nonce: on_chain_nonce, balance: on_chain_balance },
         );
-        pool.update_accounts(updated_accounts.clone());
+        let outcome = pool.update_accounts(updated_accounts.clone());
 
         assert_eq!(3, pool.pending_transactions().len());
         assert!(pool.queued_transactions().is_empty());
+        assert_eq!(outcome.promoted.len(), 2);
+        assert!(outcome.parked.is_empty());
 
         // Simulate new block arrival - and chain balance decrease.
         updated_accounts.entry(v0.sender_id()).and_modify(|v| v.balance = U256::from(1));
-        pool.update_accounts(updated_accounts);
+        let outcome = pool.update_accounts(updated_accounts);
 
         assert!(pool.pending_transactions().is_empty());
         assert_eq!(3, pool.queued_transactions().len());
+        assert_eq!(outcome.parked.len(), 3);
+        assert!(outcome.parked.iter().all(|(_, subpool)| *subpool == SubPool::Queued));
+    }
+
+    #[test]
+    fn replacement_reports_parked_descendants() {
+        let on_chain_balance = U256::from(300);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx_0 = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+        let tx_1 = tx_0.next();
+        let tx_2 = tx_1.next();
+
+        for tx in [tx_0.clone(), tx_1.clone(), tx_2.clone()] {
+            pool.add_transaction(f.validated(tx), on_chain_balance, on_chain_nonce, None).unwrap();
+        }
+        assert_eq!(3, pool.pending_transactions().len());
+
+        // The replacement costs more than the balance, which parks it and all its descendants.
+        let replacement =
+            f.validated(tx_0.clone().rng_hash().inc_price_by(10).with_value(U256::from(250)));
+        let added =
+            pool.add_transaction(replacement, on_chain_balance, on_chain_nonce, None).unwrap();
+
+        assert!(pool.pending_transactions().is_empty());
+        assert_eq!(3, pool.queued_transactions().len());
+        let AddedTransaction::Parked { subpool, replaced, parked, .. } = added else {
+            panic!("expected the replacement to be parked")
+        };
+        assert_eq!(subpool, SubPool::Queued);
+        assert_eq!(replaced.map(|tx| *tx.hash()), Some(*tx_0.get_hash()));
+        assert_eq!(
+            parked,
+            vec![(*tx_1.get_hash(), SubPool::Queued), (*tx_2.get_hash(), SubPool::Queued)]
+        );
     }
 
     #[test]
@@ -41,12 +41,14 @@ impl From<SubPool> for Destination {
 pub(crate) struct UpdateOutcome<T: PoolTransaction> {
     /// transactions promoted to the pending pool
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions moved to a parked pool, and the pool they were moved to
+    pub(crate) parked: Vec<(TxHash, SubPool)>,
     /// transaction that failed and were discarded
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
 }
 
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], parked: vec![], discarded: vec![] }
     }
 }
@@ -0,0 +1,375 @@
+//! Replays a recorded mempool against the pool to measure its behaviour offline.
+//!
+//! The [`PoolSimulation`] feeds a recorded stream of transactions and canonical state changes
+//! through [`TransactionPool::add_transactions`] and
+//! [`TransactionPoolExt::on_canonical_state_change`] and reports how the pool behaved, see
+//! [`SimulationReport`]. This allows tuning the [`PoolConfig`](crate::PoolConfig) against
+//! production traffic.
+//!
+//! Recorded transactions are usually EIP-2718 encoded pooled transactions, see
+//! [`decode_raw_transaction`].
+
+use crate::{
+    blobstore::BlobStore,
+    error::{InvalidPoolTransactionError, PoolErrorKind},
+    pool::{DiscardReason, PoolDiffEvent, PoolDiffEvents},
+    traits::{BestTransactions, BestTransactionsAttributes, CanonicalStateUpdate, PoolUpdateKind},
+    EthPoolTransaction, Pool, PoolSize, PoolTransaction, TransactionOrdering, TransactionOrigin,
+    TransactionPool, TransactionPoolExt, TransactionValidator,
+};
+use alloy_eips::eip2718::{Decodable2718, Eip2718Error};
+use alloy_primitives::{TxHash, B256};
+use reth_execution_types::ChangedAccount;
+use reth_primitives_traits::{Block, SealedBlock, SignedTransaction};
+use std::{collections::HashMap, time::Duration};
+
+/// Capacity of the event listener of the simulation.
+///
+/// Events are drained after every replayed event, so this only needs to cover the events of a
+/// single batch of transactions or a single state change.
+const SIMULATION_EVENT_CHANNEL_SIZE: usize = 1 << 20;
+
+/// Decodes an EIP-2718 encoded pooled transaction and recovers its signer.
+pub fn decode_raw_transaction<T: PoolTransaction>(
+    mut raw: &[u8],
+) -> Result<T, RawTransactionError> {
+    let pooled = T::Pooled::decode_2718(&mut raw)?;
+    let recovered =
+        pooled.try_into_recovered().map_err(|_| RawTransactionError::InvalidSignature)?;
+    Ok(T::from_pooled(recovered))
+}
+
+/// Errors that can occur when decoding a recorded transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum RawTransactionError {
+    /// The transaction is not a valid EIP-2718 encoded pooled transaction.
+    #[error(transparent)]
+    Decode(#[from] Eip2718Error),
+    /// The signer of the transaction could not be recovered.
+    #[error("invalid transaction signature")]
+    InvalidSignature,
+}
+
+/// An event of a recorded mempool.
+#[derive(Debug, Clone)]
+pub struct RecordedEvent<T, B: Block> {
+    /// When the event happened, relative to the start of the recording.
+    pub timestamp: Duration,
+    /// What happened.
+    pub event: SimulationEvent<T, B>,
+}
+
+/// An event that is replayed by the [`PoolSimulation`].
+#[derive(Debug, Clone)]
+pub enum SimulationEvent<T, B: Block> {
+    /// Transactions were received.
+    Transactions(TransactionOrigin, Vec<T>),
+    /// A new block was added to the canonical chain.
+    CanonicalStateChange(SimulatedStateChange<B>),
+}
+
+/// An owned [`CanonicalStateUpdate`] of a recorded mempool.
+#[derive(Debug, Clone)]
+pub struct SimulatedStateChange<B: Block> {
+    /// The new tip of the chain.
+    pub new_tip: SealedBlock<B>,
+    /// EIP-1559 base fee of the _next_ (pending) block.
+    pub pending_block_base_fee: u64,
+    /// EIP-4844 blob fee of the _next_ (pending) block.
+    pub pending_block_blob_fee: Option<u128>,
+    /// Accounts that changed in the block.
+    pub changed_accounts: Vec<ChangedAccount>,
+    /// Transactions that were mined in the block.
+    pub mined_transactions: Vec<B256>,
+    /// Whether the block was committed or is the tip of a reorg.
+    pub update_kind: PoolUpdateKind,
+}
+
+impl<B: Block> SimulatedStateChange<B> {
+    /// Returns the [`CanonicalStateUpdate`] of this state change.
+    pub fn as_update(&self) -> CanonicalStateUpdate<'_, B> {
+        CanonicalStateUpdate {
+            new_tip: &self.new_tip,
+            pending_block_base_fee: self.pending_block_base_fee,
+            pending_block_blob_fee: self.pending_block_blob_fee,
+            changed_accounts: self.changed_accounts.clone(),
+            mined_transactions: self.mined_transactions.clone(),
+            update_kind: self.update_kind,
+        }
+    }
+}
+
+/// The block the pool would have built right before a canonical state change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SimulatedBlockValue {
+    /// The number of the block that was built.
+    pub number: u64,
+    /// Number of transactions in the block.
+    pub transactions: usize,
+    /// Accumulated gas limit of all transactions in the block.
+    pub gas: u64,
+    /// Accumulated priority fees of all transactions in the block.
+    ///
+    /// Transactions are not executed, so this assumes that all transactions use their entire gas
+    /// limit.
+    pub value: u128,
+}
+
+/// What happened during a [`PoolSimulation`].
+#[derive(Debug, Clone, Default)]
+pub struct SimulationReport {
+    /// Number of transactions that were replayed.
+    pub transactions: usize,
+    /// Number of transactions that were added to the pool.
+    pub accepted: usize,
+    /// Number of transactions that were rejected by the pool.
+    pub rejected: usize,
+    /// Number of canonical state changes that were replayed.
+    pub blocks: usize,
+    /// Number of times a parked transaction was moved to the pending sub-pool.
+    pub promoted: usize,
+    /// Number of transactions that were evicted from the pool.
+    pub evicted: usize,
+    /// Number of transactions that were replaced by a transaction with a higher fee.
+    pub replaced: usize,
+    /// Number of transactions that were mined.
+    pub mined: usize,
+    /// Number of transactions that were discarded because they could no longer be executed.
+    pub outdated: usize,
+    /// Number of transactions that were rejected as invalid.
+    pub invalid: usize,
+    /// How long parked transactions waited until they were promoted, in recorded time.
+    pub promotion_latencies: Vec<Duration>,
+    /// The size of the pool after the last replayed event.
+    pub pool_size: PoolSize,
+    /// The largest size of all transactions in the pool, in bytes.
+    pub peak_pool_size_bytes: usize,
+    /// The largest size of the blob store, in bytes, if the blob store reports its size.
+    pub peak_blob_store_bytes: Option<usize>,
+    /// The block the pool would have built before each canonical state change.
+    pub block_values: Vec<SimulatedBlockValue>,
+}
+
+impl SimulationReport {
+    /// Returns the given percentile of the promotion latencies.
+    ///
+    /// `percentile` is clamped to `0.0..=1.0`.
+    pub fn promotion_latency(&self, percentile: f64) -> Option<Duration> {
+        if self.promotion_latencies.is_empty() {
+            return None
+        }
+        let mut latencies = self.promotion_latencies.clone();
+        latencies.sort_unstable();
+        let idx = ((latencies.len() - 1) as f64 * percentile.clamp(0.0, 1.0)).round() as usize;
+        Some(latencies[idx])
+    }
+
+    /// Returns the accumulated value of all blocks the pool would have built.
+    pub fn total_block_value(&self) -> u128 {
+        self.block_values.iter().map(|block| block.value).sum()
+    }
+}
+
+/// Replays a recorded mempool through a [`Pool`].
+///
+/// The pool should be created with the [`PoolConfig`](crate::PoolConfig) under test and a
+/// validator that matches the recording, e.g. a validator that accepts all recorded
+/// transactions if the state at the time of the recording is not available.
+///
+/// Events are replayed as fast as possible, durations in the [`SimulationReport`] are derived from
+/// the timestamps of the [`RecordedEvent`]s.
+#[derive(Debug)]
+pub struct PoolSimulation<V, T, S>
+where
+    T: TransactionOrdering,
+{
+    /// The pool under test.
+    pool: Pool<V, T, S>,
+    /// Listener for the sub-pool changes of all transactions.
+    events: PoolDiffEvents,
+    /// Since when transactions are parked, keyed by hash.
+    parked_since: HashMap<TxHash, Duration>,
+    /// The timestamp of the current event.
+    now: Duration,
+    /// What happened so far.
+    report: SimulationReport,
+}
+
+impl<V, T, S> PoolSimulation<V, T, S>
+where
+    V: TransactionValidator<Transaction: EthPoolTransaction>,
+    T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
+{
+    /// Creates a new simulation for the given pool.
+    pub fn new(pool: Pool<V, T, S>) -> Self {
+        let events =
+            pool.inner().add_pool_diff_listener_with_capacity(SIMULATION_EVENT_CHANNEL_SIZE);
+        Self {
+            pool,
+            events,
+            parked_since: Default::default(),
+            now: Duration::ZERO,
+            report: Default::default(),
+        }
+    }
+
+    /// Returns the pool under test.
+    pub const fn pool(&self) -> &Pool<V, T, S> {
+        &self.pool
+    }
+
+    /// Returns what happened so far.
+    pub const fn report(&self) -> &SimulationReport {
+        &self.report
+    }
+
+    /// Replays all events and returns the report.
+    pub async fn run<B: Block>(
+        mut self,
+        events: impl IntoIterator<Item = RecordedEvent<V::Transaction, B>>,
+    ) -> SimulationReport {
+        for event in events {
+            self.replay(event).await;
+        }
+        self.finish()
+    }
+
+    /// Replays a single event.
+    pub async fn replay<B: Block>(&mut self, event: RecordedEvent<V::Transaction, B>) {
+        let RecordedEvent { timestamp, event } = event;
+        match event {
+            SimulationEvent::Transactions(origin, transactions) => {
+                self.add_transactions(timestamp, origin, transactions).await
+            }
+            SimulationEvent::CanonicalStateChange(change) => {
+                self.on_canonical_state_change(timestamp, change.as_update())
+            }
+        }
+    }
+
+    /// Adds the transactions that were received at the given time.
+    pub async fn add_transactions(
+        &mut self,
+        timestamp: Duration,
+        origin: TransactionOrigin,
+        transactions: Vec<V::Transaction>,
+    ) {
+        self.now = self.now.max(timestamp);
+        self.report.transactions += transactions.len();
+        for result in self.pool.add_transactions(origin, transactions).await {
+            match result {
+                Ok(_) => self.report.accepted += 1,
+                Err(err) => {
+                    self.report.rejected += 1;
+                    if matches!(err.kind, PoolErrorKind::InvalidTransaction(_)) {
+                        self.report.invalid += 1;
+                    }
+                }
+            }
+        }
+        self.on_step();
+    }
+
+    /// Applies the canonical state change that happened at the given time.
+    ///
+    /// Before the update is applied, the block the pool would have built at this point is recorded
+    /// in [`SimulationReport::block_values`].
+    pub fn on_canonical_state_change<B: Block>(
+        &mut self,
+        timestamp: Duration,
+        update: CanonicalStateUpdate<'_, B>,
+    ) {
+        self.now = self.now.max(timestamp);
+        let block_value = self.build_block(update.number());
+        self.report.block_values.push(block_value);
+        self.report.blocks += 1;
+        self.pool.on_canonical_state_change(update);
+        self.on_step();
+    }
+
+    /// Consumes the simulation and returns the report.
+    pub fn finish(mut self) -> SimulationReport {
+        self.on_step();
+        self.report
+    }
+
+    /// Selects the best transactions of the pool for a block with the current block info, the
+    /// same way a payload builder would.
+    fn build_block(&self, number: u64) -> SimulatedBlockValue {
+        let block_info = self.pool.block_info();
+        let base_fee = block_info.pending_basefee;
+        let mut best =
+            self.pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
+                base_fee,
+                block_info.pending_blob_fee.map(|fee| fee as u64),
+            ));
+
+        let mut block = SimulatedBlockValue { number, ..Default::default() };
+        while let Some(tx) = best.next() {
+            let gas_limit = tx.gas_limit();
+            let remaining = block_info.block_gas_limit.saturating_sub(block.gas);
+            if gas_limit > remaining {
+                best.mark_invalid(
+                    &tx,
+                    InvalidPoolTransactionError::ExceedsGasLimit(gas_limit, remaining),
+                );
+                continue
+            }
+            block.transactions += 1;
+            block.gas += gas_limit;
+            block.value +=
+                tx.effective_tip_per_gas(base_fee).unwrap_or_default() * gas_limit as u128;
+        }
+        block
+    }
+
+    /// Processes all events that were emitted by the pool and records its size.
+    fn on_step(&mut self) {
+        while let Ok(event) = self.events.events.try_recv() {
+            self.on_event(event);
+        }
+
+        let size = self.pool.pool_size();
+        let bytes = size.pending_size + size.basefee_size + size.queued_size + size.blob_size;
+        self.report.peak_pool_size_bytes = self.report.peak_pool_size_bytes.max(bytes);
+        if let Some(blob_store_bytes) = self.pool.blob_store().data_size_hint() {
+            self.report.peak_blob_store_bytes =
+                Some(self.report.peak_blob_store_bytes.unwrap_or_default().max(blob_store_bytes));
+        }
+        self.report.pool_size = size;
+    }
+
+    fn on_event(&mut self, event: PoolDiffEvent) {
+        match event {
+            PoolDiffEvent::SubPool { tx_hash, subpool } if subpool.is_pending() => {
+                if let Some(parked_since) = self.parked_since.remove(&tx_hash) {
+                    self.report.promoted += 1;
+                    self.report.promotion_latencies.push(self.now.saturating_sub(parked_since));
+                }
+            }
+            PoolDiffEvent::SubPool { tx_hash, .. } => {
+                // keep the original timestamp if the transaction moved between parked sub-pools
+                self.parked_since.entry(tx_hash).or_insert(self.now);
+            }
+            PoolDiffEvent::Mined { tx_hash, .. } => {
+                self.report.mined += 1;
+                self.parked_since.remove(&tx_hash);
+            }
+            PoolDiffEvent::Replaced { tx_hash, .. } => {
+                self.report.replaced += 1;
+                self.parked_since.remove(&tx_hash);
+            }
+            PoolDiffEvent::Discarded { tx_hash, reason } => {
+                match reason {
+                    DiscardReason::Evicted => self.report.evicted += 1,
+                    DiscardReason::Outdated => self.report.outdated += 1,
+                    DiscardReason::Removed => {}
+                }
+                self.parked_since.remove(&tx_hash);
+            }
+            // the listener is sized to hold all events of a single step
+            PoolDiffEvent::Lagged => {}
+        }
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobArchive, BlobStoreError},
     error::{InvalidPoolTransactionError, PoolResult},
     pool::{
-        state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
-        TransactionListenerKind,
+        state::SubPool, BestTransactionFilter, NewTransactionEvent, PoolDiffEvents,
+        TransactionEvents, TransactionListenerKind,
     },
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
@@ -132,6 +132,12 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns a new stream that yields the sub-pool changes of _all_ transactions in the pool.
+    ///
+    /// Unlike [`Self::all_transactions_event_listener`] this reports which sub-pool a transaction
+    /// was moved to and why it was discarded.
+    fn pool_diff_listener(&self) -> PoolDiffEvents;
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -232,7 +238,8 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// [`GetPooledTransactions`](https://github.com/ethereum/devp2p/blob/master/caps/eth.md#getpooledtransactions-0x09):
     ///
     /// The transactions must be in same order as in the request, but it is OK to skip transactions
-    /// which are not available.
+    /// which are not available. Transactions that are not allowed to be propagated, such as
+    /// [`TransactionOrigin::Private`] transactions, are skipped as well.
     ///
     /// If the transaction is a blob transaction, the sidecar will be included.
     ///
@@ -506,6 +513,12 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError>;
+
+    /// Returns the [`BlobArchive`] sidecars of finalized blob transactions are moved to, if
+    /// enabled.
+    fn blob_archive(&self) -> Option<BlobArchive> {
+        None
+    }
 }
 
 /// Extension for [`TransactionPool`] trait that allows to set the current block info.
@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    DiscardReason, FullTransactionEvent, GetPooledTransactionLimit, PoolDiffEvent, PoolTransaction,
+    SubPool, TransactionEvent, TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -127,6 +127,43 @@ async fn txpool_listener_all() {
     assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Discarded(hash)) if hash == *transaction.transaction.get_hash());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_diff_listener() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let tx_0 = mock_tx_factory.create_eip1559().transaction;
+    let tx_1 = tx_0.next();
+
+    let mut diffs = txpool.pool_diff_listener();
+
+    // nonce gap
+    txpool.add_transaction(TransactionOrigin::External, tx_1.clone()).await.unwrap();
+    assert_eq!(
+        diffs.next().await,
+        Some(PoolDiffEvent::SubPool { tx_hash: *tx_1.get_hash(), subpool: SubPool::Queued })
+    );
+
+    // closes the nonce gap and promotes the queued transaction
+    txpool.add_transaction(TransactionOrigin::External, tx_0.clone()).await.unwrap();
+    assert_eq!(
+        diffs.next().await,
+        Some(PoolDiffEvent::SubPool { tx_hash: *tx_0.get_hash(), subpool: SubPool::Pending })
+    );
+    assert_eq!(
+        diffs.next().await,
+        Some(PoolDiffEvent::SubPool { tx_hash: *tx_1.get_hash(), subpool: SubPool::Pending })
+    );
+
+    txpool.remove_transactions(vec![*tx_1.get_hash()]);
+    assert_eq!(
+        diffs.next().await,
+        Some(PoolDiffEvent::Discarded {
+            tx_hash: *tx_1.get_hash(),
+            reason: DiscardReason::Removed
+        })
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_propagate_only() {
     let txpool =
@@ -151,6 +188,36 @@ async fn txpool_listener_propagate_only() {
     .await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_private_transactions_not_propagated() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let expected = *transaction.hash();
+    let mut listener_network = txpool.pending_transactions_listener();
+    let mut listener_all = txpool.pending_transactions_listener_for(TransactionListenerKind::All);
+    let result =
+        txpool.add_transaction(TransactionOrigin::Private, transaction.transaction.clone()).await;
+    assert!(result.is_ok());
+
+    let inserted = listener_all.recv().await.unwrap();
+    assert_eq!(inserted, expected);
+
+    poll_fn(|cx| {
+        // no propagation
+        assert!(listener_network.poll_recv(cx).is_pending());
+        Poll::Ready(())
+    })
+    .await;
+
+    // private transactions are not served to peers
+    assert!(txpool.pooled_transactions().is_empty());
+    assert!(txpool
+        .get_pooled_transaction_elements(vec![expected], GetPooledTransactionLimit::None)
+        .is_empty());
+    assert_eq!(txpool.get_private_transactions().len(), 1);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_new_propagate_only() {
     let txpool =
@@ -8,6 +8,8 @@ mod evict;
 mod listeners;
 #[cfg(feature = "test-utils")]
 mod pending;
+#[cfg(feature = "test-utils")]
+mod simulation;
 
 mod best;
 
@@ -0,0 +1,68 @@
+//! Replaying a recorded mempool with the [`PoolSimulation`].
+
+use alloy_primitives::U256;
+use reth_ethereum_primitives::Block;
+use reth_execution_types::ChangedAccount;
+use reth_primitives_traits::SealedBlock;
+use reth_transaction_pool::{
+    simulation::{PoolSimulation, RecordedEvent, SimulatedStateChange, SimulationEvent},
+    test_utils::{testing_pool, MockTransaction},
+    PoolTransaction, PoolUpdateKind, TransactionOrigin,
+};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn replay_promotion_and_mined_block() {
+    let simulation = PoolSimulation::new(testing_pool());
+
+    let tx0 = MockTransaction::eip1559().with_gas_limit(21_000).with_size(100);
+    let tx1 = tx0.next();
+    let sender = tx0.sender();
+
+    let events = vec![
+        // nonce gap, the transaction is parked
+        RecordedEvent {
+            timestamp: Duration::ZERO,
+            event: SimulationEvent::Transactions(TransactionOrigin::External, vec![tx1.clone()]),
+        },
+        // closes the gap and promotes the parked transaction
+        RecordedEvent {
+            timestamp: Duration::from_secs(2),
+            event: SimulationEvent::Transactions(TransactionOrigin::External, vec![tx0.clone()]),
+        },
+        RecordedEvent {
+            timestamp: Duration::from_secs(12),
+            event: SimulationEvent::CanonicalStateChange(SimulatedStateChange {
+                new_tip: SealedBlock::seal_slow(Block::default()),
+                pending_block_base_fee: 0,
+                pending_block_blob_fee: None,
+                changed_accounts: vec![ChangedAccount {
+                    address: sender,
+                    nonce: 2,
+                    balance: U256::MAX,
+                }],
+                mined_transactions: vec![*tx0.hash(), *tx1.hash()],
+                update_kind: PoolUpdateKind::Commit,
+            }),
+        },
+    ];
+
+    let report = simulation.run(events).await;
+
+    assert_eq!(report.transactions, 2);
+    assert_eq!(report.accepted, 2);
+    assert_eq!(report.rejected, 0);
+    assert_eq!(report.blocks, 1);
+    assert_eq!(report.promoted, 1);
+    assert_eq!(report.promotion_latency(0.5), Some(Duration::from_secs(2)));
+    assert_eq!(report.mined, 2);
+    assert_eq!(report.evicted, 0);
+    assert_eq!(report.pool_size.total, 0);
+    assert!(report.peak_pool_size_bytes > 0);
+
+    // both transactions would have been included in the block built before the state change
+    assert_eq!(report.block_values.len(), 1);
+    assert_eq!(report.block_values[0].transactions, 2);
+    assert_eq!(report.block_values[0].gas, 42_000);
+    assert!(report.total_block_value() > 0);
+}
@@ -0,0 +1,116 @@
+use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use alloy_primitives::{B256, U256};
+use reth_db_api::{transaction::DbTx, DatabaseError};
+use reth_execution_errors::StateRootError;
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    HashedPostState, StorageRoot, TrieAccount,
+};
+
+#[cfg(feature = "metrics")]
+use reth_trie::metrics::TrieRootMetrics;
+
+/// Extends [`HashedPostState`] with range queries over the hashed state in the database.
+pub trait DatabaseHashedStateRange<TX>: Sized {
+    /// Returns up to `limit` hashed accounts starting at the given hashed address, with this
+    /// state applied on top of the database.
+    ///
+    /// The storage root of every returned account is computed on top of the overlay. Up to
+    /// `storage_limit` non-zero storage slots of every account are returned as well.
+    #[expect(clippy::type_complexity)]
+    fn account_range(
+        &self,
+        tx: &TX,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> Result<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>, StateRootError>;
+
+    /// Returns up to `limit` non-zero storage slots of the given hashed address starting at the
+    /// given hashed slot, with this state applied on top of the database.
+    fn storage_range(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<Vec<(B256, U256)>, DatabaseError>;
+}
+
+impl<TX: DbTx> DatabaseHashedStateRange<TX> for HashedPostState {
+    fn account_range(
+        &self,
+        tx: &TX,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> Result<Vec<(B256, TrieAccount, Vec<(B256, U256)>)>, StateRootError> {
+        let mut prefix_sets = self.construct_prefix_sets().freeze();
+        let state_sorted = self.clone().into_sorted();
+        let hashed_cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted);
+
+        let mut accounts = Vec::with_capacity(limit.min(256));
+        let mut cursor = hashed_cursor_factory.hashed_account_cursor()?;
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if accounts.len() >= limit {
+                break
+            }
+
+            let storage_root = StorageRoot::new_hashed(
+                DatabaseTrieCursorFactory::new(tx),
+                hashed_cursor_factory.clone(),
+                hashed_address,
+                prefix_sets.storage_prefix_sets.remove(&hashed_address).unwrap_or_default(),
+                #[cfg(feature = "metrics")]
+                TrieRootMetrics::new(reth_trie::TrieType::Storage),
+            )
+            .root()?;
+            let storage =
+                collect_storage(&hashed_cursor_factory, hashed_address, B256::ZERO, storage_limit)?;
+            accounts.push((hashed_address, account.into_trie_account(storage_root), storage));
+
+            entry = cursor.next()?;
+        }
+
+        Ok(accounts)
+    }
+
+    fn storage_range(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<Vec<(B256, U256)>, DatabaseError> {
+        let state_sorted = self.clone().into_sorted();
+        let hashed_cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted);
+
+        collect_storage(&hashed_cursor_factory, hashed_address, start, limit)
+    }
+}
+
+/// Collects up to `limit` storage slots of the given hashed address starting at `start`.
+fn collect_storage<F: HashedCursorFactory>(
+    hashed_cursor_factory: &F,
+    hashed_address: B256,
+    start: B256,
+    limit: usize,
+) -> Result<Vec<(B256, U256)>, DatabaseError> {
+    let mut slots = Vec::new();
+    if limit == 0 {
+        return Ok(slots)
+    }
+    let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+    let mut entry = cursor.seek(start)?;
+    while let Some((hashed_slot, value)) = entry {
+        if slots.len() >= limit {
+            break
+        }
+        slots.push((hashed_slot, value));
+        entry = cursor.next()?;
+    }
+    Ok(slots)
+}
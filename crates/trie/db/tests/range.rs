@@ -0,0 +1,101 @@
+#![allow(missing_docs)]
+
+use alloy_primitives::{B256, U256};
+use reth_db::{tables, test_utils::create_test_rw_db};
+use reth_db_api::{database::Database, transaction::DbTxMut};
+use reth_primitives_traits::{Account, StorageEntry};
+use reth_trie::{root::storage_root_unsorted, HashedPostState, HashedStorage, EMPTY_ROOT_HASH};
+use reth_trie_db::DatabaseHashedStateRange;
+
+#[test]
+fn account_range_with_overlay() {
+    let account = |nonce| Account { nonce, ..Default::default() };
+
+    let db = create_test_rw_db();
+    db.update(|tx| {
+        for key in 1..6 {
+            tx.put::<tables::HashedAccounts>(B256::with_last_byte(key), account(key as u64))
+                .unwrap();
+        }
+        tx.put::<tables::HashedStorages>(
+            B256::with_last_byte(1),
+            StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) },
+        )
+        .unwrap();
+    })
+    .unwrap();
+
+    // destroy the second account, update the third one and add storage to the first one
+    let mut state = HashedPostState::default();
+    state.accounts.insert(B256::with_last_byte(2), None);
+    state.accounts.insert(B256::with_last_byte(3), Some(account(30)));
+    state.storages.insert(
+        B256::with_last_byte(1),
+        HashedStorage::from_iter(false, [(B256::with_last_byte(2), U256::from(2))]),
+    );
+
+    let tx = db.tx().unwrap();
+    let accounts = state.account_range(&tx, B256::ZERO, 3, 10).unwrap();
+    assert_eq!(
+        accounts.iter().map(|(key, account, _)| (*key, account.nonce)).collect::<Vec<_>>(),
+        vec![
+            (B256::with_last_byte(1), 1),
+            (B256::with_last_byte(3), 30),
+            (B256::with_last_byte(4), 4)
+        ]
+    );
+
+    let expected_storage =
+        vec![(B256::with_last_byte(1), U256::from(1)), (B256::with_last_byte(2), U256::from(2))];
+    assert_eq!(accounts[0].1.storage_root, storage_root_unsorted(expected_storage.clone()));
+    assert_eq!(accounts[0].2, expected_storage);
+    assert_eq!(accounts[1].1.storage_root, EMPTY_ROOT_HASH);
+    assert!(accounts[1].2.is_empty());
+
+    // the storage limit only truncates the returned slots, not the storage root
+    let accounts = state.account_range(&tx, B256::ZERO, 1, 1).unwrap();
+    assert_eq!(accounts[0].1.storage_root, storage_root_unsorted(expected_storage.clone()));
+    assert_eq!(accounts[0].2, expected_storage[..1]);
+
+    let accounts = state.account_range(&tx, B256::with_last_byte(4), 10, 0).unwrap();
+    assert_eq!(
+        accounts.iter().map(|(key, ..)| *key).collect::<Vec<_>>(),
+        vec![B256::with_last_byte(4), B256::with_last_byte(5)]
+    );
+}
+
+#[test]
+fn storage_range_with_overlay() {
+    let address = B256::with_last_byte(1);
+
+    let db = create_test_rw_db();
+    db.update(|tx| {
+        for key in 1..6 {
+            tx.put::<tables::HashedStorages>(
+                address,
+                StorageEntry { key: B256::with_last_byte(key), value: U256::from(key) },
+            )
+            .unwrap();
+        }
+    })
+    .unwrap();
+
+    // clear the second slot and update the fourth one
+    let state = HashedPostState::from_hashed_storage(
+        address,
+        HashedStorage::from_iter(
+            false,
+            [(B256::with_last_byte(2), U256::ZERO), (B256::with_last_byte(4), U256::from(40))],
+        ),
+    );
+
+    let tx = db.tx().unwrap();
+    assert_eq!(
+        state.storage_range(&tx, address, B256::with_last_byte(2), 2).unwrap(),
+        vec![(B256::with_last_byte(3), U256::from(3)), (B256::with_last_byte(4), U256::from(40))]
+    );
+
+    // a wiped storage hides all slots in the database
+    let state = HashedPostState::from_hashed_storage(address, HashedStorage::new(true));
+    assert!(state.storage_range(&tx, address, B256::ZERO, 10).unwrap().is_empty());
+}
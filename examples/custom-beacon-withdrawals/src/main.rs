@@ -23,10 +23,10 @@ use reth_ethereum::{
         revm::{
             context::{result::ExecutionResult, TxEnv},
             db::State,
-            primitives::{address, hardfork::SpecId, Address},
+            primitives::{address, hardfork::SpecId, Address, Bytes},
             DatabaseCommit,
         },
-        EthBlockAssembler, EthEvmConfig, RethReceiptBuilder,
+        ConfigureExtraData, EthBlockAssembler, EthEvmConfig, RethReceiptBuilder,
     },
     node::{
         api::{ConfigureEvm, FullNodeTypes, NodeTypes},
@@ -117,6 +117,12 @@ impl BlockExecutorFactory for CustomEvmConfig {
     }
 }
 
+impl ConfigureExtraData for CustomEvmConfig {
+    fn set_extra_data(&mut self, extra_data: Bytes) {
+        self.inner.set_extra_data(extra_data);
+    }
+}
+
 impl ConfigureEvm for CustomEvmConfig {
     type Primitives = <EthEvmConfig as ConfigureEvm>::Primitives;
     type Error = <EthEvmConfig as ConfigureEvm>::Error;